@@ -75,6 +75,8 @@ pub enum PeerEventType {
     Connected,
     Disconnected,
     StateChanged(String),
+    Banned(String),
+    Unbanned,
 }
 
 /// Metrics for protocol extensions
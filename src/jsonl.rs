@@ -0,0 +1,175 @@
+//! Append-only JSON-lines logs backing the file stores
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+/// Error opening a log
+#[derive(Debug, Error)]
+pub(crate) enum LogError {
+    /// IO error
+    #[error("{0}")]
+    Io(#[from] io::Error),
+
+    /// A record before the final one failed to decode
+    #[error("line {line}: {source}")]
+    Corrupt {
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+/// JSON-lines log with crash-safe appends and compaction
+///
+/// Every record is one line, synced with its newline before `append`
+/// returns. A torn final record left by a crash, including one missing
+/// only its newline, is cut off on open, and a failed append is cut back
+/// to the last complete record so a partial write never ends up in the
+/// middle of the log.
+pub(crate) struct JsonlLog {
+    path: PathBuf,
+    file: File,
+    /// Length of the log up to its last complete record
+    len: u64,
+    /// Set when a failed append could not be cut back off
+    torn: bool,
+}
+
+impl JsonlLog {
+    /// Open or create the log at `path` and decode its records
+    pub async fn open<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<(Self, Vec<T>), LogError> {
+        let path = path.as_ref().to_path_buf();
+        let mut records = Vec::new();
+
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                let line_count = contents.lines().count();
+                let mut valid_len = 0;
+                for (line_no, line) in contents.split_inclusive('\n').enumerate() {
+                    // The append was never acknowledged, even if the record parses
+                    if !line.ends_with('\n') {
+                        warn!("Ignoring unterminated record in {}", path.display());
+                        break;
+                    }
+                    if line.trim().is_empty() {
+                        valid_len += line.len();
+                        continue;
+                    }
+                    match serde_json::from_str(line) {
+                        Ok(record) => records.push(record),
+                        // A torn final write is expected after a crash
+                        Err(e) if line_no + 1 == line_count => {
+                            warn!("Ignoring truncated record in {}: {}", path.display(), e);
+                            break;
+                        }
+                        Err(source) => return Err(LogError::Corrupt { line: line_no + 1, source }),
+                    }
+                    valid_len += line.len();
+                }
+                // Cut the torn record so later appends start on a fresh line
+                if valid_len < contents.len() {
+                    let file = OpenOptions::new().write(true).open(&path).await?;
+                    file.set_len(valid_len as u64).await?;
+                    file.sync_all().await?;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let (file, len) = Self::open_append(&path).await?;
+        Ok((Self { path, file, len, torn: false }, records))
+    }
+
+    async fn open_append(path: &Path) -> io::Result<(File, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path).await?;
+        let len = file.metadata().await?.len();
+        Ok((file, len))
+    }
+
+    fn encode<T: Serialize>(record: &T) -> io::Result<Vec<u8>> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        Ok(line)
+    }
+
+    /// Append a record and sync it to disk
+    pub async fn append<T: Serialize>(&mut self, record: &T) -> io::Result<()> {
+        let line = Self::encode(record)?;
+        if self.torn {
+            self.truncate().await?;
+            self.torn = false;
+        }
+
+        let written = async {
+            self.file.write_all(&line).await?;
+            self.file.sync_data().await
+        }
+        .await;
+        match written {
+            Ok(()) => {
+                self.len += line.len() as u64;
+                Ok(())
+            }
+            Err(e) => {
+                if let Err(cut) = self.truncate().await {
+                    warn!("Failed to cut partial record from {}: {}", self.path.display(), cut);
+                    self.torn = true;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn truncate(&mut self) -> io::Result<()> {
+        self.file.set_len(self.len).await?;
+        self.file.sync_all().await
+    }
+
+    /// Atomically replace the log with `records`
+    ///
+    /// The new contents are written and synced to a temporary file that is
+    /// renamed over the log, then the directory is synced so the rename
+    /// survives a crash.
+    pub async fn rewrite<T, I>(&mut self, records: I) -> io::Result<()>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        let mut contents = Vec::new();
+        for record in records {
+            contents.extend(Self::encode(&record)?);
+        }
+
+        let tmp_path = self.path.with_extension("compact");
+        let mut tmp = File::create(&tmp_path).await?;
+        tmp.write_all(&contents).await?;
+        tmp.sync_all().await?;
+        drop(tmp);
+        tokio::fs::rename(&tmp_path, &self.path).await?;
+        Self::sync_parent(&self.path).await?;
+
+        let (file, len) = Self::open_append(&self.path).await?;
+        self.file = file;
+        self.len = len;
+        self.torn = false;
+        Ok(())
+    }
+
+    /// Flush the directory holding `path` so a rename into it is durable
+    async fn sync_parent(path: &Path) -> io::Result<()> {
+        if cfg!(unix) {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            File::open(dir).await?.sync_all().await?;
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,8 @@ pub mod metrics;
 pub mod extensions;
 pub mod substrate;
 
+pub(crate) mod jsonl;
+
 // Re-exports
 pub use finality::{FinalitySignal, FinalityMonitor};
 pub use message::{FrostMessage, MessageType};
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use metrics::counter;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use crate::message::{FrostMessage, MessageError, MessageProcessor};
+use crate::message::types::BatchMessage;
+
+/// Undo hook for batch members that succeeded in a failed batch
+#[async_trait]
+pub trait CompensationHook: Send + Sync {
+    /// Roll back the effects of a successfully processed message
+    async fn compensate(
+        &self,
+        message: &FrostMessage,
+        result: &serde_json::Value,
+    ) -> std::result::Result<(), MessageError>;
+}
+
+/// Batch executor configuration
+#[derive(Debug, Clone)]
+pub struct BatchExecutorConfig {
+    /// Maximum members processed at once for unordered batches
+    pub max_concurrency: usize,
+    /// Run the compensation hook when a batch misses its success ratio
+    pub compensate_on_failure: bool,
+}
+
+impl Default for BatchExecutorConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 8,
+            compensate_on_failure: true,
+        }
+    }
+}
+
+/// Outcome of a single batch member
+#[derive(Debug, Clone)]
+pub enum BatchItemOutcome {
+    /// Processed successfully
+    Succeeded {
+        result: serde_json::Value,
+        processing_time: Duration,
+    },
+    /// Processing failed
+    Failed {
+        error: MessageError,
+    },
+    /// Not processed because the batch stopped early
+    Skipped,
+}
+
+/// Result of a single batch member
+#[derive(Debug, Clone)]
+pub struct BatchItemResult {
+    /// Message identifier
+    pub message_id: Uuid,
+    /// Processing outcome
+    pub outcome: BatchItemOutcome,
+    /// Whether the compensation hook rolled this message back
+    pub compensated: bool,
+    /// Error returned by the compensation hook, if any
+    pub compensation_error: Option<MessageError>,
+}
+
+impl BatchItemResult {
+    /// Whether the message was processed successfully
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome, BatchItemOutcome::Succeeded { .. })
+    }
+}
+
+/// Per-message summary of a batch execution
+#[derive(Debug, Clone)]
+pub struct BatchSummary {
+    /// Batch identifier
+    pub batch_id: Uuid,
+    /// Results in batch order
+    pub results: Vec<BatchItemResult>,
+    /// Fraction of members that succeeded
+    pub success_ratio: f32,
+    /// Ratio required by the batch
+    pub required_ratio: f32,
+    /// Whether the batch met its required ratio
+    pub succeeded: bool,
+    /// Whether execution stopped before processing every member
+    pub stopped_early: bool,
+    /// Total execution time
+    pub duration: Duration,
+}
+
+impl BatchSummary {
+    /// Number of members that succeeded
+    pub fn success_count(&self) -> usize {
+        self.results.iter().filter(|r| r.succeeded()).count()
+    }
+
+    /// Number of members that failed
+    pub fn failure_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, BatchItemOutcome::Failed { .. }))
+            .count()
+    }
+
+    /// Error describing a failed batch
+    pub fn error(&self) -> Option<MessageError> {
+        (!self.succeeded).then_some(MessageError::BatchValidationFailed {
+            batch_id: self.batch_id,
+            success_ratio: self.success_ratio,
+            required_ratio: self.required_ratio,
+        })
+    }
+}
+
+/// Tracks whether the required ratio is still reachable
+struct RatioTracker {
+    total: usize,
+    required: f32,
+    failed: usize,
+}
+
+impl RatioTracker {
+    fn new(total: usize, required: f32) -> Self {
+        Self {
+            total,
+            required: required.clamp(0.0, 1.0),
+            failed: 0,
+        }
+    }
+
+    fn record_failure(&mut self) {
+        self.failed += 1;
+    }
+
+    /// Best ratio achievable if every remaining member succeeds
+    fn reachable(&self) -> bool {
+        self.total == 0 || (self.total - self.failed) as f32 / self.total as f32 >= self.required
+    }
+}
+
+/// Executes `BatchMessage` members through a `MessageProcessor`
+///
+/// Ordered batches run one message at a time in batch order; unordered
+/// batches run up to `max_concurrency` members at once. Once the batch's
+/// `min_success_ratio` can no longer be met no further members are
+/// started, and members still in flight are allowed to finish.
+pub struct BatchExecutor {
+    processor: Arc<dyn MessageProcessor>,
+    compensation: Option<Arc<dyn CompensationHook>>,
+    config: BatchExecutorConfig,
+}
+
+impl BatchExecutor {
+    /// Create executor with default configuration
+    pub fn new(processor: Arc<dyn MessageProcessor>) -> Self {
+        Self {
+            processor,
+            compensation: None,
+            config: BatchExecutorConfig::default(),
+        }
+    }
+
+    /// Use the given configuration
+    pub fn with_config(mut self, config: BatchExecutorConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Roll back successful members of failed batches with `hook`
+    pub fn with_compensation(mut self, hook: Arc<dyn CompensationHook>) -> Self {
+        self.compensation = Some(hook);
+        self
+    }
+
+    /// Get executor configuration
+    pub fn config(&self) -> &BatchExecutorConfig {
+        &self.config
+    }
+
+    /// Execute a batch and summarize the outcome of every member
+    pub async fn execute(&self, batch: &BatchMessage) -> BatchSummary {
+        let started = Instant::now();
+        let mut tracker = RatioTracker::new(batch.messages.len(), batch.min_success_ratio);
+
+        // Completion order of successful members, used to compensate in reverse
+        let mut completed = Vec::new();
+        let mut outcomes: Vec<BatchItemOutcome> = vec![BatchItemOutcome::Skipped; batch.messages.len()];
+
+        if batch.ordered {
+            for (index, message) in batch.messages.iter().enumerate() {
+                if !tracker.reachable() {
+                    break;
+                }
+                let outcome = Self::run(self.processor.as_ref(), message).await;
+                Self::record(&mut tracker, &mut completed, index, &outcome);
+                outcomes[index] = outcome;
+            }
+        } else {
+            let mut tasks = JoinSet::new();
+            let mut task_indices = HashMap::new();
+            let mut next = 0;
+            let max_concurrency = self.config.max_concurrency.max(1);
+
+            loop {
+                while next < batch.messages.len() && tasks.len() < max_concurrency && tracker.reachable() {
+                    let processor = self.processor.clone();
+                    let message = batch.messages[next].clone();
+                    let handle = tasks.spawn(async move { Self::run(processor.as_ref(), &message).await });
+                    task_indices.insert(handle.id(), next);
+                    next += 1;
+                }
+
+                let Some(joined) = tasks.join_next_with_id().await else {
+                    break;
+                };
+                let (index, outcome) = match joined {
+                    Ok((id, outcome)) => (task_indices[&id], outcome),
+                    Err(e) => (task_indices[&e.id()], BatchItemOutcome::Failed {
+                        error: MessageError::Internal(format!("Batch member task failed: {}", e)),
+                    }),
+                };
+                Self::record(&mut tracker, &mut completed, index, &outcome);
+                outcomes[index] = outcome;
+            }
+        }
+
+        let total = batch.messages.len();
+        let succeeded_count = completed.len();
+        let success_ratio = if total == 0 { 1.0 } else { succeeded_count as f32 / total as f32 };
+        let required_ratio = tracker.required;
+        let succeeded = success_ratio >= required_ratio;
+        let stopped_early = outcomes.iter().any(|o| matches!(o, BatchItemOutcome::Skipped));
+
+        let mut results: Vec<BatchItemResult> = batch
+            .messages
+            .iter()
+            .zip(outcomes)
+            .map(|(message, outcome)| BatchItemResult {
+                message_id: message.id,
+                outcome,
+                compensated: false,
+                compensation_error: None,
+            })
+            .collect();
+
+        if !succeeded && self.config.compensate_on_failure {
+            if let Some(hook) = &self.compensation {
+                for &index in completed.iter().rev() {
+                    let BatchItemOutcome::Succeeded { result, .. } = &results[index].outcome else {
+                        continue;
+                    };
+                    match hook.compensate(&batch.messages[index], result).await {
+                        Ok(()) => results[index].compensated = true,
+                        Err(e) => results[index].compensation_error = Some(e),
+                    }
+                }
+            }
+        }
+
+        counter!("frost.message.batch.executed", 1);
+        if !succeeded {
+            counter!("frost.message.batch.failed", 1);
+        }
+
+        BatchSummary {
+            batch_id: batch.batch_id,
+            results,
+            success_ratio,
+            required_ratio,
+            succeeded,
+            stopped_early,
+            duration: started.elapsed(),
+        }
+    }
+
+    async fn run(processor: &dyn MessageProcessor, message: &FrostMessage) -> BatchItemOutcome {
+        let started = Instant::now();
+        match processor.process(message).await {
+            Ok(result) => BatchItemOutcome::Succeeded {
+                result,
+                processing_time: started.elapsed(),
+            },
+            Err(error) => BatchItemOutcome::Failed { error },
+        }
+    }
+
+    fn record(tracker: &mut RatioTracker, completed: &mut Vec<usize>, index: usize, outcome: &BatchItemOutcome) {
+        match outcome {
+            BatchItemOutcome::Succeeded { .. } => completed.push(index),
+            BatchItemOutcome::Failed { .. } => tracker.record_failure(),
+            BatchItemOutcome::Skipped => {}
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use prost::Message;
+
+use crate::message::MessageError;
+
+/// Maximum nesting of `Any` messages (e.g. authz `MsgExec`)
+const MAX_DEPTH: usize = 4;
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn invalid(reason: impl Into<String>) -> MessageError {
+    MessageError::InvalidFormat(reason.into())
+}
+
+fn decode<M: Message + Default>(bytes: &[u8], what: &str) -> Result<M, MessageError> {
+    M::decode(bytes).map_err(|e| invalid(format!("Invalid {}: {}", what, e)))
+}
+
+/// `google.protobuf.Any`
+#[derive(Clone, PartialEq, Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+/// `cosmos.tx.v1beta1.TxRaw`
+#[derive(Clone, PartialEq, Message)]
+pub struct TxRaw {
+    #[prost(bytes = "vec", tag = "1")]
+    pub body_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub auth_info_bytes: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub signatures: Vec<Vec<u8>>,
+}
+
+/// `cosmos.tx.v1beta1.TxBody`
+#[derive(Clone, PartialEq, Message)]
+pub struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+    #[prost(string, tag = "2")]
+    pub memo: String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+    #[prost(message, repeated, tag = "1023")]
+    pub extension_options: Vec<Any>,
+    #[prost(message, repeated, tag = "2047")]
+    pub non_critical_extension_options: Vec<Any>,
+}
+
+/// `cosmos.tx.v1beta1.AuthInfo`
+#[derive(Clone, PartialEq, Message)]
+pub struct AuthInfo {
+    #[prost(message, repeated, tag = "1")]
+    pub signer_infos: Vec<SignerInfo>,
+    #[prost(message, optional, tag = "2")]
+    pub fee: Option<Fee>,
+}
+
+/// `cosmos.tx.v1beta1.SignerInfo`
+#[derive(Clone, PartialEq, Message)]
+pub struct SignerInfo {
+    #[prost(message, optional, tag = "1")]
+    pub public_key: Option<Any>,
+    #[prost(uint64, tag = "3")]
+    pub sequence: u64,
+}
+
+/// `cosmos.tx.v1beta1.Fee`
+#[derive(Clone, PartialEq, Message)]
+pub struct Fee {
+    #[prost(message, repeated, tag = "1")]
+    pub amount: Vec<Coin>,
+    #[prost(uint64, tag = "2")]
+    pub gas_limit: u64,
+    #[prost(string, tag = "3")]
+    pub payer: String,
+    #[prost(string, tag = "4")]
+    pub granter: String,
+}
+
+/// `cosmos.base.v1beta1.Coin`
+#[derive(Clone, PartialEq, Message)]
+pub struct Coin {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+/// `cosmos.bank.v1beta1.MsgSend`
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSend {
+    #[prost(string, tag = "1")]
+    pub from_address: String,
+    #[prost(string, tag = "2")]
+    pub to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<Coin>,
+}
+
+/// `ibc.applications.transfer.v1.MsgTransfer` (sender side only)
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    pub source_port: String,
+    #[prost(string, tag = "2")]
+    pub source_channel: String,
+    #[prost(message, optional, tag = "3")]
+    pub token: Option<Coin>,
+    #[prost(string, tag = "4")]
+    pub sender: String,
+    /// Address on the counterparty chain, not checked against local prefixes
+    #[prost(string, tag = "5")]
+    pub receiver: String,
+}
+
+/// `cosmos.staking.v1beta1.MsgDelegate`
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgDelegate {
+    #[prost(string, tag = "1")]
+    pub delegator_address: String,
+    #[prost(string, tag = "2")]
+    pub validator_address: String,
+}
+
+/// `cosmos.authz.v1beta1.MsgExec`
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgExec {
+    #[prost(string, tag = "1")]
+    pub grantee: String,
+    #[prost(message, repeated, tag = "2")]
+    pub msgs: Vec<Any>,
+}
+
+pub const MSG_SEND: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const MSG_TRANSFER: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+pub const MSG_DELEGATE: &str = "/cosmos.staking.v1beta1.MsgDelegate";
+pub const MSG_UNDELEGATE: &str = "/cosmos.staking.v1beta1.MsgUndelegate";
+pub const MSG_EXEC: &str = "/cosmos.authz.v1beta1.MsgExec";
+
+/// Kind of bech32 address expected in a message field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    /// Account address (`<prefix>1...`)
+    Account,
+    /// Validator operator address (`<prefix>valoper1...`)
+    Validator,
+}
+
+/// Address and nested messages carried by a known message type
+#[derive(Debug, Default)]
+pub struct MessageContents {
+    pub addresses: Vec<(String, AddressKind)>,
+    pub nested: Vec<Any>,
+}
+
+/// Extract addresses and nested messages from a known message type
+///
+/// Returns `None` for message types this module does not understand.
+pub fn message_contents(any: &Any) -> Result<Option<MessageContents>, MessageError> {
+    let account = |address: String| (address, AddressKind::Account);
+    let contents = match any.type_url.as_str() {
+        MSG_SEND => {
+            let msg: MsgSend = decode(&any.value, MSG_SEND)?;
+            MessageContents {
+                addresses: vec![account(msg.from_address), account(msg.to_address)],
+                nested: Vec::new(),
+            }
+        }
+        MSG_TRANSFER => {
+            let msg: MsgTransfer = decode(&any.value, MSG_TRANSFER)?;
+            MessageContents { addresses: vec![account(msg.sender)], nested: Vec::new() }
+        }
+        MSG_DELEGATE | MSG_UNDELEGATE => {
+            let msg: MsgDelegate = decode(&any.value, &any.type_url)?;
+            MessageContents {
+                addresses: vec![
+                    account(msg.delegator_address),
+                    (msg.validator_address, AddressKind::Validator),
+                ],
+                nested: Vec::new(),
+            }
+        }
+        MSG_EXEC => {
+            let msg: MsgExec = decode(&any.value, MSG_EXEC)?;
+            MessageContents { addresses: vec![account(msg.grantee)], nested: msg.msgs }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(contents))
+}
+
+type MessageVisitor<'a> = dyn FnMut(&Any, Option<&MessageContents>) -> Result<(), MessageError> + 'a;
+
+/// Decoded Cosmos SDK transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct CosmosTransaction {
+    pub body: TxBody,
+    /// Absent when only a `TxBody` was supplied
+    pub auth_info: Option<AuthInfo>,
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl CosmosTransaction {
+    /// Decode a protobuf `TxRaw`
+    pub fn decode_raw(raw: &[u8]) -> Result<Self, MessageError> {
+        let tx: TxRaw = decode(raw, "TxRaw")?;
+        let body = decode(&tx.body_bytes, "TxBody")?;
+        let auth_info: AuthInfo = decode(&tx.auth_info_bytes, "AuthInfo")?;
+
+        if tx.signatures.is_empty() {
+            return Err(invalid("Transaction has no signatures"));
+        }
+        if auth_info.signer_infos.len() != tx.signatures.len() {
+            return Err(invalid(format!(
+                "Transaction has {} signer infos but {} signatures",
+                auth_info.signer_infos.len(), tx.signatures.len()
+            )));
+        }
+        if let Some(index) = tx.signatures.iter().position(|s| s.is_empty()) {
+            return Err(invalid(format!("Signature {} is empty", index)));
+        }
+
+        Ok(Self { body, auth_info: Some(auth_info), signatures: tx.signatures })
+    }
+
+    /// Decode a bare protobuf `TxBody`
+    pub fn decode_body(raw: &[u8]) -> Result<Self, MessageError> {
+        Ok(Self { body: decode(raw, "TxBody")?, auth_info: None, signatures: Vec::new() })
+    }
+
+    /// Visit every message, including messages nested in known wrappers
+    pub fn visit_messages(
+        &self,
+        mut visit: impl FnMut(&Any, Option<&MessageContents>) -> Result<(), MessageError>,
+    ) -> Result<(), MessageError> {
+        fn walk(messages: &[Any], depth: usize, visit: &mut MessageVisitor<'_>) -> Result<(), MessageError> {
+            if depth > MAX_DEPTH {
+                return Err(invalid("Messages nested too deeply"));
+            }
+            for any in messages {
+                let contents = message_contents(any)?;
+                visit(any, contents.as_ref())?;
+                if let Some(contents) = contents {
+                    walk(&contents.nested, depth + 1, visit)?;
+                }
+            }
+            Ok(())
+        }
+        walk(&self.body.messages, 0, &mut visit)
+    }
+
+    /// Summary of decoded fields for metrics
+    pub fn to_json(&self) -> serde_json::Value {
+        let fee = self.auth_info.as_ref().and_then(|a| a.fee.as_ref());
+        serde_json::json!({
+            "messages": self.body.messages.iter().map(|m| m.type_url.clone()).collect::<Vec<_>>(),
+            "memo_len": self.body.memo.len(),
+            "timeout_height": self.body.timeout_height,
+            "signatures": self.signatures.len(),
+            "signer_infos": self.auth_info.as_ref().map(|a| a.signer_infos.len()),
+            "gas_limit": fee.map(|f| f.gas_limit),
+            "fee": fee.map(|f| {
+                f.amount.iter().map(|c| format!("{}{}", c.amount, c.denom)).collect::<Vec<_>>()
+            }),
+        })
+    }
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATORS: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.fold(1u32, |chk, value| {
+        let top = chk >> 25;
+        let mut chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+        chk
+    })
+}
+
+fn bech32_hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes().map(|b| b >> 5).chain([0]).chain(hrp.bytes().map(|b| b & 31))
+}
+
+/// Encode data bytes as a bech32 string with human-readable part `hrp`
+pub fn bech32_encode(hrp: &str, data: &[u8]) -> String {
+    let mut values = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for byte in data {
+        acc = (acc << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push(((acc >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        values.push(((acc << (5 - bits)) & 31) as u8);
+    }
+
+    let checksum_input = bech32_hrp_expand(hrp).chain(values.iter().copied()).chain([0; 6]);
+    let polymod = bech32_polymod(checksum_input) ^ 1;
+    values.extend((0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8));
+
+    let data: String = values.iter().map(|v| BECH32_CHARSET[*v as usize] as char).collect();
+    format!("{}1{}", hrp, data)
+}
+
+/// Decode a bech32 string into its human-readable part and data bytes
+pub fn bech32_decode(input: &str) -> Result<(String, Vec<u8>), MessageError> {
+    if input.len() > 90 {
+        return Err(invalid(format!("Address {} too long", input)));
+    }
+    let has_lower = input.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = input.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(invalid(format!("Address {} has mixed case", input)));
+    }
+    let input = input.to_ascii_lowercase();
+    let separator = input
+        .rfind('1')
+        .ok_or_else(|| invalid(format!("Address {} has no separator", input)))?;
+    let (hrp, data) = (&input[..separator], &input[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return Err(invalid(format!("Malformed bech32 address {}", input)));
+    }
+
+    let values = data
+        .bytes()
+        .map(|b| {
+            BECH32_CHARSET
+                .iter()
+                .position(|c| *c == b)
+                .map(|p| p as u8)
+                .ok_or_else(|| invalid(format!("Invalid bech32 character in {}", input)))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    if bech32_polymod(bech32_hrp_expand(hrp).chain(values.iter().copied())) != 1 {
+        return Err(invalid(format!("Invalid bech32 checksum in {}", input)));
+    }
+
+    // Regroup 5-bit values into bytes, dropping the checksum
+    let mut bytes = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for value in &values[..values.len() - 6] {
+        acc = (acc << 5) | *value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 5 || (acc & ((1 << bits) - 1)) != 0 {
+        return Err(invalid(format!("Invalid bech32 padding in {}", input)));
+    }
+    Ok((hrp.to_string(), bytes))
+}
+
+/// Check a bech32 account or validator address against `prefix`
+pub fn check_address(address: &str, prefix: &str, kind: AddressKind) -> Result<(), MessageError> {
+    let (hrp, bytes) = bech32_decode(address)?;
+    let expected = match kind {
+        AddressKind::Account => prefix.to_string(),
+        AddressKind::Validator => format!("{}valoper", prefix),
+    };
+    if hrp != expected {
+        return Err(invalid(format!("Address {} does not have prefix {}", address, expected)));
+    }
+    // 20-byte key hashes or 32-byte module/contract addresses
+    if bytes.len() != 20 && bytes.len() != 32 {
+        return Err(invalid(format!("Address {} has invalid length {}", address, bytes.len())));
+    }
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+use metrics::counter;
+use parking_lot::Mutex;
+use serde::{Serialize, Deserialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::message::{FrostMessage, MessageError, MessageHandler, MessageType};
+use crate::Result;
+
+/// Why a message was dead-lettered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadLetterReason {
+    /// Deadline passed before the message was delivered or processed
+    Expired,
+    /// Failed with a non-retryable error or exhausted its retries
+    Failed,
+}
+
+/// Component that dead-lettered a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadLetterOrigin {
+    Routing,
+    Outbox,
+    Validation,
+    Handler,
+}
+
+/// Dead-lettered message with the reason it was captured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub message: FrostMessage,
+    pub reason: DeadLetterReason,
+    pub origin: DeadLetterOrigin,
+    /// Error that caused the message to be dead-lettered
+    pub error: String,
+    pub dead_lettered_at: SystemTime,
+    /// Times the message was dead-lettered, including earlier replays
+    pub occurrences: u32,
+}
+
+/// Criteria for querying dead letters; unset fields match anything
+#[derive(Debug, Clone, Default)]
+pub struct DeadLetterFilter {
+    pub reason: Option<DeadLetterReason>,
+    pub origin: Option<DeadLetterOrigin>,
+    pub source: Option<String>,
+    pub msg_type: Option<MessageType>,
+    /// Only letters captured at or after this time
+    pub since: Option<SystemTime>,
+}
+
+impl DeadLetterFilter {
+    /// Whether `letter` matches every set criterion
+    pub fn matches(&self, letter: &DeadLetter) -> bool {
+        self.reason.is_none_or(|r| r == letter.reason)
+            && self.origin.is_none_or(|o| o == letter.origin)
+            && self.source.as_ref().is_none_or(|s| *s == letter.message.source)
+            && self.msg_type.as_ref().is_none_or(|t| *t == letter.message.msg_type)
+            && self.since.is_none_or(|t| letter.dead_lettered_at >= t)
+    }
+}
+
+/// Dead-letter queue configuration
+#[derive(Debug, Clone)]
+pub struct DeadLetterConfig {
+    /// Maximum letters retained; the oldest are evicted first
+    pub max_entries: usize,
+    /// TTL given to replayed messages whose deadline has passed
+    pub replay_ttl: Duration,
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            replay_ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Bounded store of expired and permanently failed messages
+///
+/// Routers, outboxes, validation pipelines and handlers push messages here
+/// instead of dropping them, so operators can inspect and replay them.
+pub struct DeadLetterQueue {
+    config: DeadLetterConfig,
+    letters: Mutex<VecDeque<DeadLetter>>,
+}
+
+impl DeadLetterQueue {
+    /// Create an empty queue
+    pub fn new(config: DeadLetterConfig) -> Self {
+        Self {
+            config,
+            letters: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Capture a message, replacing any earlier letter for the same id
+    pub fn push(&self, message: FrostMessage, reason: DeadLetterReason, origin: DeadLetterOrigin, error: impl ToString) {
+        let error = error.to_string();
+        warn!("Dead-lettering message {} from {:?}: {}", message.id, origin, error);
+        counter!("frost.message.dead_letter", 1, "reason" => format!("{:?}", reason));
+
+        let mut letters = self.letters.lock();
+        let occurrences = match letters.iter().position(|l| l.message.id == message.id) {
+            Some(index) => letters.remove(index).map_or(1, |l| l.occurrences + 1),
+            None => 1,
+        };
+        letters.push_back(DeadLetter {
+            message,
+            reason,
+            origin,
+            error,
+            dead_lettered_at: SystemTime::now(),
+            occurrences,
+        });
+        while letters.len() > self.config.max_entries {
+            letters.pop_front();
+        }
+    }
+
+    /// Capture a message that failed with `error`
+    ///
+    /// Expiry errors are recorded as `Expired`, everything else as `Failed`.
+    pub fn push_error(&self, message: FrostMessage, origin: DeadLetterOrigin, error: &MessageError) {
+        let reason = match error {
+            MessageError::Expired { .. } => DeadLetterReason::Expired,
+            _ => DeadLetterReason::Failed,
+        };
+        self.push(message, reason, origin, error);
+    }
+
+    /// Number of letters held
+    pub fn len(&self) -> usize {
+        self.letters.lock().len()
+    }
+
+    /// Whether the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.letters.lock().is_empty()
+    }
+
+    /// Letter for a message id
+    pub fn get(&self, message_id: Uuid) -> Option<DeadLetter> {
+        self.letters.lock().iter().find(|l| l.message.id == message_id).cloned()
+    }
+
+    /// Letters matching `filter`, oldest first
+    pub fn query(&self, filter: &DeadLetterFilter) -> Vec<DeadLetter> {
+        self.letters.lock().iter().filter(|l| filter.matches(l)).cloned().collect()
+    }
+
+    /// Remove and return the letter for a message id
+    pub fn remove(&self, message_id: Uuid) -> Option<DeadLetter> {
+        let mut letters = self.letters.lock();
+        let index = letters.iter().position(|l| l.message.id == message_id)?;
+        letters.remove(index)
+    }
+
+    /// Remove letters matching `filter`, returning how many were purged
+    pub fn purge(&self, filter: &DeadLetterFilter) -> usize {
+        let mut letters = self.letters.lock();
+        let before = letters.len();
+        letters.retain(|l| !filter.matches(l));
+        before - letters.len()
+    }
+
+    /// Remove a letter and prepare its message for resubmission
+    ///
+    /// The retry count is reset and an expired deadline is moved to
+    /// `ttl` (or `replay_ttl`) from now.
+    pub fn take_for_replay(&self, message_id: Uuid, ttl: Option<Duration>) -> Result<FrostMessage> {
+        let letter = self.remove(message_id).ok_or_else(|| {
+            MessageError::HandlingFailed(format!("No dead letter for message {}", message_id))
+        })?;
+        let mut message = letter.message;
+        message.metadata.retry_count = 0;
+        if message.is_expired() || ttl.is_some() {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let ttl = ttl.unwrap_or(self.config.replay_ttl);
+            message.metadata.expires_at = Some(now.saturating_add(ttl.as_secs()));
+        }
+        counter!("frost.message.dead_letter.replayed", 1);
+        Ok(message)
+    }
+
+    /// Replay a letter through `handler`
+    ///
+    /// The letter is restored if the handler refuses the message.
+    pub async fn replay(&self, message_id: Uuid, ttl: Option<Duration>, handler: &dyn MessageHandler) -> Result<()> {
+        let letter = self.get(message_id);
+        let message = self.take_for_replay(message_id, ttl)?;
+        if let Err(e) = handler.queue_message(message).await {
+            if let Some(letter) = letter {
+                self.letters.lock().push_back(letter);
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new(DeadLetterConfig::default())
+    }
+}
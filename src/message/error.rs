@@ -85,6 +85,43 @@ pub enum MessageError {
         metadata: Option<serde_json::Value>,
     },
 
+    #[error("Duplicate message: {message_id}")]
+    DuplicateMessage {
+        message_id: Uuid,
+    },
+
+    #[error("Replay detected from {source_id}: {details}")]
+    ReplayDetected {
+        source_id: String,
+        details: String,
+    },
+
+    #[error("Stale message {message_id}: {details}")]
+    StaleMessage {
+        message_id: Uuid,
+        details: String,
+    },
+
+    #[error("Message {message_id} expired at {expires_at}")]
+    Expired {
+        message_id: Uuid,
+        expires_at: u64,
+    },
+
+    #[error("Payload of {type_name} v{version} violates its schema: {details}")]
+    SchemaViolation {
+        type_name: String,
+        version: u16,
+        details: String,
+    },
+
+    #[error("Unsupported schema version {version} for {type_name}, supported: {supported:?}")]
+    UnsupportedSchemaVersion {
+        type_name: String,
+        version: u16,
+        supported: Vec<u16>,
+    },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -108,6 +145,12 @@ impl MessageError {
                 }
             }
             Self::ChainSpecific { .. } => ErrorSeverity::Warning,
+            Self::DuplicateMessage { .. } => ErrorSeverity::Warning,
+            Self::ReplayDetected { .. } => ErrorSeverity::Critical,
+            Self::StaleMessage { .. } => ErrorSeverity::Error,
+            Self::Expired { .. } => ErrorSeverity::Error,
+            Self::SchemaViolation { .. } => ErrorSeverity::Error,
+            Self::UnsupportedSchemaVersion { .. } => ErrorSeverity::Error,
             Self::Internal(_) => ErrorSeverity::Critical,
         }
     }
@@ -124,6 +167,12 @@ impl MessageError {
             Self::BatchValidationFailed { .. } => ErrorStage::PostValidation,
             Self::Timeout { .. } => ErrorStage::Handling,
             Self::ChainSpecific { .. } => ErrorStage::Handling,
+            Self::DuplicateMessage { .. } => ErrorStage::PreValidation,
+            Self::ReplayDetected { .. } => ErrorStage::PreValidation,
+            Self::StaleMessage { .. } => ErrorStage::PreValidation,
+            Self::Expired { .. } => ErrorStage::PreValidation,
+            Self::SchemaViolation { .. } => ErrorStage::PreValidation,
+            Self::UnsupportedSchemaVersion { .. } => ErrorStage::PreValidation,
             Self::Internal(_) => ErrorStage::Handling,
         }
     }
@@ -185,6 +234,42 @@ impl MessageError {
                 max_retries: Some(5),
                 alternatives: vec!["Check chain status".into()],
             },
+            Self::DuplicateMessage { .. } => RetryGuidance {
+                retryable: false,
+                retry_after: None,
+                max_retries: None,
+                alternatives: vec!["Drop duplicate".into()],
+            },
+            Self::ReplayDetected { .. } => RetryGuidance {
+                retryable: false,
+                retry_after: None,
+                max_retries: None,
+                alternatives: vec!["Review sender reputation".into()],
+            },
+            Self::StaleMessage { .. } => RetryGuidance {
+                retryable: false,
+                retry_after: None,
+                max_retries: None,
+                alternatives: vec!["Check clock synchronization".into()],
+            },
+            Self::Expired { .. } => RetryGuidance {
+                retryable: false,
+                retry_after: None,
+                max_retries: None,
+                alternatives: vec!["Resend with a later deadline".into()],
+            },
+            Self::SchemaViolation { .. } => RetryGuidance {
+                retryable: false,
+                retry_after: None,
+                max_retries: None,
+                alternatives: vec!["Fix payload to match schema".into()],
+            },
+            Self::UnsupportedSchemaVersion { .. } => RetryGuidance {
+                retryable: false,
+                retry_after: None,
+                max_retries: None,
+                alternatives: vec!["Negotiate a supported schema version".into()],
+            },
             Self::Internal(_) => RetryGuidance {
                 retryable: false,
                 retry_after: None,
@@ -0,0 +1,857 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+
+use crate::message::MessageError;
+
+/// Ethereum account address
+pub type Address = [u8; 20];
+
+/// Access list entry: an address and the storage keys it touches
+pub type AccessListItem = (Address, Vec<[u8; 32]>);
+
+/// Maximum nesting accepted when decoding RLP lists or ABI values
+const MAX_DEPTH: usize = 16;
+
+/// Maximum number of values decoded from one calldata blob
+const MAX_ABI_ELEMENTS: usize = 65_536;
+
+/// Maximum total `bytes`/`string` length decoded from one calldata blob
+const MAX_ABI_BYTES: usize = 4 * 1024 * 1024;
+
+/// secp256k1 curve order divided by two (EIP-2 upper bound for `s`)
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+fn invalid(reason: impl Into<String>) -> MessageError {
+    MessageError::InvalidFormat(reason.into())
+}
+
+/// Keccak-256 hash
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Parse a hex address, enforcing the EIP-55 checksum for mixed-case input
+pub fn parse_address(input: &str) -> Result<Address, MessageError> {
+    let digits = input
+        .strip_prefix("0x")
+        .ok_or_else(|| invalid(format!("Address {} must start with 0x", input)))?;
+    let bytes = hex::decode(digits).map_err(|e| invalid(format!("Invalid address {}: {}", input, e)))?;
+    let address: Address = bytes
+        .try_into()
+        .map_err(|_| invalid(format!("Address {} must be 20 bytes", input)))?;
+
+    let mixed_case = digits.chars().any(|c| c.is_ascii_uppercase()) && digits.chars().any(|c| c.is_ascii_lowercase());
+    if mixed_case && to_checksum_address(&address) != input {
+        return Err(invalid(format!("Address {} has an invalid EIP-55 checksum", input)));
+    }
+    Ok(address)
+}
+
+/// EIP-55 checksummed representation of an address
+pub fn to_checksum_address(address: &Address) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+    let digits: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if c.is_ascii_alphabetic() && nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", digits)
+}
+
+/// Decimal representation of a big-endian unsigned integer
+fn to_decimal(bytes: &[u8]) -> String {
+    let mut digits = Vec::new();
+    let mut value: Vec<u8> = bytes.iter().copied().skip_while(|b| *b == 0).collect();
+    while !value.is_empty() {
+        let mut remainder = 0u32;
+        for byte in value.iter_mut() {
+            let acc = (remainder << 8) | *byte as u32;
+            *byte = (acc / 10) as u8;
+            remainder = acc % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+        let leading = value.iter().take_while(|b| **b == 0).count();
+        value.drain(..leading);
+    }
+    if digits.is_empty() {
+        return "0".into();
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+/// Decoded RLP item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpItem<'a> {
+    Bytes(&'a [u8]),
+    List(Vec<RlpItem<'a>>),
+}
+
+impl<'a> RlpItem<'a> {
+    fn bytes(&self, field: &str) -> Result<&'a [u8], MessageError> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes),
+            Self::List(_) => Err(invalid(format!("Expected bytes for {}", field))),
+        }
+    }
+
+    fn list(&self, field: &str) -> Result<&[RlpItem<'a>], MessageError> {
+        match self {
+            Self::List(items) => Ok(items),
+            Self::Bytes(_) => Err(invalid(format!("Expected list for {}", field))),
+        }
+    }
+
+    /// Canonical big-endian integer of at most `N` bytes
+    fn uint<const N: usize>(&self, field: &str) -> Result<[u8; N], MessageError> {
+        let bytes = self.bytes(field)?;
+        if bytes.len() > N {
+            return Err(invalid(format!("{} exceeds {} bytes", field, N)));
+        }
+        if bytes.first() == Some(&0) {
+            return Err(invalid(format!("{} has leading zeros", field)));
+        }
+        let mut out = [0u8; N];
+        out[N - bytes.len()..].copy_from_slice(bytes);
+        Ok(out)
+    }
+
+    fn u64(&self, field: &str) -> Result<u64, MessageError> {
+        self.uint::<8>(field).map(u64::from_be_bytes)
+    }
+
+    fn u128(&self, field: &str) -> Result<u128, MessageError> {
+        self.uint::<16>(field).map(u128::from_be_bytes)
+    }
+}
+
+/// Decode a single RLP item spanning all of `input`
+pub fn decode_rlp(input: &[u8]) -> Result<RlpItem<'_>, MessageError> {
+    let (item, rest) = decode_rlp_item(input, 0)?;
+    if !rest.is_empty() {
+        return Err(invalid(format!("{} trailing bytes after RLP item", rest.len())));
+    }
+    Ok(item)
+}
+
+fn rlp_length(input: &[u8], len_of_len: usize) -> Result<usize, MessageError> {
+    let bytes = input
+        .get(1..1 + len_of_len)
+        .ok_or_else(|| invalid("Truncated RLP length"))?;
+    if bytes[0] == 0 {
+        return Err(invalid("RLP length has leading zeros"));
+    }
+    if len_of_len > std::mem::size_of::<usize>() {
+        return Err(invalid("RLP length too large"));
+    }
+    let length = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+    if length <= 55 {
+        return Err(invalid("Non-canonical RLP long length"));
+    }
+    Ok(length)
+}
+
+fn rlp_payload(input: &[u8], offset: usize, length: usize) -> Result<(&[u8], &[u8]), MessageError> {
+    let end = offset.checked_add(length).ok_or_else(|| invalid("RLP length overflow"))?;
+    if end > input.len() {
+        return Err(invalid("Truncated RLP payload"));
+    }
+    Ok((&input[offset..end], &input[end..]))
+}
+
+fn decode_rlp_item(input: &[u8], depth: usize) -> Result<(RlpItem<'_>, &[u8]), MessageError> {
+    if depth > MAX_DEPTH {
+        return Err(invalid("RLP nesting too deep"));
+    }
+    let prefix = *input.first().ok_or_else(|| invalid("Empty RLP input"))?;
+
+    match prefix {
+        0x00..=0x7f => Ok((RlpItem::Bytes(&input[..1]), &input[1..])),
+        0x80..=0xb7 => {
+            let (payload, rest) = rlp_payload(input, 1, (prefix - 0x80) as usize)?;
+            if payload.len() == 1 && payload[0] < 0x80 {
+                return Err(invalid("Non-canonical RLP single byte"));
+            }
+            Ok((RlpItem::Bytes(payload), rest))
+        }
+        0xb8..=0xbf => {
+            let len_of_len = (prefix - 0xb7) as usize;
+            let length = rlp_length(input, len_of_len)?;
+            let (payload, rest) = rlp_payload(input, 1 + len_of_len, length)?;
+            Ok((RlpItem::Bytes(payload), rest))
+        }
+        0xc0..=0xf7 => {
+            let (payload, rest) = rlp_payload(input, 1, (prefix - 0xc0) as usize)?;
+            Ok((RlpItem::List(decode_rlp_list(payload, depth)?), rest))
+        }
+        0xf8..=0xff => {
+            let len_of_len = (prefix - 0xf7) as usize;
+            let length = rlp_length(input, len_of_len)?;
+            let (payload, rest) = rlp_payload(input, 1 + len_of_len, length)?;
+            Ok((RlpItem::List(decode_rlp_list(payload, depth)?), rest))
+        }
+    }
+}
+
+fn decode_rlp_list(mut payload: &[u8], depth: usize) -> Result<Vec<RlpItem<'_>>, MessageError> {
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let (item, rest) = decode_rlp_item(payload, depth + 1)?;
+        items.push(item);
+        payload = rest;
+    }
+    Ok(items)
+}
+
+/// Transaction envelope type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionType {
+    /// Pre-EIP-2718 transaction, optionally EIP-155 replay protected
+    Legacy,
+    /// EIP-2930 access list transaction (type 1)
+    AccessList,
+    /// EIP-1559 dynamic fee transaction (type 2)
+    DynamicFee,
+}
+
+/// Decoded Ethereum transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthereumTransaction {
+    pub tx_type: TransactionType,
+    /// Chain id, absent for pre-EIP-155 legacy transactions
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    /// Gas price for legacy and access list transactions
+    pub gas_price: Option<u128>,
+    pub max_fee_per_gas: Option<u128>,
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Recipient, absent for contract creation
+    pub to: Option<Address>,
+    /// Transferred value as a big-endian 256-bit integer
+    pub value: [u8; 32],
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    /// `v` for legacy transactions, y-parity for typed transactions
+    pub v: u64,
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+}
+
+impl EthereumTransaction {
+    /// Decode a raw transaction (legacy RLP or EIP-2718 typed envelope)
+    pub fn decode(raw: &[u8]) -> Result<Self, MessageError> {
+        match raw.first() {
+            None => Err(invalid("Empty transaction")),
+            Some(0x01) => Self::decode_typed(TransactionType::AccessList, &raw[1..]),
+            Some(0x02) => Self::decode_typed(TransactionType::DynamicFee, &raw[1..]),
+            Some(0xc0..=0xff) => Self::decode_legacy(raw),
+            Some(ty) => Err(invalid(format!("Unsupported transaction type 0x{:02x}", ty))),
+        }
+    }
+
+    fn decode_legacy(raw: &[u8]) -> Result<Self, MessageError> {
+        let item = decode_rlp(raw)?;
+        let fields = item.list("transaction")?;
+        if fields.len() != 9 {
+            return Err(invalid(format!("Legacy transaction has {} fields, expected 9", fields.len())));
+        }
+
+        let v = fields[6].u64("v")?;
+        let chain_id = match v {
+            27 | 28 => None,
+            v if v >= 35 => Some((v - 35) / 2),
+            v => return Err(invalid(format!("Invalid legacy signature v {}", v))),
+        };
+
+        let tx = Self {
+            tx_type: TransactionType::Legacy,
+            chain_id,
+            nonce: fields[0].u64("nonce")?,
+            gas_price: Some(fields[1].u128("gas_price")?),
+            gas_limit: fields[2].u64("gas_limit")?,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            to: decode_to(&fields[3])?,
+            value: fields[4].uint::<32>("value")?,
+            data: fields[5].bytes("data")?.to_vec(),
+            access_list: Vec::new(),
+            v,
+            r: fields[7].uint::<32>("r")?,
+            s: fields[8].uint::<32>("s")?,
+        };
+        tx.check_signature()?;
+        Ok(tx)
+    }
+
+    fn decode_typed(tx_type: TransactionType, payload: &[u8]) -> Result<Self, MessageError> {
+        let item = decode_rlp(payload)?;
+        let fields = item.list("transaction")?;
+        let expected = match tx_type {
+            TransactionType::AccessList => 11,
+            _ => 12,
+        };
+        if fields.len() != expected {
+            return Err(invalid(format!(
+                "{:?} transaction has {} fields, expected {}", tx_type, fields.len(), expected
+            )));
+        }
+
+        // Dynamic fee transactions carry two fee fields where access list
+        // transactions carry a single gas price
+        let (gas_price, max_priority_fee_per_gas, max_fee_per_gas, rest) = match tx_type {
+            TransactionType::AccessList => (Some(fields[2].u128("gas_price")?), None, None, &fields[3..]),
+            _ => (
+                None,
+                Some(fields[2].u128("max_priority_fee_per_gas")?),
+                Some(fields[3].u128("max_fee_per_gas")?),
+                &fields[4..],
+            ),
+        };
+
+        let v = rest[5].u64("y_parity")?;
+        if v > 1 {
+            return Err(invalid(format!("Invalid y-parity {}", v)));
+        }
+        if let (Some(max_fee), Some(priority_fee)) = (max_fee_per_gas, max_priority_fee_per_gas) {
+            if priority_fee > max_fee {
+                return Err(invalid("max_priority_fee_per_gas exceeds max_fee_per_gas"));
+            }
+        }
+
+        let tx = Self {
+            tx_type,
+            chain_id: Some(fields[0].u64("chain_id")?),
+            nonce: fields[1].u64("nonce")?,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            gas_limit: rest[0].u64("gas_limit")?,
+            to: decode_to(&rest[1])?,
+            value: rest[2].uint::<32>("value")?,
+            data: rest[3].bytes("data")?.to_vec(),
+            access_list: decode_access_list(&rest[4])?,
+            v,
+            r: rest[6].uint::<32>("r")?,
+            s: rest[7].uint::<32>("s")?,
+        };
+        tx.check_signature()?;
+        Ok(tx)
+    }
+
+    fn check_signature(&self) -> Result<(), MessageError> {
+        if self.r == [0; 32] || self.s == [0; 32] {
+            return Err(invalid("Transaction signature has zero r or s"));
+        }
+        if self.s > SECP256K1_HALF_ORDER {
+            return Err(invalid("Transaction signature s is not in the lower half order (EIP-2)"));
+        }
+        Ok(())
+    }
+
+    /// Summary of decoded fields for metrics
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": format!("{:?}", self.tx_type),
+            "chain_id": self.chain_id,
+            "nonce": self.nonce,
+            "gas_limit": self.gas_limit,
+            "gas_price": self.gas_price.map(|v| v.to_string()),
+            "max_fee_per_gas": self.max_fee_per_gas.map(|v| v.to_string()),
+            "max_priority_fee_per_gas": self.max_priority_fee_per_gas.map(|v| v.to_string()),
+            "to": self.to.as_ref().map(to_checksum_address),
+            "value": to_decimal(&self.value),
+            "data_len": self.data.len(),
+            "access_list_len": self.access_list.len(),
+        })
+    }
+}
+
+fn decode_to(item: &RlpItem<'_>) -> Result<Option<Address>, MessageError> {
+    let bytes = item.bytes("to")?;
+    match bytes.len() {
+        0 => Ok(None),
+        20 => Ok(Some(bytes.try_into().expect("length checked"))),
+        n => Err(invalid(format!("Recipient must be 20 bytes, got {}", n))),
+    }
+}
+
+fn decode_access_list(item: &RlpItem<'_>) -> Result<Vec<AccessListItem>, MessageError> {
+    item.list("access_list")?
+        .iter()
+        .map(|entry| {
+            let entry = entry.list("access list entry")?;
+            if entry.len() != 2 {
+                return Err(invalid("Access list entry must have 2 fields"));
+            }
+            let address: Address = entry[0]
+                .bytes("access list address")?
+                .try_into()
+                .map_err(|_| invalid("Access list address must be 20 bytes"))?;
+            let keys = entry[1]
+                .list("storage keys")?
+                .iter()
+                .map(|key| {
+                    key.bytes("storage key")?
+                        .try_into()
+                        .map_err(|_| invalid("Storage key must be 32 bytes"))
+                })
+                .collect::<Result<Vec<[u8; 32]>, _>>()?;
+            Ok((address, keys))
+        })
+        .collect()
+}
+
+/// Solidity ABI type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiType {
+    Address,
+    Bool,
+    Uint(usize),
+    Int(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<AbiType>),
+    FixedArray(Box<AbiType>, usize),
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Parse an elementary or array type name such as `uint256[2][]`
+    pub fn parse(name: &str) -> Result<Self, MessageError> {
+        Self::parse_with_components(name, None)
+    }
+
+    fn parse_with_components(name: &str, components: Option<&[AbiParam]>) -> Result<Self, MessageError> {
+        if let Some(stripped) = name.strip_suffix(']') {
+            let open = stripped
+                .rfind('[')
+                .ok_or_else(|| invalid(format!("Invalid ABI type {}", name)))?;
+            let inner = Box::new(Self::parse_with_components(&stripped[..open], components)?);
+            let size = &stripped[open + 1..];
+            return if size.is_empty() {
+                Ok(Self::Array(inner))
+            } else {
+                let size = size
+                    .parse()
+                    .map_err(|_| invalid(format!("Invalid array size in {}", name)))?;
+                Self::FixedArray(inner, size).sized(name)
+            };
+        }
+
+        let bits = |digits: &str, default: usize| -> Result<usize, MessageError> {
+            if digits.is_empty() {
+                return Ok(default);
+            }
+            match digits.parse::<usize>() {
+                Ok(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(bits),
+                _ => Err(invalid(format!("Invalid ABI type {}", name))),
+            }
+        };
+
+        match name {
+            "address" => Ok(Self::Address),
+            "bool" => Ok(Self::Bool),
+            "bytes" => Ok(Self::Bytes),
+            "string" => Ok(Self::String),
+            "tuple" => {
+                let components = components.ok_or_else(|| invalid("Tuple type without components"))?;
+                Self::Tuple(components.iter().map(AbiParam::abi_type).collect::<Result<_, _>>()?).sized(name)
+            }
+            _ if name.starts_with("uint") => Ok(Self::Uint(bits(&name[4..], 256)?)),
+            _ if name.starts_with("int") => Ok(Self::Int(bits(&name[3..], 256)?)),
+            _ if name.starts_with("bytes") => match name[5..].parse::<usize>() {
+                Ok(size) if (1..=32).contains(&size) => Ok(Self::FixedBytes(size)),
+                _ => Err(invalid(format!("Invalid ABI type {}", name))),
+            },
+            _ => Err(invalid(format!("Unsupported ABI type {}", name))),
+        }
+    }
+
+    /// Canonical type name used in function signatures
+    pub fn canonical(&self) -> String {
+        match self {
+            Self::Address => "address".into(),
+            Self::Bool => "bool".into(),
+            Self::Uint(bits) => format!("uint{}", bits),
+            Self::Int(bits) => format!("int{}", bits),
+            Self::FixedBytes(size) => format!("bytes{}", size),
+            Self::Bytes => "bytes".into(),
+            Self::String => "string".into(),
+            Self::Array(inner) => format!("{}[]", inner.canonical()),
+            Self::FixedArray(inner, size) => format!("{}[{}]", inner.canonical(), size),
+            Self::Tuple(types) => format!(
+                "({})",
+                types.iter().map(Self::canonical).collect::<Vec<_>>().join(",")
+            ),
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Bytes | Self::String | Self::Array(_) => true,
+            Self::FixedArray(inner, _) => inner.is_dynamic(),
+            Self::Tuple(types) => types.iter().any(Self::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Size of the type's slot in the head of an enclosing tuple, or
+    /// `None` if it overflows
+    fn head_size(&self) -> Option<usize> {
+        if self.is_dynamic() {
+            return Some(32);
+        }
+        match self {
+            Self::FixedArray(inner, size) => inner.head_size()?.checked_mul(*size),
+            Self::Tuple(types) => types.iter().try_fold(0usize, |len, ty| len.checked_add(ty.head_size()?)),
+            _ => Some(32),
+        }
+    }
+
+    /// Reject composite types whose head size overflows
+    fn sized(self, name: &str) -> Result<Self, MessageError> {
+        match self.head_size() {
+            Some(_) => Ok(self),
+            None => Err(invalid(format!("ABI type {} is too large", name))),
+        }
+    }
+}
+
+/// ABI JSON parameter
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbiParam {
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub components: Option<Vec<AbiParam>>,
+}
+
+impl AbiParam {
+    fn abi_type(&self) -> Result<AbiType, MessageError> {
+        AbiType::parse_with_components(&self.kind, self.components.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AbiEntry {
+    #[serde(rename = "type", default = "default_entry_type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<AbiParam>,
+}
+
+fn default_entry_type() -> String {
+    "function".into()
+}
+
+/// ABI function definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiFunction {
+    pub name: String,
+    pub inputs: Vec<(String, AbiType)>,
+    pub selector: [u8; 4],
+}
+
+impl AbiFunction {
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub fn signature(&self) -> String {
+        let types: Vec<_> = self.inputs.iter().map(|(_, ty)| ty.canonical()).collect();
+        format!("{}({})", self.name, types.join(","))
+    }
+}
+
+/// Calldata decoded against an ABI
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedCall {
+    pub function: String,
+    pub selector: [u8; 4],
+    /// Arguments by name (or position when unnamed)
+    pub args: Vec<(String, serde_json::Value)>,
+}
+
+impl DecodedCall {
+    /// Summary of decoded fields for metrics
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "function": self.function,
+            "selector": format!("0x{}", hex::encode(self.selector)),
+            "args": self.args.iter().cloned().collect::<serde_json::Map<_, _>>(),
+        })
+    }
+}
+
+/// Contract ABI indexed by function selector
+#[derive(Debug, Clone, Default)]
+pub struct Abi {
+    functions: HashMap<[u8; 4], AbiFunction>,
+}
+
+impl Abi {
+    /// Load functions from a standard JSON ABI
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, MessageError> {
+        let entries: Vec<AbiEntry> = serde_json::from_value(value.clone())
+            .map_err(|e| invalid(format!("Invalid ABI: {}", e)))?;
+
+        let mut functions = HashMap::new();
+        for entry in entries.into_iter().filter(|e| e.kind == "function") {
+            let inputs = entry
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, param)| {
+                    let name = if param.name.is_empty() { format!("arg{}", i) } else { param.name.clone() };
+                    Ok((name, param.abi_type()?))
+                })
+                .collect::<Result<Vec<_>, MessageError>>()?;
+            let mut function = AbiFunction { name: entry.name, inputs, selector: [0; 4] };
+            let hash = keccak256(function.signature().as_bytes());
+            function.selector.copy_from_slice(&hash[..4]);
+            functions.insert(function.selector, function);
+        }
+        Ok(Self { functions })
+    }
+
+    /// Look up a function by selector
+    pub fn function(&self, selector: &[u8; 4]) -> Option<&AbiFunction> {
+        self.functions.get(selector)
+    }
+
+    /// Number of functions
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Whether the ABI has no functions
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Decode and type-check calldata
+    pub fn decode_call(&self, calldata: &[u8]) -> Result<DecodedCall, MessageError> {
+        let selector: [u8; 4] = calldata
+            .get(..4)
+            .and_then(|s| s.try_into().ok())
+            .ok_or_else(|| invalid("Calldata shorter than a function selector"))?;
+        let function = self.function(&selector).ok_or_else(|| {
+            invalid(format!("Unknown function selector 0x{}", hex::encode(selector)))
+        })?;
+
+        let types: Vec<AbiType> = function.inputs.iter().map(|(_, ty)| ty.clone()).collect();
+        let (values, _) = decode_tuple(&types, &calldata[4..], 0, &mut AbiBudget::default())?;
+
+        Ok(DecodedCall {
+            function: function.name.clone(),
+            selector,
+            args: function
+                .inputs
+                .iter()
+                .map(|(name, _)| name.clone())
+                .zip(values)
+                .collect(),
+        })
+    }
+}
+
+fn word(data: &[u8], offset: usize) -> Result<&[u8; 32], MessageError> {
+    offset
+        .checked_add(32)
+        .and_then(|end| data.get(offset..end))
+        .map(|w| w.try_into().expect("slice is 32 bytes"))
+        .ok_or_else(|| invalid("ABI data too short"))
+}
+
+fn word_usize(data: &[u8], offset: usize) -> Result<usize, MessageError> {
+    let w = word(data, offset)?;
+    if w[..24].iter().any(|b| *b != 0) {
+        return Err(invalid("ABI offset or length out of range"));
+    }
+    let value = u64::from_be_bytes(w[24..].try_into().expect("slice is 8 bytes"));
+    usize::try_from(value).map_err(|_| invalid("ABI offset or length out of range"))
+}
+
+/// Remaining output allowed while decoding one calldata blob
+struct AbiBudget {
+    elements: usize,
+    bytes: usize,
+}
+
+impl Default for AbiBudget {
+    fn default() -> Self {
+        Self {
+            elements: MAX_ABI_ELEMENTS,
+            bytes: MAX_ABI_BYTES,
+        }
+    }
+}
+
+impl AbiBudget {
+    fn take_element(&mut self) -> Result<(), MessageError> {
+        self.elements = self.elements.checked_sub(1).ok_or_else(|| invalid("ABI data has too many values"))?;
+        Ok(())
+    }
+
+    fn take_bytes(&mut self, count: usize) -> Result<(), MessageError> {
+        self.bytes = self.bytes.checked_sub(count).ok_or_else(|| invalid("ABI data has too many bytes"))?;
+        Ok(())
+    }
+}
+
+/// Decode a tuple encoding and return its values and encoded length
+///
+/// Dynamic values must be laid out after the head and after one another,
+/// so no two heads can point at the same tail bytes.
+fn decode_tuple(
+    types: &[AbiType],
+    data: &[u8],
+    depth: usize,
+    budget: &mut AbiBudget,
+) -> Result<(Vec<serde_json::Value>, usize), MessageError> {
+    if depth > MAX_DEPTH {
+        return Err(invalid("ABI nesting too deep"));
+    }
+    let head_len = types
+        .iter()
+        .try_fold(0usize, |len, ty| len.checked_add(ty.head_size()?))
+        .ok_or_else(|| invalid("ABI head too large"))?;
+
+    let mut head = 0;
+    let mut tail_end = head_len;
+    let mut values = Vec::with_capacity(types.len());
+    for ty in types {
+        if ty.is_dynamic() {
+            let offset = word_usize(data, head)?;
+            if offset < tail_end {
+                return Err(invalid("ABI offset points into already decoded data"));
+            }
+            let tail = data.get(offset..).ok_or_else(|| invalid("ABI offset out of bounds"))?;
+            let (value, used) = decode_value(ty, tail, depth, budget)?;
+            values.push(value);
+            tail_end = offset.checked_add(used).ok_or_else(|| invalid("ABI offset out of bounds"))?;
+            head += 32;
+        } else {
+            let slot = data.get(head..).ok_or_else(|| invalid("ABI data too short"))?;
+            values.push(decode_value(ty, slot, depth, budget)?.0);
+            head += ty.head_size().ok_or_else(|| invalid("ABI head too large"))?;
+        }
+    }
+    Ok((values, tail_end))
+}
+
+/// Decode one value and return it with its encoded length
+fn decode_value(
+    ty: &AbiType,
+    data: &[u8],
+    depth: usize,
+    budget: &mut AbiBudget,
+) -> Result<(serde_json::Value, usize), MessageError> {
+    use serde_json::Value;
+
+    budget.take_element()?;
+    match ty {
+        AbiType::Address => {
+            let w = word(data, 0)?;
+            if w[..12].iter().any(|b| *b != 0) {
+                return Err(invalid("Address argument has non-zero padding"));
+            }
+            let address: Address = w[12..].try_into().expect("slice is 20 bytes");
+            Ok((Value::from(to_checksum_address(&address)), 32))
+        }
+        AbiType::Bool => {
+            let w = word(data, 0)?;
+            match (w[..31].iter().all(|b| *b == 0), w[31]) {
+                (true, 0) => Ok((Value::from(false), 32)),
+                (true, 1) => Ok((Value::from(true), 32)),
+                _ => Err(invalid("Bool argument is not 0 or 1")),
+            }
+        }
+        AbiType::Uint(bits) => {
+            let w = word(data, 0)?;
+            if w[..32 - bits / 8].iter().any(|b| *b != 0) {
+                return Err(invalid(format!("Value does not fit in uint{}", bits)));
+            }
+            Ok((Value::from(to_decimal(w)), 32))
+        }
+        AbiType::Int(bits) => {
+            let w = word(data, 0)?;
+            let negative = w[32 - bits / 8] & 0x80 != 0;
+            let fill = if negative { 0xff } else { 0x00 };
+            if w[..32 - bits / 8].iter().any(|b| *b != fill) {
+                return Err(invalid(format!("Value does not fit in int{}", bits)));
+            }
+            if !negative {
+                return Ok((Value::from(to_decimal(w)), 32));
+            }
+            // Two's complement magnitude
+            let mut magnitude = w.map(|b| !b);
+            for byte in magnitude.iter_mut().rev() {
+                let (sum, carry) = byte.overflowing_add(1);
+                *byte = sum;
+                if !carry {
+                    break;
+                }
+            }
+            Ok((Value::from(format!("-{}", to_decimal(&magnitude))), 32))
+        }
+        AbiType::FixedBytes(size) => {
+            let w = word(data, 0)?;
+            if w[*size..].iter().any(|b| *b != 0) {
+                return Err(invalid(format!("bytes{} argument has non-zero padding", size)));
+            }
+            Ok((Value::from(format!("0x{}", hex::encode(&w[..*size]))), 32))
+        }
+        AbiType::Bytes | AbiType::String => {
+            let length = word_usize(data, 0)?;
+            let bytes = 32usize
+                .checked_add(length)
+                .and_then(|end| data.get(32..end))
+                .ok_or_else(|| invalid("ABI bytes length out of bounds"))?;
+            budget.take_bytes(length)?;
+            // Length word plus the contents padded to whole words
+            let used = 32 + length.div_ceil(32) * 32;
+            if *ty == AbiType::String {
+                let s = std::str::from_utf8(bytes).map_err(|_| invalid("String argument is not UTF-8"))?;
+                Ok((Value::from(s), used))
+            } else {
+                Ok((Value::from(format!("0x{}", hex::encode(bytes))), used))
+            }
+        }
+        AbiType::Array(inner) => {
+            let length = word_usize(data, 0)?;
+            let elements = &data[32..];
+            // Every element occupies at least one word
+            if length > elements.len() / 32 || length > budget.elements {
+                return Err(invalid("ABI array length out of bounds"));
+            }
+            let types = vec![(**inner).clone(); length];
+            let (values, used) = decode_tuple(&types, elements, depth + 1, budget)?;
+            Ok((Value::Array(values), 32 + used))
+        }
+        AbiType::FixedArray(inner, size) => {
+            if *size > data.len() / 32 || *size > budget.elements {
+                return Err(invalid("ABI array length out of bounds"));
+            }
+            let types = vec![(**inner).clone(); *size];
+            let (values, used) = decode_tuple(&types, data, depth + 1, budget)?;
+            Ok((Value::Array(values), used))
+        }
+        AbiType::Tuple(types) => {
+            let (values, used) = decode_tuple(types, data, depth + 1, budget)?;
+            Ok((Value::Array(values), used))
+        }
+    }
+}
@@ -1,8 +1,21 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
 use async_trait::async_trait;
-use crate::message::{FrostMessage, MessageType, MessageError};
+use metrics::counter;
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::message::{FrostMessage, MessageType, MessageError, MessagePriority};
+use crate::message::dead_letter::{DeadLetterOrigin, DeadLetterQueue};
+use crate::network::{NetworkError, RetryPolicy};
 use crate::Result;
 
 /// Handler for FROST Protocol messages
@@ -48,4 +61,463 @@ pub struct MessageResult {
     pub success: bool,
     pub processing_time: std::time::Duration,
     pub metadata: serde_json::Value,
-} 
\ No newline at end of file
+} 
+
+/// Processes a single dequeued message
+#[async_trait]
+pub trait MessageProcessor: Send + Sync {
+    /// Process a message, returning result metadata on success
+    async fn process(&self, message: &FrostMessage) -> std::result::Result<serde_json::Value, MessageError>;
+}
+
+/// Configuration for `QueuedMessageHandler`
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    /// Maximum number of queued messages
+    pub max_queue_size: usize,
+    /// Maximum total payload bytes held in the queue
+    pub max_queued_bytes: usize,
+    /// Maximum number of finished (completed or failed) statuses retained
+    pub max_tracked_messages: usize,
+    /// Waiting time after which a message is promoted one priority level
+    pub starvation_threshold: Duration,
+    /// Processing time assumed before any message has completed
+    pub initial_processing_estimate: Duration,
+    /// Number of workers spawned by `start`
+    pub workers: usize,
+    /// Automatically re-queue failures the retry policy accepts
+    pub auto_retry: bool,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_queue_size: 10_000,
+            max_queued_bytes: 64 * 1024 * 1024,
+            max_tracked_messages: 100_000,
+            starvation_threshold: Duration::from_secs(5),
+            initial_processing_estimate: Duration::from_millis(10),
+            workers: 4,
+            auto_retry: true,
+        }
+    }
+}
+
+struct QueueEntry {
+    seq: u64,
+    message: FrostMessage,
+    enqueued_at: Instant,
+    ready_at: Instant,
+}
+
+struct Tracked {
+    status: MessageStatus,
+    attempts: u32,
+    /// Retained for failed messages so they can be retried
+    message: Option<FrostMessage>,
+}
+
+#[derive(Default)]
+struct HandlerState {
+    queue: Vec<QueueEntry>,
+    queued_bytes: usize,
+    next_seq: u64,
+    tracked: HashMap<Uuid, Tracked>,
+    finished: VecDeque<Uuid>,
+    average_processing: Option<Duration>,
+}
+
+fn priority_rank(priority: MessagePriority) -> u64 {
+    match priority {
+        MessagePriority::Low => 0,
+        MessagePriority::Normal => 1,
+        MessagePriority::High => 2,
+        MessagePriority::Critical => 3,
+    }
+}
+
+/// Map a message error onto the network error taxonomy used by `RetryPolicy`
+fn retry_error(error: &MessageError) -> NetworkError {
+    match error {
+        MessageError::Timeout { .. } => NetworkError::Timeout(error.to_string()),
+        _ if error.is_retryable() => NetworkError::MessageSendFailed(error.to_string()),
+        _ => NetworkError::ProtocolError(error.to_string()),
+    }
+}
+
+/// Priority-scheduled `MessageHandler` backed by a `MessageProcessor`
+///
+/// Messages are dequeued by priority, then age. A message waiting longer
+/// than `starvation_threshold` is promoted one level per elapsed threshold
+/// so low-priority traffic is never starved indefinitely. Queue positions
+/// are computed against the current ordering, so they account for aging.
+pub struct QueuedMessageHandler {
+    config: QueueConfig,
+    processor: Arc<dyn MessageProcessor>,
+    retry_policy: Arc<dyn RetryPolicy>,
+    dead_letters: Option<Arc<DeadLetterQueue>>,
+    state: Mutex<HandlerState>,
+    notify: Notify,
+    shutdown: AtomicBool,
+}
+
+impl QueuedMessageHandler {
+    /// Create handler with the given processor and retry policy
+    pub fn new(
+        config: QueueConfig,
+        processor: Arc<dyn MessageProcessor>,
+        retry_policy: Arc<dyn RetryPolicy>,
+    ) -> Self {
+        Self {
+            config,
+            processor,
+            retry_policy,
+            dead_letters: None,
+            state: Mutex::new(HandlerState::default()),
+            notify: Notify::new(),
+            shutdown: AtomicBool::new(false),
+        }
+    }
+
+    /// Capture expired and permanently failed messages in `dead_letters`
+    pub fn with_dead_letter_queue(mut self, dead_letters: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Get handler configuration
+    pub fn config(&self) -> &QueueConfig {
+        &self.config
+    }
+
+    /// Number of queued messages
+    pub fn queue_len(&self) -> usize {
+        self.state.lock().queue.len()
+    }
+
+    /// Total payload bytes held in the queue
+    pub fn queued_bytes(&self) -> usize {
+        self.state.lock().queued_bytes
+    }
+
+    /// Number of message ids with a tracked status
+    pub fn tracked_len(&self) -> usize {
+        self.state.lock().tracked.len()
+    }
+
+    fn effective_rank(&self, entry: &QueueEntry, now: Instant) -> u64 {
+        let waited = now.saturating_duration_since(entry.enqueued_at);
+        let threshold = self.config.starvation_threshold.as_nanos().max(1);
+        let boost = (waited.as_nanos() / threshold) as u64;
+        (priority_rank(entry.message.metadata.priority) + boost)
+            .min(priority_rank(MessagePriority::Critical))
+    }
+
+    /// Ordering key: higher rank first, then oldest first
+    fn order_key(&self, entry: &QueueEntry, now: Instant) -> (std::cmp::Reverse<u64>, u64) {
+        (std::cmp::Reverse(self.effective_rank(entry, now)), entry.seq)
+    }
+
+    fn queued_status(&self, state: &HandlerState, id: Uuid, now: Instant) -> Option<MessageStatus> {
+        let entry = state.queue.iter().find(|e| e.message.id == id)?;
+        let key = self.order_key(entry, now);
+        let position = state
+            .queue
+            .iter()
+            .filter(|e| self.order_key(e, now) < key)
+            .count() as u64;
+
+        let average = state
+            .average_processing
+            .unwrap_or(self.config.initial_processing_estimate);
+        let workers = self.config.workers.max(1) as u64;
+        let estimated_time = (average * (position / workers) as u32)
+            .max(entry.ready_at.saturating_duration_since(now));
+
+        Some(MessageStatus::Queued { position, estimated_time })
+    }
+
+    fn enqueue(&self, state: &mut HandlerState, message: FrostMessage, delay: Duration) -> Result<()> {
+        if state.queue.len() >= self.config.max_queue_size {
+            return Err(MessageError::HandlingFailed(format!(
+                "Message queue full ({} messages)", self.config.max_queue_size
+            )).into());
+        }
+        let size = message.payload.len();
+        if state.queued_bytes + size > self.config.max_queued_bytes {
+            return Err(MessageError::HandlingFailed(format!(
+                "Message queue byte limit exceeded ({} bytes)", self.config.max_queued_bytes
+            )).into());
+        }
+
+        let now = Instant::now();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queued_bytes += size;
+        state.queue.push(QueueEntry {
+            seq,
+            message,
+            enqueued_at: now,
+            ready_at: now + delay,
+        });
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    fn finish(&self, state: &mut HandlerState, id: Uuid, status: MessageStatus, message: Option<FrostMessage>) {
+        if let Some(tracked) = state.tracked.get_mut(&id) {
+            tracked.status = status;
+            tracked.message = message;
+        }
+        state.finished.push_back(id);
+
+        while state.finished.len() > self.config.max_tracked_messages {
+            if let Some(evicted) = state.finished.pop_front() {
+                state.tracked.remove(&evicted);
+            }
+        }
+    }
+
+    fn record_processing_time(state: &mut HandlerState, elapsed: Duration) {
+        state.average_processing = Some(match state.average_processing {
+            Some(average) => average.mul_f64(0.8) + elapsed.mul_f64(0.2),
+            None => elapsed,
+        });
+    }
+
+    /// Run a message through the processor and record the outcome
+    async fn process(&self, mut message: FrostMessage) -> MessageStatus {
+        let id = message.id;
+        let started = Instant::now();
+        {
+            let mut state = self.state.lock();
+            let tracked = state.tracked.entry(id).or_insert(Tracked {
+                status: MessageStatus::Processing { started_at: SystemTime::now(), progress: 0.0 },
+                attempts: 0,
+                message: None,
+            });
+            tracked.attempts += 1;
+            tracked.status = MessageStatus::Processing {
+                started_at: SystemTime::now(),
+                progress: 0.0,
+            };
+        }
+
+        // Expired messages are never processed or retried
+        let expiry = message.check_expiry();
+        let expired = expiry.is_err();
+        let outcome = match expiry {
+            Ok(()) => self.processor.process(&message).await,
+            Err(e) => Err(e),
+        };
+        let elapsed = started.elapsed();
+
+        match outcome {
+            Ok(metadata) => {
+                let status = MessageStatus::Completed {
+                    completed_at: SystemTime::now(),
+                    result: MessageResult {
+                        success: true,
+                        processing_time: elapsed,
+                        metadata,
+                    },
+                };
+                let mut state = self.state.lock();
+                Self::record_processing_time(&mut state, elapsed);
+                self.finish(&mut state, id, status.clone(), None);
+                counter!("frost.message.handler.completed", 1);
+                status
+            }
+            Err(error) => {
+                let attempts = self.state.lock().tracked.get(&id).map(|t| t.attempts).unwrap_or(1);
+                if self.config.auto_retry
+                    && !expired
+                    && self.retry_policy.should_retry(&retry_error(&error), attempts).await
+                {
+                    let delay = self.retry_policy.get_delay(attempts).await;
+                    message.metadata.retry_count += 1;
+                    let mut state = self.state.lock();
+                    Self::record_processing_time(&mut state, elapsed);
+                    if self.enqueue(&mut state, message.clone(), delay).is_ok() {
+                        counter!("frost.message.handler.retried", 1);
+                        let status = self
+                            .queued_status(&state, id, Instant::now())
+                            .expect("message was just queued");
+                        if let Some(tracked) = state.tracked.get_mut(&id) {
+                            tracked.status = status.clone();
+                        }
+                        return status;
+                    }
+                }
+
+                if let Some(dead_letters) = &self.dead_letters {
+                    dead_letters.push_error(message.clone(), DeadLetterOrigin::Handler, &error);
+                }
+                let status = MessageStatus::Failed {
+                    can_retry: error.is_retryable(),
+                    error,
+                };
+                let mut state = self.state.lock();
+                Self::record_processing_time(&mut state, elapsed);
+                self.finish(&mut state, id, status.clone(), Some(message));
+                counter!("frost.message.handler.failed", 1);
+                status
+            }
+        }
+    }
+
+    fn next_ready(&self) -> Option<FrostMessage> {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        let index = state
+            .queue
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.ready_at <= now)
+            .min_by_key(|(_, e)| self.order_key(e, now))
+            .map(|(i, _)| i)?;
+
+        let entry = state.queue.swap_remove(index);
+        state.queued_bytes -= entry.message.payload.len();
+        Some(entry.message)
+    }
+
+    /// Process the highest-priority ready message, if any
+    pub async fn process_next(&self) -> Option<(Uuid, MessageStatus)> {
+        let message = self.next_ready()?;
+        let id = message.id;
+        Some((id, self.process(message).await))
+    }
+
+    /// Time until the earliest delayed message becomes ready
+    fn next_wakeup(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.state
+            .lock()
+            .queue
+            .iter()
+            .map(|e| e.ready_at.saturating_duration_since(now))
+            .min()
+    }
+
+    /// Spawn `config.workers` tasks draining the queue until `shutdown`
+    pub fn start(self: &Arc<Self>) -> Vec<JoinHandle<()>> {
+        (0..self.config.workers.max(1))
+            .map(|_| {
+                let handler = self.clone();
+                tokio::spawn(async move {
+                    while !handler.shutdown.load(Ordering::SeqCst) {
+                        if handler.process_next().await.is_some() {
+                            continue;
+                        }
+                        let wait = handler.next_wakeup().unwrap_or(Duration::from_secs(1));
+                        tokio::select! {
+                            _ = handler.notify.notified() => {}
+                            _ = tokio::time::sleep(wait) => {}
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Stop workers spawned by `start` after their current message
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    fn check_not_active(state: &mut HandlerState, id: Uuid) -> Result<()> {
+        match state.tracked.get(&id).map(|t| &t.status) {
+            Some(MessageStatus::Queued { .. }) | Some(MessageStatus::Processing { .. }) => {
+                Err(MessageError::HandlingFailed(format!("Message {} is already in flight", id)).into())
+            }
+            Some(_) => {
+                state.finished.retain(|finished| *finished != id);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageHandler for QueuedMessageHandler {
+    /// Process a message immediately, bypassing the queue
+    async fn handle_message(&self, message: FrostMessage) -> Result<MessageStatus> {
+        Self::check_not_active(&mut self.state.lock(), message.id)?;
+        Ok(self.process(message).await)
+    }
+
+    async fn queue_message(&self, message: FrostMessage) -> Result<()> {
+        let id = message.id;
+        let mut state = self.state.lock();
+        Self::check_not_active(&mut state, id)?;
+        self.enqueue(&mut state, message, Duration::ZERO)?;
+
+        let status = self
+            .queued_status(&state, id, Instant::now())
+            .expect("message was just queued");
+        state.tracked.insert(id, Tracked { status, attempts: 0, message: None });
+        counter!("frost.message.handler.queued", 1);
+        Ok(())
+    }
+
+    async fn message_status(&self, message_id: Uuid) -> Result<MessageStatus> {
+        let state = self.state.lock();
+        if let Some(status) = self.queued_status(&state, message_id, Instant::now()) {
+            return Ok(status);
+        }
+        state
+            .tracked
+            .get(&message_id)
+            .map(|t| t.status.clone())
+            .ok_or_else(|| MessageError::HandlingFailed(format!("Unknown message {}", message_id)).into())
+    }
+
+    /// Re-queue a failed message after the retry policy's delay
+    async fn retry_message(&self, message_id: Uuid) -> Result<MessageStatus> {
+        let attempts = {
+            let state = self.state.lock();
+            match state.tracked.get(&message_id) {
+                Some(Tracked { status: MessageStatus::Failed { can_retry: true, .. }, attempts, message: Some(_) }) => *attempts,
+                Some(Tracked { status: MessageStatus::Failed { .. }, .. }) => {
+                    return Err(MessageError::HandlingFailed(format!(
+                        "Message {} cannot be retried", message_id
+                    )).into());
+                }
+                Some(_) => {
+                    return Err(MessageError::HandlingFailed(format!(
+                        "Message {} has not failed", message_id
+                    )).into());
+                }
+                None => {
+                    return Err(MessageError::HandlingFailed(format!("Unknown message {}", message_id)).into());
+                }
+            }
+        };
+        let delay = self.retry_policy.get_delay(attempts).await;
+
+        let mut state = self.state.lock();
+        let Some(mut message) = state.tracked.get_mut(&message_id).and_then(|t| t.message.take()) else {
+            return Err(MessageError::HandlingFailed(format!("Message {} is already being retried", message_id)).into());
+        };
+        message.metadata.retry_count += 1;
+        if let Err(e) = self.enqueue(&mut state, message.clone(), delay) {
+            if let Some(tracked) = state.tracked.get_mut(&message_id) {
+                tracked.message = Some(message);
+            }
+            return Err(e);
+        }
+        state.finished.retain(|finished| *finished != message_id);
+
+        let status = self
+            .queued_status(&state, message_id, Instant::now())
+            .expect("message was just queued");
+        if let Some(tracked) = state.tracked.get_mut(&message_id) {
+            tracked.status = status.clone();
+        }
+        counter!("frost.message.handler.retried", 1);
+        Ok(status)
+    }
+}
@@ -1,8 +1,17 @@
 #![allow(unused_imports)]
 
 pub mod types;
+pub mod batch;
+pub mod cosmos;
+pub mod dead_letter;
+pub mod ethereum;
 pub mod handler;
+pub mod replay;
+pub mod rules;
+pub mod schema;
+pub mod solana;
 pub mod validation;
+pub mod validator;
 pub mod error;
 
 pub use types::{
@@ -11,7 +20,12 @@ pub use types::{
     MessageMetadata,
     MessagePriority,
 };
-pub use handler::MessageHandler;
+pub use dead_letter::{DeadLetter, DeadLetterConfig, DeadLetterQueue, DeadLetterReason};
+pub use batch::{BatchExecutor, BatchExecutorConfig, BatchSummary, CompensationHook};
+pub use handler::{MessageHandler, MessageProcessor, QueueConfig, QueuedMessageHandler};
+pub use replay::{ReplayConfig, ReplayGuard};
+pub use rules::{RuleSet, RuleSetConfig, RuleSpec};
+pub use schema::{PayloadSchema, SchemaRegistry};
 pub use validation::MessageValidator;
 pub use error::MessageError;
 
@@ -0,0 +1,300 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant, SystemTime};
+
+use metrics::counter;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::message::{FrostMessage, MessageError};
+use crate::state::ChainId;
+
+/// Content digest of a message
+pub type MessageDigest = [u8; 32];
+
+/// Digest over a message's content
+///
+/// Covers everything a sender controls except the id. Routing-local
+/// metadata such as retry counts and processing metrics is excluded so a
+/// message relayed along different paths keeps the same digest.
+pub fn message_digest(message: &FrostMessage) -> MessageDigest {
+    let content = serde_json::to_vec(&(
+        &message.msg_type,
+        message.timestamp,
+        &message.source,
+        &message.target,
+        &message.source_chain,
+        &message.target_chain,
+        &message.payload,
+        &message.state_transition,
+        &message.finality_signal,
+        &message.block_ref,
+        &message.proof_metadata,
+        message.metadata.nonce,
+    ))
+    .unwrap_or_default();
+    Sha256::digest(content).into()
+}
+
+/// Chain and height a message refers to, if any
+fn message_height(message: &FrostMessage) -> Option<(ChainId, u64)> {
+    if let Some(block_ref) = &message.block_ref {
+        return Some((block_ref.chain_id.clone(), block_ref.number));
+    }
+    message
+        .state_transition
+        .as_ref()
+        .map(|t| (t.chain_id.clone(), t.block_height))
+}
+
+/// Replay protection configuration
+#[derive(Debug, Clone)]
+pub struct ReplayConfig {
+    /// How long seen messages are remembered. Raised to at least twice
+    /// `max_clock_skew` so nothing expires while its timestamp is valid.
+    pub window: Duration,
+    /// Forget and reject messages more than this many blocks behind the
+    /// latest height seen for their chain
+    pub height_window: Option<u64>,
+    /// Maximum number of remembered messages
+    pub max_entries: usize,
+    /// Maximum allowed difference between message timestamp and local time
+    pub max_clock_skew: Duration,
+    /// Sources that must attach a strictly increasing nonce. Nonces are
+    /// tracked for these sources only, so the guard must run after their
+    /// signatures have been verified.
+    pub signed_sources: HashSet<String>,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(600),
+            height_window: None,
+            max_entries: 100_000,
+            max_clock_skew: Duration::from_secs(60),
+            signed_sources: HashSet::new(),
+        }
+    }
+}
+
+struct SeenMessage {
+    digest: MessageDigest,
+    height: Option<(ChainId, u64)>,
+}
+
+#[derive(Default)]
+struct ReplayState {
+    by_id: HashMap<Uuid, SeenMessage>,
+    by_digest: HashMap<MessageDigest, Uuid>,
+    /// Insertion order for expiry
+    order: VecDeque<(Instant, Uuid)>,
+    nonces: HashMap<String, u64>,
+    latest_heights: HashMap<ChainId, u64>,
+}
+
+impl ReplayState {
+    fn forget(&mut self, id: &Uuid) {
+        if let Some(seen) = self.by_id.remove(id) {
+            if self.by_digest.get(&seen.digest) == Some(id) {
+                self.by_digest.remove(&seen.digest);
+            }
+        }
+    }
+}
+
+/// Deduplication and replay protection for incoming messages
+///
+/// A message is rejected as `DuplicateMessage` when the same id and
+/// content was already seen, and as `ReplayDetected` when its id or
+/// content reappears with different counterparts or its nonce does not
+/// advance. Messages outside the clock skew or height window are
+/// rejected as `StaleMessage`.
+///
+/// `source` is only trusted for `signed_sources`, whose nonces must be
+/// checked after the message signature; nonces from any other source are
+/// ignored so an unauthenticated sender cannot advance them.
+pub struct ReplayGuard {
+    config: ReplayConfig,
+    retention: Duration,
+    state: Mutex<ReplayState>,
+}
+
+impl ReplayGuard {
+    /// Create guard with the given configuration
+    pub fn new(config: ReplayConfig) -> Self {
+        let retention = config.window.max(config.max_clock_skew * 2);
+        Self {
+            config,
+            retention,
+            state: Mutex::new(ReplayState::default()),
+        }
+    }
+
+    /// Get guard configuration
+    pub fn config(&self) -> &ReplayConfig {
+        &self.config
+    }
+
+    /// Require nonces from `source`
+    pub fn add_signed_source(&mut self, source: impl Into<String>) {
+        self.config.signed_sources.insert(source.into());
+    }
+
+    /// Number of remembered messages
+    pub fn len(&self) -> usize {
+        self.state.lock().by_id.len()
+    }
+
+    /// Whether no messages are remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Last accepted nonce for `source`
+    pub fn last_nonce(&self, source: &str) -> Option<u64> {
+        self.state.lock().nonces.get(source).copied()
+    }
+
+    /// Check a message and remember it if accepted
+    pub fn check(&self, message: &FrostMessage) -> Result<(), MessageError> {
+        let result = self.check_inner(message);
+        match &result {
+            Ok(()) => {}
+            Err(MessageError::DuplicateMessage { .. }) => counter!("frost.message.replay.duplicates", 1),
+            Err(MessageError::ReplayDetected { .. }) => counter!("frost.message.replay.replays", 1),
+            Err(_) => counter!("frost.message.replay.stale", 1),
+        }
+        result
+    }
+
+    fn check_inner(&self, message: &FrostMessage) -> Result<(), MessageError> {
+        let now = Instant::now();
+        let digest = message_digest(message);
+        let height = message_height(message);
+
+        let mut state = self.state.lock();
+        self.expire(&mut state, now);
+
+        if let Some(seen) = state.by_id.get(&message.id) {
+            return Err(if seen.digest == digest {
+                MessageError::DuplicateMessage { message_id: message.id }
+            } else {
+                MessageError::ReplayDetected {
+                    source_id: message.source.clone(),
+                    details: format!("message id {} reused with different content", message.id),
+                }
+            });
+        }
+        if let Some(original) = state.by_digest.get(&digest) {
+            return Err(MessageError::ReplayDetected {
+                source_id: message.source.clone(),
+                details: format!("content of message {} replayed as {}", original, message.id),
+            });
+        }
+
+        self.check_timestamp(message)?;
+
+        if let (Some((chain_id, height)), Some(window)) = (&height, self.config.height_window) {
+            if let Some(&latest) = state.latest_heights.get(chain_id) {
+                if height.saturating_add(window) < latest {
+                    return Err(MessageError::StaleMessage {
+                        message_id: message.id,
+                        details: format!(
+                            "height {} is more than {} blocks behind {} on {}", height, window, latest, chain_id
+                        ),
+                    });
+                }
+            }
+        }
+
+        // Only signed sources have authenticated nonces worth remembering
+        if self.config.signed_sources.contains(&message.source) {
+            let nonce = message.metadata.nonce.ok_or_else(|| MessageError::ReplayDetected {
+                source_id: message.source.clone(),
+                details: "missing nonce from signed source".into(),
+            })?;
+            if let Some(&last) = state.nonces.get(&message.source) {
+                if nonce <= last {
+                    return Err(MessageError::ReplayDetected {
+                        source_id: message.source.clone(),
+                        details: format!("nonce {} does not advance past {}", nonce, last),
+                    });
+                }
+            }
+            state.nonces.insert(message.source.clone(), nonce);
+        }
+
+        if let Some((chain_id, height)) = &height {
+            let latest = state.latest_heights.entry(chain_id.clone()).or_insert(*height);
+            if *height > *latest {
+                *latest = *height;
+                let latest = *latest;
+                if let Some(window) = self.config.height_window {
+                    self.expire_heights(&mut state, chain_id, latest.saturating_sub(window));
+                }
+            }
+        }
+
+        state.by_id.insert(message.id, SeenMessage { digest, height });
+        state.by_digest.insert(digest, message.id);
+        state.order.push_back((now, message.id));
+
+        while state.by_id.len() > self.config.max_entries {
+            match state.order.pop_front() {
+                Some((_, id)) => state.forget(&id),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn check_timestamp(&self, message: &FrostMessage) -> Result<(), MessageError> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let skew = self.config.max_clock_skew.as_secs();
+
+        if message.timestamp.saturating_add(skew) < now {
+            return Err(MessageError::StaleMessage {
+                message_id: message.id,
+                details: format!("timestamp {} is more than {}s in the past", message.timestamp, skew),
+            });
+        }
+        if message.timestamp > now.saturating_add(skew) {
+            return Err(MessageError::StaleMessage {
+                message_id: message.id,
+                details: format!("timestamp {} is more than {}s in the future", message.timestamp, skew),
+            });
+        }
+        Ok(())
+    }
+
+    fn expire(&self, state: &mut ReplayState, now: Instant) {
+        while let Some(&(seen_at, id)) = state.order.front() {
+            if now.duration_since(seen_at) < self.retention {
+                break;
+            }
+            state.order.pop_front();
+            state.forget(&id);
+        }
+    }
+
+    fn expire_heights(&self, state: &mut ReplayState, chain_id: &ChainId, min_height: u64) {
+        let expired: Vec<Uuid> = state
+            .by_id
+            .iter()
+            .filter(|(_, seen)| {
+                matches!(&seen.height, Some((chain, height)) if chain == chain_id && *height < min_height)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for id in &expired {
+            state.forget(id);
+        }
+        if !expired.is_empty() {
+            state.order.retain(|(_, id)| state.by_id.contains_key(id));
+        }
+    }
+}
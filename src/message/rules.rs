@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::time::{Instant, SystemTime};
+
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+
+use crate::message::{FrostMessage, MessageError, MessagePriority, MessageType, MessageValidator};
+use crate::message::validation::{
+    ValidationFailure, ValidationResult, ValidationRule, ValidationSeverity, ValidationStage,
+};
+use crate::Result;
+
+/// Optional message field required by `RuleSpec::RequiredChainFields`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChainField {
+    SourceChain,
+    TargetChain,
+    StateTransition,
+    FinalitySignal,
+    BlockRef,
+    ProofMetadata,
+}
+
+impl ChainField {
+    fn is_present(&self, message: &FrostMessage) -> bool {
+        match self {
+            Self::SourceChain => message.source_chain.is_some(),
+            Self::TargetChain => message.target_chain.is_some(),
+            Self::StateTransition => message.state_transition.is_some(),
+            Self::FinalitySignal => message.finality_signal.is_some(),
+            Self::BlockRef => message.block_ref.is_some(),
+            Self::ProofMetadata => message.proof_metadata.is_some(),
+        }
+    }
+}
+
+/// Built-in rule definitions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RuleSpec {
+    /// Payload must not exceed `max_bytes`
+    MaxPayloadSize { max_bytes: usize },
+    /// Message type must be one of `types`
+    AllowedMessageTypes { types: Vec<MessageType> },
+    /// Messages of `message_types` (all if empty) must carry `fields`
+    RequiredChainFields {
+        #[serde(default)]
+        message_types: Vec<MessageType>,
+        fields: Vec<ChainField>,
+    },
+    /// Priority must not exceed `max_priority` for `sources` (all if empty)
+    PriorityCap {
+        max_priority: MessagePriority,
+        #[serde(default)]
+        sources: Vec<String>,
+    },
+    /// Source must be one of `sources`
+    SourceAllowlist { sources: Vec<String> },
+    /// Timestamp must be within `max_skew_secs` of local time
+    TimestampSkew { max_skew_secs: u64 },
+}
+
+impl RuleSpec {
+    fn default_id(&self) -> &'static str {
+        match self {
+            Self::MaxPayloadSize { .. } => "max_payload_size",
+            Self::AllowedMessageTypes { .. } => "allowed_message_types",
+            Self::RequiredChainFields { .. } => "required_chain_fields",
+            Self::PriorityCap { .. } => "priority_cap",
+            Self::SourceAllowlist { .. } => "source_allowlist",
+            Self::TimestampSkew { .. } => "timestamp_skew",
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Self::MaxPayloadSize { max_bytes } => format!("Payload must not exceed {} bytes", max_bytes),
+            Self::AllowedMessageTypes { types } => format!("Message type must be one of {:?}", types),
+            Self::RequiredChainFields { fields, .. } => format!("Message must carry {:?}", fields),
+            Self::PriorityCap { max_priority, .. } => format!("Priority must not exceed {:?}", max_priority),
+            Self::SourceAllowlist { .. } => "Source must be allowlisted".to_string(),
+            Self::TimestampSkew { max_skew_secs } => {
+                format!("Timestamp must be within {}s of local time", max_skew_secs)
+            }
+        }
+    }
+
+    fn check(&self, message: &FrostMessage) -> std::result::Result<(), String> {
+        match self {
+            Self::MaxPayloadSize { max_bytes } => {
+                if message.payload.len() > *max_bytes {
+                    return Err(format!(
+                        "Payload size {} exceeds maximum {}", message.payload.len(), max_bytes
+                    ));
+                }
+            }
+            Self::AllowedMessageTypes { types } => {
+                if !types.contains(&message.msg_type) {
+                    return Err(format!("Message type {:?} is not allowed", message.msg_type));
+                }
+            }
+            Self::RequiredChainFields { message_types, fields } => {
+                if message_types.is_empty() || message_types.contains(&message.msg_type) {
+                    let missing: Vec<_> = fields.iter().filter(|f| !f.is_present(message)).collect();
+                    if !missing.is_empty() {
+                        return Err(format!("Missing required fields {:?}", missing));
+                    }
+                }
+            }
+            Self::PriorityCap { max_priority, sources } => {
+                let applies = sources.is_empty() || sources.contains(&message.source);
+                if applies && message.metadata.priority > *max_priority {
+                    return Err(format!(
+                        "Priority {:?} exceeds cap {:?} for source {}",
+                        message.metadata.priority, max_priority, message.source
+                    ));
+                }
+            }
+            Self::SourceAllowlist { sources } => {
+                if !sources.contains(&message.source) {
+                    return Err(format!("Source {} is not allowlisted", message.source));
+                }
+            }
+            Self::TimestampSkew { max_skew_secs } => {
+                let now = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                if now.abs_diff(message.timestamp) > *max_skew_secs {
+                    return Err(format!(
+                        "Timestamp {} is more than {}s from local time {}",
+                        message.timestamp, max_skew_secs, now
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Configured rule with optional id and severity overrides
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleConfig {
+    /// Rule identifier, defaults to the rule kind
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Severity if the rule fails
+    #[serde(default = "default_severity")]
+    pub severity: ValidationSeverity,
+    /// Rule definition
+    #[serde(flatten)]
+    pub spec: RuleSpec,
+}
+
+fn default_severity() -> ValidationSeverity {
+    ValidationSeverity::Error
+}
+
+/// Built-in rule instantiated from a `RuleConfig`
+pub struct ConfiguredRule {
+    id: String,
+    description: String,
+    severity: ValidationSeverity,
+    spec: RuleSpec,
+}
+
+impl ConfiguredRule {
+    /// Build a rule from its configuration
+    pub fn new(config: RuleConfig) -> Self {
+        Self {
+            id: config.id.unwrap_or_else(|| config.spec.default_id().to_string()),
+            description: config.spec.description(),
+            severity: config.severity,
+            spec: config.spec,
+        }
+    }
+
+    /// Rule definition
+    pub fn spec(&self) -> &RuleSpec {
+        &self.spec
+    }
+}
+
+#[async_trait]
+impl ValidationRule for ConfiguredRule {
+    fn rule_id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn validate(&self, message: &FrostMessage) -> Result<bool> {
+        Ok(self.spec.check(message).is_ok())
+    }
+
+    fn severity(&self) -> ValidationSeverity {
+        self.severity
+    }
+
+    fn failure_reason(&self, message: &FrostMessage) -> String {
+        self.spec.check(message).err().unwrap_or_else(|| self.description.clone())
+    }
+}
+
+/// Serialized rule set, one rule list per stage
+///
+/// ```toml
+/// short_circuit = "Critical"
+///
+/// [[pre_validation]]
+/// rule = "max_payload_size"
+/// max_bytes = 1048576
+/// severity = "Critical"
+///
+/// [[pre_validation]]
+/// rule = "source_allowlist"
+/// sources = ["relayer-1", "relayer-2"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleSetConfig {
+    /// Stop evaluating a stage at the first failure of at least this severity
+    #[serde(default = "default_short_circuit")]
+    pub short_circuit: ValidationSeverity,
+    #[serde(default)]
+    pub pre_validation: Vec<RuleConfig>,
+    #[serde(default)]
+    pub proof_validation: Vec<RuleConfig>,
+    #[serde(default)]
+    pub state_validation: Vec<RuleConfig>,
+    #[serde(default)]
+    pub post_validation: Vec<RuleConfig>,
+}
+
+fn default_short_circuit() -> ValidationSeverity {
+    ValidationSeverity::Critical
+}
+
+impl Default for RuleSetConfig {
+    fn default() -> Self {
+        Self {
+            short_circuit: default_short_circuit(),
+            pre_validation: Vec::new(),
+            proof_validation: Vec::new(),
+            state_validation: Vec::new(),
+            post_validation: Vec::new(),
+        }
+    }
+}
+
+impl RuleSetConfig {
+    /// Parse from JSON
+    pub fn from_json(input: &str) -> std::result::Result<Self, MessageError> {
+        serde_json::from_str(input)
+            .map_err(|e| MessageError::InvalidFormat(format!("Invalid rule set: {}", e)))
+    }
+
+    /// Parse from TOML
+    pub fn from_toml(input: &str) -> std::result::Result<Self, MessageError> {
+        toml::from_str(input)
+            .map_err(|e| MessageError::InvalidFormat(format!("Invalid rule set TOML: {}", e)))
+    }
+}
+
+/// Validation rules grouped by pipeline stage
+///
+/// Rules in a stage run in order. Warnings are reported without failing
+/// the stage; a failure at or above `short_circuit` severity stops the
+/// stage without running the remaining rules.
+pub struct RuleSet {
+    stages: HashMap<ValidationStage, Vec<Box<dyn ValidationRule>>>,
+    short_circuit: ValidationSeverity,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuleSet {
+    const STAGES: [ValidationStage; 4] = [
+        ValidationStage::PreValidation,
+        ValidationStage::ProofValidation,
+        ValidationStage::StateValidation,
+        ValidationStage::PostValidation,
+    ];
+
+    /// Create empty rule set
+    pub fn new() -> Self {
+        Self {
+            stages: HashMap::new(),
+            short_circuit: ValidationSeverity::Critical,
+        }
+    }
+
+    /// Build a rule set from configuration
+    pub fn from_config(config: RuleSetConfig) -> Self {
+        let mut set = Self::new();
+        set.short_circuit = config.short_circuit;
+        let stages = [
+            (ValidationStage::PreValidation, config.pre_validation),
+            (ValidationStage::ProofValidation, config.proof_validation),
+            (ValidationStage::StateValidation, config.state_validation),
+            (ValidationStage::PostValidation, config.post_validation),
+        ];
+        for (stage, rules) in stages {
+            for rule in rules {
+                set.add_stage_rule(stage, Box::new(ConfiguredRule::new(rule)));
+            }
+        }
+        set
+    }
+
+    /// Build a rule set from JSON configuration
+    pub fn from_json(input: &str) -> std::result::Result<Self, MessageError> {
+        RuleSetConfig::from_json(input).map(Self::from_config)
+    }
+
+    /// Build a rule set from TOML configuration
+    pub fn from_toml(input: &str) -> std::result::Result<Self, MessageError> {
+        RuleSetConfig::from_toml(input).map(Self::from_config)
+    }
+
+    /// Severity at which a stage stops evaluating
+    pub fn short_circuit(&self) -> ValidationSeverity {
+        self.short_circuit
+    }
+
+    /// Add a rule to a stage
+    pub fn add_stage_rule(&mut self, stage: ValidationStage, rule: Box<dyn ValidationRule>) {
+        self.stages.entry(stage).or_default().push(rule);
+    }
+
+    /// Rule ids configured for a stage
+    pub fn rule_ids(&self, stage: ValidationStage) -> Vec<String> {
+        self.stages
+            .get(&stage)
+            .map(|rules| rules.iter().map(|r| r.rule_id().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Total number of rules
+    pub fn len(&self) -> usize {
+        self.stages.values().map(Vec::len).sum()
+    }
+
+    /// Whether no rules are configured
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evaluate the rules of one stage
+    pub async fn evaluate(&self, stage: ValidationStage, message: &FrostMessage) -> ValidationResult {
+        let start = Instant::now();
+        let mut rules_passed = Vec::new();
+        let mut rules_failed = Vec::new();
+        let mut short_circuited = false;
+
+        for rule in self.stages.get(&stage).into_iter().flatten() {
+            let failure = match rule.validate(message).await {
+                Ok(true) => {
+                    rules_passed.push(rule.rule_id().to_string());
+                    continue;
+                }
+                Ok(false) => rule.failure_reason(message),
+                Err(e) => format!("Rule {} errored: {}", rule.rule_id(), e),
+            };
+            let severity = rule.severity();
+            rules_failed.push(ValidationFailure {
+                rule_id: rule.rule_id().to_string(),
+                reason: failure,
+                severity,
+            });
+            if severity >= self.short_circuit {
+                short_circuited = true;
+                break;
+            }
+        }
+
+        ValidationResult {
+            is_valid: rules_failed.iter().all(|f| f.severity == ValidationSeverity::Warning),
+            rules_passed,
+            rules_failed,
+            stage,
+            duration_ms: start.elapsed().as_millis() as u64,
+            metadata: short_circuited.then(|| serde_json::json!({ "short_circuited": true })),
+        }
+    }
+
+    /// Evaluate every stage in order, stopping after the first invalid one
+    pub async fn evaluate_all(&self, message: &FrostMessage) -> Vec<ValidationResult> {
+        let mut results = Vec::new();
+        for stage in Self::STAGES {
+            let result = self.evaluate(stage, message).await;
+            let is_valid = result.is_valid;
+            results.push(result);
+            if !is_valid {
+                break;
+            }
+        }
+        results
+    }
+}
+
+#[async_trait]
+impl MessageValidator for RuleSet {
+    /// Validate against all stages, merging the per-stage results
+    async fn validate(&self, message: &FrostMessage) -> Result<ValidationResult> {
+        let start = Instant::now();
+        let results = self.evaluate_all(message).await;
+        let stage = results.last().map(|r| r.stage).unwrap_or(ValidationStage::PreValidation);
+
+        let mut merged = ValidationResult {
+            is_valid: results.iter().all(|r| r.is_valid),
+            rules_passed: Vec::new(),
+            rules_failed: Vec::new(),
+            stage,
+            duration_ms: 0,
+            metadata: None,
+        };
+        for result in results {
+            merged.rules_passed.extend(result.rules_passed);
+            merged.rules_failed.extend(result.rules_failed);
+            if result.metadata.is_some() {
+                merged.metadata = result.metadata;
+            }
+        }
+        merged.duration_ms = start.elapsed().as_millis() as u64;
+        Ok(merged)
+    }
+
+    /// Add a rule to the pre-validation stage
+    fn add_rule(&mut self, rule: Box<dyn ValidationRule>) {
+        self.add_stage_rule(ValidationStage::PreValidation, rule);
+    }
+
+    /// Remove a rule from every stage
+    fn remove_rule(&mut self, rule_id: &str) {
+        for rules in self.stages.values_mut() {
+            rules.retain(|r| r.rule_id() != rule_id);
+        }
+    }
+}
+
@@ -0,0 +1,604 @@
+use std::collections::{BTreeMap, HashMap};
+
+use parity_scale_codec::{Compact, Decode, Encode};
+use parking_lot::RwLock;
+use regex::Regex;
+use scale_info::{MetaType, PortableRegistry, Registry, TypeDef, TypeDefPrimitive, TypeInfo};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::message::{FrostMessage, MessageError, MessageType};
+
+/// Maximum nesting followed when validating a payload
+const MAX_DEPTH: usize = 32;
+
+/// Maximum length of a SCALE sequence of zero-sized elements
+const MAX_ZERO_SIZED_ELEMENTS: u32 = 1024;
+
+/// Names accepted by the JSON Schema `type` keyword
+const TYPE_NAMES: [&str; 7] = ["null", "boolean", "object", "array", "string", "number", "integer"];
+
+/// Declared shape of a custom message payload
+#[derive(Debug, Clone)]
+pub enum PayloadSchema {
+    /// UTF-8 JSON payload checked against a JSON Schema
+    Json(JsonSchema),
+    /// SCALE-encoded payload described by `TypeInfo`
+    Scale(ScaleSchema),
+}
+
+impl PayloadSchema {
+    /// Check `payload` against the schema
+    pub fn validate(&self, payload: &[u8]) -> std::result::Result<(), String> {
+        match self {
+            Self::Json(schema) => {
+                let value: Value = serde_json::from_slice(payload).map_err(|e| format!("invalid JSON: {}", e))?;
+                schema.validate(&value)
+            }
+            Self::Scale(schema) => schema.validate(payload),
+        }
+    }
+}
+
+/// JSON Schema subset
+///
+/// Supports `type`, `enum`, `const`, `properties`, `required`,
+/// `additionalProperties`, `items`, `minItems`/`maxItems`,
+/// `minLength`/`maxLength`, `pattern`, `minimum`/`maximum` (and their
+/// exclusive forms), and `allOf`/`anyOf`/`oneOf`, plus annotations such as
+/// `title` and `description`. Schemas using any other keyword, including
+/// `$ref`, are rejected at registration rather than silently accepted.
+#[derive(Debug, Clone)]
+pub struct JsonSchema {
+    schema: Value,
+    patterns: HashMap<String, Regex>,
+}
+
+impl JsonSchema {
+    /// Compile a schema, rejecting unsupported or malformed keywords
+    pub fn new(schema: Value) -> Result<Self, MessageError> {
+        let mut patterns = HashMap::new();
+        Self::compile(&schema, &mut patterns, 0)?;
+        Ok(Self { schema, patterns })
+    }
+
+    /// Underlying schema document
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    fn compile(schema: &Value, patterns: &mut HashMap<String, Regex>, depth: usize) -> Result<(), MessageError> {
+        let invalid = |reason: String| MessageError::InvalidFormat(format!("Invalid JSON schema: {}", reason));
+        if depth > MAX_DEPTH {
+            return Err(invalid("nested too deeply".into()));
+        }
+        let object = match schema {
+            Value::Bool(_) => return Ok(()),
+            Value::Object(object) => object,
+            other => return Err(invalid(format!("expected object, got {}", other))),
+        };
+
+        for (key, value) in object {
+            match key.as_str() {
+                // Annotations without validation semantics
+                "$schema" | "$id" | "$comment" | "title" | "description" | "default" | "examples" => {}
+                "type" => {
+                    let names = match value {
+                        Value::Array(names) => names.iter().collect(),
+                        name => vec![name],
+                    };
+                    for name in names {
+                        if !name.as_str().is_some_and(|n| TYPE_NAMES.contains(&n)) {
+                            return Err(invalid(format!("unknown type {}", name)));
+                        }
+                    }
+                }
+                "enum" => {
+                    value.as_array().ok_or_else(|| invalid("enum must be an array".into()))?;
+                }
+                "const" => {}
+                "required" => {
+                    let all_strings = value.as_array().is_some_and(|names| names.iter().all(Value::is_string));
+                    if !all_strings {
+                        return Err(invalid("required must be an array of strings".into()));
+                    }
+                }
+                "minItems" | "maxItems" | "minLength" | "maxLength" => {
+                    value.as_u64().ok_or_else(|| invalid(format!("{} must be a non-negative integer", key)))?;
+                }
+                "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                    value.as_f64().ok_or_else(|| invalid(format!("{} must be a number", key)))?;
+                }
+                "pattern" => {
+                    let pattern = value.as_str().ok_or_else(|| invalid("pattern must be a string".into()))?;
+                    let regex = Regex::new(pattern).map_err(|e| invalid(format!("bad pattern {}: {}", pattern, e)))?;
+                    patterns.insert(pattern.to_string(), regex);
+                }
+                "properties" => {
+                    let properties = value.as_object().ok_or_else(|| invalid("properties must be an object".into()))?;
+                    for property in properties.values() {
+                        Self::compile(property, patterns, depth + 1)?;
+                    }
+                }
+                "items" | "additionalProperties" => Self::compile(value, patterns, depth + 1)?,
+                "allOf" | "anyOf" | "oneOf" => {
+                    let subs = value.as_array().ok_or_else(|| invalid(format!("{} must be an array", key)))?;
+                    for sub in subs {
+                        Self::compile(sub, patterns, depth + 1)?;
+                    }
+                }
+                // `$ref` and every other keyword would otherwise be silently ignored
+                other => return Err(invalid(format!("{} is not supported", other))),
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a JSON value against the schema
+    pub fn validate(&self, value: &Value) -> std::result::Result<(), String> {
+        self.check(&self.schema, value, "$")
+    }
+
+    fn check(&self, schema: &Value, value: &Value, path: &str) -> std::result::Result<(), String> {
+        let object = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => return Err(format!("{}: not allowed", path)),
+            Value::Object(object) => object,
+            _ => return Ok(()),
+        };
+
+        if let Some(expected) = object.get("type") {
+            let matches = match expected {
+                Value::String(name) => type_matches(name, value),
+                Value::Array(names) => names.iter().filter_map(Value::as_str).any(|n| type_matches(n, value)),
+                _ => true,
+            };
+            if !matches {
+                return Err(format!("{}: expected type {}", path, expected));
+            }
+        }
+        if let Some(Value::Array(options)) = object.get("enum") {
+            if !options.contains(value) {
+                return Err(format!("{}: value not in enum", path));
+            }
+        }
+        if let Some(constant) = object.get("const") {
+            if constant != value {
+                return Err(format!("{}: expected {}", path, constant));
+            }
+        }
+
+        match value {
+            Value::Object(fields) => {
+                if let Some(Value::Array(required)) = object.get("required") {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !fields.contains_key(name) {
+                            return Err(format!("{}: missing required property {}", path, name));
+                        }
+                    }
+                }
+                let properties = object.get("properties").and_then(Value::as_object);
+                for (name, field) in fields {
+                    let field_path = format!("{}.{}", path, name);
+                    match properties.and_then(|p| p.get(name)) {
+                        Some(sub) => self.check(sub, field, &field_path)?,
+                        None => {
+                            if let Some(additional) = object.get("additionalProperties") {
+                                self.check(additional, field, &field_path)?;
+                            }
+                        }
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = object.get("minItems").and_then(Value::as_u64) {
+                    if (items.len() as u64) < min {
+                        return Err(format!("{}: fewer than {} items", path, min));
+                    }
+                }
+                if let Some(max) = object.get("maxItems").and_then(Value::as_u64) {
+                    if items.len() as u64 > max {
+                        return Err(format!("{}: more than {} items", path, max));
+                    }
+                }
+                if let Some(sub) = object.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        self.check(sub, item, &format!("{}[{}]", path, i))?;
+                    }
+                }
+            }
+            Value::String(s) => {
+                let length = s.chars().count() as u64;
+                if let Some(min) = object.get("minLength").and_then(Value::as_u64) {
+                    if length < min {
+                        return Err(format!("{}: shorter than {} characters", path, min));
+                    }
+                }
+                if let Some(max) = object.get("maxLength").and_then(Value::as_u64) {
+                    if length > max {
+                        return Err(format!("{}: longer than {} characters", path, max));
+                    }
+                }
+                if let Some(pattern) = object.get("pattern").and_then(Value::as_str) {
+                    if !self.patterns.get(pattern).is_some_and(|re| re.is_match(s)) {
+                        return Err(format!("{}: does not match {}", path, pattern));
+                    }
+                }
+            }
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or_default();
+                let bound = |key: &str| object.get(key).and_then(Value::as_f64);
+                if bound("minimum").is_some_and(|min| n < min)
+                    || bound("maximum").is_some_and(|max| n > max)
+                    || bound("exclusiveMinimum").is_some_and(|min| n <= min)
+                    || bound("exclusiveMaximum").is_some_and(|max| n >= max)
+                {
+                    return Err(format!("{}: {} out of range", path, n));
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(Value::Array(subs)) = object.get("allOf") {
+            for sub in subs {
+                self.check(sub, value, path)?;
+            }
+        }
+        if let Some(Value::Array(subs)) = object.get("anyOf") {
+            if !subs.iter().any(|sub| self.check(sub, value, path).is_ok()) {
+                return Err(format!("{}: matches none of anyOf", path));
+            }
+        }
+        if let Some(Value::Array(subs)) = object.get("oneOf") {
+            let matched = subs.iter().filter(|sub| self.check(sub, value, path).is_ok()).count();
+            if matched != 1 {
+                return Err(format!("{}: matches {} of oneOf, expected exactly 1", path, matched));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0),
+        _ => false,
+    }
+}
+
+/// SCALE payload description built from a type's `TypeInfo`
+#[derive(Debug, Clone)]
+pub struct ScaleSchema {
+    registry: PortableRegistry,
+    type_id: u32,
+}
+
+impl ScaleSchema {
+    /// Describe payloads encoding `T`
+    pub fn of<T: TypeInfo + 'static>() -> Self {
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&MetaType::new::<T>()).id;
+        Self { registry: registry.into(), type_id }
+    }
+
+    /// Describe payloads encoding type `type_id` of an existing registry
+    pub fn from_registry(registry: PortableRegistry, type_id: u32) -> Result<Self, MessageError> {
+        if registry.resolve(type_id).is_none() {
+            return Err(MessageError::InvalidFormat(format!("Type {} is not in the registry", type_id)));
+        }
+        Ok(Self { registry, type_id })
+    }
+
+    /// Check that `payload` is exactly one encoded value of the type
+    pub fn validate(&self, payload: &[u8]) -> std::result::Result<(), String> {
+        let mut input = payload;
+        self.skip(self.type_id, &mut input, 0)?;
+        if !input.is_empty() {
+            return Err(format!("{} trailing bytes", input.len()));
+        }
+        Ok(())
+    }
+
+    fn skip(&self, type_id: u32, input: &mut &[u8], depth: usize) -> std::result::Result<(), String> {
+        if depth > MAX_DEPTH {
+            return Err("nested too deeply".into());
+        }
+        let ty = self.registry.resolve(type_id).ok_or_else(|| format!("unknown type {}", type_id))?;
+        let name = || ty.path.segments.join("::");
+
+        match &ty.type_def {
+            TypeDef::Composite(composite) => {
+                for field in &composite.fields {
+                    self.skip(field.ty.id, input, depth + 1)?;
+                }
+            }
+            TypeDef::Variant(variant) => {
+                let index = decode::<u8>(input, "variant index")?;
+                let selected = variant
+                    .variants
+                    .iter()
+                    .find(|v| v.index == index)
+                    .ok_or_else(|| format!("{}: unknown variant index {}", name(), index))?;
+                for field in &selected.fields {
+                    self.skip(field.ty.id, input, depth + 1)?;
+                }
+            }
+            TypeDef::Sequence(sequence) => {
+                let len = decode::<Compact<u32>>(input, "sequence length")?.0;
+                // Every element takes at least one byte unless it is zero-sized,
+                // in which case only the declared count bounds the work
+                if self.is_zero_sized(sequence.type_param.id, 0) {
+                    if len > MAX_ZERO_SIZED_ELEMENTS {
+                        return Err(format!("sequence of {} zero-sized elements is too long", len));
+                    }
+                } else if len as usize > input.len() {
+                    return Err(format!("sequence length {} exceeds remaining input", len));
+                }
+                for _ in 0..len {
+                    self.skip(sequence.type_param.id, input, depth + 1)?;
+                }
+            }
+            TypeDef::Array(array) => {
+                for _ in 0..array.len {
+                    self.skip(array.type_param.id, input, depth + 1)?;
+                }
+            }
+            TypeDef::Tuple(tuple) => {
+                for field in &tuple.fields {
+                    self.skip(field.id, input, depth + 1)?;
+                }
+            }
+            TypeDef::Primitive(primitive) => skip_primitive(primitive, input)?,
+            TypeDef::Compact(compact) => self.skip_compact(compact.type_param.id, input, depth + 1)?,
+            TypeDef::BitSequence(_) => return Err("bit sequences are not supported".into()),
+        }
+        Ok(())
+    }
+
+    /// Decode a compact integer at the width of its inner type, seen
+    /// through single-field wrappers
+    fn skip_compact(&self, type_id: u32, input: &mut &[u8], depth: usize) -> std::result::Result<(), String> {
+        if depth > MAX_DEPTH {
+            return Err("nested too deeply".into());
+        }
+        let ty = self.registry.resolve(type_id).ok_or_else(|| format!("unknown type {}", type_id))?;
+        match &ty.type_def {
+            TypeDef::Primitive(TypeDefPrimitive::U8) => decode::<Compact<u8>>(input, "compact u8").map(drop),
+            TypeDef::Primitive(TypeDefPrimitive::U16) => decode::<Compact<u16>>(input, "compact u16").map(drop),
+            TypeDef::Primitive(TypeDefPrimitive::U32) => decode::<Compact<u32>>(input, "compact u32").map(drop),
+            TypeDef::Primitive(TypeDefPrimitive::U64) => decode::<Compact<u64>>(input, "compact u64").map(drop),
+            TypeDef::Primitive(TypeDefPrimitive::U128) => decode::<Compact<u128>>(input, "compact u128").map(drop),
+            TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+                self.skip_compact(composite.fields[0].ty.id, input, depth + 1)
+            }
+            TypeDef::Tuple(tuple) if tuple.fields.len() == 1 => self.skip_compact(tuple.fields[0].id, input, depth + 1),
+            _ => Err(format!("compact encoding of {} is not supported", ty.path.segments.join("::"))),
+        }
+    }
+
+    fn is_zero_sized(&self, type_id: u32, depth: usize) -> bool {
+        let Some(ty) = self.registry.resolve(type_id).filter(|_| depth <= MAX_DEPTH) else {
+            return false;
+        };
+        match &ty.type_def {
+            TypeDef::Composite(c) => c.fields.iter().all(|f| self.is_zero_sized(f.ty.id, depth + 1)),
+            TypeDef::Tuple(t) => t.fields.iter().all(|f| self.is_zero_sized(f.id, depth + 1)),
+            TypeDef::Array(a) => a.len == 0 || self.is_zero_sized(a.type_param.id, depth + 1),
+            _ => false,
+        }
+    }
+}
+
+fn decode<T: Decode>(input: &mut &[u8], what: &str) -> std::result::Result<T, String> {
+    T::decode(input).map_err(|e| format!("invalid {}: {}", what, e))
+}
+
+fn skip_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> std::result::Result<(), String> {
+    let width = match primitive {
+        TypeDefPrimitive::Bool => return decode::<bool>(input, "bool").map(drop),
+        TypeDefPrimitive::Char => {
+            let code = decode::<u32>(input, "char")?;
+            return char::from_u32(code).map(drop).ok_or_else(|| format!("invalid char {}", code));
+        }
+        TypeDefPrimitive::Str => return decode::<String>(input, "string").map(drop),
+        TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+        TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+        TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+        TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+        TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+        TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+    };
+    if input.len() < width {
+        return Err("input too short".into());
+    }
+    *input = &input[width..];
+    Ok(())
+}
+
+/// Registry of versioned schemas for `MessageType::Custom` payloads
+///
+/// Each custom type name maps to one schema per version. A message's
+/// schema version is carried in `MessageMetadata::version`; peers agree on
+/// a version with `negotiate`.
+#[derive(Debug, Default)]
+pub struct SchemaRegistry {
+    schemas: RwLock<HashMap<String, BTreeMap<u16, PayloadSchema>>>,
+    reject_unregistered: bool,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject custom messages whose type has no registered schema
+    pub fn with_unregistered_rejected(mut self, reject: bool) -> Self {
+        self.reject_unregistered = reject;
+        self
+    }
+
+    /// Register `schema` as `version` of `type_name`
+    pub fn register(&self, type_name: &str, version: u16, schema: PayloadSchema) -> Result<(), MessageError> {
+        let mut schemas = self.schemas.write();
+        let versions = schemas.entry(type_name.to_string()).or_default();
+        if versions.contains_key(&version) {
+            return Err(MessageError::InvalidFormat(format!(
+                "Schema {} version {} is already registered", type_name, version
+            )));
+        }
+        versions.insert(version, schema);
+        Ok(())
+    }
+
+    /// Register a JSON Schema
+    pub fn register_json(&self, type_name: &str, version: u16, schema: Value) -> Result<(), MessageError> {
+        self.register(type_name, version, PayloadSchema::Json(JsonSchema::new(schema)?))
+    }
+
+    /// Register the SCALE encoding of `T`
+    pub fn register_scale<T: TypeInfo + 'static>(&self, type_name: &str, version: u16) -> Result<(), MessageError> {
+        self.register(type_name, version, PayloadSchema::Scale(ScaleSchema::of::<T>()))
+    }
+
+    /// Registered type names
+    pub fn type_names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.schemas.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Registered versions of `type_name`, ascending
+    pub fn versions(&self, type_name: &str) -> Vec<u16> {
+        self.schemas
+            .read()
+            .get(type_name)
+            .map(|versions| versions.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Schema for `version` of `type_name`
+    pub fn schema(&self, type_name: &str, version: u16) -> Option<PayloadSchema> {
+        self.schemas.read().get(type_name)?.get(&version).cloned()
+    }
+
+    /// Highest version of `type_name` supported both locally and by a peer
+    pub fn negotiate(&self, type_name: &str, peer_versions: &[u16]) -> Option<u16> {
+        self.versions(type_name)
+            .into_iter()
+            .rev()
+            .find(|v| peer_versions.contains(v))
+    }
+
+    fn resolve(&self, type_name: &str, version: u16) -> Result<PayloadSchema, MessageError> {
+        let schemas = self.schemas.read();
+        let versions = schemas.get(type_name).ok_or_else(|| MessageError::SchemaViolation {
+            type_name: type_name.to_string(),
+            version,
+            details: "no schema registered".into(),
+        })?;
+        versions.get(&version).cloned().ok_or_else(|| MessageError::UnsupportedSchemaVersion {
+            type_name: type_name.to_string(),
+            version,
+            supported: versions.keys().copied().collect(),
+        })
+    }
+
+    /// Validate the payload of a custom message
+    ///
+    /// Non-custom messages pass. Custom messages of unregistered types pass
+    /// unless `with_unregistered_rejected` is set.
+    pub fn validate(&self, message: &FrostMessage) -> Result<(), MessageError> {
+        let MessageType::Custom(type_name) = &message.msg_type else {
+            return Ok(());
+        };
+        if !self.reject_unregistered && !self.schemas.read().contains_key(type_name) {
+            return Ok(());
+        }
+        self.check_payload(type_name, message.metadata.version, &message.payload)
+    }
+
+    fn check_payload(&self, type_name: &str, version: u16, payload: &[u8]) -> Result<(), MessageError> {
+        self.resolve(type_name, version)?
+            .validate(payload)
+            .map_err(|details| MessageError::SchemaViolation {
+                type_name: type_name.to_string(),
+                version,
+                details,
+            })
+    }
+
+    /// Build a custom message with a JSON payload checked against its schema
+    pub fn encode_json<T: Serialize>(
+        &self,
+        type_name: &str,
+        version: u16,
+        value: &T,
+        source: String,
+        target: Option<String>,
+    ) -> Result<FrostMessage, MessageError> {
+        let payload = serde_json::to_vec(value)
+            .map_err(|e| MessageError::InvalidFormat(format!("Failed to encode {}: {}", type_name, e)))?;
+        self.check_payload(type_name, version, &payload)?;
+        let mut message = FrostMessage::new(MessageType::Custom(type_name.to_string()), payload, source, target);
+        message.metadata.version = version;
+        Ok(message)
+    }
+
+    /// Build a custom message with a SCALE payload checked against its schema
+    pub fn encode_scale<T: Encode>(
+        &self,
+        type_name: &str,
+        version: u16,
+        value: &T,
+        source: String,
+        target: Option<String>,
+    ) -> Result<FrostMessage, MessageError> {
+        let payload = value.encode();
+        self.check_payload(type_name, version, &payload)?;
+        let mut message = FrostMessage::new(MessageType::Custom(type_name.to_string()), payload, source, target);
+        message.metadata.version = version;
+        Ok(message)
+    }
+
+    fn expect_type<'a>(message: &'a FrostMessage, type_name: &str) -> Result<&'a str, MessageError> {
+        match &message.msg_type {
+            MessageType::Custom(name) if name == type_name => Ok(name),
+            other => Err(MessageError::InvalidFormat(format!("Expected {} message, got {:?}", type_name, other))),
+        }
+    }
+
+    /// Validate and decode the JSON payload of a `type_name` message
+    pub fn decode_json<T: DeserializeOwned>(&self, type_name: &str, message: &FrostMessage) -> Result<T, MessageError> {
+        Self::expect_type(message, type_name)?;
+        self.check_payload(type_name, message.metadata.version, &message.payload)?;
+        serde_json::from_slice(&message.payload)
+            .map_err(|e| MessageError::InvalidFormat(format!("Failed to decode {}: {}", type_name, e)))
+    }
+
+    /// Validate and decode the SCALE payload of a `type_name` message
+    pub fn decode_scale<T: Decode>(&self, type_name: &str, message: &FrostMessage) -> Result<T, MessageError> {
+        Self::expect_type(message, type_name)?;
+        self.check_payload(type_name, message.metadata.version, &message.payload)?;
+        T::decode(&mut message.payload.as_slice())
+            .map_err(|e| MessageError::InvalidFormat(format!("Failed to decode {}: {}", type_name, e)))
+    }
+}
+
+impl From<ScaleSchema> for PayloadSchema {
+    fn from(schema: ScaleSchema) -> Self {
+        Self::Scale(schema)
+    }
+}
+
+impl From<JsonSchema> for PayloadSchema {
+    fn from(schema: JsonSchema) -> Self {
+        Self::Json(schema)
+    }
+}
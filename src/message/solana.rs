@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+
+use crate::message::MessageError;
+
+/// Solana account address
+pub type Pubkey = [u8; 32];
+
+const SIGNATURE_LEN: usize = 64;
+const PUBKEY_LEN: usize = 32;
+
+fn invalid(reason: impl Into<String>) -> MessageError {
+    MessageError::InvalidFormat(reason.into())
+}
+
+/// Parse a base58 account address
+pub fn parse_pubkey(input: &str) -> Result<Pubkey, MessageError> {
+    bs58::decode(input)
+        .into_vec()
+        .map_err(|e| invalid(format!("Invalid pubkey {}: {}", input, e)))?
+        .try_into()
+        .map_err(|_| invalid(format!("Pubkey {} must be 32 bytes", input)))
+}
+
+/// Base58 representation of an account address
+pub fn encode_pubkey(key: &Pubkey) -> String {
+    bs58::encode(key).into_string()
+}
+
+/// Byte reader for the wire format
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, field: &str) -> Result<&'a [u8], MessageError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| invalid(format!("Transaction truncated in {}", field)))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self, field: &str) -> Result<u8, MessageError> {
+        Ok(self.take(1, field)?[0])
+    }
+
+    fn array<const N: usize>(&mut self, field: &str) -> Result<[u8; N], MessageError> {
+        Ok(self.take(N, field)?.try_into().expect("length checked"))
+    }
+
+    /// Compact-u16 ("shortvec") length, rejecting non-canonical encodings
+    fn compact_u16(&mut self, field: &str) -> Result<usize, MessageError> {
+        let mut value = 0usize;
+        for i in 0..3 {
+            let byte = self.u8(field)?;
+            value |= ((byte & 0x7f) as usize) << (7 * i);
+            if byte & 0x80 == 0 {
+                if byte == 0 && i > 0 {
+                    return Err(invalid(format!("Non-canonical length in {}", field)));
+                }
+                if value > u16::MAX as usize {
+                    return Err(invalid(format!("Length overflow in {}", field)));
+                }
+                return Ok(value);
+            }
+        }
+        Err(invalid(format!("Length overflow in {}", field)))
+    }
+
+    fn vec<T>(
+        &mut self,
+        field: &str,
+        min_item_len: usize,
+        mut item: impl FnMut(&mut Self) -> Result<T, MessageError>,
+    ) -> Result<Vec<T>, MessageError> {
+        let count = self.compact_u16(field)?;
+        // Bound allocation by what the remaining input could hold
+        if count.saturating_mul(min_item_len) > self.data.len() - self.pos {
+            return Err(invalid(format!("Transaction truncated in {}", field)));
+        }
+        (0..count).map(|_| item(self)).collect()
+    }
+}
+
+/// Message version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageVersion {
+    Legacy,
+    V0,
+}
+
+/// Message header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+/// Compiled instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    /// Index of the program in the static account keys
+    pub program_id_index: u8,
+    /// Indices into static keys followed by lookup table addresses
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// Address lookup table reference (v0 messages)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressTableLookup {
+    pub account_key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// Decoded Solana transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaTransaction {
+    pub signatures: Vec<[u8; SIGNATURE_LEN]>,
+    pub version: MessageVersion,
+    pub header: MessageHeader,
+    pub account_keys: Vec<Pubkey>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+    pub address_table_lookups: Vec<AddressTableLookup>,
+    /// Serialized message the signatures cover
+    pub message: Vec<u8>,
+}
+
+impl SolanaTransaction {
+    /// Decode a serialized legacy or v0 transaction
+    pub fn decode(raw: &[u8]) -> Result<Self, MessageError> {
+        let mut reader = Reader { data: raw, pos: 0 };
+        let signatures = reader.vec("signatures", SIGNATURE_LEN, |r| r.array("signature"))?;
+        let message_start = reader.pos;
+
+        let prefix = *raw.get(reader.pos).ok_or_else(|| invalid("Transaction has no message"))?;
+        let version = if prefix & 0x80 != 0 {
+            reader.pos += 1;
+            match prefix & 0x7f {
+                0 => MessageVersion::V0,
+                v => return Err(invalid(format!("Unsupported message version {}", v))),
+            }
+        } else {
+            MessageVersion::Legacy
+        };
+
+        let header = MessageHeader {
+            num_required_signatures: reader.u8("header")?,
+            num_readonly_signed_accounts: reader.u8("header")?,
+            num_readonly_unsigned_accounts: reader.u8("header")?,
+        };
+        let account_keys = reader.vec("account keys", PUBKEY_LEN, |r| r.array("account key"))?;
+        let recent_blockhash = reader.array("recent blockhash")?;
+        let instructions = reader.vec("instructions", 3, |r| {
+            Ok(CompiledInstruction {
+                program_id_index: r.u8("program id index")?,
+                accounts: r.vec("instruction accounts", 1, |r| r.u8("account index"))?,
+                data: r.vec("instruction data", 1, |r| r.u8("instruction data"))?,
+            })
+        })?;
+        let address_table_lookups = match version {
+            MessageVersion::Legacy => Vec::new(),
+            MessageVersion::V0 => reader.vec("address table lookups", PUBKEY_LEN + 2, |r| {
+                Ok(AddressTableLookup {
+                    account_key: r.array("lookup table")?,
+                    writable_indexes: r.vec("writable indexes", 1, |r| r.u8("lookup index"))?,
+                    readonly_indexes: r.vec("readonly indexes", 1, |r| r.u8("lookup index"))?,
+                })
+            })?,
+        };
+
+        if reader.pos != raw.len() {
+            return Err(invalid(format!("{} trailing bytes after transaction", raw.len() - reader.pos)));
+        }
+
+        let tx = Self {
+            signatures,
+            version,
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups,
+            message: raw[message_start..].to_vec(),
+        };
+        tx.check_structure()?;
+        Ok(tx)
+    }
+
+    /// Accounts loaded through address lookup tables
+    pub fn lookup_account_count(&self) -> usize {
+        self.address_table_lookups
+            .iter()
+            .map(|l| l.writable_indexes.len() + l.readonly_indexes.len())
+            .sum()
+    }
+
+    /// Total accounts referenced, including lookup table entries
+    pub fn total_account_count(&self) -> usize {
+        self.account_keys.len() + self.lookup_account_count()
+    }
+
+    /// Accounts that must sign
+    pub fn signers(&self) -> &[Pubkey] {
+        let count = (self.header.num_required_signatures as usize).min(self.account_keys.len());
+        &self.account_keys[..count]
+    }
+
+    /// Program ids invoked by the instructions
+    pub fn program_ids(&self) -> impl Iterator<Item = &Pubkey> {
+        self.instructions
+            .iter()
+            .map(|ix| &self.account_keys[ix.program_id_index as usize])
+    }
+
+    fn check_structure(&self) -> Result<(), MessageError> {
+        let header = &self.header;
+        let static_count = self.account_keys.len();
+        let required = header.num_required_signatures as usize;
+
+        if required == 0 {
+            return Err(invalid("Transaction requires no signatures"));
+        }
+        if self.signatures.len() != required {
+            return Err(invalid(format!(
+                "Transaction has {} signatures but requires {}", self.signatures.len(), required
+            )));
+        }
+        if required + header.num_readonly_unsigned_accounts as usize > static_count {
+            return Err(invalid("Header references more accounts than the transaction has"));
+        }
+        if header.num_readonly_signed_accounts >= header.num_required_signatures {
+            return Err(invalid("Fee payer must be writable"));
+        }
+
+        let mut seen = HashSet::new();
+        for (i, key) in self.account_keys.iter().enumerate() {
+            if !seen.insert(key) {
+                return Err(invalid(if i < required {
+                    format!("Duplicate signer {}", encode_pubkey(key))
+                } else {
+                    format!("Duplicate account key {}", encode_pubkey(key))
+                }));
+            }
+        }
+
+        let total = self.total_account_count();
+        if total > 256 {
+            return Err(invalid(format!("Transaction references {} accounts", total)));
+        }
+        for ix in &self.instructions {
+            // Programs cannot be loaded through lookup tables
+            if ix.program_id_index as usize >= static_count || ix.program_id_index == 0 {
+                return Err(invalid(format!("Invalid program id index {}", ix.program_id_index)));
+            }
+            if let Some(index) = ix.accounts.iter().find(|i| **i as usize >= total) {
+                return Err(invalid(format!("Instruction account index {} out of range", index)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify each signature against its signer over the message
+    pub fn verify_signatures(&self) -> Result<(), MessageError> {
+        for (signature, signer) in self.signatures.iter().zip(self.signers()) {
+            let key = VerifyingKey::from_bytes(signer)
+                .map_err(|_| invalid(format!("Signer {} is not a valid ed25519 key", encode_pubkey(signer))))?;
+            key.verify_strict(&self.message, &Signature::from_bytes(signature))
+                .map_err(|_| invalid(format!("Invalid signature for signer {}", encode_pubkey(signer))))?;
+        }
+        Ok(())
+    }
+
+    /// Summary of decoded fields for metrics
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut programs: Vec<String> = self.program_ids().map(encode_pubkey).collect();
+        programs.dedup();
+        serde_json::json!({
+            "version": format!("{:?}", self.version),
+            "signatures": self.signatures.len(),
+            "fee_payer": self.account_keys.first().map(encode_pubkey),
+            "accounts": self.total_account_count(),
+            "lookup_tables": self.address_table_lookups.len(),
+            "instructions": self.instructions.len(),
+            "programs": programs,
+            "recent_blockhash": bs58::encode(self.recent_blockhash).into_string(),
+        })
+    }
+}
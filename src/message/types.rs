@@ -2,6 +2,7 @@ use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use std::time::SystemTime;
 use crate::state::{ChainId, StateTransition, BlockRef};
+use crate::state::proof::VerificationParams;
 use crate::finality::FinalitySignal;
 
 /// Protocol message types
@@ -22,7 +23,7 @@ pub enum MessageType {
 }
 
 /// Message priority levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum MessagePriority {
     Low,
     Normal,
@@ -51,6 +52,17 @@ pub struct ProofMetadata {
     pub expires_at: Option<SystemTime>,
 }
 
+impl ProofMetadata {
+    /// Build verification parameters carrying the chain-specific params
+    pub fn to_verification_params(&self) -> VerificationParams {
+        VerificationParams {
+            security_level: self.security_level,
+            extra_params: self.verification_params.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 /// Additional message metadata
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct MessageMetadata {
@@ -60,6 +72,12 @@ pub struct MessageMetadata {
     pub priority: MessagePriority,
     /// Retry count for failed messages
     pub retry_count: u32,
+    /// Sender nonce, strictly increasing per source for signed senders
+    #[serde(default)]
+    pub nonce: Option<u64>,
+    /// Deadline in seconds since the Unix epoch, after which the message is dropped
+    #[serde(default)]
+    pub expires_at: Option<u64>,
     /// Chain-specific metadata
     pub chain_metadata: Option<serde_json::Value>,
     /// Custom metadata fields
@@ -243,6 +261,44 @@ impl FrostMessage {
         }
     }
 
+    /// Expire the message `ttl` after its timestamp
+    pub fn with_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.metadata.expires_at = Some(self.timestamp.saturating_add(ttl.as_secs()));
+        self
+    }
+
+    /// Expire the message at `deadline` seconds since the Unix epoch
+    pub fn with_deadline(mut self, deadline: u64) -> Self {
+        self.metadata.expires_at = Some(deadline);
+        self
+    }
+
+    /// Whether the message is past its deadline at `now` (Unix seconds)
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.metadata.expires_at.is_some_and(|deadline| now >= deadline)
+    }
+
+    /// Whether the message is past its deadline
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        )
+    }
+
+    /// Fail with `MessageError::Expired` if the message is past its deadline
+    pub fn check_expiry(&self) -> std::result::Result<(), super::MessageError> {
+        match self.metadata.expires_at {
+            Some(expires_at) if self.is_expired() => Err(super::MessageError::Expired {
+                message_id: self.id,
+                expires_at,
+            }),
+            _ => Ok(()),
+        }
+    }
+
     /// Validate basic message properties
     pub fn validate(&self) -> bool {
         // Basic validation
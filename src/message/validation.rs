@@ -5,11 +5,17 @@ use async_trait::async_trait;
 use crate::message::{FrostMessage, MessageError};
 use crate::message::types::BatchMessage;
 use crate::Result;
+use crate::message::rules::RuleSet;
+use crate::message::schema::SchemaRegistry;
+use crate::message::dead_letter::{DeadLetterOrigin, DeadLetterQueue};
 use crate::extensions::ExtensionHooks;
+use crate::network::{ReputationEvent, ReputationManager};
+use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 use tracing::{info, warn, error};
 use metrics::{counter, histogram};
 use std::fmt;
+use uuid::Uuid;
 
 /// Trait for validation rules
 #[async_trait]
@@ -25,6 +31,11 @@ pub trait ValidationRule: Send + Sync {
     
     /// Severity if validation fails
     fn severity(&self) -> ValidationSeverity;
+
+    /// Reason reported when `message` fails this rule
+    fn failure_reason(&self, message: &FrostMessage) -> String {
+        self.description().to_string()
+    }
 }
 
 /// Validator for FROST Protocol messages
@@ -66,7 +77,7 @@ pub struct ValidationFailure {
 }
 
 /// Severity of validation failures
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ValidationSeverity {
     Warning,
     Error,
@@ -74,7 +85,7 @@ pub enum ValidationSeverity {
 }
 
 /// Validation stage in the pipeline
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ValidationStage {
     PreValidation,
     ProofValidation,
@@ -108,8 +119,8 @@ pub struct TransformationResult {
 /// Message validation pipeline
 #[async_trait]
 pub trait ValidationPipeline: Send + Sync {
-    /// Get extension hooks
-    fn extension_hooks(&self) -> &ExtensionHooks;
+    /// Extension hooks run around each stage, `None` to skip them
+    fn installed_extension_hooks(&self) -> Option<&ExtensionHooks>;
 
     /// Pre-validation checks
     async fn pre_validate(&self, msg: &FrostMessage) -> ValidationResult;
@@ -124,34 +135,57 @@ pub trait ValidationPipeline: Send + Sync {
     async fn post_validate(&self, msg: &FrostMessage) -> ValidationResult;
     
     /// Run full validation pipeline
+    ///
+    /// Failures are not attributed to any peer.
     async fn validate(&self, msg: &mut FrostMessage) -> Result<()> {
+        self.run_stages(None, msg).await
+    }
+
+    /// Run full validation pipeline on a message received from `peer_id`
+    ///
+    /// `peer_id` must be the sender as authenticated by the transport, never
+    /// a value taken from the message itself; failures are attributed to it.
+    async fn validate_from(&self, peer_id: Uuid, msg: &mut FrostMessage) -> Result<()> {
+        self.run_stages(Some(peer_id), msg).await
+    }
+
+    /// Stages run by `validate` and `validate_from`
+    async fn run_stages(&self, peer_id: Option<Uuid>, msg: &mut FrostMessage) -> Result<()> {
         // Update metrics first
         msg.update_metrics();
         
         // Run extension pre-validation hooks
-        self.extension_hooks().pre_validate(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        if let Some(hooks) = self.installed_extension_hooks() {
+            hooks.pre_validate(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        }
         
         // Run validation stages sequentially
         let pre_result = self.pre_validate(msg).await;
-        self.process_validation_result(msg, &pre_result, ValidationStage::PreValidation).await?;
+        self.process_validation_result_from(msg, &pre_result, ValidationStage::PreValidation, peer_id).await?;
         
         // Run extension proof validation hooks
-        self.extension_hooks().validate_proof(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        if let Some(hooks) = self.installed_extension_hooks() {
+            hooks.validate_proof(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        }
         
         let proof_result = self.validate_proof(msg).await;
-        self.process_validation_result(msg, &proof_result, ValidationStage::ProofValidation).await?;
+        self.process_validation_result_from(msg, &proof_result, ValidationStage::ProofValidation, peer_id).await?;
         
         // Run extension state validation hooks
-        self.extension_hooks().validate_state(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        if let Some(hooks) = self.installed_extension_hooks() {
+            hooks.validate_state(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        }
         
         let state_result = self.validate_state(msg).await;
-        self.process_validation_result(msg, &state_result, ValidationStage::StateValidation).await?;
+        self.process_validation_result_from(msg, &state_result, ValidationStage::StateValidation, peer_id).await?;
         
         // Run extension post-validation hooks
-        self.extension_hooks().post_validate(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        if let Some(hooks) = self.installed_extension_hooks() {
+            hooks.post_validate(msg).await.map_err(|e| MessageError::ValidationFailed(e.to_string()))?;
+        }
         
         let post_result = self.post_validate(msg).await;
-        self.process_validation_result(msg, &post_result, ValidationStage::PostValidation).await?;
+        self.process_validation_result_from(msg, &post_result, ValidationStage::PostValidation, peer_id).await?;
         
         Ok(())
     }
@@ -190,6 +224,20 @@ pub trait ValidationPipeline: Send + Sync {
         Ok(())
     }
     
+    /// Process the validation result of a message from `peer_id`
+    ///
+    /// `peer_id` is the authenticated sender, if known. Defaults to
+    /// `process_validation_result`, ignoring the sender.
+    async fn process_validation_result_from(
+        &self,
+        msg: &mut FrostMessage,
+        result: &ValidationResult,
+        stage: ValidationStage,
+        peer_id: Option<Uuid>,
+    ) -> Result<()> {
+        self.process_validation_result(msg, result, stage).await
+    }
+
     /// Validate batch of messages
     async fn validate_batch(&self, batch: &mut BatchMessage) -> Result<Vec<ValidationResult>> {
         let mut results = Vec::with_capacity(batch.messages.len());
@@ -263,24 +311,78 @@ pub trait TransformationPipeline: Send + Sync {
 /// Basic validation pipeline implementation
 pub struct BasicValidationPipeline {
     transformers: Vec<Arc<dyn TransformationPipeline>>,
+    rules: RuleSet,
+    schemas: Option<Arc<SchemaRegistry>>,
+    dead_letters: Option<Arc<DeadLetterQueue>>,
+    reputation: Option<Arc<ReputationManager>>,
+    hooks: Option<ExtensionHooks>,
 }
 
 impl BasicValidationPipeline {
     pub fn new() -> Self {
         Self {
             transformers: Vec::new(),
+            rules: RuleSet::new(),
+            schemas: None,
+            dead_letters: None,
+            reputation: None,
+            hooks: None,
         }
     }
-    
+
+    /// Use the given rule set for every stage
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Check custom message payloads against `schemas` during pre-validation
+    pub fn with_schema_registry(mut self, schemas: Arc<SchemaRegistry>) -> Self {
+        self.schemas = Some(schemas);
+        self
+    }
+
+    /// Capture expired messages in `dead_letters` during pre-validation
+    pub fn with_dead_letter_queue(mut self, dead_letters: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Report failed validations against the sending peer's reputation
+    ///
+    /// Failures are attributed to the peer passed to `validate_from`;
+    /// proof failures count as invalid proofs.
+    pub fn with_reputation(mut self, reputation: Arc<ReputationManager>) -> Self {
+        self.reputation = Some(reputation);
+        self
+    }
+
+    /// Run the given extension hooks around each stage
+    pub fn with_extension_hooks(mut self, hooks: ExtensionHooks) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Configured rule set
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
     pub fn add_transformer(&mut self, transformer: Arc<dyn TransformationPipeline>) {
         self.transformers.push(transformer);
     }
 }
 
+impl Default for BasicValidationPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl ValidationPipeline for BasicValidationPipeline {
-    fn extension_hooks(&self) -> &ExtensionHooks {
-        unimplemented!("Extension hooks not implemented")
+    fn installed_extension_hooks(&self) -> Option<&ExtensionHooks> {
+        self.hooks.as_ref()
     }
 
     async fn process_validation_result(
@@ -288,6 +390,16 @@ impl ValidationPipeline for BasicValidationPipeline {
         msg: &mut FrostMessage,
         result: &ValidationResult,
         stage: ValidationStage,
+    ) -> Result<()> {
+        self.process_validation_result_from(msg, result, stage, None).await
+    }
+
+    async fn process_validation_result_from(
+        &self,
+        msg: &mut FrostMessage,
+        result: &ValidationResult,
+        stage: ValidationStage,
+        peer_id: Option<Uuid>,
     ) -> Result<()> {
         // Update validation attempts
         if let Some(metrics) = &mut msg.metadata.metrics {
@@ -306,6 +418,15 @@ impl ValidationPipeline for BasicValidationPipeline {
         );
         
         if !result.is_valid {
+            if let (Some(reputation), Some(peer_id)) = (&self.reputation, peer_id) {
+                let event = match stage {
+                    ValidationStage::ProofValidation => ReputationEvent::InvalidProof,
+                    _ => ReputationEvent::FailedValidation,
+                };
+                if let Err(e) = reputation.update_reputation(peer_id, event).await {
+                    warn!("Failed to update reputation of {}: {}", peer_id, e);
+                }
+            }
             if !result.rules_failed.is_empty() {
                 return Err(MessageError::ValidationFailed(
                     result.rules_failed[0].reason.clone()
@@ -320,62 +441,74 @@ impl ValidationPipeline for BasicValidationPipeline {
         let start = std::time::Instant::now();
         
         // Basic message validation
-        let is_valid = msg.validate();
-        
-        ValidationResult {
-            is_valid,
-            rules_passed: vec![],
-            rules_failed: if !is_valid {
-                vec![ValidationFailure {
+        if !msg.validate() {
+            return ValidationResult {
+                is_valid: false,
+                rules_passed: vec![],
+                rules_failed: vec![ValidationFailure {
                     rule_id: "basic_validation".into(),
                     reason: "Basic validation failed".into(),
                     severity: ValidationSeverity::Error,
-                }]
-            } else {
-                vec![]
-            },
-            stage: ValidationStage::PreValidation,
-            duration_ms: start.elapsed().as_millis() as u64,
-            metadata: None,
+                }],
+                stage: ValidationStage::PreValidation,
+                duration_ms: start.elapsed().as_millis() as u64,
+                metadata: None,
+            };
+        }
+
+        let mut passed = vec!["basic_validation".to_string()];
+        if let Err(e) = msg.check_expiry() {
+            if let Some(dead_letters) = &self.dead_letters {
+                dead_letters.push_error(msg.clone(), DeadLetterOrigin::Validation, &e);
+            }
+            return ValidationResult {
+                is_valid: false,
+                rules_passed: passed,
+                rules_failed: vec![ValidationFailure {
+                    rule_id: "message_expiry".into(),
+                    reason: e.to_string(),
+                    severity: ValidationSeverity::Error,
+                }],
+                stage: ValidationStage::PreValidation,
+                duration_ms: start.elapsed().as_millis() as u64,
+                metadata: None,
+            };
+        }
+        passed.push("message_expiry".into());
+
+        if let Some(schemas) = &self.schemas {
+            if let Err(e) = schemas.validate(msg) {
+                return ValidationResult {
+                    is_valid: false,
+                    rules_passed: passed,
+                    rules_failed: vec![ValidationFailure {
+                        rule_id: "payload_schema".into(),
+                        reason: e.to_string(),
+                        severity: ValidationSeverity::Error,
+                    }],
+                    stage: ValidationStage::PreValidation,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    metadata: None,
+                };
+            }
+            passed.push("payload_schema".into());
         }
+
+        let mut result = self.rules.evaluate(ValidationStage::PreValidation, msg).await;
+        result.rules_passed.splice(0..0, passed);
+        result.duration_ms = start.elapsed().as_millis() as u64;
+        result
     }
     
     async fn validate_proof(&self, msg: &FrostMessage) -> ValidationResult {
-        let start = std::time::Instant::now();
-        
-        ValidationResult {
-            is_valid: true,
-            rules_passed: vec![],
-            rules_failed: vec![],
-            stage: ValidationStage::ProofValidation,
-            duration_ms: start.elapsed().as_millis() as u64,
-            metadata: None,
-        }
+        self.rules.evaluate(ValidationStage::ProofValidation, msg).await
     }
     
     async fn validate_state(&self, msg: &FrostMessage) -> ValidationResult {
-        let start = std::time::Instant::now();
-        
-        ValidationResult {
-            is_valid: true,
-            rules_passed: vec![],
-            rules_failed: vec![],
-            stage: ValidationStage::StateValidation,
-            duration_ms: start.elapsed().as_millis() as u64,
-            metadata: None,
-        }
+        self.rules.evaluate(ValidationStage::StateValidation, msg).await
     }
     
     async fn post_validate(&self, msg: &FrostMessage) -> ValidationResult {
-        let start = std::time::Instant::now();
-        
-        ValidationResult {
-            is_valid: true,
-            rules_passed: vec![],
-            rules_failed: vec![],
-            stage: ValidationStage::PostValidation,
-            duration_ms: start.elapsed().as_millis() as u64,
-            metadata: None,
-        }
+        self.rules.evaluate(ValidationStage::PostValidation, msg).await
     }
 }
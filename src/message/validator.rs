@@ -1,9 +1,12 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
-use tracing::{info, warn, error};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use parking_lot::RwLock;
 
-use crate::state::BlockRef;
 use crate::message::{FrostMessage, MessageError};
+use crate::message::cosmos::{self, AddressKind, CosmosTransaction};
+use crate::message::ethereum::{self, Abi, Address, EthereumTransaction};
+use crate::message::solana::{self, Pubkey, SolanaTransaction};
 
 /// Message validation metrics
 #[derive(Debug, Clone, Default)]
@@ -40,20 +43,135 @@ pub trait MessageValidator: Send + Sync {
     async fn update_config(&mut self, config: ValidatorConfig) -> Result<(), MessageError>;
 }
 
+/// Record a validation outcome
+fn record_validation(metrics: &RwLock<ValidationMetrics>, started: Instant, failed: bool) {
+    let mut metrics = metrics.write();
+    let duration = started.elapsed().as_secs_f64();
+
+    metrics.total_validated += 1;
+    metrics.avg_validation_time = (metrics.avg_validation_time * (metrics.total_validated - 1) as f64
+        + duration) / metrics.total_validated as f64;
+
+    if failed {
+        metrics.failed_validations += 1;
+    }
+}
+
+/// Publish decoded fields under `chain` in the chain metrics
+///
+/// Keeps a running `counter` next to the most recent decoded value.
+fn publish_decoded(
+    metrics: &RwLock<ValidationMetrics>,
+    chain: &str,
+    counter: &str,
+    key: &str,
+    decoded: serde_json::Value,
+) {
+    let mut metrics = metrics.write();
+    let entry = metrics
+        .chain_metrics
+        .entry(chain.into())
+        .or_insert_with(|| serde_json::json!({}));
+    let count = entry.get(counter).and_then(|c| c.as_u64()).unwrap_or(0);
+    entry[counter] = (count + 1).into();
+    entry[key] = decoded;
+}
+
+/// Payload of a chain message and its `payload_type` hint
+///
+/// Messages name their chain and payload kind in `chain_metadata`, e.g.
+/// `{"chain": "ethereum", "payload_type": "transaction"}`. Messages that
+/// name a different chain are rejected.
+fn chain_payload<'a>(message: &'a FrostMessage, chain: &str) -> Result<(&'a [u8], Option<&'a str>), MessageError> {
+    let metadata = message.metadata.chain_metadata.as_ref();
+    if let Some(name) = metadata.and_then(|m| m.get("chain")).and_then(|c| c.as_str()) {
+        if !name.eq_ignore_ascii_case(chain) {
+            return Err(MessageError::InvalidFormat(format!("Not a {} message", chain)));
+        }
+    }
+    let payload_type = metadata.and_then(|m| m.get("payload_type")).and_then(|t| t.as_str());
+    Ok((&message.payload, payload_type))
+}
+
+/// Ethereum chain parameters parsed from `ValidatorConfig::chain_params`
+///
+/// Recognized keys: `abi` (JSON ABI), `chain_id`, `allowed_addresses`
+/// (permitted transaction recipients) and `payload_type` (`calldata` or
+/// `transaction`). Messages may repeat the payload type as a hint, but a
+/// hint that differs from the configured type is rejected.
+#[derive(Debug, Clone, Default)]
+struct EthereumParams {
+    abi: Option<Abi>,
+    chain_id: Option<u64>,
+    allowed_addresses: Option<HashSet<Address>>,
+    decode_transactions: bool,
+}
+
+impl EthereumParams {
+    fn parse(params: &serde_json::Value) -> Result<Self, MessageError> {
+        let abi = params.get("abi").map(Abi::from_json).transpose()?;
+
+        let chain_id = match params.get("chain_id") {
+            None => None,
+            Some(value) => Some(value.as_u64().ok_or_else(|| {
+                MessageError::InvalidFormat(format!("Invalid chain_id {}", value))
+            })?),
+        };
+
+        let allowed_addresses = match params.get("allowed_addresses") {
+            None => None,
+            Some(serde_json::Value::Array(addresses)) => Some(
+                addresses
+                    .iter()
+                    .map(|a| {
+                        a.as_str()
+                            .ok_or_else(|| MessageError::InvalidFormat(format!("Invalid address {}", a)))
+                            .and_then(ethereum::parse_address)
+                    })
+                    .collect::<Result<HashSet<_>, _>>()?,
+            ),
+            Some(other) => {
+                return Err(MessageError::InvalidFormat(format!("allowed_addresses must be a list, got {}", other)));
+            }
+        };
+
+        let decode_transactions = Self::is_transaction(params.get("payload_type").and_then(|t| t.as_str()))?;
+
+        Ok(Self { abi, chain_id, allowed_addresses, decode_transactions })
+    }
+
+    fn is_transaction(payload_type: Option<&str>) -> Result<bool, MessageError> {
+        match payload_type {
+            None | Some("calldata") => Ok(false),
+            Some("transaction") => Ok(true),
+            Some(other) => Err(MessageError::InvalidFormat(format!("Unknown Ethereum payload type {}", other))),
+        }
+    }
+}
+
 /// Ethereum message validator
+///
+/// Payloads are contract calldata or raw signed transactions (legacy,
+/// EIP-2930 or EIP-1559). Calldata is decoded and type-checked against the
+/// configured ABI; transactions are checked against the configured chain id
+/// and recipient allowlist. Decoded fields are published under the
+/// `ethereum` key of `ValidationMetrics::chain_metrics`.
 pub struct EthereumValidator {
     config: ValidatorConfig,
-    metrics: ValidationMetrics,
+    params: EthereumParams,
+    metrics: RwLock<ValidationMetrics>,
 }
 
 impl EthereumValidator {
-    pub fn new(config: ValidatorConfig) -> Self {
-        Self {
+    /// Create validator, rejecting malformed chain parameters
+    pub fn new(config: ValidatorConfig) -> Result<Self, MessageError> {
+        Ok(Self {
+            params: EthereumParams::parse(&config.chain_params)?,
             config,
-            metrics: ValidationMetrics::default(),
-        }
+            metrics: RwLock::new(ValidationMetrics::default()),
+        })
     }
-    
+
     fn validate_calldata(&self, calldata: &[u8]) -> Result<(), MessageError> {
         if calldata.len() > self.config.max_message_size {
             return Err(MessageError::InvalidFormat(
@@ -61,72 +179,219 @@ impl EthereumValidator {
                     calldata.len(), self.config.max_message_size)
             ));
         }
-        
-        // TODO: Add more Ethereum-specific calldata validation
+
+        // Plain value transfers carry no calldata
+        let Some(abi) = self.params.abi.as_ref().filter(|_| !calldata.is_empty()) else {
+            return Ok(());
+        };
+        let call = abi.decode_call(calldata)?;
+        publish_decoded(&self.metrics, "ethereum", "decoded_calls", "last_call", call.to_json());
+        Ok(())
+    }
+
+    fn validate_transaction(&self, raw: &[u8]) -> Result<(), MessageError> {
+        if raw.len() > self.config.max_message_size {
+            return Err(MessageError::InvalidFormat(
+                format!("Transaction size {} exceeds maximum {}",
+                    raw.len(), self.config.max_message_size)
+            ));
+        }
+
+        let tx = EthereumTransaction::decode(raw)?;
+
+        if let Some(expected) = self.params.chain_id {
+            match tx.chain_id {
+                Some(chain_id) if chain_id == expected => {}
+                Some(chain_id) => {
+                    return Err(MessageError::InvalidFormat(format!(
+                        "Transaction chain id {} does not match {}", chain_id, expected
+                    )));
+                }
+                None => {
+                    return Err(MessageError::InvalidFormat(
+                        "Transaction lacks EIP-155 replay protection".into()
+                    ));
+                }
+            }
+        }
+
+        if let Some(allowed) = &self.params.allowed_addresses {
+            match &tx.to {
+                Some(to) if allowed.contains(to) => {}
+                Some(to) => {
+                    return Err(MessageError::InvalidFormat(format!(
+                        "Recipient {} is not allowed", ethereum::to_checksum_address(to)
+                    )));
+                }
+                None => {
+                    return Err(MessageError::InvalidFormat("Contract creation is not allowed".into()));
+                }
+            }
+        }
+
+        publish_decoded(&self.metrics, "ethereum", "decoded_transactions", "last_transaction", tx.to_json());
+        if tx.to.is_some() {
+            self.validate_calldata(&tx.data)?;
+        }
         Ok(())
     }
+
 }
 
 #[async_trait]
 impl MessageValidator for EthereumValidator {
     async fn validate_message(&self, message: &FrostMessage) -> Result<(), MessageError> {
-        let start = std::time::Instant::now();
-        
-        let result = match message.payload {
-            MessagePayload::Ethereum { ref calldata, .. } => {
-                self.validate_calldata(calldata)
+        let start = Instant::now();
+
+        let result = chain_payload(message, "ethereum").and_then(|(payload, payload_type)| {
+            // Only the operator picks the payload type; a sender could
+            // otherwise skip the transaction checks by claiming calldata
+            if let Some(hint) = payload_type {
+                if EthereumParams::is_transaction(Some(hint))? != self.params.decode_transactions {
+                    return Err(MessageError::InvalidFormat(format!(
+                        "Payload type {} does not match the configured payload type", hint
+                    )));
+                }
             }
-            _ => Err(MessageError::InvalidFormat("Not an Ethereum message".into())),
-        };
-        
-        // Update metrics
-        let mut metrics = self.metrics.clone();
-        let duration = start.elapsed().as_secs_f64();
-        
-        metrics.total_validated += 1;
-        metrics.avg_validation_time = (metrics.avg_validation_time * (metrics.total_validated - 1) as f64
-            + duration) / metrics.total_validated as f64;
-            
-        if result.is_err() {
-            metrics.failed_validations += 1;
-        }
-        
+            if self.params.decode_transactions {
+                self.validate_transaction(payload)
+            } else {
+                self.validate_calldata(payload)
+            }
+        });
+
+        record_validation(&self.metrics, start, result.is_err());
         result
     }
     
     async fn get_metrics(&self) -> ValidationMetrics {
-        self.metrics.clone()
+        self.metrics.read().clone()
     }
     
     async fn update_config(&mut self, config: ValidatorConfig) -> Result<(), MessageError> {
+        self.params = EthereumParams::parse(&config.chain_params)?;
         self.config = config;
         Ok(())
     }
 }
 
+/// Solana chain parameters parsed from `ValidatorConfig::chain_params`
+///
+/// Recognized keys: `allowed_programs` (base58 program ids),
+/// `max_accounts` (including lookup table entries),
+/// `max_instruction_accounts` and `verify_signatures` (default true).
+#[derive(Debug, Clone)]
+struct SolanaParams {
+    allowed_programs: Option<HashSet<Pubkey>>,
+    max_accounts: Option<usize>,
+    max_instruction_accounts: Option<usize>,
+    verify_signatures: bool,
+}
+
+impl SolanaParams {
+    fn parse(params: &serde_json::Value) -> Result<Self, MessageError> {
+        let limit = |key: &str| -> Result<Option<usize>, MessageError> {
+            match params.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_u64()
+                    .map(|v| Some(v as usize))
+                    .ok_or_else(|| MessageError::InvalidFormat(format!("Invalid {} {}", key, value))),
+            }
+        };
+
+        let allowed_programs = match params.get("allowed_programs") {
+            None => None,
+            Some(serde_json::Value::Array(programs)) => Some(
+                programs
+                    .iter()
+                    .map(|p| {
+                        p.as_str()
+                            .ok_or_else(|| MessageError::InvalidFormat(format!("Invalid program id {}", p)))
+                            .and_then(solana::parse_pubkey)
+                    })
+                    .collect::<Result<HashSet<_>, _>>()?,
+            ),
+            Some(other) => {
+                return Err(MessageError::InvalidFormat(format!("allowed_programs must be a list, got {}", other)));
+            }
+        };
+
+        let verify_signatures = match params.get("verify_signatures") {
+            None => true,
+            Some(value) => value.as_bool().ok_or_else(|| {
+                MessageError::InvalidFormat(format!("Invalid verify_signatures {}", value))
+            })?,
+        };
+
+        Ok(Self {
+            allowed_programs,
+            max_accounts: limit("max_accounts")?,
+            max_instruction_accounts: limit("max_instruction_accounts")?,
+            verify_signatures,
+        })
+    }
+}
+
 /// Solana message validator
+///
+/// Payloads are serialized legacy or v0 transactions. Signatures are
+/// verified over the message, invoked programs are checked against the
+/// configured allowlist and account counts against the configured limits.
+/// Decoded fields are published under the `solana` key of
+/// `ValidationMetrics::chain_metrics`.
 pub struct SolanaValidator {
     config: ValidatorConfig,
-    metrics: ValidationMetrics,
+    params: SolanaParams,
+    metrics: RwLock<ValidationMetrics>,
 }
 
 impl SolanaValidator {
-    pub fn new(config: ValidatorConfig) -> Self {
-        Self {
+    /// Create validator, rejecting malformed chain parameters
+    pub fn new(config: ValidatorConfig) -> Result<Self, MessageError> {
+        Ok(Self {
+            params: SolanaParams::parse(&config.chain_params)?,
             config,
-            metrics: ValidationMetrics::default(),
-        }
+            metrics: RwLock::new(ValidationMetrics::default()),
+        })
     }
-    
-    fn validate_instruction(&self, instruction: &[u8]) -> Result<(), MessageError> {
-        if instruction.len() > self.config.max_message_size {
+
+    fn validate_transaction(&self, raw: &[u8]) -> Result<(), MessageError> {
+        if raw.len() > self.config.max_message_size {
             return Err(MessageError::InvalidFormat(
-                format!("Instruction size {} exceeds maximum {}", 
-                    instruction.len(), self.config.max_message_size)
+                format!("Transaction size {} exceeds maximum {}",
+                    raw.len(), self.config.max_message_size)
             ));
         }
-        
-        // TODO: Add more Solana-specific instruction validation
+
+        let tx = SolanaTransaction::decode(raw)?;
+
+        if let Some(max) = self.params.max_accounts {
+            if tx.total_account_count() > max {
+                return Err(MessageError::InvalidFormat(format!(
+                    "Transaction references {} accounts, maximum is {}", tx.total_account_count(), max
+                )));
+            }
+        }
+        if let Some(max) = self.params.max_instruction_accounts {
+            if let Some(ix) = tx.instructions.iter().find(|ix| ix.accounts.len() > max) {
+                return Err(MessageError::InvalidFormat(format!(
+                    "Instruction references {} accounts, maximum is {}", ix.accounts.len(), max
+                )));
+            }
+        }
+        if let Some(allowed) = &self.params.allowed_programs {
+            if let Some(program) = tx.program_ids().find(|p| !allowed.contains(*p)) {
+                return Err(MessageError::InvalidFormat(format!(
+                    "Program {} is not allowed", solana::encode_pubkey(program)
+                )));
+            }
+        }
+        if self.params.verify_signatures {
+            tx.verify_signatures()?;
+        }
+
+        publish_decoded(&self.metrics, "solana", "decoded_transactions", "last_transaction", tx.to_json());
         Ok(())
     }
 }
@@ -134,63 +399,170 @@ impl SolanaValidator {
 #[async_trait]
 impl MessageValidator for SolanaValidator {
     async fn validate_message(&self, message: &FrostMessage) -> Result<(), MessageError> {
-        let start = std::time::Instant::now();
-        
-        let result = match message.payload {
-            MessagePayload::Solana { ref instruction, .. } => {
-                self.validate_instruction(instruction)
-            }
-            _ => Err(MessageError::InvalidFormat("Not a Solana message".into())),
-        };
-        
-        // Update metrics
-        let mut metrics = self.metrics.clone();
-        let duration = start.elapsed().as_secs_f64();
-        
-        metrics.total_validated += 1;
-        metrics.avg_validation_time = (metrics.avg_validation_time * (metrics.total_validated - 1) as f64
-            + duration) / metrics.total_validated as f64;
-            
-        if result.is_err() {
-            metrics.failed_validations += 1;
-        }
-        
+        let start = Instant::now();
+
+        let result = chain_payload(message, "solana").and_then(|(payload, _)| self.validate_transaction(payload));
+
+        record_validation(&self.metrics, start, result.is_err());
         result
     }
     
     async fn get_metrics(&self) -> ValidationMetrics {
-        self.metrics.clone()
+        self.metrics.read().clone()
     }
     
     async fn update_config(&mut self, config: ValidatorConfig) -> Result<(), MessageError> {
+        self.params = SolanaParams::parse(&config.chain_params)?;
         self.config = config;
         Ok(())
     }
 }
 
+/// Cosmos chain parameters parsed from `ValidatorConfig::chain_params`
+///
+/// Recognized keys: `allowed_type_urls`, `bech32_prefix` (account prefix;
+/// validator addresses use `<prefix>valoper`), `max_messages` and
+/// `payload_type` (`tx_raw` or `tx_body`). Messages may repeat the payload
+/// type as a hint, but a hint that differs from the configured type is
+/// rejected.
+#[derive(Debug, Clone, Default)]
+struct CosmosParams {
+    allowed_type_urls: Option<HashSet<String>>,
+    bech32_prefix: Option<String>,
+    max_messages: Option<usize>,
+    body_only: bool,
+}
+
+impl CosmosParams {
+    fn parse(params: &serde_json::Value) -> Result<Self, MessageError> {
+        let allowed_type_urls = match params.get("allowed_type_urls") {
+            None => None,
+            Some(serde_json::Value::Array(urls)) => Some(
+                urls.iter()
+                    .map(|u| {
+                        u.as_str()
+                            .map(String::from)
+                            .ok_or_else(|| MessageError::InvalidFormat(format!("Invalid type URL {}", u)))
+                    })
+                    .collect::<Result<HashSet<_>, _>>()?,
+            ),
+            Some(other) => {
+                return Err(MessageError::InvalidFormat(format!("allowed_type_urls must be a list, got {}", other)));
+            }
+        };
+
+        let bech32_prefix = match params.get("bech32_prefix") {
+            None => None,
+            Some(value) => Some(
+                value
+                    .as_str()
+                    .filter(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()))
+                    .map(String::from)
+                    .ok_or_else(|| MessageError::InvalidFormat(format!("Invalid bech32_prefix {}", value)))?,
+            ),
+        };
+
+        let max_messages = match params.get("max_messages") {
+            None => None,
+            Some(value) => Some(value.as_u64().ok_or_else(|| {
+                MessageError::InvalidFormat(format!("Invalid max_messages {}", value))
+            })? as usize),
+        };
+
+        let body_only = Self::is_body(params.get("payload_type").and_then(|t| t.as_str()))?;
+
+        Ok(Self { allowed_type_urls, bech32_prefix, max_messages, body_only })
+    }
+
+    fn is_body(payload_type: Option<&str>) -> Result<bool, MessageError> {
+        match payload_type {
+            None | Some("tx_raw") => Ok(false),
+            Some("tx_body") => Ok(true),
+            Some(other) => Err(MessageError::InvalidFormat(format!("Unknown Cosmos payload type {}", other))),
+        }
+    }
+}
+
 /// Cosmos message validator
+///
+/// Payloads are protobuf `TxRaw` (or bare `TxBody`) encodings. Every
+/// message, including those nested in authz `MsgExec`, and every critical
+/// extension option must have an allowed type URL, and addresses in known
+/// message types must carry the configured bech32 prefix. For `TxRaw` the
+/// signer-info count must match the signatures. Decoded fields are
+/// published under the `cosmos` key of `ValidationMetrics::chain_metrics`.
 pub struct CosmosValidator {
     config: ValidatorConfig,
-    metrics: ValidationMetrics,
+    params: CosmosParams,
+    metrics: RwLock<ValidationMetrics>,
 }
 
 impl CosmosValidator {
-    pub fn new(config: ValidatorConfig) -> Self {
-        Self {
+    /// Create validator, rejecting malformed chain parameters
+    pub fn new(config: ValidatorConfig) -> Result<Self, MessageError> {
+        Ok(Self {
+            params: CosmosParams::parse(&config.chain_params)?,
             config,
-            metrics: ValidationMetrics::default(),
-        }
+            metrics: RwLock::new(ValidationMetrics::default()),
+        })
     }
-    
-    fn validate_msg(&self, msg: &[u8]) -> Result<(), MessageError> {
-        if msg.len() > self.config.max_message_size {
+
+    fn validate_tx(&self, raw: &[u8], body_only: bool) -> Result<(), MessageError> {
+        if raw.len() > self.config.max_message_size {
             return Err(MessageError::InvalidFormat(
                 format!("Message size {} exceeds maximum {}", 
-                    msg.len(), self.config.max_message_size)
+                    raw.len(), self.config.max_message_size)
             ));
         }
-        
-        // TODO: Add more Cosmos-specific message validation
+
+        let tx = if body_only {
+            CosmosTransaction::decode_body(raw)?
+        } else {
+            CosmosTransaction::decode_raw(raw)?
+        };
+
+        if tx.body.messages.is_empty() {
+            return Err(MessageError::InvalidFormat("Transaction has no messages".into()));
+        }
+        if let Some(max) = self.params.max_messages {
+            if tx.body.messages.len() > max {
+                return Err(MessageError::InvalidFormat(format!(
+                    "Transaction has {} messages, maximum is {}", tx.body.messages.len(), max
+                )));
+            }
+        }
+
+        if let Some(allowed) = &self.params.allowed_type_urls {
+            if let Some(option) = tx.body.extension_options.iter().find(|o| !allowed.contains(&o.type_url)) {
+                return Err(MessageError::InvalidFormat(format!(
+                    "Extension option {} is not allowed", option.type_url
+                )));
+            }
+        }
+
+        tx.visit_messages(|any, contents| {
+            if let Some(allowed) = &self.params.allowed_type_urls {
+                if !allowed.contains(&any.type_url) {
+                    return Err(MessageError::InvalidFormat(format!(
+                        "Message type {} is not allowed", any.type_url
+                    )));
+                }
+            }
+            if let (Some(prefix), Some(contents)) = (&self.params.bech32_prefix, contents) {
+                for (address, kind) in &contents.addresses {
+                    cosmos::check_address(address, prefix, *kind)?;
+                }
+            }
+            Ok(())
+        })?;
+
+        if let (Some(prefix), Some(fee)) = (&self.params.bech32_prefix, tx.auth_info.as_ref().and_then(|a| a.fee.as_ref())) {
+            for address in [&fee.payer, &fee.granter].into_iter().filter(|a| !a.is_empty()) {
+                cosmos::check_address(address, prefix, AddressKind::Account)?;
+            }
+        }
+
+        publish_decoded(&self.metrics, "cosmos", "decoded_transactions", "last_transaction", tx.to_json());
         Ok(())
     }
 }
@@ -198,36 +570,32 @@ impl CosmosValidator {
 #[async_trait]
 impl MessageValidator for CosmosValidator {
     async fn validate_message(&self, message: &FrostMessage) -> Result<(), MessageError> {
-        let start = std::time::Instant::now();
-        
-        let result = match message.payload {
-            MessagePayload::Cosmos { ref msg, .. } => {
-                self.validate_msg(msg)
+        let start = Instant::now();
+
+        let result = chain_payload(message, "cosmos").and_then(|(payload, payload_type)| {
+            // Only the operator may select body-only mode, which skips the
+            // signer-info and signature checks
+            if let Some(hint) = payload_type {
+                if CosmosParams::is_body(Some(hint))? != self.params.body_only {
+                    return Err(MessageError::InvalidFormat(format!(
+                        "Payload type {} does not match the configured payload type", hint
+                    )));
+                }
             }
-            _ => Err(MessageError::InvalidFormat("Not a Cosmos message".into())),
-        };
-        
-        // Update metrics
-        let mut metrics = self.metrics.clone();
-        let duration = start.elapsed().as_secs_f64();
-        
-        metrics.total_validated += 1;
-        metrics.avg_validation_time = (metrics.avg_validation_time * (metrics.total_validated - 1) as f64
-            + duration) / metrics.total_validated as f64;
-            
-        if result.is_err() {
-            metrics.failed_validations += 1;
-        }
-        
+            self.validate_tx(payload, self.params.body_only)
+        });
+
+        record_validation(&self.metrics, start, result.is_err());
         result
     }
     
     async fn get_metrics(&self) -> ValidationMetrics {
-        self.metrics.clone()
+        self.metrics.read().clone()
     }
     
     async fn update_config(&mut self, config: ValidatorConfig) -> Result<(), MessageError> {
+        self.params = CosmosParams::parse(&config.chain_params)?;
         self.config = config;
         Ok(())
     }
-} 
\ No newline at end of file
+}
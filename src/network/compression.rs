@@ -0,0 +1,203 @@
+use std::io::Read;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::error::Error;
+use crate::network::transport::{CompressionConfig, TransportCounters};
+use crate::Result;
+
+/// Flag byte prefixed to each data payload on a compressed connection
+const RAW: u8 = 0;
+const COMPRESSED: u8 = 1;
+
+/// Bytes added to each data payload on a compressed connection
+pub const COMPRESSION_HEADER_LEN: usize = 1;
+
+/// Payload compression algorithm
+///
+/// Codecs are looked up by `name`, which is what `CompressionConfig::algorithm`
+/// selects and what peers exchange during negotiation.
+pub trait Codec: Send + Sync {
+    /// Name used in configuration and negotiation
+    fn name(&self) -> &str;
+
+    /// Compress `data` at `level`; 0 selects the codec default
+    fn compress(&self, data: &[u8], level: u8) -> Result<Vec<u8>>;
+
+    /// Decompress `data`, failing if the output would exceed `max_size`
+    fn decompress(&self, data: &[u8], max_size: usize) -> Result<Vec<u8>>;
+}
+
+/// Zstandard; best ratio, suited to state proofs
+pub struct ZstdCodec;
+
+impl Codec for ZstdCodec {
+    fn name(&self) -> &str {
+        "zstd"
+    }
+
+    fn compress(&self, data: &[u8], level: u8) -> Result<Vec<u8>> {
+        zstd::bulk::compress(data, i32::from(level))
+            .map_err(|e| Error::Network(format!("zstd compression failed: {}", e)))
+    }
+
+    fn decompress(&self, data: &[u8], max_size: usize) -> Result<Vec<u8>> {
+        // Stream so the buffer grows with the actual output rather than
+        // reserving `max_size` up front
+        let decoder = zstd::stream::read::Decoder::new(data)
+            .map_err(|e| Error::Network(format!("zstd decompression failed: {}", e)))?;
+        let mut output = Vec::new();
+        decoder
+            .take((max_size as u64).saturating_add(1))
+            .read_to_end(&mut output)
+            .map_err(|e| Error::Network(format!("zstd decompression failed: {}", e)))?;
+        if output.len() > max_size {
+            return Err(Error::Network(format!("zstd payload exceeds limit {}", max_size)));
+        }
+        Ok(output)
+    }
+}
+
+/// LZ4; lower ratio but much cheaper on CPU. Ignores the level
+pub struct Lz4Codec;
+
+impl Codec for Lz4Codec {
+    fn name(&self) -> &str {
+        "lz4"
+    }
+
+    fn compress(&self, data: &[u8], _level: u8) -> Result<Vec<u8>> {
+        Ok(lz4_flex::compress_prepend_size(data))
+    }
+
+    fn decompress(&self, data: &[u8], max_size: usize) -> Result<Vec<u8>> {
+        let size = data
+            .get(..4)
+            .map(|prefix| u32::from_le_bytes(prefix.try_into().expect("4 bytes")) as usize)
+            .ok_or_else(|| Error::Network("lz4 payload truncated".into()))?;
+        if size > max_size {
+            return Err(Error::Network(format!("lz4 payload of {} bytes exceeds limit {}", size, max_size)));
+        }
+        lz4_flex::decompress_size_prepended(data)
+            .map_err(|e| Error::Network(format!("lz4 decompression failed: {}", e)))
+    }
+}
+
+/// Codecs available to a transport, in preference order
+#[derive(Clone)]
+pub struct CodecRegistry {
+    codecs: Vec<Arc<dyn Codec>>,
+}
+
+impl CodecRegistry {
+    /// Registry without any codecs
+    pub fn empty() -> Self {
+        Self { codecs: Vec::new() }
+    }
+
+    /// Add a codec, replacing any codec with the same name
+    pub fn register(&mut self, codec: Arc<dyn Codec>) {
+        self.codecs.retain(|c| c.name() != codec.name());
+        self.codecs.push(codec);
+    }
+
+    /// Codec by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Codec>> {
+        self.codecs.iter().find(|c| c.name() == name).cloned()
+    }
+
+    /// Registered codec names, in preference order
+    pub fn names(&self) -> Vec<String> {
+        self.codecs.iter().map(|c| c.name().to_string()).collect()
+    }
+
+    /// Names to advertise for `config`: the configured codec first, then
+    /// the other registered codecs. Empty when compression is disabled
+    pub(crate) fn advertise(&self, config: &CompressionConfig) -> Result<Vec<String>> {
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+        if self.get(&config.algorithm).is_none() {
+            return Err(Error::Network(format!(
+                "Unsupported compression algorithm {:?}, expected one of {:?}",
+                config.algorithm,
+                self.names()
+            )));
+        }
+        let mut names = vec![config.algorithm.clone()];
+        names.extend(self.names().into_iter().filter(|n| *n != config.algorithm));
+        Ok(names)
+    }
+}
+
+impl Default for CodecRegistry {
+    fn default() -> Self {
+        Self {
+            codecs: vec![Arc::new(ZstdCodec), Arc::new(Lz4Codec)],
+        }
+    }
+}
+
+/// Pick the codec for a connection: the dialer's most preferred codec the
+/// listener also offers. Both sides compute the same answer
+pub fn negotiate(dialer: &[String], listener: &[String]) -> Option<String> {
+    dialer.iter().find(|name| listener.contains(name)).cloned()
+}
+
+/// Negotiated compression state of one connection
+pub(crate) struct ConnectionCodec {
+    codec: Arc<dyn Codec>,
+    level: u8,
+    threshold: usize,
+}
+
+impl ConnectionCodec {
+    pub(crate) fn new(codec: Arc<dyn Codec>, config: &CompressionConfig) -> Self {
+        Self {
+            codec,
+            level: config.level,
+            threshold: config.threshold,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        self.codec.name()
+    }
+
+    /// Payload to put on the wire; small or incompressible data is sent raw
+    pub(crate) fn encode(&self, data: &[u8], counters: &TransportCounters) -> Result<Vec<u8>> {
+        if data.len() >= self.threshold {
+            let started = Instant::now();
+            let compressed = self.codec.compress(data, self.level)?;
+            counters.compressed(data.len(), compressed.len(), started.elapsed());
+            if compressed.len() < data.len() {
+                let mut payload = Vec::with_capacity(COMPRESSION_HEADER_LEN + compressed.len());
+                payload.push(COMPRESSED);
+                payload.extend_from_slice(&compressed);
+                return Ok(payload);
+            }
+        }
+        let mut payload = Vec::with_capacity(COMPRESSION_HEADER_LEN + data.len());
+        payload.push(RAW);
+        payload.extend_from_slice(data);
+        Ok(payload)
+    }
+
+    /// Original data from a wire payload
+    pub(crate) fn decode(&self, payload: &[u8], max_size: usize, counters: &TransportCounters) -> Result<Vec<u8>> {
+        match payload.split_first() {
+            Some((&RAW, data)) if data.len() <= max_size => Ok(data.to_vec()),
+            Some((&RAW, data)) => Err(Error::Network(format!(
+                "Payload of {} bytes exceeds limit {}", data.len(), max_size
+            ))),
+            Some((&COMPRESSED, data)) => {
+                let started = Instant::now();
+                let decompressed = self.codec.decompress(data, max_size)?;
+                counters.decompressed(started.elapsed());
+                Ok(decompressed)
+            }
+            Some((flag, _)) => Err(Error::Network(format!("Unknown compression flag {}", flag))),
+            None => Err(Error::Network("Empty compressed payload".into())),
+        }
+    }
+}
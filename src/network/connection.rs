@@ -0,0 +1,328 @@
+//! Connection registry shared by the stream transports
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use tokio::sync::{mpsc, Mutex, MutexGuard, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::network::compression::ConnectionCodec;
+use crate::network::rate_limit::RateLimiter;
+use crate::network::reputation::{ReputationEvent, ReputationManager};
+use crate::network::security::DefaultSecurityManager;
+use crate::network::transport::{TransportCounters, TransportMetrics};
+use crate::network::Peer;
+use crate::Result;
+
+/// Received data buffered per connection before reads apply backpressure
+pub(crate) const INBOX_CAPACITY: usize = 256;
+
+/// Inbound handshakes in progress before new connections are refused
+pub(crate) const MAX_PENDING_HANDSHAKES: usize = 64;
+
+/// Write half of a transport connection
+#[async_trait]
+pub(crate) trait FrameWriter: Send + 'static {
+    /// Unit written to the wire
+    type Frame: Send;
+
+    /// Write one frame, returning the bytes put on the wire
+    async fn write_frame(&mut self, frame: Self::Frame) -> Result<usize>;
+
+    /// Keep-alive ping carrying `nonce`
+    fn ping(nonce: u64) -> Self::Frame;
+}
+
+/// Established connection to one peer
+pub(crate) struct Connection<W> {
+    pub(crate) peer: Peer,
+    pub(crate) codec: Option<ConnectionCodec>,
+    writer: Mutex<W>,
+    inbox: Mutex<mpsc::Receiver<Vec<u8>>>,
+    closed: AtomicBool,
+    /// Nonce and send time of the outstanding ping
+    ping: parking_lot::Mutex<Option<(u64, Instant)>>,
+    tasks: parking_lot::Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl<W> Connection<W> {
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Mark closed, leaving buffered data readable
+    pub(crate) fn mark_closed(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Mark closed and stop the connection's tasks
+    pub(crate) fn close(&self) {
+        self.mark_closed();
+        for task in self.tasks.lock().drain(..) {
+            task.abort();
+        }
+    }
+
+    /// Write half, for transport-specific shutdown
+    pub(crate) async fn writer(&self) -> MutexGuard<'_, W> {
+        self.writer.lock().await
+    }
+}
+
+/// Live connections keyed by remote node id
+pub(crate) struct ConnectionRegistry<W> {
+    connections: RwLock<HashMap<Uuid, Arc<Connection<W>>>>,
+    timeout: Duration,
+    limiter: Option<Arc<RateLimiter>>,
+    /// Reputation fed for peers the security manager authenticated
+    reputation: Option<(Arc<ReputationManager>, Arc<DefaultSecurityManager>)>,
+    handshakes: Arc<Semaphore>,
+    pub(crate) counters: TransportCounters,
+}
+
+impl<W: FrameWriter> ConnectionRegistry<W> {
+    pub(crate) fn new(
+        timeout: Duration,
+        limiter: Option<Arc<RateLimiter>>,
+        reputation: Option<(Arc<ReputationManager>, Arc<DefaultSecurityManager>)>,
+    ) -> Self {
+        Self {
+            connections: RwLock::new(HashMap::new()),
+            timeout,
+            limiter,
+            reputation,
+            handshakes: Arc::new(Semaphore::new(MAX_PENDING_HANDSHAKES)),
+            counters: TransportCounters::default(),
+        }
+    }
+
+    /// Reserve a slot for an inbound handshake
+    ///
+    /// Returns `None`, counting an error, once `MAX_PENDING_HANDSHAKES`
+    /// are in progress; the caller should drop the connection. The slot is
+    /// released when the permit is dropped.
+    pub(crate) fn try_begin_handshake(&self) -> Option<OwnedSemaphorePermit> {
+        let permit = self.handshakes.clone().try_acquire_owned().ok();
+        if permit.is_none() {
+            self.counters.error();
+        }
+        permit
+    }
+
+    /// Open connection to a peer
+    pub(crate) fn connection(&self, peer: &Peer) -> Result<Arc<Connection<W>>> {
+        self.connections
+            .read()
+            .get(&peer.id)
+            .filter(|c| !c.is_closed())
+            .cloned()
+            .ok_or_else(|| Error::Network(format!("Not connected to peer {}", peer.id)))
+    }
+
+    /// Register a connection and start its tasks
+    ///
+    /// Node ids come from an unauthenticated handshake, so a connection
+    /// claiming the id of an open one is refused rather than replacing it.
+    /// `spawn` receives the connection and the sender feeding its inbox.
+    pub(crate) fn register(
+        &self,
+        peer: Peer,
+        writer: W,
+        codec: Option<ConnectionCodec>,
+        spawn: impl FnOnce(Arc<Connection<W>>, mpsc::Sender<Vec<u8>>) -> Vec<JoinHandle<()>>,
+    ) -> Result<()> {
+        let mut connections = self.connections.write();
+        if connections.get(&peer.id).is_some_and(|c| !c.is_closed()) {
+            self.counters.error();
+            return Err(Error::Network(format!("Peer {} is already connected", peer.id)));
+        }
+
+        let (inbox_tx, inbox_rx) = mpsc::channel(INBOX_CAPACITY);
+        let connection = Arc::new(Connection {
+            peer,
+            codec,
+            writer: Mutex::new(writer),
+            inbox: Mutex::new(inbox_rx),
+            closed: AtomicBool::new(false),
+            ping: parking_lot::Mutex::new(None),
+            tasks: parking_lot::Mutex::new(Vec::new()),
+        });
+        let tasks = spawn(connection.clone(), inbox_tx);
+        connection.tasks.lock().extend(tasks);
+
+        // A reconnect after the old connection closed replaces it
+        if let Some(previous) = connections.insert(connection.peer.id, connection) {
+            previous.close();
+        }
+        Ok(())
+    }
+
+    /// Drop a connection unless it was already replaced
+    pub(crate) fn remove(&self, connection: &Arc<Connection<W>>) {
+        let mut connections = self.connections.write();
+        if connections.get(&connection.peer.id).is_some_and(|c| Arc::ptr_eq(c, connection)) {
+            connections.remove(&connection.peer.id);
+        }
+    }
+
+    /// Remove and return a peer's connection
+    pub(crate) fn take(&self, peer: &Peer) -> Option<Arc<Connection<W>>> {
+        self.connections.write().remove(&peer.id)
+    }
+
+    /// Close every connection
+    pub(crate) fn close_all(&self) {
+        for connection in self.connections.write().drain().map(|(_, c)| c) {
+            connection.close();
+        }
+    }
+
+    /// Currently connected peers
+    pub(crate) fn peers(&self) -> Vec<Peer> {
+        self.connections
+            .read()
+            .values()
+            .filter(|c| !c.is_closed())
+            .map(|c| c.peer.clone())
+            .collect()
+    }
+
+    /// Compression codec negotiated with a peer, if any
+    pub(crate) fn compression(&self, peer: &Peer) -> Option<String> {
+        let connections = self.connections.read();
+        connections.get(&peer.id)?.codec.as_ref().map(|c| c.name().to_string())
+    }
+
+    /// Write a frame, closing the connection on failure or timeout
+    pub(crate) async fn write(&self, connection: &Connection<W>, frame: W::Frame) -> Result<usize> {
+        let mut writer = connection.writer.lock().await;
+        match tokio::time::timeout(self.timeout, writer.write_frame(frame)).await {
+            Ok(Ok(written)) => {
+                self.counters.sent(written);
+                Ok(written)
+            }
+            Ok(Err(e)) => {
+                self.counters.error();
+                connection.mark_closed();
+                Err(e)
+            }
+            Err(_) => {
+                self.counters.error();
+                connection.mark_closed();
+                Err(Error::Network(format!("Timed out writing to peer {}", connection.peer.id)))
+            }
+        }
+    }
+
+    /// Next buffered payload from a peer
+    ///
+    /// Data received before a connection closed stays readable; the
+    /// connection is removed once drained.
+    pub(crate) async fn receive(&self, peer: &Peer) -> Result<Vec<u8>> {
+        let connection = self
+            .connections
+            .read()
+            .get(&peer.id)
+            .cloned()
+            .ok_or_else(|| Error::Network(format!("Not connected to peer {}", peer.id)))?;
+        let mut inbox = connection.inbox.lock().await;
+        match tokio::time::timeout(self.timeout, inbox.recv()).await {
+            Ok(Some(data)) => Ok(data),
+            Ok(None) => {
+                self.remove(&connection);
+                Err(Error::Network(format!("Connection to peer {} closed", peer.id)))
+            }
+            Err(_) => Err(Error::Network(format!("Timed out receiving from peer {}", peer.id))),
+        }
+    }
+
+    /// Whether the rate limiter admits another data frame from a connection
+    pub(crate) async fn admit(&self, connection: &Connection<W>) -> bool {
+        let Some(limiter) = &self.limiter else {
+            return true;
+        };
+        limiter.check(connection.peer.id, connection.peer.info.ip(), None).await.is_ok()
+    }
+
+    /// Record a pong, sampling latency if it answers the outstanding ping
+    pub(crate) async fn pong(&self, connection: &Connection<W>, nonce: &[u8]) {
+        let latency = {
+            let mut ping = connection.ping.lock();
+            match *ping {
+                Some((expected, sent_at)) if nonce == expected.to_be_bytes() => {
+                    *ping = None;
+                    sent_at.elapsed()
+                }
+                _ => return,
+            }
+        };
+        self.counters.latency(latency);
+        self.report(connection, ReputationEvent::Latency(latency)).await;
+    }
+
+    /// Ping every third of the timeout until the connection closes
+    ///
+    /// Once pings are under way, the share of them answered before the
+    /// next one is due is reported as the peer's uptime.
+    pub(crate) async fn keep_alive(&self, connection: &Connection<W>) {
+        let mut interval = tokio::time::interval(self.timeout / 3);
+        interval.tick().await;
+        let mut nonce = 0u64;
+        let mut answered = 0u64;
+        while !connection.is_closed() {
+            interval.tick().await;
+            if nonce > 0 {
+                if connection.ping.lock().is_none() {
+                    answered += 1;
+                }
+                let uptime = answered as f64 * 100.0 / nonce as f64;
+                self.report(connection, ReputationEvent::Uptime(uptime)).await;
+            }
+            nonce += 1;
+            *connection.ping.lock() = Some((nonce, Instant::now()));
+            if self.write(connection, W::ping(nonce)).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Report an event against the peer of a connection
+    ///
+    /// Node ids come from an unauthenticated handshake, so events are only
+    /// reported once the peer holds a session opened from the connection's
+    /// address.
+    async fn report(&self, connection: &Connection<W>, event: ReputationEvent) {
+        let Some((reputation, security)) = &self.reputation else {
+            return;
+        };
+        if !security.is_authenticated(&connection.peer) {
+            return;
+        }
+        if let Err(e) = reputation.report_connection(&connection.peer, event).await {
+            warn!("Failed to update reputation of {}: {}", connection.peer.id, e);
+        }
+    }
+
+    /// Called when a read loop exits
+    pub(crate) fn finish(&self, connection: &Arc<Connection<W>>, inbox: &mpsc::Sender<Vec<u8>>) {
+        debug!("Connection to peer {} closed", connection.peer.id);
+        connection.mark_closed();
+        // Undelivered data stays readable; `receive` removes the
+        // connection once drained
+        if inbox.capacity() == inbox.max_capacity() {
+            self.remove(connection);
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> TransportMetrics {
+        let active = self.connections.read().values().filter(|c| !c.is_closed()).count();
+        self.counters.snapshot(active)
+    }
+}
@@ -0,0 +1,681 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use metrics::counter;
+use parity_scale_codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::message::{FrostMessage, MessageType};
+use crate::network::NetworkProtocol;
+use crate::Result;
+
+/// Message type carrying a transfer manifest
+pub const MANIFEST_TYPE: &str = "frost.fragment.manifest";
+/// Message type carrying a single chunk
+pub const FRAGMENT_TYPE: &str = "frost.fragment";
+/// Message type requesting missing chunks
+pub const RETRANSMIT_TYPE: &str = "frost.fragment.retransmit";
+/// Bookkeeping charged against the reassembly limits for every buffered
+/// chunk on top of its data
+pub const CHUNK_OVERHEAD: usize = 64;
+
+/// Fragmentation configuration
+#[derive(Debug, Clone)]
+pub struct FragmentConfig {
+    /// Messages whose encoding exceeds this many bytes are fragmented
+    pub max_message_size: usize,
+    /// Chunk size, kept below `max_message_size` to leave room for the envelope
+    pub chunk_size: usize,
+    /// Drop incomplete transfers after this long without progress
+    pub reassembly_timeout: Duration,
+    /// Request missing chunks after this long without progress
+    pub retransmit_after: Duration,
+    /// Retransmission requests per transfer before giving up
+    pub max_retransmit_requests: u32,
+    /// Largest message accepted for reassembly, plus `CHUNK_OVERHEAD` per chunk
+    pub max_transfer_bytes: usize,
+    /// Total bytes buffered across all incomplete transfers, plus
+    /// `CHUNK_OVERHEAD` per chunk
+    pub max_buffered_bytes: usize,
+    /// Maximum concurrent incomplete transfers
+    pub max_transfers: usize,
+    /// How long sent fragments are kept to answer retransmission requests
+    pub retransmit_cache_ttl: Duration,
+    /// Total bytes of sent fragments kept for retransmission
+    pub retransmit_cache_bytes: usize,
+    /// Retransmission requests answered per sent transfer, across all peers
+    pub max_retransmits_per_transfer: u32,
+    /// Minimum time between answered retransmission requests from one peer
+    /// for the same transfer
+    pub retransmit_interval: Duration,
+}
+
+impl Default for FragmentConfig {
+    fn default() -> Self {
+        Self {
+            max_message_size: 1024 * 1024,
+            chunk_size: 256 * 1024,
+            reassembly_timeout: Duration::from_secs(60),
+            retransmit_after: Duration::from_secs(5),
+            max_retransmit_requests: 3,
+            max_transfer_bytes: 64 * 1024 * 1024,
+            max_buffered_bytes: 256 * 1024 * 1024,
+            max_transfers: 1024,
+            retransmit_cache_ttl: Duration::from_secs(120),
+            retransmit_cache_bytes: 256 * 1024 * 1024,
+            max_retransmits_per_transfer: 16,
+            retransmit_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Describes a fragmented message
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct FragmentManifest {
+    /// Id of the original message
+    pub transfer_id: [u8; 16],
+    /// Length of the encoded message
+    pub total_len: u64,
+    /// Chunk size used by the sender
+    pub chunk_size: u32,
+    /// SHA-256 of each chunk
+    pub chunk_hashes: Vec<[u8; 32]>,
+    /// SHA-256 of the encoded message
+    pub message_hash: [u8; 32],
+}
+
+/// A single numbered chunk
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Fragment {
+    pub transfer_id: [u8; 16],
+    pub index: u32,
+    pub data: Vec<u8>,
+}
+
+/// Request to resend chunks of a transfer
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct RetransmitRequest {
+    pub transfer_id: [u8; 16],
+    /// Whether the manifest is missing too
+    pub manifest: bool,
+    /// Missing chunk indices
+    pub missing: Vec<u32>,
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+fn decode<T: Decode>(payload: &[u8], what: &str) -> Result<T> {
+    T::decode(&mut &payload[..]).map_err(|e| Error::Network(format!("Invalid {}: {}", what, e)))
+}
+
+/// Envelope for a fragmentation control message derived from `original`
+fn envelope(original: &FrostMessage, msg_type: &str, payload: Vec<u8>) -> FrostMessage {
+    let mut message = FrostMessage::new(
+        MessageType::Custom(msg_type.into()),
+        payload,
+        original.source.clone(),
+        original.target.clone(),
+    );
+    message.metadata.priority = original.metadata.priority;
+    message.metadata.version = original.metadata.version;
+    message
+}
+
+/// Split `message` into a manifest and fragment messages
+///
+/// Returns `None` if the message fits within `max_message_size`.
+pub fn fragment(message: &FrostMessage, config: &FragmentConfig) -> Result<Option<(FrostMessage, Vec<FrostMessage>)>> {
+    let encoded = serde_json::to_vec(message)
+        .map_err(|e| Error::Network(format!("Failed to encode message: {}", e)))?;
+    if encoded.len() <= config.max_message_size {
+        return Ok(None);
+    }
+
+    let chunk_size = config.chunk_size.max(1);
+    let transfer_id = *message.id.as_bytes();
+    let chunks: Vec<&[u8]> = encoded.chunks(chunk_size).collect();
+    if chunks.len() > u32::MAX as usize {
+        return Err(Error::Network("Message has too many chunks".into()));
+    }
+
+    let manifest = FragmentManifest {
+        transfer_id,
+        total_len: encoded.len() as u64,
+        chunk_size: chunk_size as u32,
+        chunk_hashes: chunks.iter().map(|c| sha256(c)).collect(),
+        message_hash: sha256(&encoded),
+    };
+    let fragments = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, data)| {
+            let fragment = Fragment { transfer_id, index: index as u32, data: data.to_vec() };
+            envelope(message, FRAGMENT_TYPE, fragment.encode())
+        })
+        .collect();
+
+    Ok(Some((envelope(message, MANIFEST_TYPE, manifest.encode()), fragments)))
+}
+
+/// Outcome of feeding a message to the reassembler
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReassemblyEvent {
+    /// Not a fragmentation message; deliver as is
+    Passthrough(FrostMessage),
+    /// A fragmented message was fully reassembled
+    Complete(FrostMessage),
+    /// Fragment or manifest buffered, transfer still incomplete
+    Buffered,
+    /// A peer asked for chunks to be resent
+    RetransmitRequested { peer: String, request: RetransmitRequest },
+}
+
+struct Transfer {
+    manifest: Option<FragmentManifest>,
+    chunks: HashMap<u32, Vec<u8>>,
+    bytes: usize,
+    last_progress: Instant,
+    last_request: Option<Instant>,
+    requests_sent: u32,
+}
+
+impl Transfer {
+    fn missing(&self) -> Vec<u32> {
+        match &self.manifest {
+            Some(manifest) => (0..manifest.chunk_hashes.len() as u32)
+                .filter(|i| !self.chunks.contains_key(i))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Incomplete transfers are keyed by sending peer and transfer id
+type TransferKey = (String, [u8; 16]);
+
+#[derive(Default)]
+struct ReassemblyState {
+    transfers: HashMap<TransferKey, Transfer>,
+    buffered_bytes: usize,
+}
+
+/// Receiver-side reassembly with timeouts and memory limits
+///
+/// Transfers are tracked per sending peer as reported by the transport, so
+/// a peer can neither add chunks to nor request retransmits on behalf of
+/// another peer's transfer.
+pub struct Reassembler {
+    config: FragmentConfig,
+    state: Mutex<ReassemblyState>,
+}
+
+impl Reassembler {
+    /// Create reassembler with the given limits
+    pub fn new(config: FragmentConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(ReassemblyState::default()),
+        }
+    }
+
+    /// Number of incomplete transfers
+    pub fn pending_transfers(&self) -> usize {
+        self.state.lock().transfers.len()
+    }
+
+    /// Bytes buffered for incomplete transfers
+    pub fn buffered_bytes(&self) -> usize {
+        self.state.lock().buffered_bytes
+    }
+
+    /// Feed a message received from `peer`
+    ///
+    /// `peer` must be the sender as authenticated by the transport, never
+    /// the message's own `source`.
+    pub fn accept(&self, peer: &str, message: FrostMessage) -> Result<ReassemblyEvent> {
+        let MessageType::Custom(kind) = &message.msg_type else {
+            return Ok(ReassemblyEvent::Passthrough(message));
+        };
+        match kind.as_str() {
+            MANIFEST_TYPE => {
+                let manifest: FragmentManifest = decode(&message.payload, "fragment manifest")?;
+                self.accept_manifest(peer, manifest)
+            }
+            FRAGMENT_TYPE => {
+                let fragment: Fragment = decode(&message.payload, "fragment")?;
+                self.accept_fragment(peer, fragment)
+            }
+            RETRANSMIT_TYPE => Ok(ReassemblyEvent::RetransmitRequested {
+                peer: peer.to_string(),
+                request: decode(&message.payload, "retransmit request")?,
+            }),
+            _ => Ok(ReassemblyEvent::Passthrough(message)),
+        }
+    }
+
+    fn transfer<'a>(&self, state: &'a mut ReassemblyState, key: &TransferKey) -> Result<&'a mut Transfer> {
+        if !state.transfers.contains_key(key) && state.transfers.len() >= self.config.max_transfers {
+            counter!("frost.network.fragment.rejected", 1);
+            return Err(Error::Network("Too many concurrent fragmented transfers".into()));
+        }
+        Ok(state.transfers.entry(key.clone()).or_insert_with(|| Transfer {
+            manifest: None,
+            chunks: HashMap::new(),
+            bytes: 0,
+            last_progress: Instant::now(),
+            last_request: None,
+            requests_sent: 0,
+        }))
+    }
+
+    fn accept_manifest(&self, peer: &str, manifest: FragmentManifest) -> Result<ReassemblyEvent> {
+        let expected_chunks = (manifest.total_len as usize).div_ceil((manifest.chunk_size as usize).max(1));
+        let charged = expected_chunks
+            .checked_mul(CHUNK_OVERHEAD)
+            .and_then(|overhead| overhead.checked_add(manifest.total_len as usize));
+        if charged.is_none_or(|charged| charged > self.config.max_transfer_bytes) {
+            counter!("frost.network.fragment.rejected", 1);
+            return Err(Error::Network(format!(
+                "Fragmented message of {} bytes exceeds limit {}", manifest.total_len, self.config.max_transfer_bytes
+            )));
+        }
+        if manifest.chunk_size == 0 || manifest.chunk_hashes.len() != expected_chunks {
+            return Err(Error::Network("Inconsistent fragment manifest".into()));
+        }
+
+        let key = (peer.to_string(), manifest.transfer_id);
+        let mut state = self.state.lock();
+        let transfer = self.transfer(&mut state, &key)?;
+        if transfer.manifest.is_some() {
+            return Ok(ReassemblyEvent::Buffered);
+        }
+
+        // Chunks that arrived first are checked now that hashes are known
+        let mut dropped = 0;
+        transfer.chunks.retain(|index, data| {
+            let valid = manifest.chunk_hashes.get(*index as usize) == Some(&sha256(data));
+            if !valid {
+                dropped += data.len() + CHUNK_OVERHEAD;
+            }
+            valid
+        });
+        transfer.bytes -= dropped;
+        transfer.manifest = Some(manifest);
+        transfer.last_progress = Instant::now();
+        state.buffered_bytes -= dropped;
+
+        self.try_complete(&mut state, &key)
+    }
+
+    fn accept_fragment(&self, peer: &str, fragment: Fragment) -> Result<ReassemblyEvent> {
+        let key = (peer.to_string(), fragment.transfer_id);
+        // Chunks are never empty, and each one costs more than its data
+        if fragment.data.is_empty() {
+            counter!("frost.network.fragment.rejected", 1);
+            return Err(Error::Network("Empty fragment".into()));
+        }
+        let len = fragment.data.len() + CHUNK_OVERHEAD;
+        let mut state = self.state.lock();
+        if state.buffered_bytes + len > self.config.max_buffered_bytes {
+            counter!("frost.network.fragment.rejected", 1);
+            return Err(Error::Network("Reassembly buffer full".into()));
+        }
+        // Checked before `transfer` so a rejected chunk leaves no empty transfer
+        if len > self.config.max_transfer_bytes {
+            counter!("frost.network.fragment.rejected", 1);
+            return Err(Error::Network("Fragmented message exceeds size limit".into()));
+        }
+
+        let max_transfer_bytes = self.config.max_transfer_bytes;
+        let transfer = self.transfer(&mut state, &key)?;
+        if transfer.chunks.contains_key(&fragment.index) {
+            return Ok(ReassemblyEvent::Buffered);
+        }
+        if let Some(manifest) = &transfer.manifest {
+            match manifest.chunk_hashes.get(fragment.index as usize) {
+                Some(hash) if *hash == sha256(&fragment.data) => {}
+                Some(_) => {
+                    counter!("frost.network.fragment.corrupt", 1);
+                    warn!("Dropping corrupt chunk {} of transfer {}", fragment.index, Uuid::from_bytes(key.1));
+                    return Ok(ReassemblyEvent::Buffered);
+                }
+                None => return Err(Error::Network(format!("Chunk index {} out of range", fragment.index))),
+            }
+        }
+        if transfer.bytes + len > max_transfer_bytes {
+            counter!("frost.network.fragment.rejected", 1);
+            return Err(Error::Network("Fragmented message exceeds size limit".into()));
+        }
+
+        transfer.chunks.insert(fragment.index, fragment.data);
+        transfer.bytes += len;
+        transfer.last_progress = Instant::now();
+        state.buffered_bytes += len;
+
+        self.try_complete(&mut state, &key)
+    }
+
+    fn try_complete(&self, state: &mut ReassemblyState, key: &TransferKey) -> Result<ReassemblyEvent> {
+        let complete = state.transfers.get(key).is_some_and(|t| {
+            t.manifest.as_ref().is_some_and(|m| t.chunks.len() == m.chunk_hashes.len())
+        });
+        if !complete {
+            return Ok(ReassemblyEvent::Buffered);
+        }
+
+        let mut transfer = state.transfers.remove(key).expect("transfer exists");
+        state.buffered_bytes -= transfer.bytes;
+        let manifest = transfer.manifest.take().expect("manifest present");
+
+        let mut encoded = Vec::with_capacity(manifest.total_len as usize);
+        for index in 0..manifest.chunk_hashes.len() as u32 {
+            encoded.extend(transfer.chunks.remove(&index).expect("chunk present"));
+        }
+        if encoded.len() as u64 != manifest.total_len || sha256(&encoded) != manifest.message_hash {
+            counter!("frost.network.fragment.corrupt", 1);
+            return Err(Error::Network("Reassembled message does not match manifest".into()));
+        }
+        let message: FrostMessage = serde_json::from_slice(&encoded)
+            .map_err(|e| Error::Network(format!("Failed to decode reassembled message: {}", e)))?;
+        if *message.id.as_bytes() != key.1 {
+            return Err(Error::Network("Reassembled message id does not match transfer".into()));
+        }
+
+        counter!("frost.network.fragment.reassembled", 1);
+        Ok(ReassemblyEvent::Complete(message))
+    }
+
+    /// Drop timed-out transfers and collect retransmission requests
+    ///
+    /// Returns `(peer, request)` pairs for transfers that made no progress
+    /// for `retransmit_after`, at most `max_retransmit_requests` per transfer.
+    pub fn poll(&self) -> Vec<(String, RetransmitRequest)> {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+
+        let expired: Vec<_> = state
+            .transfers
+            .iter()
+            .filter(|(_, t)| now.duration_since(t.last_progress) >= self.config.reassembly_timeout)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            if let Some(transfer) = state.transfers.remove(&key) {
+                state.buffered_bytes -= transfer.bytes;
+                counter!("frost.network.fragment.expired", 1);
+                debug!("Fragmented transfer {} from {} timed out", Uuid::from_bytes(key.1), key.0);
+            }
+        }
+
+        let mut requests = Vec::new();
+        for ((peer, id), transfer) in state.transfers.iter_mut() {
+            let since = transfer.last_request.map_or(transfer.last_progress, |r| r.max(transfer.last_progress));
+            if now.duration_since(since) < self.config.retransmit_after
+                || transfer.requests_sent >= self.config.max_retransmit_requests
+            {
+                continue;
+            }
+            transfer.last_request = Some(now);
+            transfer.requests_sent += 1;
+            requests.push((peer.clone(), RetransmitRequest {
+                transfer_id: *id,
+                manifest: transfer.manifest.is_none(),
+                missing: transfer.missing(),
+            }));
+        }
+        requests
+    }
+}
+
+struct SentTransfer {
+    /// Peers the transfer was sent to, the only ones answered
+    recipients: HashSet<String>,
+    manifest: FrostMessage,
+    fragments: Vec<FrostMessage>,
+    bytes: usize,
+    sent_at: Instant,
+    retransmits: u32,
+    /// When each recipient's last request was answered
+    answered: HashMap<String, Instant>,
+}
+
+#[derive(Default)]
+struct SentCache {
+    transfers: HashMap<[u8; 16], SentTransfer>,
+    order: VecDeque<[u8; 16]>,
+    bytes: usize,
+}
+
+/// Transparent fragmentation in front of any `NetworkProtocol`
+///
+/// Outgoing messages larger than `max_message_size` are sent as a
+/// manifest followed by numbered chunks. Incoming messages are fed through
+/// `receive`, which reassembles fragments and answers retransmission
+/// requests; call `poll_retransmits` periodically to request missing
+/// chunks and expire stalled transfers.
+///
+/// Retransmits only go to peers a transfer was sent to: the target of a
+/// direct send, or the peers connected when a message was broadcast.
+pub struct FragmentingNetwork<N: NetworkProtocol> {
+    config: FragmentConfig,
+    network: N,
+    reassembler: Reassembler,
+    sent: Mutex<SentCache>,
+    node_id: String,
+}
+
+impl<N: NetworkProtocol> FragmentingNetwork<N> {
+    /// Wrap `network`; `node_id` is the source of retransmission requests
+    pub fn new(network: N, node_id: impl Into<String>, config: FragmentConfig) -> Self {
+        Self {
+            reassembler: Reassembler::new(config.clone()),
+            config,
+            network,
+            sent: Mutex::new(SentCache::default()),
+            node_id: node_id.into(),
+        }
+    }
+
+    /// Wrapped network
+    pub fn network(&self) -> &N {
+        &self.network
+    }
+
+    /// Receiver-side state
+    pub fn reassembler(&self) -> &Reassembler {
+        &self.reassembler
+    }
+
+    /// Transfers kept for retransmission
+    pub fn cached_transfers(&self) -> usize {
+        self.sent.lock().transfers.len()
+    }
+
+    async fn deliver(&self, peer: Option<&str>, message: FrostMessage) -> Result<()> {
+        match peer {
+            Some(peer) => self.network.send_to(peer, message).await,
+            None => self.network.broadcast(message).await,
+        }
+    }
+
+    async fn send(&self, peer: Option<&str>, message: FrostMessage) -> Result<()> {
+        let Some((manifest, fragments)) = fragment(&message, &self.config)? else {
+            return self.deliver(peer, message).await;
+        };
+
+        let recipients = match peer {
+            Some(peer) => HashSet::from([peer.to_string()]),
+            None => match self.network.get_peers().await {
+                Ok(peers) => peers.into_iter().collect(),
+                Err(e) => {
+                    warn!("Failed to list broadcast recipients, retransmits disabled: {}", e);
+                    HashSet::new()
+                }
+            },
+        };
+
+        counter!("frost.network.fragment.fragmented", 1);
+        self.deliver(peer, manifest.clone()).await?;
+        for fragment in &fragments {
+            self.deliver(peer, fragment.clone()).await?;
+        }
+        self.remember(*message.id.as_bytes(), recipients, manifest, fragments);
+        Ok(())
+    }
+
+    fn remember(&self, id: [u8; 16], recipients: HashSet<String>, manifest: FrostMessage, fragments: Vec<FrostMessage>) {
+        let bytes = manifest.payload.len() + fragments.iter().map(|f| f.payload.len()).sum::<usize>();
+        if bytes > self.config.retransmit_cache_bytes {
+            return;
+        }
+        let mut cache = self.sent.lock();
+        self.evict(&mut cache, bytes);
+        cache.bytes += bytes;
+        cache.order.push_back(id);
+        cache.transfers.insert(id, SentTransfer {
+            recipients,
+            manifest,
+            fragments,
+            bytes,
+            sent_at: Instant::now(),
+            retransmits: 0,
+            answered: HashMap::new(),
+        });
+    }
+
+    /// Evict expired transfers and make room for `incoming` bytes
+    fn evict(&self, cache: &mut SentCache, incoming: usize) {
+        while let Some(id) = cache.order.front().copied() {
+            let expired = cache
+                .transfers
+                .get(&id)
+                .is_none_or(|t| t.sent_at.elapsed() >= self.config.retransmit_cache_ttl);
+            if !expired && cache.bytes + incoming <= self.config.retransmit_cache_bytes {
+                break;
+            }
+            cache.order.pop_front();
+            if let Some(transfer) = cache.transfers.remove(&id) {
+                cache.bytes -= transfer.bytes;
+            }
+        }
+    }
+
+    /// Process a message received from `peer`
+    ///
+    /// `peer` must be the sender as authenticated by the transport. Returns
+    /// the message to deliver, if any: ordinary messages as is and
+    /// fragmented messages once fully reassembled.
+    pub async fn receive(&self, peer: &str, message: FrostMessage) -> Result<Option<FrostMessage>> {
+        match self.reassembler.accept(peer, message)? {
+            ReassemblyEvent::Passthrough(message) | ReassemblyEvent::Complete(message) => Ok(Some(message)),
+            ReassemblyEvent::Buffered => Ok(None),
+            ReassemblyEvent::RetransmitRequested { peer, request } => {
+                self.retransmit(&peer, &request).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Resend requested chunks to `peer`
+    ///
+    /// `peer` is the authenticated requester and must be a recipient of the
+    /// transfer. Returns the number of messages resent; unknown or expired
+    /// transfers resend nothing. Each chunk is resent at most once per
+    /// request, a transfer answers at most `max_retransmits_per_transfer`
+    /// requests, and a peer's requests for one transfer are answered at most
+    /// once per `retransmit_interval`.
+    pub async fn retransmit(&self, peer: &str, request: &RetransmitRequest) -> Result<usize> {
+        let resend: Vec<FrostMessage> = {
+            let mut cache = self.sent.lock();
+            self.evict(&mut cache, 0);
+            let Some(transfer) = cache.transfers.get_mut(&request.transfer_id) else {
+                debug!("Retransmit request for unknown transfer {}", Uuid::from_bytes(request.transfer_id));
+                return Ok(0);
+            };
+            if !transfer.recipients.contains(peer) {
+                return Err(Error::Network(format!("Peer {} did not receive transfer", peer)));
+            }
+            let now = Instant::now();
+            let too_soon = transfer
+                .answered
+                .get(peer)
+                .is_some_and(|last| now.duration_since(*last) < self.config.retransmit_interval);
+            if too_soon || transfer.retransmits >= self.config.max_retransmits_per_transfer {
+                counter!("frost.network.fragment.retransmit_limited", 1);
+                return Err(Error::Network(format!(
+                    "Retransmission limit reached for transfer {}", Uuid::from_bytes(request.transfer_id)
+                )));
+            }
+            transfer.retransmits += 1;
+            transfer.answered.insert(peer.to_string(), now);
+
+            let chunks = transfer.fragments.len();
+            let missing: BTreeSet<usize> = request
+                .missing
+                .iter()
+                .map(|i| *i as usize)
+                .filter(|i| *i < chunks)
+                .collect();
+            let manifest = request.manifest.then(|| transfer.manifest.clone());
+            manifest
+                .into_iter()
+                .chain(missing.into_iter().map(|i| transfer.fragments[i].clone()))
+                .collect()
+        };
+
+        counter!("frost.network.fragment.retransmitted", resend.len() as u64);
+        let count = resend.len();
+        for message in resend {
+            self.network.send_to(peer, message).await?;
+        }
+        Ok(count)
+    }
+
+    /// Request missing chunks for stalled transfers
+    ///
+    /// Returns the number of requests sent.
+    pub async fn poll_retransmits(&self) -> Result<usize> {
+        let requests = self.reassembler.poll();
+        let count = requests.len();
+        for (peer, request) in requests {
+            let message = FrostMessage::new(
+                MessageType::Custom(RETRANSMIT_TYPE.into()),
+                request.encode(),
+                self.node_id.clone(),
+                Some(peer.clone()),
+            );
+            self.network.send_to(&peer, message).await?;
+        }
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl<N: NetworkProtocol> NetworkProtocol for FragmentingNetwork<N> {
+    async fn start(&mut self) -> Result<()> {
+        self.network.start().await
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.network.stop().await
+    }
+
+    async fn broadcast(&self, message: FrostMessage) -> Result<()> {
+        self.send(None, message).await
+    }
+
+    async fn send_to(&self, peer_id: &str, message: FrostMessage) -> Result<()> {
+        self.send(Some(peer_id), message).await
+    }
+
+    async fn get_peers(&self) -> Result<Vec<String>> {
+        self.network.get_peers().await
+    }
+}
@@ -5,29 +5,46 @@
 
 pub mod protocol;
 pub mod transport;
+pub mod tcp;
+mod connection;
+pub mod compression;
+pub mod noise;
+pub mod ws;
 pub mod peer;
 pub mod error;
 pub mod discovery;
 pub mod security;
+pub mod policy;
+pub mod rate_limit;
+pub mod reputation;
 pub mod circuit_breaker;
 pub mod backpressure;
 pub mod pool;
 pub mod retry;
 pub mod telemetry;
 pub mod p2p;
+pub mod fragment;
 
 pub use protocol::{NetworkProtocol as ImportedNetworkProtocol, ProtocolConfig};
 pub use transport::{Transport, TransportConfig};
-pub use peer::{Peer, PeerInfo, PeerManager};
+pub use tcp::TcpTransport;
+pub use compression::{Codec, CodecRegistry};
+pub use noise::EncryptedTransport;
+pub use ws::WebSocketTransport;
+pub use peer::{Peer, PeerInfo, PeerManager, InMemoryPeerManager};
 pub use error::NetworkError;
 pub use discovery::{PeerDiscovery, DiscoveryConfig, PeerHealthCheck};
-pub use security::{SecurityManager, SecurityConfig, AuthenticationResult};
+pub use security::{SecurityManager, SecurityConfig, AuthenticationResult, DefaultSecurityManager};
+pub use policy::{AuthorizationPolicy, PolicyEngine};
+pub use rate_limit::RateLimiter;
+pub use reputation::{ReputationManager, ReputationConfig, ReputationEvent};
 pub use circuit_breaker::{CircuitBreaker, CircuitConfig, CircuitState};
 pub use backpressure::{BackpressureController, BackpressureConfig, PressureLevel};
 pub use pool::{ConnectionPool, PoolConfig, PooledConnection};
 pub use retry::{RetryPolicy, RetryConfig, with_retry};
 pub use telemetry::{TelemetryManager, NetworkMetrics as ImportedNetworkMetrics, NetworkEvent};
 pub use p2p::{P2PNode, P2PConfig, NodeIdentity};
+pub use fragment::{FragmentConfig, FragmentingNetwork, Reassembler};
 
 use crate::Result;
 use async_trait::async_trait;
@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use libp2p::identity::PublicKey;
+use libp2p::PeerId;
+use metrics::counter;
+use parking_lot::{Mutex, RwLock};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use snow::{HandshakeState, TransportState};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::network::p2p::NodeIdentity;
+use crate::network::security::SessionKeys;
+use crate::network::transport::{EncryptionConfig, TransportConfig, TransportMetrics};
+use crate::network::{Peer, Transport};
+use crate::Result;
+
+/// Prefix signed by the identity key to bind it to the Noise static key
+const STATIC_KEY_DOMAIN: &[u8] = b"frost-noise-static-key:";
+/// Largest Noise message, ciphertext and tag included
+const MAX_NOISE_MESSAGE: usize = 65535;
+const TAG_LEN: usize = 16;
+const MAX_CHUNK: usize = MAX_NOISE_MESSAGE - TAG_LEN;
+
+/// Flag byte leading the plaintext of each message
+const DATA: u8 = 0;
+/// The sender rotates its outgoing key after this message
+const REKEYED: u8 = 1;
+
+/// Noise protocol name for an `EncryptionConfig`
+///
+/// `algorithm` selects the cipher (`chacha20poly1305` or `aes256gcm`) and
+/// `key_size` must be 256 bits.
+pub fn noise_params(config: &EncryptionConfig) -> Result<snow::params::NoiseParams> {
+    let cipher = match config.algorithm.to_ascii_lowercase().as_str() {
+        "chacha20poly1305" | "chachapoly" => "ChaChaPoly",
+        "aes256gcm" | "aes-256-gcm" | "aesgcm" => "AESGCM",
+        other => return Err(Error::Network(format!("Unsupported encryption algorithm {:?}", other))),
+    };
+    if config.key_size != 256 {
+        return Err(Error::Network(format!("Unsupported key size {}, expected 256", config.key_size)));
+    }
+    format!("Noise_XX_25519_{}_BLAKE2s", cipher)
+        .parse()
+        .map_err(|e| Error::Network(format!("Invalid Noise parameters: {}", e)))
+}
+
+/// Handshake payload proving the sender owns a `NodeIdentity` key
+#[derive(Serialize, Deserialize)]
+struct IdentityProof {
+    /// Protobuf-encoded libp2p public key
+    public_key: Vec<u8>,
+    /// Signature over `STATIC_KEY_DOMAIN` and the Noise static key
+    signature: Vec<u8>,
+}
+
+struct Session {
+    cipher: Mutex<TransportState>,
+    /// Held from encryption until the inner send completes, so messages
+    /// reach the wire in nonce order
+    sending: tokio::sync::Mutex<()>,
+    /// Held from the inner receive until decryption completes
+    receiving: tokio::sync::Mutex<()>,
+    remote_identity: PeerId,
+    handshake_hash: Vec<u8>,
+    keys: RwLock<SessionKeys>,
+    generation: Mutex<u64>,
+    rotated_at: Mutex<Instant>,
+    /// Outgoing key rotation due after the next message is sent
+    rekeyed: AtomicBool,
+}
+
+/// Keys for the application derived from the handshake hash; the traffic
+/// keys never leave the cipher state
+fn export_keys(handshake_hash: &[u8], session_id: Uuid, generation: u64, interval: Duration) -> SessionKeys {
+    let derive = |label: &[u8]| {
+        let mut hasher = Sha256::new();
+        hasher.update(label);
+        hasher.update(handshake_hash);
+        hasher.update(generation.to_be_bytes());
+        hasher.finalize().to_vec()
+    };
+    let created_at = SystemTime::now();
+    SessionKeys {
+        session_id,
+        encryption_key: derive(b"frost-noise-encryption"),
+        signing_key: derive(b"frost-noise-signing"),
+        created_at,
+        expires_at: created_at + interval,
+    }
+}
+
+/// Authenticated encryption over any `Transport`
+///
+/// Sessions are established with a Noise XX handshake carried in the inner
+/// transport's messages. Each side signs its Noise static key with its
+/// `NodeIdentity` key, so a session is bound to the remote libp2p identity.
+/// Outgoing keys are rotated once they are older than the key rotation
+/// interval; the first message under the new key tells the receiver to
+/// rotate too. With encryption disabled the wrapper passes data through.
+pub struct EncryptedTransport<T: Transport> {
+    inner: T,
+    identity: NodeIdentity,
+    key_rotation_interval: Duration,
+    params: Option<snow::params::NoiseParams>,
+    sessions: RwLock<HashMap<Uuid, Arc<Session>>>,
+}
+
+impl<T: Transport> EncryptedTransport<T> {
+    /// Wrap `inner`, authenticating sessions with `identity`
+    pub fn new(inner: T, identity: NodeIdentity) -> Self {
+        Self {
+            inner,
+            identity,
+            key_rotation_interval: Duration::from_secs(3600),
+            params: None,
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Session key lifetime, normally `SecurityConfig::key_rotation_interval`
+    pub fn with_key_rotation_interval(mut self, interval: Duration) -> Self {
+        self.key_rotation_interval = interval;
+        self
+    }
+
+    /// Wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Local node identity
+    pub fn identity(&self) -> &NodeIdentity {
+        &self.identity
+    }
+
+    /// Current keys of the session with a peer
+    pub fn session(&self, peer: &Peer) -> Option<SessionKeys> {
+        self.sessions.read().get(&peer.id).map(|s| s.keys.read().clone())
+    }
+
+    /// Authenticated libp2p identity of a peer
+    pub fn remote_identity(&self, peer: &Peer) -> Option<PeerId> {
+        self.sessions.read().get(&peer.id).map(|s| s.remote_identity)
+    }
+
+    /// Noise handshake hash of the session with a peer, unique to the
+    /// connection; bind authentication challenges to it
+    pub fn channel_binding(&self, peer: &Peer) -> Option<Vec<u8>> {
+        self.sessions.read().get(&peer.id).map(|s| s.handshake_hash.clone())
+    }
+
+    /// Run the responder side of the handshake for an inbound peer
+    ///
+    /// Call this for peers accepted by the inner transport; `connect` runs
+    /// the initiator side.
+    pub async fn accept(&self, peer: &Peer) -> Result<SessionKeys> {
+        self.establish(peer, false).await
+    }
+
+    /// Rotate the outgoing key of a session
+    ///
+    /// The traffic key changes after the next message, which tells the peer
+    /// to rotate its incoming key in step. Exported `SessionKeys` follow
+    /// the local outgoing key, so the two sides' keys diverge until the
+    /// peer rotates as well.
+    pub async fn rotate_keys(&self, peer: &Peer) -> Result<SessionKeys> {
+        let session = self.session_for(peer)?;
+        let _sending = session.sending.lock().await;
+        Ok(self.rotate(&session))
+    }
+
+    fn session_for(&self, peer: &Peer) -> Result<Arc<Session>> {
+        self.sessions
+            .read()
+            .get(&peer.id)
+            .cloned()
+            .ok_or_else(|| Error::Network(format!("No encrypted session with peer {}", peer.id)))
+    }
+
+    fn rotate(&self, session: &Session) -> SessionKeys {
+        session.rekeyed.store(true, Ordering::SeqCst);
+        *session.rotated_at.lock() = Instant::now();
+        let generation = {
+            let mut generation = session.generation.lock();
+            *generation += 1;
+            *generation
+        };
+        let session_id = session.keys.read().session_id;
+        let keys = export_keys(&session.handshake_hash, session_id, generation, self.key_rotation_interval);
+        *session.keys.write() = keys.clone();
+        counter!("frost.network.noise.key_rotations", 1);
+        keys
+    }
+
+    fn prove_identity(&self, static_key: &[u8]) -> Result<Vec<u8>> {
+        let mut message = STATIC_KEY_DOMAIN.to_vec();
+        message.extend_from_slice(static_key);
+        let proof = IdentityProof {
+            public_key: self.identity.keypair.public().encode_protobuf(),
+            signature: self
+                .identity
+                .keypair
+                .sign(&message)
+                .map_err(|e| Error::Network(format!("Failed to sign static key: {}", e)))?,
+        };
+        serde_json::to_vec(&proof).map_err(|e| Error::Network(format!("Failed to encode identity proof: {}", e)))
+    }
+
+    fn verify_identity(handshake: &HandshakeState, payload: &[u8]) -> Result<PeerId> {
+        let proof: IdentityProof = serde_json::from_slice(payload)
+            .map_err(|e| Error::Network(format!("Invalid identity proof: {}", e)))?;
+        let public_key = PublicKey::try_decode_protobuf(&proof.public_key)
+            .map_err(|e| Error::Network(format!("Invalid identity key: {}", e)))?;
+        let static_key = handshake
+            .get_remote_static()
+            .ok_or_else(|| Error::Network("Remote static key missing".into()))?;
+        let mut message = STATIC_KEY_DOMAIN.to_vec();
+        message.extend_from_slice(static_key);
+        if !public_key.verify(&message, &proof.signature) {
+            return Err(Error::Network("Identity signature does not match static key".into()));
+        }
+        Ok(public_key.to_peer_id())
+    }
+
+    async fn write_handshake(&self, peer: &Peer, handshake: &mut HandshakeState, payload: &[u8]) -> Result<()> {
+        let mut buffer = vec![0u8; MAX_NOISE_MESSAGE];
+        let len = handshake
+            .write_message(payload, &mut buffer)
+            .map_err(|e| Error::Network(format!("Noise handshake failed: {}", e)))?;
+        self.inner.send_data(peer, &buffer[..len]).await?;
+        Ok(())
+    }
+
+    async fn read_handshake(&self, peer: &Peer, handshake: &mut HandshakeState) -> Result<Vec<u8>> {
+        let message = self.inner.receive_data(peer).await?;
+        let mut payload = vec![0u8; MAX_NOISE_MESSAGE];
+        let len = handshake
+            .read_message(&message, &mut payload)
+            .map_err(|e| Error::Network(format!("Noise handshake failed: {}", e)))?;
+        payload.truncate(len);
+        Ok(payload)
+    }
+
+    /// XX: -> e; <- e, ee, s, es; -> s, se
+    async fn handshake(&self, peer: &Peer, initiator: bool) -> Result<Session> {
+        let params = self
+            .params
+            .clone()
+            .ok_or_else(|| Error::Network("Encryption not enabled".into()))?;
+        let builder = snow::Builder::new(params);
+        let keypair = builder
+            .generate_keypair()
+            .map_err(|e| Error::Network(format!("Failed to generate Noise key: {}", e)))?;
+        let proof = self.prove_identity(&keypair.public)?;
+        let builder = builder.local_private_key(&keypair.private);
+        let mut handshake = if initiator {
+            builder.build_initiator()
+        } else {
+            builder.build_responder()
+        }
+        .map_err(|e| Error::Network(format!("Failed to start Noise handshake: {}", e)))?;
+
+        let remote_identity = if initiator {
+            self.write_handshake(peer, &mut handshake, &[]).await?;
+            let payload = self.read_handshake(peer, &mut handshake).await?;
+            let remote = Self::verify_identity(&handshake, &payload)?;
+            self.write_handshake(peer, &mut handshake, &proof).await?;
+            remote
+        } else {
+            self.read_handshake(peer, &mut handshake).await?;
+            self.write_handshake(peer, &mut handshake, &proof).await?;
+            let payload = self.read_handshake(peer, &mut handshake).await?;
+            Self::verify_identity(&handshake, &payload)?
+        };
+        if remote_identity == self.identity.peer_id {
+            return Err(Error::Network("Refusing session with own identity".into()));
+        }
+
+        let handshake_hash = handshake.get_handshake_hash().to_vec();
+        let cipher = handshake
+            .into_transport_mode()
+            .map_err(|e| Error::Network(format!("Noise handshake incomplete: {}", e)))?;
+        // Both sides derive the same session id from the handshake hash
+        let session_id = Uuid::from_slice(&handshake_hash[..16])
+            .map_err(|e| Error::Network(format!("Invalid handshake hash: {}", e)))?;
+        let keys = export_keys(&handshake_hash, session_id, 0, self.key_rotation_interval);
+        Ok(Session {
+            cipher: Mutex::new(cipher),
+            sending: tokio::sync::Mutex::new(()),
+            receiving: tokio::sync::Mutex::new(()),
+            remote_identity,
+            handshake_hash,
+            keys: RwLock::new(keys),
+            generation: Mutex::new(0),
+            rotated_at: Mutex::new(Instant::now()),
+            rekeyed: AtomicBool::new(false),
+        })
+    }
+
+    async fn establish(&self, peer: &Peer, initiator: bool) -> Result<SessionKeys> {
+        match self.handshake(peer, initiator).await {
+            Ok(session) => {
+                debug!("Noise session with peer {} as {}", peer.id, session.remote_identity);
+                counter!("frost.network.noise.handshakes", 1, "result" => "success");
+                let keys = session.keys.read().clone();
+                self.sessions.write().insert(peer.id, Arc::new(session));
+                Ok(keys)
+            }
+            Err(e) => {
+                warn!("Noise handshake with peer {} failed: {}", peer.id, e);
+                counter!("frost.network.noise.handshakes", 1, "result" => "failure");
+                Err(e)
+            }
+        }
+    }
+
+    fn encrypt(&self, session: &Session, data: &[u8]) -> Result<Vec<u8>> {
+        if session.rotated_at.lock().elapsed() >= self.key_rotation_interval {
+            self.rotate(session);
+        }
+        let rekeyed = session.rekeyed.swap(false, Ordering::SeqCst);
+        // The flag is encrypted with the data so it cannot be forged
+        let mut plaintext = Vec::with_capacity(1 + data.len());
+        plaintext.push(if rekeyed { REKEYED } else { DATA });
+        plaintext.extend_from_slice(data);
+
+        let mut cipher = session.cipher.lock();
+        // Each chunk is a length-prefixed Noise message
+        let mut message = Vec::with_capacity(plaintext.len() + (plaintext.len() / MAX_CHUNK + 1) * (2 + TAG_LEN));
+        let mut buffer = vec![0u8; MAX_NOISE_MESSAGE];
+        for chunk in plaintext.chunks(MAX_CHUNK) {
+            let len = cipher
+                .write_message(chunk, &mut buffer)
+                .map_err(|e| Error::Network(format!("Encryption failed: {}", e)))?;
+            message.extend_from_slice(&(len as u16).to_be_bytes());
+            message.extend_from_slice(&buffer[..len]);
+        }
+        if rekeyed {
+            cipher.rekey_outgoing();
+        }
+        Ok(message)
+    }
+
+    fn decrypt(&self, session: &Session, message: &[u8]) -> Result<Vec<u8>> {
+        let mut rest = message;
+        let mut cipher = session.cipher.lock();
+        let mut data = Vec::with_capacity(rest.len());
+        let mut buffer = vec![0u8; MAX_NOISE_MESSAGE];
+        while !rest.is_empty() {
+            if rest.len() < 2 {
+                return Err(Error::Network("Truncated encrypted message".into()));
+            }
+            let len = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+            let chunk = rest
+                .get(2..2 + len)
+                .ok_or_else(|| Error::Network("Truncated encrypted message".into()))?;
+            let read = cipher
+                .read_message(chunk, &mut buffer)
+                .map_err(|e| Error::Network(format!("Decryption failed: {}", e)))?;
+            data.extend_from_slice(&buffer[..read]);
+            rest = &rest[2 + len..];
+        }
+        // Only an authenticated flag may rotate the incoming key
+        match data.first() {
+            Some(&DATA) => {}
+            Some(&REKEYED) => cipher.rekey_incoming(),
+            Some(other) => return Err(Error::Network(format!("Unknown encrypted message flag {}", other))),
+            None => return Err(Error::Network("Empty encrypted message".into())),
+        }
+        data.remove(0);
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for EncryptedTransport<T> {
+    async fn init(&mut self, mut config: TransportConfig) -> Result<()> {
+        if config.encryption.enabled {
+            self.params = Some(noise_params(&config.encryption)?);
+            if self.key_rotation_interval.is_zero() {
+                return Err(Error::Network("Key rotation interval must be non-zero".into()));
+            }
+        }
+        // Encryption happens here, not in the wrapped transport
+        config.encryption.enabled = false;
+        self.inner.init(config).await
+    }
+
+    async fn connect(&mut self, address: &str) -> Result<Peer> {
+        let peer = self.inner.connect(address).await?;
+        if self.params.is_some() {
+            if let Err(e) = self.establish(&peer, true).await {
+                let _ = self.inner.disconnect(&peer).await;
+                return Err(e);
+            }
+        }
+        Ok(peer)
+    }
+
+    async fn disconnect(&mut self, peer: &Peer) -> Result<()> {
+        self.sessions.write().remove(&peer.id);
+        self.inner.disconnect(peer).await
+    }
+
+    async fn send_data(&self, peer: &Peer, data: &[u8]) -> Result<usize> {
+        if self.params.is_none() {
+            return self.inner.send_data(peer, data).await;
+        }
+        let session = self.session_for(peer)?;
+        let _sending = session.sending.lock().await;
+        let message = self.encrypt(&session, data)?;
+        self.inner.send_data(peer, &message).await?;
+        Ok(data.len())
+    }
+
+    async fn receive_data(&self, peer: &Peer) -> Result<Vec<u8>> {
+        if self.params.is_none() {
+            return self.inner.receive_data(peer).await;
+        }
+        let session = self.session_for(peer)?;
+        let _receiving = session.receiving.lock().await;
+        let message = self.inner.receive_data(peer).await?;
+        self.decrypt(&session, &message).inspect_err(|_| {
+            counter!("frost.network.noise.decryption_failures", 1);
+        })
+    }
+
+    async fn is_connected(&self, peer: &Peer) -> bool {
+        let secured = self.params.is_none() || self.sessions.read().contains_key(&peer.id);
+        secured && self.inner.is_connected(peer).await
+    }
+
+    fn metrics(&self) -> TransportMetrics {
+        self.inner.metrics()
+    }
+}
@@ -1,6 +1,14 @@
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
-use std::time::{Duration, SystemTime};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, warn};
+use uuid::Uuid;
+use crate::error::Error;
+use crate::extensions::{ExtensionHooks, PeerEventType};
 use crate::Result;
 
 /// Peer representation in the network
@@ -21,6 +29,27 @@ pub struct PeerInfo {
     pub node_type: NodeType,
 }
 
+impl PeerInfo {
+    /// IP of the peer's address, when it is an IP or socket address
+    pub fn ip(&self) -> Option<IpAddr> {
+        parse_ip(&self.address)
+    }
+}
+
+fn parse_ip(address: &str) -> Option<IpAddr> {
+    address
+        .parse::<SocketAddr>()
+        .map(|a| a.ip())
+        .or_else(|_| address.parse::<IpAddr>())
+        .ok()
+}
+
+/// Key a ban on `address` applies to: its IP, ignoring the port, or the
+/// whole address when it is not an IP or socket address
+fn ban_key(address: &str) -> String {
+    parse_ip(address).map_or_else(|| address.to_string(), |ip| ip.to_string())
+}
+
 /// Peer connection state
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum PeerState {
@@ -42,8 +71,8 @@ pub enum NodeType {
 /// Peer manager for handling peer connections
 #[async_trait]
 pub trait PeerManager: Send + Sync {
-    /// Add a new peer
-    async fn add_peer(&mut self, info: PeerInfo) -> Result<Peer>;
+    /// Add a new peer under its node id
+    async fn add_peer(&mut self, id: Uuid, info: PeerInfo) -> Result<Peer>;
 
     /// Remove a peer
     async fn remove_peer(&mut self, peer: &Peer) -> Result<()>;
@@ -76,4 +105,341 @@ pub struct PeerStats {
     pub messages_sent: u64,
     pub messages_received: u64,
     pub bytes_transferred: u64,
-} 
\ No newline at end of file
+}
+
+/// Ban on a peer's IP
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanEntry {
+    pub peer_id: Uuid,
+    /// Banned IP, or the full address when it is not an IP address
+    pub address: String,
+    pub reason: String,
+    pub banned_at: SystemTime,
+    /// `None` for a permanent ban
+    pub expires_at: Option<SystemTime>,
+}
+
+impl BanEntry {
+    /// Whether the ban is still in force
+    pub fn is_active(&self) -> bool {
+        self.expires_at.is_none_or(|t| t > SystemTime::now())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Traffic {
+    messages_sent: u64,
+    messages_received: u64,
+    bytes: u64,
+    last_message: Option<SystemTime>,
+}
+
+struct PeerRecord {
+    peer: Peer,
+    connected_at: Option<Instant>,
+}
+
+/// In-memory `PeerManager`
+///
+/// Peers start out `Handshaking` and move between states through
+/// `set_state`, which only allows handshaking to connect or drop,
+/// connected peers to disconnect, and disconnected peers to handshake
+/// again; `Banned` is entered and left through the ban methods only.
+/// Limits from `with_max_peers` cap the handshaking and connected peers
+/// of a node type. Bans apply to the IP of the peer's address, so a
+/// banned node cannot rejoin under a fresh id or source port, and are written to the ban list file
+/// given to `open` after every change. State changes and bans are
+/// reported to the extension hooks, if set.
+pub struct InMemoryPeerManager {
+    peers: HashMap<Uuid, PeerRecord>,
+    bans: HashMap<String, BanEntry>,
+    traffic: Traffic,
+    max_peers: HashMap<NodeType, usize>,
+    ban_duration: Option<Duration>,
+    ban_list: Option<PathBuf>,
+    hooks: Option<Arc<ExtensionHooks>>,
+}
+
+impl InMemoryPeerManager {
+    /// Manager without ban persistence
+    pub fn new() -> Self {
+        Self {
+            peers: HashMap::new(),
+            bans: HashMap::new(),
+            traffic: Traffic::default(),
+            max_peers: HashMap::new(),
+            ban_duration: None,
+            ban_list: None,
+            hooks: None,
+        }
+    }
+
+    /// Manager persisting bans to `path`, loading the active ones already there
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut manager = Self::new();
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                let bans: Vec<BanEntry> = serde_json::from_str(&contents)
+                    .map_err(|e| Error::Network(format!("Corrupt ban list {}: {}", path.display(), e)))?;
+                manager.bans = bans
+                    .into_iter()
+                    .filter(BanEntry::is_active)
+                    .map(|b| (ban_key(&b.address), b))
+                    .collect();
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        manager.ban_list = Some(path);
+        Ok(manager)
+    }
+
+    /// Allow at most `max` handshaking or connected peers of `node_type`
+    pub fn with_max_peers(mut self, node_type: NodeType, max: usize) -> Self {
+        self.max_peers.insert(node_type, max);
+        self
+    }
+
+    /// Duration of bans made through `ban_peer`; permanent if unset
+    pub fn with_ban_duration(mut self, duration: Duration) -> Self {
+        self.ban_duration = Some(duration);
+        self
+    }
+
+    /// Report peer events to extensions
+    pub fn with_hooks(mut self, hooks: Arc<ExtensionHooks>) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Move a peer to `state`
+    pub async fn set_state(&mut self, id: Uuid, state: PeerState) -> Result<Peer> {
+        let record = self
+            .peers
+            .get(&id)
+            .ok_or_else(|| Error::Network(format!("Unknown peer {}", id)))?;
+        let current = record.peer.state;
+        if current == state {
+            return Ok(record.peer.clone());
+        }
+        let allowed = matches!(
+            (current, state),
+            (PeerState::Handshaking, PeerState::Connected)
+                | (PeerState::Handshaking, PeerState::Disconnected)
+                | (PeerState::Connected, PeerState::Disconnected)
+                | (PeerState::Disconnected, PeerState::Handshaking)
+        );
+        if !allowed {
+            return Err(Error::Network(format!("Peer {} cannot go from {:?} to {:?}", id, current, state)));
+        }
+        if state == PeerState::Handshaking {
+            self.check_capacity(&record.peer.info.node_type)?;
+        }
+
+        let record = self.peers.get_mut(&id).expect("peer checked above");
+        record.peer.state = state;
+        record.connected_at = (state == PeerState::Connected).then(Instant::now);
+        let peer = record.peer.clone();
+        debug!("Peer {} moved from {:?} to {:?}", id, current, state);
+        self.notify(&peer, state_event(state)).await;
+        Ok(peer)
+    }
+
+    /// Ban a peer's IP for `duration`, or permanently
+    pub async fn ban_peer_for(&mut self, peer: &Peer, reason: String, duration: Option<Duration>) -> Result<()> {
+        let banned_at = SystemTime::now();
+        let key = ban_key(&peer.info.address);
+        self.bans.insert(key.clone(), BanEntry {
+            peer_id: peer.id,
+            address: key,
+            reason: reason.clone(),
+            banned_at,
+            expires_at: duration.map(|d| banned_at + d),
+        });
+        let banned = match self.peers.get_mut(&peer.id) {
+            Some(record) => {
+                record.peer.state = PeerState::Banned;
+                record.connected_at = None;
+                record.peer.clone()
+            }
+            None => Peer { state: PeerState::Banned, ..peer.clone() },
+        };
+        self.persist_bans().await?;
+        warn!("Banned peer {} at {}: {}", peer.id, peer.info.address, reason);
+        self.notify(&banned, PeerEventType::Banned(reason)).await;
+        Ok(())
+    }
+
+    /// Whether `address`, on any port, is banned
+    pub fn is_banned(&self, address: &str) -> bool {
+        self.bans.get(&ban_key(address)).is_some_and(BanEntry::is_active)
+    }
+
+    /// Bans in force
+    pub fn bans(&self) -> Vec<BanEntry> {
+        self.bans.values().filter(|b| b.is_active()).cloned().collect()
+    }
+
+    /// Lift expired bans, returning how many were lifted
+    pub async fn prune_bans(&mut self) -> Result<usize> {
+        let expired: Vec<BanEntry> = self.bans.values().filter(|b| !b.is_active()).cloned().collect();
+        if expired.is_empty() {
+            return Ok(0);
+        }
+        for ban in &expired {
+            self.bans.remove(&ban.address);
+        }
+        self.persist_bans().await?;
+        for ban in &expired {
+            self.lift(&ban.address).await;
+        }
+        Ok(expired.len())
+    }
+
+    /// Record traffic with a peer
+    pub fn record_message(&mut self, outbound: bool, bytes: usize) {
+        if outbound {
+            self.traffic.messages_sent += 1;
+        } else {
+            self.traffic.messages_received += 1;
+        }
+        self.traffic.bytes += bytes as u64;
+        self.traffic.last_message = Some(SystemTime::now());
+    }
+
+    fn check_capacity(&self, node_type: &NodeType) -> Result<()> {
+        let Some(max) = self.max_peers.get(node_type) else {
+            return Ok(());
+        };
+        let active = self
+            .peers
+            .values()
+            .filter(|r| &r.peer.info.node_type == node_type)
+            .filter(|r| matches!(r.peer.state, PeerState::Connected | PeerState::Handshaking))
+            .count();
+        if active >= *max {
+            return Err(Error::Network(format!("Peer limit of {} {:?} peers reached", max, node_type)));
+        }
+        Ok(())
+    }
+
+    /// Return banned peers under ban key `key` to `Disconnected`
+    async fn lift(&mut self, key: &str) {
+        let mut lifted = Vec::new();
+        for record in self.peers.values_mut() {
+            if record.peer.state == PeerState::Banned && ban_key(&record.peer.info.address) == key {
+                record.peer.state = PeerState::Disconnected;
+                lifted.push(record.peer.clone());
+            }
+        }
+        for peer in lifted {
+            self.notify(&peer, PeerEventType::Unbanned).await;
+        }
+    }
+
+    async fn persist_bans(&self) -> Result<()> {
+        let Some(path) = &self.ban_list else {
+            return Ok(());
+        };
+        let bans = self.bans();
+        let contents = serde_json::to_vec_pretty(&bans)
+            .map_err(|e| Error::Network(format!("Failed to encode ban list: {}", e)))?;
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, &contents).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        Ok(())
+    }
+
+    async fn notify(&self, peer: &Peer, event: PeerEventType) {
+        if let Some(hooks) = &self.hooks {
+            if let Err(e) = hooks.handle_network_event(peer, event).await {
+                warn!("Extension failed to handle event for peer {}: {}", peer.id, e);
+            }
+        }
+    }
+}
+
+impl Default for InMemoryPeerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn state_event(state: PeerState) -> PeerEventType {
+    match state {
+        PeerState::Connected => PeerEventType::Connected,
+        PeerState::Disconnected => PeerEventType::Disconnected,
+        other => PeerEventType::StateChanged(format!("{:?}", other)),
+    }
+}
+
+#[async_trait]
+impl PeerManager for InMemoryPeerManager {
+    async fn add_peer(&mut self, id: Uuid, info: PeerInfo) -> Result<Peer> {
+        if self.peers.contains_key(&id) {
+            return Err(Error::Network(format!("Peer {} is already tracked", id)));
+        }
+        if let Some(ban) = self.bans.get(&ban_key(&info.address)).filter(|b| b.is_active()) {
+            return Err(Error::Network(format!("Address {} is banned: {}", info.address, ban.reason)));
+        }
+        self.check_capacity(&info.node_type)?;
+        let peer = Peer {
+            id,
+            info,
+            state: PeerState::Handshaking,
+        };
+        self.peers.insert(peer.id, PeerRecord { peer: peer.clone(), connected_at: None });
+        self.notify(&peer, state_event(PeerState::Handshaking)).await;
+        Ok(peer)
+    }
+
+    async fn remove_peer(&mut self, peer: &Peer) -> Result<()> {
+        let record = self
+            .peers
+            .remove(&peer.id)
+            .ok_or_else(|| Error::Network(format!("Unknown peer {}", peer.id)))?;
+        if record.peer.state == PeerState::Connected {
+            let peer = Peer { state: PeerState::Disconnected, ..record.peer };
+            self.notify(&peer, PeerEventType::Disconnected).await;
+        }
+        Ok(())
+    }
+
+    async fn ban_peer(&mut self, peer: &Peer, reason: String) -> Result<()> {
+        self.ban_peer_for(peer, reason, self.ban_duration).await
+    }
+
+    async fn unban_peer(&mut self, peer: &Peer) -> Result<()> {
+        let key = ban_key(&peer.info.address);
+        if self.bans.remove(&key).is_none() {
+            return Ok(());
+        }
+        self.persist_bans().await?;
+        self.lift(&key).await;
+        Ok(())
+    }
+
+    async fn get_peer(&self, id: Uuid) -> Result<Option<Peer>> {
+        Ok(self.peers.get(&id).map(|r| r.peer.clone()))
+    }
+
+    async fn list_peers(&self) -> Result<Vec<Peer>> {
+        Ok(self.peers.values().map(|r| r.peer.clone()).collect())
+    }
+
+    fn peer_stats(&self) -> PeerStats {
+        let count = |state| self.peers.values().filter(|r| r.peer.state == state).count();
+        PeerStats {
+            total_peers: self.peers.len(),
+            connected_peers: count(PeerState::Connected),
+            banned_peers: self.bans.values().filter(|b| b.is_active()).count(),
+            handshaking_peers: count(PeerState::Handshaking),
+            peer_uptime: self.peers.values().filter_map(|r| r.connected_at).map(|t| t.elapsed()).sum(),
+            last_message: self.traffic.last_message,
+            messages_sent: self.traffic.messages_sent,
+            messages_received: self.traffic.messages_received,
+            bytes_transferred: self.traffic.bytes,
+        }
+    }
+}
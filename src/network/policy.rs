@@ -0,0 +1,397 @@
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use metrics::counter;
+use parking_lot::{Mutex, RwLock};
+use serde::{Serialize, Deserialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::message::MessageType;
+use crate::network::peer::NodeType;
+use crate::network::security::{Action, ActionType};
+use crate::Result;
+
+/// Key in `Action::metadata` naming the message type an action carries
+pub const MESSAGE_TYPE_KEY: &str = "message_type";
+
+/// Name used for a message type in policies
+pub fn message_type_name(msg_type: &MessageType) -> String {
+    match msg_type {
+        MessageType::Custom(name) => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Whether a rule grants or forbids what it matches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Effect {
+    #[default]
+    Allow,
+    Deny,
+}
+
+/// Rule within a role; empty lists match anything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyRule {
+    pub effect: Effect,
+    pub actions: Vec<ActionType>,
+    /// Resource patterns where `*` matches any run of characters
+    pub resources: Vec<String>,
+    /// Message type names, see `message_type_name`
+    pub message_types: Vec<String>,
+}
+
+impl PolicyRule {
+    fn matches(&self, action: &Action) -> bool {
+        if !self.actions.is_empty() && !self.actions.contains(&action.action_type) {
+            return false;
+        }
+        if !self.resources.is_empty() && !self.resources.iter().any(|p| wildcard_match(p, &action.resource)) {
+            return false;
+        }
+        if self.message_types.is_empty() {
+            return true;
+        }
+        action
+            .metadata
+            .get(MESSAGE_TYPE_KEY)
+            .and_then(|t| t.as_str())
+            .is_some_and(|t| self.message_types.iter().any(|m| m == t))
+    }
+}
+
+/// Named set of rules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Role {
+    pub name: String,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// Grants a role to peers matching any of the listed subjects
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoleBinding {
+    pub role: String,
+    pub peers: Vec<Uuid>,
+    pub node_types: Vec<NodeType>,
+    /// Session permissions granted by certificates or tokens
+    pub permissions: Vec<String>,
+}
+
+impl RoleBinding {
+    fn applies_to(&self, request: &AuthorizationRequest<'_>) -> bool {
+        self.peers.contains(&request.peer_id)
+            || request.node_type.is_some_and(|t| self.node_types.contains(t))
+            || self.permissions.iter().any(|p| request.permissions.contains(p))
+    }
+}
+
+/// Declarative authorization policy
+///
+/// Requests are denied unless a rule of a role bound to the peer allows
+/// them, and a matching deny rule always wins over allows.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthorizationPolicy {
+    pub roles: Vec<Role>,
+    pub bindings: Vec<RoleBinding>,
+}
+
+impl AuthorizationPolicy {
+    /// Parse from JSON
+    pub fn from_json(input: &str) -> Result<Self> {
+        let policy: Self = serde_json::from_str(input)
+            .map_err(|e| Error::Network(format!("Invalid authorization policy: {}", e)))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Parse from TOML
+    pub fn from_toml(input: &str) -> Result<Self> {
+        let policy: Self = toml::from_str(input)
+            .map_err(|e| Error::Network(format!("Invalid authorization policy TOML: {}", e)))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Load a `.toml` or `.json` file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let input = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&input),
+            _ => Self::from_json(&input),
+        }
+    }
+
+    /// Check that every binding names a defined role
+    pub fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for role in &self.roles {
+            if !names.insert(role.name.as_str()) {
+                return Err(Error::Network(format!("Role {} defined twice", role.name)));
+            }
+        }
+        if let Some(binding) = self.bindings.iter().find(|b| !names.contains(b.role.as_str())) {
+            return Err(Error::Network(format!("Binding refers to unknown role {}", binding.role)));
+        }
+        Ok(())
+    }
+
+    /// Baseline policy keyed on node type
+    ///
+    /// Validators may do anything; relays and gateways may connect and
+    /// exchange messages; observers may only connect and receive, and are
+    /// explicitly barred from broadcasting state transitions.
+    pub fn standard() -> Self {
+        let allow = |actions: Vec<ActionType>| PolicyRule { actions, ..Default::default() };
+        let session = vec![ActionType::Connect, ActionType::Disconnect, ActionType::ReceiveMessage];
+        let mut messaging = session.clone();
+        messaging.extend([ActionType::SendMessage, ActionType::BroadcastMessage]);
+        let role = |name: &str, rules| Role { name: name.into(), rules };
+        let bind = |role: &str, node_type| RoleBinding {
+            role: role.into(),
+            node_types: vec![node_type],
+            ..Default::default()
+        };
+        Self {
+            roles: vec![
+                role("validator", vec![PolicyRule::default()]),
+                role("relay", vec![allow(messaging.clone())]),
+                role("gateway", vec![allow(messaging)]),
+                role("observer", vec![
+                    allow(session),
+                    PolicyRule {
+                        effect: Effect::Deny,
+                        actions: vec![ActionType::BroadcastMessage],
+                        message_types: vec![message_type_name(&MessageType::StateTransition)],
+                        ..Default::default()
+                    },
+                ]),
+            ],
+            bindings: vec![
+                bind("validator", NodeType::Validator),
+                bind("relay", NodeType::Relay),
+                bind("gateway", NodeType::Gateway),
+                bind("observer", NodeType::Observer),
+            ],
+        }
+    }
+}
+
+/// `*` matches any run of characters, everything else literally
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Who is asking to do what
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorizationRequest<'a> {
+    pub peer_id: Uuid,
+    /// Node type attested by the peer's credentials; `None` matches no
+    /// node type binding
+    pub node_type: Option<&'a NodeType>,
+    /// Permissions of the peer's authenticated session
+    pub permissions: &'a [String],
+    pub action: &'a Action,
+}
+
+/// Outcome of a policy evaluation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decision {
+    pub allowed: bool,
+    /// Role and rule index that decided, `None` for the default deny
+    pub matched: Option<(String, usize)>,
+}
+
+/// Audit log entry for one decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: SystemTime,
+    pub peer_id: Uuid,
+    pub node_type: Option<NodeType>,
+    pub action_type: ActionType,
+    pub resource: String,
+    pub message_type: Option<String>,
+    pub decision: Decision,
+}
+
+/// Evaluates an `AuthorizationPolicy` and audits every decision
+///
+/// The policy can be swapped at runtime with `reload`, or re-read from its
+/// file by `watch` whenever the file changes; a policy that fails to load
+/// leaves the current one in place.
+pub struct PolicyEngine {
+    policy: RwLock<Arc<AuthorizationPolicy>>,
+    source: Option<PathBuf>,
+    modified: Mutex<Option<SystemTime>>,
+    audit: Mutex<VecDeque<AuditRecord>>,
+    audit_capacity: usize,
+}
+
+impl PolicyEngine {
+    /// Engine for a validated policy
+    pub fn new(policy: AuthorizationPolicy) -> Result<Self> {
+        policy.validate()?;
+        Ok(Self {
+            policy: RwLock::new(Arc::new(policy)),
+            source: None,
+            modified: Mutex::new(None),
+            audit: Mutex::new(VecDeque::new()),
+            audit_capacity: 1024,
+        })
+    }
+
+    /// Engine for a policy file, which `reload_from_file` and `watch` re-read
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut engine = Self::new(AuthorizationPolicy::from_file(&path)?)?;
+        *engine.modified.get_mut() = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        engine.source = Some(path);
+        Ok(engine)
+    }
+
+    /// Audit records retained for `audit_log`
+    pub fn with_audit_capacity(mut self, capacity: usize) -> Self {
+        self.audit_capacity = capacity;
+        self
+    }
+
+    /// Current policy
+    pub fn policy(&self) -> Arc<AuthorizationPolicy> {
+        self.policy.read().clone()
+    }
+
+    /// Replace the policy
+    pub fn reload(&self, policy: AuthorizationPolicy) -> Result<()> {
+        policy.validate()?;
+        *self.policy.write() = Arc::new(policy);
+        counter!("frost.network.policy.reloads", 1);
+        info!("Authorization policy reloaded");
+        Ok(())
+    }
+
+    /// Re-read the policy file if it changed, returning whether it was reloaded
+    pub fn reload_from_file(&self) -> Result<bool> {
+        let path = self
+            .source
+            .as_ref()
+            .ok_or_else(|| Error::Network("Policy was not loaded from a file".into()))?;
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified.is_some() && *self.modified.lock() == modified {
+            return Ok(false);
+        }
+        self.reload(AuthorizationPolicy::from_file(path)?)?;
+        *self.modified.lock() = modified;
+        Ok(true)
+    }
+
+    /// Poll the policy file every `interval`, reloading on change
+    pub fn watch(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let engine = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(engine) = engine.upgrade() else {
+                    break;
+                };
+                if let Err(e) = engine.reload_from_file() {
+                    warn!("Keeping current authorization policy: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Decide a request; deny wins, then allow, then the default deny
+    pub fn evaluate(&self, request: &AuthorizationRequest<'_>) -> Decision {
+        let policy = self.policy();
+        let roles: HashSet<&str> = policy
+            .bindings
+            .iter()
+            .filter(|b| b.applies_to(request))
+            .map(|b| b.role.as_str())
+            .collect();
+
+        let mut allowed_by = None;
+        let mut denied_by = None;
+        for role in policy.roles.iter().filter(|r| roles.contains(r.name.as_str())) {
+            for (index, rule) in role.rules.iter().enumerate().filter(|(_, r)| r.matches(request.action)) {
+                match rule.effect {
+                    Effect::Deny if denied_by.is_none() => denied_by = Some((role.name.clone(), index)),
+                    Effect::Allow if allowed_by.is_none() => allowed_by = Some((role.name.clone(), index)),
+                    _ => {}
+                }
+            }
+        }
+        let decision = match (denied_by, allowed_by) {
+            (Some(rule), _) => Decision { allowed: false, matched: Some(rule) },
+            (None, Some(rule)) => Decision { allowed: true, matched: Some(rule) },
+            (None, None) => Decision { allowed: false, matched: None },
+        };
+        self.audit(request, &decision);
+        decision
+    }
+
+    fn audit(&self, request: &AuthorizationRequest<'_>, decision: &Decision) {
+        let message_type = request
+            .action
+            .metadata
+            .get(MESSAGE_TYPE_KEY)
+            .and_then(|t| t.as_str())
+            .map(str::to_string);
+        info!(
+            target: "frost::audit",
+            peer = %request.peer_id,
+            action = ?request.action.action_type,
+            resource = %request.action.resource,
+            message_type = ?message_type,
+            allowed = decision.allowed,
+            matched = ?decision.matched,
+            "authorization decision"
+        );
+        counter!("frost.network.policy.decisions", 1, "allowed" => decision.allowed.to_string());
+
+        let mut audit = self.audit.lock();
+        audit.push_back(AuditRecord {
+            timestamp: SystemTime::now(),
+            peer_id: request.peer_id,
+            node_type: request.node_type.cloned(),
+            action_type: request.action.action_type.clone(),
+            resource: request.action.resource.clone(),
+            message_type,
+            decision: decision.clone(),
+        });
+        while audit.len() > self.audit_capacity {
+            audit.pop_front();
+        }
+    }
+
+    /// Recent decisions, oldest first
+    pub fn audit_log(&self) -> Vec<AuditRecord> {
+        self.audit.lock().iter().cloned().collect()
+    }
+}
@@ -171,6 +171,11 @@ impl<T: Transport + Send + Sync> DefaultConnectionPool<T> {
         }
     }
 
+    /// Underlying transport
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
     /// Adjust connection limits based on peer performance
     async fn adjust_peer_limit(&self, peer: &Peer) -> Result<()> {
         let mut limits = self.peer_limits.write();
@@ -268,10 +273,12 @@ impl<T: Transport + Send + Sync> DefaultConnectionPool<T> {
 
     async fn validate_connection(&self, connection: &mut PooledConnection) -> Result<bool> {
         connection.status = ConnectionStatus::Validating;
-        
-        // Implement connection validation logic here
-        // For example, send a ping message or check the underlying transport
-        
+
+        if !self.transport.is_connected(&connection.peer).await {
+            connection.status = ConnectionStatus::Failed { reason: FailureReason::PeerDisconnected };
+            return Ok(false);
+        }
+
         connection.status = ConnectionStatus::Idle;
         Ok(true)
     }
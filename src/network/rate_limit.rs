@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use metrics::counter;
+use parking_lot::Mutex;
+use serde::{Serialize, Deserialize};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::message::MessageType;
+use crate::network::policy::message_type_name;
+use crate::network::reputation::{ReputationEvent, ReputationManager};
+use crate::network::security::RateLimitConfig;
+use crate::Result;
+
+/// Default number of buckets tracked per key kind
+const MAX_TRACKED: usize = 65_536;
+
+/// Token bucket refilled continuously at a fixed rate
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// Full bucket of `capacity` tokens regaining `rate` tokens per `window`
+    pub fn new(capacity: u32, rate: u32, window: Duration) -> Self {
+        let capacity = capacity.max(1) as f64;
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: rate as f64 / window.as_secs_f64().max(f64::EPSILON),
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Tokens currently available
+    pub fn available(&mut self) -> f64 {
+        self.refill(Instant::now());
+        self.tokens
+    }
+
+    /// Take one token if available
+    pub fn try_take(&mut self) -> bool {
+        self.refill(Instant::now());
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// Which limit rejected a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitKind {
+    Peer,
+    Ip,
+    MessageType,
+}
+
+impl LimitKind {
+    fn label(self) -> &'static str {
+        match self {
+            LimitKind::Peer => "peer",
+            LimitKind::Ip => "ip",
+            LimitKind::MessageType => "message_type",
+        }
+    }
+}
+
+/// Reliability penalty applied per rejected message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitPenalties {
+    pub peer: f64,
+    pub ip: f64,
+    pub message_type: f64,
+}
+
+impl Default for RateLimitPenalties {
+    fn default() -> Self {
+        Self {
+            peer: 1.0,
+            ip: 1.0,
+            message_type: 0.5,
+        }
+    }
+}
+
+impl RateLimitPenalties {
+    fn for_kind(&self, kind: LimitKind) -> f64 {
+        match kind {
+            LimitKind::Peer => self.peer,
+            LimitKind::Ip => self.ip,
+            LimitKind::MessageType => self.message_type,
+        }
+    }
+}
+
+/// Buckets for one key kind
+struct Buckets<K> {
+    buckets: Mutex<HashMap<K, TokenBucket>>,
+}
+
+impl<K: Eq + Hash + Clone> Buckets<K> {
+    fn new() -> Self {
+        Self { buckets: Mutex::new(HashMap::new()) }
+    }
+
+    fn len(&self) -> usize {
+        self.buckets.lock().len()
+    }
+
+    fn take(&self, key: &K, max_tracked: usize, bucket: impl FnOnce() -> TokenBucket) -> bool {
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= max_tracked && !buckets.contains_key(key) {
+            // Drop the least recently used eighth in one pass, so a flood of
+            // new keys costs amortized constant time and the map stays capped
+            let mut updated: Vec<Instant> = buckets.values().map(|b| b.updated).collect();
+            let (_, cutoff, _) = updated.select_nth_unstable(max_tracked / 8);
+            let cutoff = *cutoff;
+            buckets.retain(|_, b| b.updated > cutoff);
+        }
+        buckets.entry(key.clone()).or_insert_with(bucket).try_take()
+    }
+}
+
+/// Inbound rate limiter keyed by peer id, remote IP and message type
+///
+/// Every peer and, with `per_ip_limit`, every remote IP gets a bucket of
+/// `burst_size` tokens refilled at `max_requests` per `window_size`; a
+/// `max_requests` of zero disables the limiter. Message types with a limit
+/// from `with_message_type_limit` get an additional bucket per peer, holding
+/// and refilling that many tokens per window.
+///
+/// Rejections count towards `hits` and, with `with_reputation`, lower the
+/// peer's reliability by the configured penalty. At most `max_tracked`
+/// buckets are kept per key kind; the least recently used are evicted.
+/// Transports apply the limiter to data frames as they arrive through
+/// their `with_rate_limiter`.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    message_limits: HashMap<String, u32>,
+    max_tracked: usize,
+    peers: Buckets<Uuid>,
+    ips: Buckets<IpAddr>,
+    message_types: Buckets<(Uuid, String)>,
+    reputation: Option<Arc<ReputationManager>>,
+    penalties: RateLimitPenalties,
+    hits: AtomicU64,
+}
+
+impl RateLimiter {
+    /// Limiter enforcing `config`
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            message_limits: HashMap::new(),
+            max_tracked: MAX_TRACKED,
+            peers: Buckets::new(),
+            ips: Buckets::new(),
+            message_types: Buckets::new(),
+            reputation: None,
+            penalties: RateLimitPenalties::default(),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Allow `max_requests` messages of `msg_type` per window from each peer
+    pub fn with_message_type_limit(mut self, msg_type: &MessageType, max_requests: u32) -> Self {
+        self.message_limits.insert(message_type_name(msg_type), max_requests);
+        self
+    }
+
+    /// Per message type limits by type name, as in `SecurityParams::rate_limits`
+    pub fn with_message_type_limits(mut self, limits: &HashMap<String, u32>) -> Self {
+        self.message_limits.extend(limits.iter().map(|(k, v)| (k.clone(), *v)));
+        self
+    }
+
+    /// Buckets kept per key kind before the least recently used are evicted
+    pub fn with_max_tracked(mut self, max_tracked: usize) -> Self {
+        self.max_tracked = max_tracked.max(1);
+        self
+    }
+
+    /// Report rejections to `reputation`
+    pub fn with_reputation(mut self, reputation: Arc<ReputationManager>) -> Self {
+        self.reputation = Some(reputation);
+        self
+    }
+
+    /// Reputation penalties per rejected message
+    pub fn with_penalties(mut self, penalties: RateLimitPenalties) -> Self {
+        self.penalties = penalties;
+        self
+    }
+
+    /// Messages rejected so far
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of buckets tracked
+    pub fn tracked(&self) -> usize {
+        self.peers.len() + self.ips.len() + self.message_types.len()
+    }
+
+    /// Limit that would reject a message right now, consuming a token from
+    /// each bucket that admits it
+    fn exceeded(&self, peer_id: Uuid, ip: Option<IpAddr>, msg_type: Option<&MessageType>) -> Option<LimitKind> {
+        let config = &self.config;
+        if config.max_requests == 0 {
+            return None;
+        }
+        let bucket = |capacity, rate| move || TokenBucket::new(capacity, rate, config.window_size);
+        let max = self.max_tracked;
+
+        if let Some(ip) = ip.filter(|_| config.per_ip_limit) {
+            if !self.ips.take(&ip, max, bucket(config.burst_size, config.max_requests)) {
+                return Some(LimitKind::Ip);
+            }
+        }
+        if !self.peers.take(&peer_id, max, bucket(config.burst_size, config.max_requests)) {
+            return Some(LimitKind::Peer);
+        }
+        self.exceeded_message_type(peer_id, msg_type?)
+    }
+
+    /// Per message type limit that would reject a message right now
+    fn exceeded_message_type(&self, peer_id: Uuid, msg_type: &MessageType) -> Option<LimitKind> {
+        if self.config.max_requests == 0 {
+            return None;
+        }
+        let name = message_type_name(msg_type);
+        let limit = *self.message_limits.get(&name)?;
+        let bucket = || TokenBucket::new(limit, limit, self.config.window_size);
+        if !self.message_types.take(&(peer_id, name), self.max_tracked, bucket) {
+            return Some(LimitKind::MessageType);
+        }
+        None
+    }
+
+    /// Admit or reject an inbound message
+    pub async fn check(&self, peer_id: Uuid, ip: Option<IpAddr>, msg_type: Option<&MessageType>) -> Result<()> {
+        match self.exceeded(peer_id, ip, msg_type) {
+            Some(kind) => self.reject(peer_id, kind).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Admit or reject an inbound message under its per message type limit
+    /// only, for messages whose frames already passed `check`
+    pub async fn check_message_type(&self, peer_id: Uuid, msg_type: &MessageType) -> Result<()> {
+        match self.exceeded_message_type(peer_id, msg_type) {
+            Some(kind) => self.reject(peer_id, kind).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn reject(&self, peer_id: Uuid, kind: LimitKind) -> Result<()> {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        counter!("frost.network.rate_limit.hits", 1, "limit" => kind.label());
+        debug!("Rate limited peer {} by {} limit", peer_id, kind.label());
+
+        if let Some(reputation) = &self.reputation {
+            let penalty = self.penalties.for_kind(kind);
+            if let Err(e) = reputation.update_reputation(peer_id, ReputationEvent::RateLimited { penalty }).await {
+                warn!("Failed to penalize peer {}: {}", peer_id, e);
+            }
+        }
+        Err(Error::Network(format!("Peer {} exceeded {} rate limit", peer_id, kind.label())))
+    }
+}
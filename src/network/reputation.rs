@@ -1,9 +1,16 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
+use metrics::counter;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
+use tracing::{debug, warn};
 use uuid::Uuid;
-use crate::network::{Peer, NodeIdentity, P2PEvent};
+use crate::error::Error;
+use crate::finality::FinalitySignal;
+use crate::network::{Peer, NodeIdentity, PeerManager};
+use crate::network::peer::PeerState;
 use crate::Result;
 
 /// Reputation score components
@@ -56,7 +63,7 @@ impl ReputationScore {
 }
 
 /// Reputation metrics
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReputationMetrics {
     /// Total messages processed
     pub messages_processed: u64,
@@ -66,6 +73,9 @@ pub struct ReputationMetrics {
     pub failed_validations: u64,
     /// Average response time
     pub avg_response_time: Duration,
+    /// Response times averaged into `avg_response_time`
+    #[serde(default)]
+    pub response_samples: u64,
     /// Uptime percentage
     pub uptime_percentage: f64,
     /// Resource contribution
@@ -76,6 +86,22 @@ pub struct ReputationMetrics {
     pub last_update: SystemTime,
 }
 
+impl Default for ReputationMetrics {
+    fn default() -> Self {
+        Self {
+            messages_processed: 0,
+            successful_validations: 0,
+            failed_validations: 0,
+            avg_response_time: Duration::ZERO,
+            response_samples: 0,
+            uptime_percentage: 0.0,
+            resource_contribution: ResourceMetrics::default(),
+            protocol_violations: 0,
+            last_update: SystemTime::now(),
+        }
+    }
+}
+
 /// Resource contribution metrics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResourceMetrics {
@@ -117,6 +143,20 @@ impl Default for Rewards {
 }
 
 /// Reputation manager
+///
+/// Scores move with the `ReputationEvent`s reported for a peer, by the
+/// amounts in `ReputationWeights`, and decay back towards neutral by
+/// `score_decay_rate` every `update_interval`. Peers whose total score
+/// falls below `min_score_threshold` are demoted, which excludes them from
+/// `rank`; below `ban_threshold` they are banned through the peer manager
+/// given to `with_peer_manager`. Managers created with `open` keep scores
+/// across restarts.
+///
+/// Validation results arrive through the pipeline's `with_reputation`, rate
+/// limit hits through the limiter's, and keep-alive latency and uptime of
+/// authenticated peers through the transports'. Finality claims are not
+/// checked by the crate itself; nodes comparing a peer's claim against
+/// their own verifier report the outcome with `report_finality`.
 pub struct ReputationManager {
     /// Node identity
     identity: NodeIdentity,
@@ -128,6 +168,10 @@ pub struct ReputationManager {
     rewards: RwLock<HashMap<Uuid, Rewards>>,
     /// Configuration
     config: ReputationConfig,
+    /// Peer manager used to ban peers
+    peer_manager: Option<Arc<RwLock<dyn PeerManager>>>,
+    /// Score file
+    path: Option<PathBuf>,
 }
 
 /// Reputation system configuration
@@ -145,6 +189,63 @@ pub struct ReputationConfig {
     pub max_rewards_per_interval: u64,
     /// Minimum stake requirement
     pub min_stake_requirement: u64,
+    /// Score below which peers are banned
+    #[serde(default = "default_ban_threshold")]
+    pub ban_threshold: f64,
+    /// Score changes per event
+    #[serde(default)]
+    pub weights: ReputationWeights,
+}
+
+fn default_ban_threshold() -> f64 {
+    20.0
+}
+
+/// Score changes applied per `ReputationEvent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReputationWeights {
+    /// Performance gained per successful validation
+    pub successful_validation: f64,
+    /// Performance lost per failed validation
+    pub failed_validation: f64,
+    /// Base and performance lost per invalid proof
+    pub invalid_proof: f64,
+    /// Base and reliability lost per finality disagreement
+    pub finality_disagreement: f64,
+    /// Reliability lost per protocol violation
+    pub protocol_violation: f64,
+    /// Latency up to which responses are rewarded
+    pub target_latency: Duration,
+    /// Performance gained per response within the target latency
+    pub latency_reward: f64,
+    /// Performance lost per slower response
+    pub latency_penalty: f64,
+    /// Fraction of the gap to a reported uptime that participation closes
+    pub uptime: f64,
+}
+
+impl Default for ReputationWeights {
+    fn default() -> Self {
+        Self {
+            successful_validation: 1.0,
+            failed_validation: 2.0,
+            invalid_proof: 10.0,
+            finality_disagreement: 15.0,
+            protocol_violation: 5.0,
+            target_latency: Duration::from_millis(500),
+            latency_reward: 0.5,
+            latency_penalty: 1.0,
+            uptime: 0.2,
+        }
+    }
+}
+
+/// Persisted scores
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    reputations: HashMap<Uuid, ReputationScore>,
+    metrics: HashMap<Uuid, ReputationMetrics>,
 }
 
 impl Default for ReputationConfig {
@@ -156,6 +257,8 @@ impl Default for ReputationConfig {
             reward_interval: Duration::from_secs(3600),
             max_rewards_per_interval: 1000,
             min_stake_requirement: 100,
+            ban_threshold: default_ban_threshold(),
+            weights: ReputationWeights::default(),
         }
     }
 }
@@ -169,13 +272,61 @@ impl ReputationManager {
             metrics: RwLock::new(HashMap::new()),
             rewards: RwLock::new(HashMap::new()),
             config,
+            peer_manager: None,
+            path: None,
         }
     }
 
+    /// Create a manager keeping scores in `path`, loading any saved there
+    pub async fn open(path: impl AsRef<Path>, identity: NodeIdentity, config: ReputationConfig) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut manager = Self::new(identity, config);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                let snapshot: Snapshot = serde_json::from_str(&contents)
+                    .map_err(|e| Error::Network(format!("Corrupt reputation file {}: {}", path.display(), e)))?;
+                manager.reputations = RwLock::new(snapshot.reputations);
+                manager.metrics = RwLock::new(snapshot.metrics);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        manager.path = Some(path);
+        Ok(manager)
+    }
+
+    /// Ban peers through `peer_manager` when their score drops below `ban_threshold`
+    ///
+    /// Scores are keyed by node id, so `peer_manager` must track peers
+    /// under the node ids their transports authenticated.
+    pub fn with_peer_manager(mut self, peer_manager: Arc<RwLock<dyn PeerManager>>) -> Self {
+        self.peer_manager = Some(peer_manager);
+        self
+    }
+
+    /// Write scores to the file given to `open`
+    pub async fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let snapshot = Snapshot {
+            reputations: self.reputations.read().await.clone(),
+            metrics: self.metrics.read().await.clone(),
+        };
+        let contents = serde_json::to_vec(&snapshot)
+            .map_err(|e| Error::Network(format!("Failed to encode reputations: {}", e)))?;
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, &contents).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        Ok(())
+    }
+
     /// Start reputation management
-    pub async fn start(&self) -> Result<()> {
-        tokio::spawn(self.update_loop());
-        tokio::spawn(self.reward_loop());
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
+        let manager = self.clone();
+        tokio::spawn(async move { manager.update_loop().await });
+        let manager = self.clone();
+        tokio::spawn(async move { manager.reward_loop().await });
         Ok(())
     }
 
@@ -183,8 +334,9 @@ impl ReputationManager {
     async fn update_loop(&self) {
         loop {
             tokio::time::sleep(self.config.update_interval).await;
-            if let Err(e) = self.update_all_scores().await {
-                eprintln!("Error updating reputation scores: {}", e);
+            self.update_all_scores().await;
+            if let Err(e) = self.save().await {
+                warn!("Error saving reputation scores: {}", e);
             }
         }
     }
@@ -194,7 +346,7 @@ impl ReputationManager {
         loop {
             tokio::time::sleep(self.config.reward_interval).await;
             if let Err(e) = self.distribute_rewards().await {
-                eprintln!("Error distributing rewards: {}", e);
+                warn!("Error distributing rewards: {}", e);
             }
         }
     }
@@ -205,32 +357,75 @@ impl ReputationManager {
         peer_id: Uuid,
         event: ReputationEvent,
     ) -> Result<()> {
-        let mut reputations = self.reputations.write().await;
-        let mut metrics = self.metrics.write().await;
+        self.apply(peer_id, None, event).await
+    }
+
+    /// Update reputation for the peer of a connection
+    ///
+    /// The peer is only banned if `peer.info.address`, the address of the
+    /// connection the event was observed on, is the one the peer manager
+    /// has on record for its node id.
+    pub async fn report_connection(&self, peer: &Peer, event: ReputationEvent) -> Result<()> {
+        self.apply(peer.id, Some(&peer.info.address), event).await
+    }
 
-        let score = reputations.entry(peer_id).or_default();
-        let metric = metrics.entry(peer_id).or_default();
+    async fn apply(&self, peer_id: Uuid, address: Option<&str>, event: ReputationEvent) -> Result<()> {
+        let total = {
+            let mut reputations = self.reputations.write().await;
+            let mut metrics = self.metrics.write().await;
+            let score = reputations.entry(peer_id).or_default();
+            let metric = metrics.entry(peer_id).or_default();
+            self.apply_event(score, metric, event);
+            score.total_score()
+        };
+        self.enforce(peer_id, address, total).await
+    }
 
+    fn apply_event(&self, score: &mut ReputationScore, metric: &mut ReputationMetrics, event: ReputationEvent) {
+        let weights = &self.config.weights;
         match event {
             ReputationEvent::SuccessfulValidation { response_time } => {
-                score.performance_score += 1.0;
+                score.performance_score += weights.successful_validation;
                 metric.successful_validations += 1;
-                metric.avg_response_time = update_average_duration(
-                    metric.avg_response_time,
-                    response_time,
-                    metric.messages_processed
-                );
+                record_response_time(metric, response_time);
             }
             ReputationEvent::FailedValidation => {
-                score.performance_score -= 2.0;
+                score.performance_score -= weights.failed_validation;
                 metric.failed_validations += 1;
             }
+            ReputationEvent::InvalidProof => {
+                score.base_score -= weights.invalid_proof;
+                score.performance_score -= weights.invalid_proof;
+                metric.failed_validations += 1;
+            }
+            ReputationEvent::FinalityDisagreement => {
+                score.base_score -= weights.finality_disagreement;
+                score.reliability_score -= weights.finality_disagreement;
+                metric.protocol_violations += 1;
+            }
+            ReputationEvent::Latency(latency) => {
+                if latency <= weights.target_latency {
+                    score.performance_score += weights.latency_reward;
+                } else {
+                    score.performance_score -= weights.latency_penalty;
+                }
+                record_response_time(metric, latency);
+            }
+            ReputationEvent::Uptime(percentage) => {
+                let percentage = percentage.clamp(0.0, 100.0);
+                score.participation_score += (percentage - score.participation_score) * weights.uptime;
+                metric.uptime_percentage = percentage;
+            }
             ReputationEvent::ResourceContribution(resources) => {
                 score.participation_score += 1.0;
                 metric.resource_contribution = resources;
             }
             ReputationEvent::ProtocolViolation => {
-                score.reliability_score -= 5.0;
+                score.reliability_score -= weights.protocol_violation;
+                metric.protocol_violations += 1;
+            }
+            ReputationEvent::RateLimited { penalty } => {
+                score.reliability_score -= penalty;
                 metric.protocol_violations += 1;
             }
             ReputationEvent::StakeUpdate(stake) => {
@@ -243,37 +438,126 @@ impl ReputationManager {
         metric.last_update = SystemTime::now();
 
         // Normalize scores
+        normalize_score(&mut score.base_score);
         normalize_score(&mut score.performance_score);
         normalize_score(&mut score.participation_score);
         normalize_score(&mut score.reliability_score);
+    }
 
+    /// Ban a peer whose score fell below `ban_threshold` unless already banned
+    ///
+    /// With the `address` of the connection that reported the event, the
+    /// ban is refused when the peer is on record at another address.
+    async fn enforce(&self, peer_id: Uuid, address: Option<&str>, total: f64) -> Result<()> {
+        let Some(peer_manager) = &self.peer_manager else {
+            return Ok(());
+        };
+        if total >= self.config.ban_threshold {
+            return Ok(());
+        }
+        let mut peer_manager = peer_manager.write().await;
+        let Some(peer) = peer_manager.get_peer(peer_id).await? else {
+            debug!("Peer {} fell below the ban threshold but is not tracked", peer_id);
+            return Ok(());
+        };
+        if peer.state == PeerState::Banned {
+            return Ok(());
+        }
+        if let Some(address) = address.filter(|a| *a != peer.info.address) {
+            warn!(
+                "Not banning peer {}: reported from {} but on record at {}",
+                peer_id, address, peer.info.address
+            );
+            return Ok(());
+        }
+        peer_manager
+            .ban_peer(&peer, format!("Reputation {:.1} below {:.1}", total, self.config.ban_threshold))
+            .await?;
+        counter!("frost.network.reputation.bans", 1);
+        warn!("Banned peer {} for reputation {:.1}", peer_id, total);
         Ok(())
     }
 
+    /// Penalize a peer whose finality claim contradicts the locally verified
+    /// signal for the same block, returning whether they disagreed
+    pub async fn report_finality(&self, peer_id: Uuid, reported: &FinalitySignal, verified: &FinalitySignal) -> Result<bool> {
+        let disagrees = reported.chain_id == verified.chain_id
+            && reported.block_number == verified.block_number
+            && reported.block_hash != verified.block_hash;
+        if disagrees {
+            self.update_reputation(peer_id, ReputationEvent::FinalityDisagreement).await?;
+        }
+        Ok(disagrees)
+    }
+
+    /// Move scores back towards neutral as if `elapsed` had passed
+    ///
+    /// Each `update_interval` closes `score_decay_rate` of the gap between
+    /// a score and its default, so old events count for less over time.
+    pub async fn apply_decay(&self, elapsed: Duration) {
+        let intervals = elapsed.as_secs_f64() / self.config.update_interval.as_secs_f64().max(f64::EPSILON);
+        let retained = (1.0 - self.config.score_decay_rate).clamp(0.0, 1.0).powf(intervals);
+        let neutral = ReputationScore::default();
+        let decay = |value: &mut f64, target: f64| *value = target + (*value - target) * retained;
+        for score in self.reputations.write().await.values_mut() {
+            decay(&mut score.base_score, neutral.base_score);
+            decay(&mut score.participation_score, neutral.participation_score);
+            decay(&mut score.performance_score, neutral.performance_score);
+            decay(&mut score.reliability_score, neutral.reliability_score);
+        }
+    }
+
+    /// Whether a peer's score is below `min_score_threshold`
+    pub async fn is_demoted(&self, peer_id: &Uuid) -> bool {
+        self.reputations
+            .read()
+            .await
+            .get(peer_id)
+            .is_some_and(|s| s.total_score() < self.config.min_score_threshold)
+    }
+
+    /// Candidates that are not demoted, best score first
+    ///
+    /// Peers without a score rank as neutral.
+    pub async fn rank(&self, candidates: &[Uuid]) -> Vec<Uuid> {
+        let reputations = self.reputations.read().await;
+        let neutral = ReputationScore::default().total_score();
+        let mut ranked: Vec<(Uuid, f64)> = candidates
+            .iter()
+            .map(|id| (*id, reputations.get(id).map_or(neutral, |s| s.total_score())))
+            .filter(|(_, total)| *total >= self.config.min_score_threshold)
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked.into_iter().map(|(id, _)| id).collect()
+    }
+
     /// Update all peer scores
-    async fn update_all_scores(&self) -> Result<()> {
-        let mut reputations = self.reputations.write().await;
-        let metrics = self.metrics.read().await;
-
-        for (peer_id, score) in reputations.iter_mut() {
-            // Apply score decay
-            score.base_score *= 1.0 - self.config.score_decay_rate;
-            score.participation_score *= 1.0 - self.config.score_decay_rate;
-            score.performance_score *= 1.0 - self.config.score_decay_rate;
-            score.reliability_score *= 1.0 - self.config.score_decay_rate;
-
-            // Update time weight
-            if let Some(metric) = metrics.get(peer_id) {
-                score.time_weight = calculate_time_weight(metric.last_update);
+    ///
+    /// A failed ban is logged and does not stop the remaining peers from
+    /// being enforced.
+    async fn update_all_scores(&self) {
+        self.apply_decay(self.config.update_interval).await;
+
+        let low_scores: Vec<(Uuid, f64)> = {
+            let mut reputations = self.reputations.write().await;
+            let metrics = self.metrics.read().await;
+            for (peer_id, score) in reputations.iter_mut() {
+                // Update time weight
+                if let Some(metric) = metrics.get(peer_id) {
+                    score.time_weight = calculate_time_weight(metric.last_update);
+                }
             }
-
-            // Check minimum threshold
-            if score.total_score() < self.config.min_score_threshold {
-                // Emit low score warning event
+            reputations
+                .iter()
+                .map(|(id, s)| (*id, s.total_score()))
+                .filter(|(_, total)| *total < self.config.ban_threshold)
+                .collect()
+        };
+        for (peer_id, total) in low_scores {
+            if let Err(e) = self.enforce(peer_id, None, total).await {
+                warn!("Failed to ban peer {} for reputation {:.1}: {}", peer_id, total, e);
             }
         }
-
-        Ok(())
     }
 
     /// Distribute rewards
@@ -336,8 +620,20 @@ pub enum ReputationEvent {
     FailedValidation,
     /// Resource contribution update
     ResourceContribution(ResourceMetrics),
+    /// Proof that failed verification
+    InvalidProof,
+    /// Finality claim contradicting the verified chain
+    FinalityDisagreement,
+    /// Observed response latency
+    Latency(Duration),
+    /// Observed uptime percentage (0-100)
+    Uptime(f64),
     /// Protocol violation
     ProtocolViolation,
+    /// Exceeded a rate limit
+    RateLimited {
+        penalty: f64,
+    },
     /// Stake update
     StakeUpdate(u64),
 }
@@ -345,7 +641,16 @@ pub enum ReputationEvent {
 // Helper functions
 
 fn normalize_score(score: &mut f64) {
-    *score = score.max(0.0).min(100.0);
+    *score = score.clamp(0.0, 100.0);
+}
+
+fn record_response_time(metric: &mut ReputationMetrics, response_time: Duration) {
+    metric.avg_response_time = update_average_duration(
+        metric.avg_response_time,
+        response_time,
+        metric.response_samples
+    );
+    metric.response_samples += 1;
 }
 
 fn update_average_duration(
@@ -381,7 +686,7 @@ fn calculate_time_weight(last_update: SystemTime) -> f64 {
 
 fn calculate_performance_multiplier(score: &ReputationScore) -> f64 {
     let base_multiplier = score.performance_score / 50.0;
-    1.0 + base_multiplier.max(0.0).min(1.0)
+    1.0 + base_multiplier.clamp(0.0, 1.0)
 }
 
 fn calculate_bonus_rewards(
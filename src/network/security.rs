@@ -1,9 +1,24 @@
 #![allow(unused_imports)]
 
 use async_trait::async_trait;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use metrics::counter;
+use parking_lot::RwLock;
 use serde::{Serialize, Deserialize};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+use crate::error::Error;
+use crate::network::peer::NodeType;
 use crate::network::{Peer, NetworkError};
+use crate::message::{FrostMessage, MessageType};
+use crate::network::policy::{message_type_name, AuthorizationRequest, PolicyEngine, MESSAGE_TYPE_KEY};
+use crate::network::rate_limit::RateLimiter;
 use crate::Result;
 
 /// Network security manager
@@ -36,6 +51,9 @@ pub struct SecurityConfig {
     pub signature_algorithm: String,
     pub tls_config: Option<TlsConfig>,
     pub rate_limiting: RateLimitConfig,
+    /// JSON or TOML `AuthorizationPolicy` file, reloaded when it changes
+    #[serde(default)]
+    pub authorization_policy: Option<PathBuf>,
 }
 
 /// Authentication methods
@@ -84,8 +102,29 @@ pub struct Action {
     pub metadata: serde_json::Value,
 }
 
+impl Action {
+    /// Action on `resource` happening now
+    pub fn new(action_type: ActionType, resource: impl Into<String>) -> Self {
+        Self {
+            action_type,
+            resource: resource.into(),
+            timestamp: SystemTime::now(),
+            metadata: serde_json::Value::Null,
+        }
+    }
+
+    /// Record the type of message the action carries, for policy rules
+    pub fn with_message_type(mut self, msg_type: &MessageType) -> Self {
+        if !self.metadata.is_object() {
+            self.metadata = serde_json::json!({});
+        }
+        self.metadata[MESSAGE_TYPE_KEY] = message_type_name(msg_type).into();
+        self
+    }
+}
+
 /// Action types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActionType {
     Connect,
     Disconnect,
@@ -125,4 +164,746 @@ pub struct SecurityMetrics {
     pub rate_limit_hits: u64,
     pub signature_validations: u64,
     pub failed_validations: u64,
-} 
\ No newline at end of file
+}
+
+/// Domain separator for challenge signatures
+const CHALLENGE_DOMAIN: &[u8] = b"frost-auth-challenge:";
+/// How long an issued challenge can be answered
+const CHALLENGE_TTL: Duration = Duration::from_secs(60);
+
+/// Factor names accepted in `AuthenticationMethod::MultiFactor::methods`
+pub const CERTIFICATE_FACTOR: &str = "certificate";
+pub const TOKEN_FACTOR: &str = "token";
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn from_unix(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn security_error(message: impl Into<String>) -> Error {
+    Error::Network(NetworkError::SecurityError(message.into()).to_string())
+}
+
+/// Decode a hex-encoded ed25519 public key
+pub fn decode_verifying_key(hex_key: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| security_error("Expected a 32-byte hex ed25519 public key"))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| security_error(format!("Invalid ed25519 public key: {}", e)))
+}
+
+fn decode_signing_key(hex_key: &str) -> Result<SigningKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| security_error("Expected a 32-byte hex ed25519 secret key"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+fn verify(key: &VerifyingKey, message: &[u8], signature: &[u8]) -> bool {
+    Signature::from_slice(signature).is_ok_and(|signature| key.verify(message, &signature).is_ok())
+}
+
+/// Certificate binding a node id to an ed25519 key, signed by a local CA
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerCertificate {
+    pub node_id: Uuid,
+    pub public_key: Vec<u8>,
+    /// Node type attested for policy role bindings
+    #[serde(default)]
+    pub node_type: Option<NodeType>,
+    /// Permissions granted to sessions authenticated with this certificate
+    pub permissions: Vec<String>,
+    /// Unix seconds
+    pub expires_at: u64,
+    pub signature: Vec<u8>,
+}
+
+impl PeerCertificate {
+    /// Issue a certificate valid for `ttl`
+    pub fn issue(
+        node_id: Uuid,
+        public_key: &VerifyingKey,
+        node_type: Option<NodeType>,
+        permissions: Vec<String>,
+        ttl: Duration,
+        ca: &SigningKey,
+    ) -> Self {
+        let mut certificate = Self {
+            node_id,
+            public_key: public_key.to_bytes().to_vec(),
+            node_type,
+            permissions,
+            expires_at: unix_now().saturating_add(ttl.as_secs()),
+            signature: Vec::new(),
+        };
+        certificate.signature = ca.sign(&certificate.signed_bytes()).to_bytes().to_vec();
+        certificate
+    }
+
+    fn signed_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(&(&self.node_id, &self.public_key, &self.node_type, &self.permissions, self.expires_at))
+            .expect("certificate fields serialize")
+    }
+
+    /// Check the CA signature and expiry
+    pub fn verify(&self, ca: &VerifyingKey) -> std::result::Result<(), String> {
+        if !verify(ca, &self.signed_bytes(), &self.signature) {
+            return Err("certificate signature invalid".into());
+        }
+        if self.expires_at <= unix_now() {
+            return Err("certificate expired".into());
+        }
+        Ok(())
+    }
+
+    /// Hex encoding used in `AuthenticationMethod::Certificate::client_cert`
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).expect("certificate serializes"))
+    }
+
+    /// Parse a hex-encoded certificate
+    pub fn decode(encoded: &str) -> Result<Self> {
+        let bytes = hex::decode(encoded.trim()).map_err(|e| security_error(format!("Invalid certificate encoding: {}", e)))?;
+        serde_json::from_slice(&bytes).map_err(|e| security_error(format!("Invalid certificate: {}", e)))
+    }
+}
+
+/// Claims carried by a signed token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenClaims {
+    /// Node id the token was issued to
+    pub subject: Uuid,
+    /// Ed25519 key the holder must sign challenges with
+    pub public_key: Vec<u8>,
+    /// Node type attested for policy role bindings
+    #[serde(default)]
+    pub node_type: Option<NodeType>,
+    pub permissions: Vec<String>,
+    /// Unix seconds
+    pub expires_at: u64,
+}
+
+impl TokenClaims {
+    /// Sign the claims, producing `hex(claims).hex(signature)`
+    pub fn sign(&self, issuer: &SigningKey) -> String {
+        let claims = serde_json::to_vec(self).expect("claims serialize");
+        let signature = issuer.sign(&claims);
+        format!("{}.{}", hex::encode(&claims), hex::encode(signature.to_bytes()))
+    }
+
+    /// Parse a token, checking its signature and expiry
+    pub fn verify(token: &str, issuer: &VerifyingKey) -> std::result::Result<Self, String> {
+        let (claims, signature) = token.split_once('.').ok_or("malformed token")?;
+        let claims = hex::decode(claims).map_err(|_| "malformed token claims")?;
+        let signature = hex::decode(signature).map_err(|_| "malformed token signature")?;
+        if !verify(issuer, &claims, &signature) {
+            return Err("token signature invalid".into());
+        }
+        let claims: Self = serde_json::from_slice(&claims).map_err(|e| format!("invalid token claims: {}", e))?;
+        if claims.expires_at <= unix_now() {
+            return Err("token expired".into());
+        }
+        Ok(claims)
+    }
+}
+
+/// Credentials a peer presents in answer to a challenge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PeerCredentials {
+    /// Ed25519 key the challenge was signed with
+    pub public_key: Option<Vec<u8>>,
+    pub challenge_signature: Option<Vec<u8>>,
+    pub certificate: Option<PeerCertificate>,
+    pub token: Option<String>,
+}
+
+/// Factors that verified for one authentication attempt
+struct VerifiedFactors {
+    public_key: Option<VerifyingKey>,
+    certificate: Option<std::result::Result<Option<PeerCertificate>, String>>,
+    token: Option<std::result::Result<TokenClaims, String>>,
+}
+
+/// Challenge awaiting an answer from a peer
+struct PendingChallenge {
+    challenge: Vec<u8>,
+    channel_binding: Vec<u8>,
+    issued: Instant,
+}
+
+struct SessionRecord {
+    peer_id: Uuid,
+    /// Node type from the session's certificate or token, never the hello
+    node_type: Option<NodeType>,
+    permissions: Vec<String>,
+    public_key: Option<VerifyingKey>,
+    /// Address of the connection the peer authenticated on
+    address: String,
+    expires_at: SystemTime,
+}
+
+impl SessionRecord {
+    fn is_active(&self) -> bool {
+        self.expires_at > SystemTime::now()
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    authentication_attempts: AtomicU64,
+    failed_authentications: AtomicU64,
+    revoked_sessions: AtomicU64,
+    signature_validations: AtomicU64,
+    failed_validations: AtomicU64,
+}
+
+/// Default `SecurityManager`
+///
+/// Peers authenticate by answering a challenge from `issue_challenge` with
+/// `PeerCredentials`, registered through `present_credentials` before
+/// `authenticate_peer` runs. Both factors need a signature over the
+/// challenge, the prover and verifier node ids and the channel binding
+/// (e.g. `EncryptedTransport::channel_binding`), so an answer cannot be
+/// relayed to another verifier or connection. For the certificate factor
+/// the key must be either pinned for the peer or certified by a trusted CA;
+/// the token factor needs a token signed by a trusted issuer for the peer's
+/// id and that key. `AuthenticationMethod` decides which factors are
+/// required. Successful authentication opens a session that lasts until the
+/// session TTL or the earliest credential expiry, or until it is revoked.
+///
+/// Actions need an active session and, once a `PolicyEngine` is set or
+/// configured, an allow from its policy. Node type role bindings match the
+/// type attested by the session's certificate or token, not the one the
+/// peer advertised. Inbound frames are rate limited by the transports
+/// given `rate_limiter`, and messages by type in `check_inbound`.
+pub struct DefaultSecurityManager {
+    config: Option<SecurityConfig>,
+    node_id: Option<Uuid>,
+    session_ttl: Duration,
+    pinned_keys: RwLock<HashMap<Uuid, VerifyingKey>>,
+    certificate_authorities: RwLock<Vec<VerifyingKey>>,
+    token_issuers: RwLock<Vec<VerifyingKey>>,
+    signing_key: Option<SigningKey>,
+    certificate: Option<PeerCertificate>,
+    token: Option<String>,
+    challenges: RwLock<HashMap<Uuid, PendingChallenge>>,
+    credentials: RwLock<HashMap<Uuid, PeerCredentials>>,
+    sessions: RwLock<HashMap<Uuid, SessionRecord>>,
+    policy: Option<Arc<PolicyEngine>>,
+    policy_reload_interval: Duration,
+    policy_watcher: Option<JoinHandle<()>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    counters: Counters,
+}
+
+impl DefaultSecurityManager {
+    /// Create a manager; call `init` before authenticating peers
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            node_id: None,
+            session_ttl: Duration::from_secs(3600),
+            pinned_keys: RwLock::new(HashMap::new()),
+            certificate_authorities: RwLock::new(Vec::new()),
+            token_issuers: RwLock::new(Vec::new()),
+            signing_key: None,
+            certificate: None,
+            token: None,
+            challenges: RwLock::new(HashMap::new()),
+            credentials: RwLock::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
+            policy: None,
+            policy_reload_interval: Duration::from_secs(5),
+            policy_watcher: None,
+            rate_limiter: None,
+            counters: Counters::default(),
+        }
+    }
+
+    /// Local node id, which challenge answers must name as the verifier
+    ///
+    /// Without it no challenge signature verifies.
+    pub fn with_node_id(mut self, node_id: Uuid) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Longest lifetime of an authenticated session
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    /// Trust `key` as the certificate factor for `peer_id`
+    pub fn with_pinned_key(self, peer_id: Uuid, key: VerifyingKey) -> Self {
+        self.pinned_keys.write().insert(peer_id, key);
+        self
+    }
+
+    /// Trust certificates signed by `ca`
+    pub fn with_certificate_authority(self, ca: VerifyingKey) -> Self {
+        self.certificate_authorities.write().push(ca);
+        self
+    }
+
+    /// Trust tokens signed by `issuer`
+    pub fn with_token_issuer(self, issuer: VerifyingKey) -> Self {
+        self.token_issuers.write().push(issuer);
+        self
+    }
+
+    /// Key used to answer challenges from other nodes
+    pub fn with_signing_key(mut self, key: SigningKey) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+
+    /// Certificate presented to other nodes
+    pub fn with_certificate(mut self, certificate: PeerCertificate) -> Self {
+        self.certificate = Some(certificate);
+        self
+    }
+
+    /// Bearer token presented to other nodes
+    pub fn with_token(mut self, token: String) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Authorize actions with `policy`, overriding the configured policy file
+    pub fn with_policy(mut self, policy: Arc<PolicyEngine>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// How often a configured policy file is checked for changes
+    pub fn with_policy_reload_interval(mut self, interval: Duration) -> Self {
+        self.policy_reload_interval = interval;
+        self
+    }
+
+    /// Policy engine in use, if any
+    pub fn policy(&self) -> Option<Arc<PolicyEngine>> {
+        self.policy.clone()
+    }
+
+    /// Rate limit inbound messages with `limiter` instead of one built from
+    /// the configured `RateLimitConfig`
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Limiter used by `check_inbound`, available after `init`
+    ///
+    /// Hand it to the transports with their `with_rate_limiter`: they
+    /// apply the per-peer and per-IP limits to data frames as they arrive,
+    /// leaving the per message type limits to `check_inbound`.
+    pub fn rate_limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.rate_limiter.clone()
+    }
+
+    /// Admit an inbound message from `peer` under the per message type
+    /// rate limits
+    ///
+    /// Per-peer and per-IP limits are taken once per frame by the
+    /// transports sharing `rate_limiter`, so they are not charged again
+    /// here.
+    pub async fn check_inbound(&self, peer: &Peer, message: &FrostMessage) -> Result<()> {
+        let Some(limiter) = &self.rate_limiter else {
+            return Ok(());
+        };
+        limiter.check_message_type(peer.id, &message.msg_type).await
+    }
+
+    /// Pin a key for a peer at runtime
+    pub fn pin_key(&self, peer_id: Uuid, key: VerifyingKey) {
+        self.pinned_keys.write().insert(peer_id, key);
+    }
+
+    /// Fresh challenge for a peer, replacing any outstanding one
+    ///
+    /// `channel_binding` identifies the connection the answer must arrive
+    /// on, such as the Noise handshake hash.
+    pub fn issue_challenge(&self, peer: &Peer, channel_binding: &[u8]) -> Vec<u8> {
+        let challenge = rand::random::<[u8; 32]>().to_vec();
+        self.challenges.write().insert(peer.id, PendingChallenge {
+            challenge: challenge.clone(),
+            channel_binding: channel_binding.to_vec(),
+            issued: Instant::now(),
+        });
+        challenge
+    }
+
+    /// Answer a challenge from `verifier`, `node_id` being our own id and
+    /// `channel_binding` that of the connection to the verifier
+    pub fn respond_to_challenge(&self, node_id: Uuid, verifier: Uuid, channel_binding: &[u8], challenge: &[u8]) -> PeerCredentials {
+        let signed = self.signing_key.as_ref().map(|key| {
+            let signature = key.sign(&challenge_message(node_id, verifier, channel_binding, challenge));
+            (key.verifying_key().to_bytes().to_vec(), signature.to_bytes().to_vec())
+        });
+        PeerCredentials {
+            public_key: signed.as_ref().map(|(key, _)| key.clone()),
+            challenge_signature: signed.map(|(_, signature)| signature),
+            certificate: self.certificate.clone(),
+            token: self.token.clone(),
+        }
+    }
+
+    /// Record credentials a peer presented, for the next `authenticate_peer`
+    pub fn present_credentials(&self, peer: &Peer, credentials: PeerCredentials) {
+        self.credentials.write().insert(peer.id, credentials);
+    }
+
+    /// Revoke a session, returning whether it was active
+    pub fn revoke_session(&self, session_id: Uuid) -> bool {
+        let Some(record) = self.sessions.write().remove(&session_id) else {
+            return false;
+        };
+        self.counters.revoked_sessions.fetch_add(1, Ordering::Relaxed);
+        counter!("frost.network.security.sessions_revoked", 1);
+        debug!("Revoked session {} of peer {}", session_id, record.peer_id);
+        record.is_active()
+    }
+
+    /// Revoke every session of a peer, returning how many were revoked
+    pub fn revoke_peer(&self, peer_id: Uuid) -> usize {
+        let ids: Vec<Uuid> = self
+            .sessions
+            .read()
+            .iter()
+            .filter(|(_, r)| r.peer_id == peer_id)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.into_iter().filter(|id| self.revoke_session(*id)).count()
+    }
+
+    /// Whether a session exists and has not expired
+    pub fn is_session_active(&self, session_id: Uuid) -> bool {
+        self.sessions.read().get(&session_id).is_some_and(|r| r.is_active())
+    }
+
+    /// Permissions of a peer's active session
+    pub fn permissions(&self, peer: &Peer) -> Option<Vec<String>> {
+        self.active_session(peer.id, |record| record.permissions.clone())
+    }
+
+    /// Whether the peer has an active session opened from its current address
+    pub fn is_authenticated(&self, peer: &Peer) -> bool {
+        self.active_session(peer.id, |record| record.address == peer.info.address).unwrap_or(false)
+    }
+
+    /// Apply `f` to the peer's newest active session
+    fn active_session<R>(&self, peer_id: Uuid, f: impl FnOnce(&SessionRecord) -> R) -> Option<R> {
+        self.sessions
+            .read()
+            .values()
+            .filter(|r| r.peer_id == peer_id && r.is_active())
+            .max_by_key(|r| r.expires_at)
+            .map(f)
+    }
+
+    fn method(&self) -> Result<&AuthenticationMethod> {
+        self.config
+            .as_ref()
+            .map(|c| &c.authentication_method)
+            .ok_or_else(|| security_error("Security manager not initialized"))
+    }
+
+    /// Check every factor the credentials offer
+    fn verify_factors(&self, peer: &Peer, credentials: &PeerCredentials) -> VerifiedFactors {
+        let challenge = self.challenges.write().remove(&peer.id);
+        let public_key = match (&credentials.public_key, &credentials.challenge_signature, challenge, self.node_id) {
+            (Some(key), Some(signature), Some(pending), Some(node_id)) if pending.issued.elapsed() < CHALLENGE_TTL => {
+                let message = challenge_message(peer.id, node_id, &pending.channel_binding, &pending.challenge);
+                <[u8; 32]>::try_from(key.as_slice())
+                    .ok()
+                    .and_then(|key| VerifyingKey::from_bytes(&key).ok())
+                    .filter(|key| verify(key, &message, signature))
+            }
+            _ => None,
+        };
+
+        let certificate = public_key.map(|key| {
+            if self.pinned_keys.read().get(&peer.id) == Some(&key) {
+                return Ok(None);
+            }
+            let certificate = credentials.certificate.as_ref().ok_or("key not pinned and no certificate")?;
+            if certificate.node_id != peer.id || certificate.public_key != key.to_bytes() {
+                return Err("certificate issued to a different node or key".to_string());
+            }
+            let authorities = self.certificate_authorities.read();
+            let mut result = Err("no trusted certificate authority".to_string());
+            for ca in authorities.iter() {
+                result = certificate.verify(ca);
+                if result.is_ok() {
+                    break;
+                }
+            }
+            result.map(|_| Some(certificate.clone()))
+        });
+
+        let token = credentials.token.as_ref().map(|token| {
+            let key = public_key.ok_or("no valid challenge signature")?;
+            let issuers = self.token_issuers.read();
+            let mut result = Err("no trusted token issuer".to_string());
+            for issuer in issuers.iter() {
+                result = TokenClaims::verify(token, issuer);
+                if result.is_ok() {
+                    break;
+                }
+            }
+            result.and_then(|claims| {
+                if claims.subject != peer.id || claims.public_key != key.to_bytes() {
+                    return Err("token issued to a different node or key".into());
+                }
+                Ok(claims)
+            })
+        });
+
+        VerifiedFactors { public_key, certificate, token }
+    }
+
+    fn fail(&self, peer: &Peer, reason: &str) -> AuthenticationResult {
+        warn!("Authentication of peer {} failed: {}", peer.id, reason);
+        self.counters.failed_authentications.fetch_add(1, Ordering::Relaxed);
+        counter!("frost.network.security.authentications", 1, "result" => "failure");
+        AuthenticationResult {
+            success: false,
+            session_id: None,
+            permissions: Vec::new(),
+            expiry: None,
+        }
+    }
+}
+
+impl Default for DefaultSecurityManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DefaultSecurityManager {
+    fn drop(&mut self) {
+        if let Some(watcher) = self.policy_watcher.take() {
+            watcher.abort();
+        }
+    }
+}
+
+fn challenge_message(prover: Uuid, verifier: Uuid, channel_binding: &[u8], challenge: &[u8]) -> Vec<u8> {
+    let mut message = CHALLENGE_DOMAIN.to_vec();
+    message.extend_from_slice(prover.as_bytes());
+    message.extend_from_slice(verifier.as_bytes());
+    message.extend_from_slice(&(channel_binding.len() as u32).to_be_bytes());
+    message.extend_from_slice(channel_binding);
+    message.extend_from_slice(challenge);
+    message
+}
+
+#[async_trait]
+impl SecurityManager for DefaultSecurityManager {
+    async fn init(&mut self, config: SecurityConfig) -> Result<()> {
+        if !config.signature_algorithm.eq_ignore_ascii_case("ed25519") {
+            return Err(security_error(format!("Unsupported signature algorithm {}", config.signature_algorithm)));
+        }
+        match &config.authentication_method {
+            AuthenticationMethod::Certificate { ca_cert, client_cert, client_key } => {
+                if !ca_cert.is_empty() {
+                    self.certificate_authorities.write().push(decode_verifying_key(ca_cert)?);
+                }
+                if !client_key.is_empty() {
+                    self.signing_key = Some(decode_signing_key(client_key)?);
+                }
+                if !client_cert.is_empty() {
+                    self.certificate = Some(PeerCertificate::decode(client_cert)?);
+                }
+            }
+            AuthenticationMethod::Token { token_type, token_value } => {
+                if !token_type.eq_ignore_ascii_case("ed25519") {
+                    return Err(security_error(format!("Unsupported token type {}", token_type)));
+                }
+                self.token_issuers.write().push(decode_verifying_key(token_value)?);
+            }
+            AuthenticationMethod::MultiFactor { methods, required_factors } => {
+                if let Some(unknown) = methods.iter().find(|m| ![CERTIFICATE_FACTOR, TOKEN_FACTOR].contains(&m.as_str())) {
+                    return Err(security_error(format!("Unknown authentication factor {}", unknown)));
+                }
+                // Each factor counts once towards `required_factors`
+                if let Some((_, duplicate)) = methods.iter().enumerate().find(|(i, m)| methods[..*i].contains(m)) {
+                    return Err(security_error(format!("Authentication factor {} listed twice", duplicate)));
+                }
+                if *required_factors == 0 || *required_factors > methods.len() {
+                    return Err(security_error(format!(
+                        "Cannot require {} of {} factors", required_factors, methods.len()
+                    )));
+                }
+            }
+        }
+        if let (None, Some(path)) = (&self.policy, &config.authorization_policy) {
+            let engine = Arc::new(PolicyEngine::from_file(path)?);
+            self.policy_watcher = Some(engine.watch(self.policy_reload_interval));
+            self.policy = Some(engine);
+        }
+        if self.rate_limiter.is_none() {
+            self.rate_limiter = Some(Arc::new(RateLimiter::new(config.rate_limiting.clone())));
+        }
+        self.config = Some(config);
+        Ok(())
+    }
+
+    async fn authenticate_peer(&self, peer: &Peer) -> Result<AuthenticationResult> {
+        let method = self.method()?.clone();
+        self.counters.authentication_attempts.fetch_add(1, Ordering::Relaxed);
+        let Some(credentials) = self.credentials.write().remove(&peer.id) else {
+            return Ok(self.fail(peer, "no credentials presented"));
+        };
+        let factors = self.verify_factors(peer, &credentials);
+
+        let (required, offered): (usize, Vec<&str>) = match &method {
+            AuthenticationMethod::Certificate { .. } => (1, vec![CERTIFICATE_FACTOR]),
+            AuthenticationMethod::Token { .. } => (1, vec![TOKEN_FACTOR]),
+            AuthenticationMethod::MultiFactor { methods, required_factors } => {
+                (*required_factors, methods.iter().map(String::as_str).collect())
+            }
+        };
+        let mut permissions = Vec::new();
+        let mut node_types = Vec::new();
+        let mut expiry = SystemTime::now() + self.session_ttl;
+        let mut passed = 0;
+        let mut failures = Vec::new();
+        for factor in offered {
+            let outcome = match factor {
+                CERTIFICATE_FACTOR => match &factors.certificate {
+                    Some(Ok(certificate)) => {
+                        if let Some(certificate) = certificate {
+                            node_types.extend(certificate.node_type.clone());
+                            permissions.extend(certificate.permissions.iter().cloned());
+                            expiry = expiry.min(from_unix(certificate.expires_at));
+                        }
+                        Ok(())
+                    }
+                    Some(Err(e)) => Err(e.clone()),
+                    None => Err("no valid challenge signature".to_string()),
+                },
+                _ => match &factors.token {
+                    Some(Ok(claims)) => {
+                        node_types.extend(claims.node_type.clone());
+                        permissions.extend(claims.permissions.iter().cloned());
+                        expiry = expiry.min(from_unix(claims.expires_at));
+                        Ok(())
+                    }
+                    Some(Err(e)) => Err(e.clone()),
+                    None => Err("no token presented".to_string()),
+                },
+            };
+            match outcome {
+                Ok(()) => passed += 1,
+                Err(e) => failures.push(format!("{}: {}", factor, e)),
+            }
+        }
+        if passed < required {
+            return Ok(self.fail(peer, &failures.join("; ")));
+        }
+        node_types.dedup();
+        if node_types.len() > 1 {
+            return Ok(self.fail(peer, "credentials attest different node types"));
+        }
+
+        permissions.sort();
+        permissions.dedup();
+        let session_id = Uuid::new_v4();
+        let mut sessions = self.sessions.write();
+        sessions.retain(|_, r| r.is_active());
+        sessions.insert(session_id, SessionRecord {
+            peer_id: peer.id,
+            node_type: node_types.pop(),
+            permissions: permissions.clone(),
+            public_key: factors.public_key,
+            address: peer.info.address.clone(),
+            expires_at: expiry,
+        });
+        drop(sessions);
+        counter!("frost.network.security.authentications", 1, "result" => "success");
+        debug!("Authenticated peer {} with {} factor(s)", peer.id, passed);
+        Ok(AuthenticationResult {
+            success: true,
+            session_id: Some(session_id),
+            permissions,
+            expiry: Some(expiry),
+        })
+    }
+
+    async fn authorize_action(&self, action: &Action, peer: &Peer) -> Result<bool> {
+        let Some((node_type, permissions)) = self.active_session(peer.id, |r| (r.node_type.clone(), r.permissions.clone())) else {
+            return Ok(false);
+        };
+        let Some(policy) = &self.policy else {
+            return Ok(true);
+        };
+        let decision = policy.evaluate(&AuthorizationRequest {
+            peer_id: peer.id,
+            node_type: node_type.as_ref(),
+            permissions: &permissions,
+            action,
+        });
+        Ok(decision.allowed)
+    }
+
+    async fn generate_session(&self, peer: &Peer) -> Result<SessionKeys> {
+        let rotation = self
+            .config
+            .as_ref()
+            .map(|c| c.key_rotation_interval)
+            .ok_or_else(|| security_error("Security manager not initialized"))?;
+        let (session_id, expires_at) = self
+            .sessions
+            .read()
+            .iter()
+            .filter(|(_, r)| r.peer_id == peer.id && r.is_active())
+            .max_by_key(|(_, r)| r.expires_at)
+            .map(|(id, r)| (*id, r.expires_at))
+            .ok_or_else(|| security_error(format!("Peer {} is not authenticated", peer.id)))?;
+        // Keys never outlive the session they belong to
+        let created_at = SystemTime::now();
+        Ok(SessionKeys {
+            session_id,
+            encryption_key: rand::random::<[u8; 32]>().to_vec(),
+            signing_key: rand::random::<[u8; 32]>().to_vec(),
+            created_at,
+            expires_at: (created_at + rotation).min(expires_at),
+        })
+    }
+
+    async fn validate_signature(&self, message: &[u8], signature: &[u8], peer: &Peer) -> Result<bool> {
+        self.counters.signature_validations.fetch_add(1, Ordering::Relaxed);
+        let valid = self
+            .active_session(peer.id, |r| r.public_key)
+            .flatten()
+            .is_some_and(|key| verify(&key, message, signature));
+        if !valid {
+            self.counters.failed_validations.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(valid)
+    }
+
+    fn metrics(&self) -> SecurityMetrics {
+        SecurityMetrics {
+            authentication_attempts: self.counters.authentication_attempts.load(Ordering::Relaxed),
+            failed_authentications: self.counters.failed_authentications.load(Ordering::Relaxed),
+            active_sessions: self.sessions.read().values().filter(|r| r.is_active()).count(),
+            revoked_sessions: self.counters.revoked_sessions.load(Ordering::Relaxed),
+            rate_limit_hits: self.rate_limiter.as_ref().map_or(0, |l| l.hits()),
+            signature_validations: self.counters.signature_validations.load(Ordering::Relaxed),
+            failed_validations: self.counters.failed_validations.load(Ordering::Relaxed),
+        }
+    }
+}
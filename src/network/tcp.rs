@@ -0,0 +1,597 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use metrics::counter;
+use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::network::compression::{self, Codec, CodecRegistry, ConnectionCodec, COMPRESSION_HEADER_LEN};
+use crate::network::connection::{Connection, ConnectionRegistry, FrameWriter, INBOX_CAPACITY};
+use crate::network::peer::{NodeType, PeerState};
+use crate::network::rate_limit::RateLimiter;
+use crate::network::reputation::ReputationManager;
+use crate::network::security::DefaultSecurityManager;
+use crate::network::transport::{CompressionConfig, TransportConfig, TransportMetrics, TransportProtocol};
+use crate::network::{Peer, PeerInfo, Transport};
+use crate::Result;
+
+/// Wire protocol version exchanged in the handshake
+pub const PROTOCOL_VERSION: u16 = 1;
+/// Protocol name exchanged in the handshake
+pub const PROTOCOL_NAME: &str = "frost/tcp";
+
+/// Length prefix plus frame kind
+const FRAME_HEADER_LEN: usize = 5;
+/// Largest handshake frame accepted, read before the peer is known
+const MAX_HANDSHAKE_SIZE: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum FrameKind {
+    Handshake = 0,
+    Data = 1,
+    Ping = 2,
+    Pong = 3,
+    Close = 4,
+}
+
+impl TryFrom<u8> for FrameKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            0 => Self::Handshake,
+            1 => Self::Data,
+            2 => Self::Ping,
+            3 => Self::Pong,
+            4 => Self::Close,
+            other => return Err(Error::Network(format!("Unknown frame kind {}", other))),
+        })
+    }
+}
+
+/// First frame sent in each direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Handshake {
+    protocol: String,
+    version: u16,
+    node_id: Uuid,
+    node_type: NodeType,
+    features: Vec<String>,
+    chain_ids: Vec<u64>,
+    /// Compression codecs offered, most preferred first
+    #[serde(default)]
+    compression: Vec<String>,
+}
+
+/// Length-prefixed frame ready for the wire
+fn encode_frame(kind: FrameKind, payload: &[u8]) -> Result<Vec<u8>> {
+    let len = u32::try_from(payload.len() + 1)
+        .map_err(|_| Error::Network("Frame too large".into()))?;
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.push(kind as u8);
+    frame.extend_from_slice(payload);
+    Ok(frame)
+}
+
+/// Write one frame, returning the bytes put on the wire
+async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, kind: FrameKind, payload: &[u8]) -> Result<usize> {
+    let frame = encode_frame(kind, payload)?;
+    writer.write_all(&frame).await?;
+    writer.flush().await?;
+    Ok(frame.len())
+}
+
+/// Read one frame
+///
+/// `idle` bounds the wait for the frame to start; the rest of the frame
+/// must arrive within `timeout`. Returns `None` on a clean end of stream.
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    idle: Option<Duration>,
+    timeout: Duration,
+    max_frame_size: usize,
+) -> Result<Option<(FrameKind, Vec<u8>)>> {
+    let mut first = [0u8; 1];
+    let read = match idle {
+        Some(idle) => tokio::time::timeout(idle, reader.read(&mut first))
+            .await
+            .map_err(|_| Error::Network("Connection idle timeout".into()))??,
+        None => reader.read(&mut first).await?,
+    };
+    if read == 0 {
+        return Ok(None);
+    }
+
+    tokio::time::timeout(timeout, async {
+        let mut rest = [0u8; FRAME_HEADER_LEN - 1];
+        reader.read_exact(&mut rest).await?;
+        let len = u32::from_be_bytes([first[0], rest[0], rest[1], rest[2]]) as usize;
+        if len == 0 || len - 1 > max_frame_size {
+            return Err(Error::Network(format!("Invalid frame length {}", len)));
+        }
+        let kind = FrameKind::try_from(rest[3])?;
+        let mut payload = vec![0u8; len - 1];
+        reader.read_exact(&mut payload).await?;
+        Ok(Some((kind, payload)))
+    })
+    .await
+    .map_err(|_| Error::Network("Timed out reading frame".into()))?
+}
+
+#[async_trait]
+impl FrameWriter for OwnedWriteHalf {
+    /// Encoded frame, see `encode_frame`
+    type Frame = Vec<u8>;
+
+    async fn write_frame(&mut self, frame: Vec<u8>) -> Result<usize> {
+        self.write_all(&frame).await?;
+        self.flush().await?;
+        Ok(frame.len())
+    }
+
+    fn ping(nonce: u64) -> Vec<u8> {
+        encode_frame(FrameKind::Ping, &nonce.to_be_bytes()).expect("ping fits in a frame")
+    }
+}
+
+struct Inner {
+    local: Handshake,
+    timeout: Duration,
+    keep_alive: bool,
+    max_frame_size: usize,
+    codecs: CodecRegistry,
+    compression: CompressionConfig,
+    registry: ConnectionRegistry<OwnedWriteHalf>,
+    incoming_tx: mpsc::Sender<Peer>,
+    incoming: Mutex<mpsc::Receiver<Peer>>,
+}
+
+impl Inner {
+    async fn write(&self, connection: &Connection<OwnedWriteHalf>, kind: FrameKind, payload: &[u8]) -> Result<usize> {
+        self.registry.write(connection, encode_frame(kind, payload)?).await
+    }
+
+    /// Exchange handshakes and register the connection
+    async fn establish(self: &Arc<Self>, stream: TcpStream, address: SocketAddr, outbound: bool) -> Result<Peer> {
+        let result = tokio::time::timeout(self.timeout, self.handshake(stream, outbound)).await;
+        let (stream, remote, rtt) = match result {
+            Ok(Ok(established)) => established,
+            Ok(Err(e)) => {
+                self.registry.counters.error();
+                return Err(e);
+            }
+            Err(_) => {
+                self.registry.counters.error();
+                return Err(Error::Network(format!("Handshake with {} timed out", address)));
+            }
+        };
+        if let Some(rtt) = rtt {
+            self.registry.counters.latency(rtt);
+        }
+
+        let peer = Peer {
+            id: remote.node_id,
+            info: PeerInfo {
+                address: address.to_string(),
+                protocol_version: format!("{}/{}", remote.protocol, remote.version),
+                supported_features: remote.features,
+                chain_ids: remote.chain_ids,
+                node_type: remote.node_type,
+            },
+            state: PeerState::Connected,
+        };
+        let (dialer, listener) = if outbound {
+            (&self.local.compression, &remote.compression)
+        } else {
+            (&remote.compression, &self.local.compression)
+        };
+        let codec = compression::negotiate(dialer, listener)
+            .and_then(|name| self.codecs.get(&name))
+            .map(|codec| ConnectionCodec::new(codec, &self.compression));
+
+        let (read_half, write_half) = stream.into_split();
+        self.registry.register(peer.clone(), write_half, codec, |connection, inbox| {
+            let mut tasks = vec![tokio::spawn(self.clone().read_loop(connection.clone(), read_half, inbox))];
+            if self.keep_alive {
+                let inner = self.clone();
+                tasks.push(tokio::spawn(async move { inner.registry.keep_alive(&connection).await }));
+            }
+            tasks
+        })?;
+        counter!("frost.network.tcp.connections", 1, "direction" => if outbound { "outbound" } else { "inbound" });
+        Ok(peer)
+    }
+
+    async fn handshake(&self, mut stream: TcpStream, outbound: bool) -> Result<(TcpStream, Handshake, Option<Duration>)> {
+        let hello = serde_json::to_vec(&self.local)
+            .map_err(|e| Error::Network(format!("Failed to encode handshake: {}", e)))?;
+        let started = Instant::now();
+        if outbound {
+            self.registry.counters.sent(write_frame(&mut stream, FrameKind::Handshake, &hello).await?);
+        }
+
+        let (kind, payload) = read_frame(&mut stream, None, self.timeout, MAX_HANDSHAKE_SIZE)
+            .await?
+            .ok_or_else(|| Error::Network("Connection closed during handshake".into()))?;
+        self.registry.counters.received(FRAME_HEADER_LEN + payload.len());
+        let rtt = outbound.then(|| started.elapsed());
+        if kind != FrameKind::Handshake {
+            return Err(Error::Network(format!("Expected handshake, got {:?} frame", kind)));
+        }
+        let remote: Handshake = serde_json::from_slice(&payload)
+            .map_err(|e| Error::Network(format!("Invalid handshake: {}", e)))?;
+
+        if remote.protocol != PROTOCOL_NAME || remote.version != PROTOCOL_VERSION {
+            return Err(Error::Network(format!(
+                "Unsupported protocol {}/{}, expected {}/{}",
+                remote.protocol, remote.version, PROTOCOL_NAME, PROTOCOL_VERSION
+            )));
+        }
+        if remote.node_id == self.local.node_id {
+            return Err(Error::Network("Refusing connection to self".into()));
+        }
+
+        if !outbound {
+            self.registry.counters.sent(write_frame(&mut stream, FrameKind::Handshake, &hello).await?);
+        }
+        Ok((stream, remote, rtt))
+    }
+
+    async fn read_loop(
+        self: Arc<Self>,
+        connection: Arc<Connection<OwnedWriteHalf>>,
+        mut reader: tokio::net::tcp::OwnedReadHalf,
+        inbox: mpsc::Sender<Vec<u8>>,
+    ) {
+        // Keep-alive pings arrive well within the timeout on a live connection
+        let idle = self.keep_alive.then_some(self.timeout);
+        let header = if connection.codec.is_some() { COMPRESSION_HEADER_LEN } else { 0 };
+        loop {
+            let frame = match read_frame(&mut reader, idle, self.timeout, self.max_frame_size + header).await {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(e) => {
+                    if !connection.is_closed() {
+                        warn!("Connection to peer {} failed: {}", connection.peer.id, e);
+                        self.registry.counters.error();
+                    }
+                    break;
+                }
+            };
+            self.registry.counters.received(FRAME_HEADER_LEN + frame.1.len());
+
+            match frame {
+                (FrameKind::Data, payload) => {
+                    if !self.registry.admit(&connection).await {
+                        continue;
+                    }
+                    let data = match &connection.codec {
+                        Some(codec) => match codec.decode(&payload, self.max_frame_size, &self.registry.counters) {
+                            Ok(data) => data,
+                            Err(e) => {
+                                warn!("Invalid payload from peer {}: {}", connection.peer.id, e);
+                                self.registry.counters.error();
+                                break;
+                            }
+                        },
+                        None => payload,
+                    };
+                    if inbox.send(data).await.is_err() {
+                        break;
+                    }
+                }
+                (FrameKind::Ping, nonce) => {
+                    if self.write(&connection, FrameKind::Pong, &nonce).await.is_err() {
+                        break;
+                    }
+                }
+                (FrameKind::Pong, nonce) => self.registry.pong(&connection, &nonce).await,
+                (FrameKind::Close, _) => break,
+                (FrameKind::Handshake, _) => {
+                    warn!("Unexpected handshake from peer {}", connection.peer.id);
+                    self.registry.counters.error();
+                    break;
+                }
+            }
+        }
+
+        self.registry.finish(&connection, &inbox);
+    }
+}
+
+/// Standalone tokio TCP transport
+///
+/// Frames are a 4-byte big-endian length followed by a kind byte and the
+/// payload. Each connection starts with a handshake carrying the protocol
+/// version and node id; the remote node id becomes the `Peer` id. The id is
+/// not authenticated, so a connection claiming the id of a live peer is
+/// refused rather than replacing it. With `keep_alive` enabled, idle
+/// connections exchange pings every third of `TransportConfig::timeout` and
+/// are dropped after a full timeout of silence. Inbound connections are
+/// returned by `accept`. With compression enabled, peers negotiate a codec
+/// during the handshake and each data payload carries a flag byte saying
+/// whether it was compressed.
+pub struct TcpTransport {
+    node_id: Uuid,
+    node_type: NodeType,
+    features: Vec<String>,
+    chain_ids: Vec<u64>,
+    listen_ip: IpAddr,
+    max_frame_size: usize,
+    codecs: CodecRegistry,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    reputation: Option<(Arc<ReputationManager>, Arc<DefaultSecurityManager>)>,
+    inner: Option<Arc<Inner>>,
+    local_addr: Option<SocketAddr>,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl TcpTransport {
+    /// Create transport for the local node
+    pub fn new(node_id: Uuid) -> Self {
+        Self {
+            node_id,
+            node_type: NodeType::Validator,
+            features: Vec::new(),
+            chain_ids: Vec::new(),
+            listen_ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            max_frame_size: 16 * 1024 * 1024,
+            codecs: CodecRegistry::default(),
+            rate_limiter: None,
+            reputation: None,
+            inner: None,
+            local_addr: None,
+            listener: None,
+        }
+    }
+
+    /// Node type advertised in the handshake
+    pub fn with_node_type(mut self, node_type: NodeType) -> Self {
+        self.node_type = node_type;
+        self
+    }
+
+    /// Features advertised in the handshake
+    pub fn with_features(mut self, features: Vec<String>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Chain ids advertised in the handshake
+    pub fn with_chain_ids(mut self, chain_ids: Vec<u64>) -> Self {
+        self.chain_ids = chain_ids;
+        self
+    }
+
+    /// Address to listen on, all interfaces by default
+    pub fn with_listen_ip(mut self, ip: IpAddr) -> Self {
+        self.listen_ip = ip;
+        self
+    }
+
+    /// Largest frame payload accepted or sent
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Register a compression codec alongside zstd and lz4
+    pub fn with_codec(mut self, codec: Arc<dyn Codec>) -> Self {
+        self.codecs.register(codec);
+        self
+    }
+
+    /// Drop inbound data frames the limiter rejects, e.g. the one from
+    /// `DefaultSecurityManager::rate_limiter`
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Report keep-alive latency and uptime of connected peers to
+    /// `reputation`, once `security` has authenticated them on the same
+    /// address
+    pub fn with_reputation(mut self, reputation: Arc<ReputationManager>, security: Arc<DefaultSecurityManager>) -> Self {
+        self.reputation = Some((reputation, security));
+        self
+    }
+
+    /// Local node id
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    /// Bound listen address, available after `init`
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    fn inner(&self) -> Result<&Arc<Inner>> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| Error::Network("TCP transport not initialized".into()))
+    }
+
+    /// Wait for the next inbound connection
+    pub async fn accept(&self) -> Result<Peer> {
+        let inner = self.inner()?;
+        inner
+            .incoming
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or_else(|| Error::Network("TCP listener stopped".into()))
+    }
+
+    /// Compression codec negotiated with a peer, if any
+    pub fn compression(&self, peer: &Peer) -> Option<String> {
+        self.inner.as_ref()?.registry.compression(peer)
+    }
+
+    /// Currently connected peers
+    pub fn peers(&self) -> Vec<Peer> {
+        self.inner.as_ref().map(|inner| inner.registry.peers()).unwrap_or_default()
+    }
+}
+
+impl Drop for TcpTransport {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+        }
+        if let Some(inner) = &self.inner {
+            inner.registry.close_all();
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    async fn init(&mut self, config: TransportConfig) -> Result<()> {
+        if self.inner.is_some() {
+            return Err(Error::Network("TCP transport already initialized".into()));
+        }
+        let TransportProtocol::TCP { port, keep_alive } = config.protocol else {
+            return Err(Error::Network(format!("TCP transport cannot use {:?}", config.protocol)));
+        };
+        if config.encryption.enabled {
+            return Err(Error::Network("TCP transport does not support encryption".into()));
+        }
+        if config.timeout.is_zero() {
+            return Err(Error::Network("Transport timeout must be non-zero".into()));
+        }
+
+        let compression = self.codecs.advertise(&config.compression)?;
+        let listener = TcpListener::bind((self.listen_ip, port)).await?;
+        let (incoming_tx, incoming_rx) = mpsc::channel(INBOX_CAPACITY);
+        let inner = Arc::new(Inner {
+            local: Handshake {
+                protocol: PROTOCOL_NAME.into(),
+                version: PROTOCOL_VERSION,
+                node_id: self.node_id,
+                node_type: self.node_type.clone(),
+                features: self.features.clone(),
+                chain_ids: self.chain_ids.clone(),
+                compression,
+            },
+            timeout: config.timeout,
+            keep_alive,
+            max_frame_size: self.max_frame_size,
+            codecs: self.codecs.clone(),
+            compression: config.compression,
+            registry: ConnectionRegistry::new(config.timeout, self.rate_limiter.clone(), self.reputation.clone()),
+            incoming_tx,
+            incoming: Mutex::new(incoming_rx),
+        });
+        self.local_addr = Some(listener.local_addr()?);
+
+        let acceptor = inner.clone();
+        self.listener = Some(tokio::spawn(async move {
+            loop {
+                let (stream, address) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!("TCP accept failed: {}", e);
+                        acceptor.registry.counters.error();
+                        continue;
+                    }
+                };
+                let Some(permit) = acceptor.registry.try_begin_handshake() else {
+                    debug!("Refusing connection from {}: too many pending handshakes", address);
+                    continue;
+                };
+                let _ = stream.set_nodelay(true);
+                let inner = acceptor.clone();
+                // Handshakes run concurrently so a slow peer cannot stall the listener
+                tokio::spawn(async move {
+                    let established = inner.establish(stream, address, false).await;
+                    drop(permit);
+                    match established {
+                        Ok(peer) => {
+                            let _ = inner.incoming_tx.try_send(peer);
+                        }
+                        Err(e) => debug!("Inbound handshake from {} failed: {}", address, e),
+                    }
+                });
+            }
+        }));
+        self.inner = Some(inner);
+        Ok(())
+    }
+
+    async fn connect(&mut self, address: &str) -> Result<Peer> {
+        let inner = self.inner()?.clone();
+        let stream = match tokio::time::timeout(inner.timeout, TcpStream::connect(address)).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => {
+                inner.registry.counters.error();
+                return Err(Error::Network(format!("Failed to connect to {}: {}", address, e)));
+            }
+            Err(_) => {
+                inner.registry.counters.error();
+                return Err(Error::Network(format!("Timed out connecting to {}", address)));
+            }
+        };
+        let _ = stream.set_nodelay(true);
+        let remote = stream.peer_addr()?;
+        inner.establish(stream, remote, true).await
+    }
+
+    async fn disconnect(&mut self, peer: &Peer) -> Result<()> {
+        let inner = self.inner()?;
+        let Some(connection) = inner.registry.take(peer) else {
+            return Ok(());
+        };
+        if !connection.is_closed() {
+            // Best effort; the peer also notices the closed socket
+            let _ = inner.write(&connection, FrameKind::Close, &[]).await;
+            let _ = connection.writer().await.shutdown().await;
+        }
+        connection.close();
+        Ok(())
+    }
+
+    async fn send_data(&self, peer: &Peer, data: &[u8]) -> Result<usize> {
+        let inner = self.inner()?;
+        if data.len() > inner.max_frame_size {
+            return Err(Error::Network(format!(
+                "Payload of {} bytes exceeds frame limit {}", data.len(), inner.max_frame_size
+            )));
+        }
+        let connection = inner.registry.connection(peer)?;
+        match &connection.codec {
+            Some(codec) => {
+                let payload = codec.encode(data, &inner.registry.counters)?;
+                inner.write(&connection, FrameKind::Data, &payload).await?;
+            }
+            None => {
+                inner.write(&connection, FrameKind::Data, data).await?;
+            }
+        }
+        Ok(data.len())
+    }
+
+    async fn receive_data(&self, peer: &Peer) -> Result<Vec<u8>> {
+        self.inner()?.registry.receive(peer).await
+    }
+
+    async fn is_connected(&self, peer: &Peer) -> bool {
+        self.inner.as_ref().is_some_and(|inner| inner.registry.connection(peer).is_ok())
+    }
+
+    fn metrics(&self) -> TransportMetrics {
+        self.inner.as_ref().map(|inner| inner.registry.metrics()).unwrap_or_default()
+    }
+}
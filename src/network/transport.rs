@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use crate::network::{Peer, NetworkError};
 use crate::Result;
@@ -65,11 +66,21 @@ pub struct EncryptionConfig {
 }
 
 /// Compression configuration
+///
+/// `algorithm` names a codec in the transport's `CodecRegistry`; peers
+/// negotiate a codec both support when the connection is established.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompressionConfig {
     pub enabled: bool,
     pub algorithm: String,
     pub level: u8,
+    /// Payloads smaller than this many bytes are sent uncompressed
+    #[serde(default = "default_compression_threshold")]
+    pub threshold: usize,
+}
+
+fn default_compression_threshold() -> usize {
+    512
 }
 
 /// Transport metrics
@@ -80,4 +91,84 @@ pub struct TransportMetrics {
     pub bytes_received: u64,
     pub connection_errors: u64,
     pub average_latency: Duration,
-} 
\ No newline at end of file
+    /// Payload bytes passed to a compression codec
+    pub uncompressed_bytes: u64,
+    /// Size of those payloads after compression
+    pub compressed_bytes: u64,
+    /// CPU time spent compressing
+    pub compression_time: Duration,
+    /// CPU time spent decompressing
+    pub decompression_time: Duration,
+}
+
+impl TransportMetrics {
+    /// Compressed size as a fraction of the original, 1.0 if nothing was compressed
+    pub fn compression_ratio(&self) -> f64 {
+        if self.uncompressed_bytes == 0 {
+            return 1.0;
+        }
+        self.compressed_bytes as f64 / self.uncompressed_bytes as f64
+    }
+}
+
+/// Shared counters behind `TransportMetrics`
+#[derive(Default)]
+pub(crate) struct TransportCounters {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    connection_errors: AtomicU64,
+    average_latency: parking_lot::Mutex<Option<Duration>>,
+    uncompressed_bytes: AtomicU64,
+    compressed_bytes: AtomicU64,
+    compression_nanos: AtomicU64,
+    decompression_nanos: AtomicU64,
+}
+
+impl TransportCounters {
+    pub(crate) fn sent(&self, bytes: usize) {
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn received(&self, bytes: usize) {
+        self.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn error(&self) {
+        self.connection_errors.fetch_add(1, Ordering::Relaxed);
+        metrics::counter!("frost.network.transport.connection_errors", 1);
+    }
+
+    /// Fold a round-trip sample into the moving average
+    pub(crate) fn latency(&self, sample: Duration) {
+        let mut average = self.average_latency.lock();
+        *average = Some(match *average {
+            Some(average) => average.mul_f64(0.8) + sample.mul_f64(0.2),
+            None => sample,
+        });
+    }
+
+    pub(crate) fn compressed(&self, original: usize, compressed: usize, elapsed: Duration) {
+        self.uncompressed_bytes.fetch_add(original as u64, Ordering::Relaxed);
+        self.compressed_bytes.fetch_add(compressed as u64, Ordering::Relaxed);
+        self.compression_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        metrics::counter!("frost.network.transport.compressed_bytes", compressed as u64);
+    }
+
+    pub(crate) fn decompressed(&self, elapsed: Duration) {
+        self.decompression_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self, active_connections: usize) -> TransportMetrics {
+        TransportMetrics {
+            active_connections,
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            connection_errors: self.connection_errors.load(Ordering::Relaxed),
+            average_latency: self.average_latency.lock().unwrap_or_default(),
+            uncompressed_bytes: self.uncompressed_bytes.load(Ordering::Relaxed),
+            compressed_bytes: self.compressed_bytes.load(Ordering::Relaxed),
+            compression_time: Duration::from_nanos(self.compression_nanos.load(Ordering::Relaxed)),
+            decompression_time: Duration::from_nanos(self.decompression_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
@@ -0,0 +1,627 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use metrics::counter;
+use serde::{Serialize, Deserialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::{HeaderValue, StatusCode, Uri};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, WebSocketConfig};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::network::compression::{self, Codec, CodecRegistry, ConnectionCodec, COMPRESSION_HEADER_LEN};
+use crate::network::connection::{Connection, ConnectionRegistry, FrameWriter, INBOX_CAPACITY};
+use crate::network::peer::{NodeType, PeerState};
+use crate::network::rate_limit::RateLimiter;
+use crate::network::reputation::ReputationManager;
+use crate::network::security::DefaultSecurityManager;
+use crate::network::transport::{CompressionConfig, TransportConfig, TransportMetrics, TransportProtocol};
+use crate::network::{Peer, PeerInfo, Transport};
+use crate::Result;
+
+/// Subprotocol prefix; the protocol version is appended, e.g. `frost.v1`
+pub const SUBPROTOCOL_PREFIX: &str = "frost.v";
+/// Protocol versions this node speaks, newest last
+pub const SUPPORTED_VERSIONS: &[u16] = &[1];
+
+const SUBPROTOCOL_HEADER: &str = "Sec-WebSocket-Protocol";
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type WsSink = SplitSink<WsStream, Message>;
+
+fn subprotocol(version: u16) -> String {
+    format!("{}{}", SUBPROTOCOL_PREFIX, version)
+}
+
+/// Highest supported version offered in a `Sec-WebSocket-Protocol` header
+fn negotiate(offered: &str) -> Option<u16> {
+    offered
+        .split(',')
+        .filter_map(|p| p.trim().strip_prefix(SUBPROTOCOL_PREFIX)?.parse::<u16>().ok())
+        .filter(|v| SUPPORTED_VERSIONS.contains(v))
+        .max()
+}
+
+/// Identity exchanged as the first text message in each direction
+///
+/// Browsers cannot set custom upgrade headers, so identity travels in-band.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Hello {
+    node_id: Uuid,
+    node_type: NodeType,
+    features: Vec<String>,
+    chain_ids: Vec<u64>,
+    /// Compression codecs offered, most preferred first
+    #[serde(default)]
+    compression: Vec<String>,
+}
+
+#[async_trait]
+impl FrameWriter for WsSink {
+    type Frame = Message;
+
+    async fn write_frame(&mut self, message: Message) -> Result<usize> {
+        let len = message.len();
+        self.send(message)
+            .await
+            .map_err(|e| Error::Network(format!("WebSocket write failed: {}", e)))?;
+        Ok(len)
+    }
+
+    fn ping(nonce: u64) -> Message {
+        Message::Ping(nonce.to_be_bytes().to_vec().into())
+    }
+}
+
+struct Inner {
+    local: Hello,
+    timeout: Duration,
+    use_tls: bool,
+    max_message_size: usize,
+    codecs: CodecRegistry,
+    compression: CompressionConfig,
+    registry: ConnectionRegistry<WsSink>,
+    incoming_tx: mpsc::Sender<Peer>,
+    incoming: Mutex<mpsc::Receiver<Peer>>,
+}
+
+impl Inner {
+    fn ws_config(&self) -> WebSocketConfig {
+        let limit = self.max_message_size + COMPRESSION_HEADER_LEN;
+        WebSocketConfig::default()
+            .max_message_size(Some(limit))
+            .max_frame_size(Some(limit))
+    }
+
+    async fn write(&self, connection: &Connection<WsSink>, message: Message) -> Result<()> {
+        self.registry.write(connection, message).await.map(|_| ())
+    }
+
+    /// Exchange hellos over an upgraded stream and register the connection
+    async fn establish(self: &Arc<Self>, mut stream: WsStream, address: String, version: u16, outbound: bool) -> Result<Peer> {
+        let exchange = async {
+            let hello = serde_json::to_string(&self.local)
+                .map_err(|e| Error::Network(format!("Failed to encode hello: {}", e)))?;
+            self.registry.counters.sent(hello.len());
+            stream
+                .send(Message::text(hello))
+                .await
+                .map_err(|e| Error::Network(format!("Failed to send hello: {}", e)))?;
+
+            loop {
+                match stream.next().await {
+                    Some(Ok(Message::Text(text))) => {
+                        self.registry.counters.received(text.len());
+                        return serde_json::from_str::<Hello>(text.as_str())
+                            .map_err(|e| Error::Network(format!("Invalid hello: {}", e)));
+                    }
+                    Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+                    Some(Ok(other)) => {
+                        return Err(Error::Network(format!("Expected hello, got {:?}", other)));
+                    }
+                    Some(Err(e)) => return Err(Error::Network(format!("Hello exchange failed: {}", e))),
+                    None => return Err(Error::Network("Connection closed during hello".into())),
+                }
+            }
+        };
+        let remote = match tokio::time::timeout(self.timeout, exchange).await {
+            Ok(Ok(remote)) if remote.node_id != self.local.node_id => remote,
+            Ok(Ok(_)) => {
+                self.registry.counters.error();
+                let _ = stream.close(Some(close_frame(CloseCode::Policy, "connection to self"))).await;
+                return Err(Error::Network("Refusing connection to self".into()));
+            }
+            Ok(Err(e)) => {
+                self.registry.counters.error();
+                let _ = stream.close(Some(close_frame(CloseCode::Protocol, "invalid hello"))).await;
+                return Err(e);
+            }
+            Err(_) => {
+                self.registry.counters.error();
+                return Err(Error::Network(format!("Hello from {} timed out", address)));
+            }
+        };
+
+        let peer = Peer {
+            id: remote.node_id,
+            info: PeerInfo {
+                address,
+                protocol_version: subprotocol(version),
+                supported_features: remote.features,
+                chain_ids: remote.chain_ids,
+                node_type: remote.node_type,
+            },
+            state: PeerState::Connected,
+        };
+        let (dialer, listener) = if outbound {
+            (&self.local.compression, &remote.compression)
+        } else {
+            (&remote.compression, &self.local.compression)
+        };
+        let codec = compression::negotiate(dialer, listener)
+            .and_then(|name| self.codecs.get(&name))
+            .map(|codec| ConnectionCodec::new(codec, &self.compression));
+
+        let (sink, source) = stream.split();
+        self.registry.register(peer.clone(), sink, codec, |connection, inbox| {
+            let inner = self.clone();
+            let keep_alive = connection.clone();
+            vec![
+                tokio::spawn(self.clone().read_loop(connection, source, inbox)),
+                tokio::spawn(async move { inner.registry.keep_alive(&keep_alive).await }),
+            ]
+        })?;
+        counter!("frost.network.ws.connections", 1, "direction" => if outbound { "outbound" } else { "inbound" });
+        Ok(peer)
+    }
+
+    async fn read_loop(
+        self: Arc<Self>,
+        connection: Arc<Connection<WsSink>>,
+        mut source: SplitStream<WsStream>,
+        inbox: mpsc::Sender<Vec<u8>>,
+    ) {
+        // Pings are sent every third of the timeout, so a live peer is never idle this long
+        loop {
+            let message = match tokio::time::timeout(self.timeout, source.next()).await {
+                Ok(Some(Ok(message))) => message,
+                Ok(None) => break,
+                Ok(Some(Err(e))) => {
+                    if !connection.is_closed() {
+                        warn!("WebSocket connection to peer {} failed: {}", connection.peer.id, e);
+                        self.registry.counters.error();
+                    }
+                    break;
+                }
+                Err(_) => {
+                    warn!("WebSocket connection to peer {} idle timeout", connection.peer.id);
+                    self.registry.counters.error();
+                    let _ = self.write(&connection, Message::Close(Some(close_frame(CloseCode::Away, "idle timeout")))).await;
+                    break;
+                }
+            };
+            self.registry.counters.received(message.len());
+
+            match message {
+                Message::Binary(payload) => {
+                    if !self.registry.admit(&connection).await {
+                        continue;
+                    }
+                    let data = match &connection.codec {
+                        Some(codec) => match codec.decode(&payload, self.max_message_size, &self.registry.counters) {
+                            Ok(data) => data,
+                            Err(e) => {
+                                warn!("Invalid payload from peer {}: {}", connection.peer.id, e);
+                                self.registry.counters.error();
+                                let _ = self.write(&connection, Message::Close(Some(close_frame(CloseCode::Invalid, "invalid payload")))).await;
+                                break;
+                            }
+                        },
+                        None if payload.len() > self.max_message_size => {
+                            warn!("Oversized message from peer {}", connection.peer.id);
+                            self.registry.counters.error();
+                            let _ = self.write(&connection, Message::Close(Some(close_frame(CloseCode::Size, "message too large")))).await;
+                            break;
+                        }
+                        None => payload.to_vec(),
+                    };
+                    if inbox.send(data).await.is_err() {
+                        break;
+                    }
+                }
+                Message::Pong(nonce) => self.registry.pong(&connection, &nonce).await,
+                // Pongs to incoming pings are queued by the protocol layer
+                Message::Ping(_) => {}
+                Message::Close(frame) => {
+                    debug!("Peer {} closed the connection: {:?}", connection.peer.id, frame);
+                    // Complete the closing handshake
+                    let _ = connection.writer().await.close().await;
+                    break;
+                }
+                Message::Text(_) | Message::Frame(_) => {
+                    warn!("Unexpected text message from peer {}", connection.peer.id);
+                    self.registry.counters.error();
+                    let _ = self.write(&connection, Message::Close(Some(close_frame(CloseCode::Unsupported, "binary frames only")))).await;
+                    break;
+                }
+            }
+        }
+
+        self.registry.finish(&connection, &inbox);
+    }
+}
+
+fn close_frame(code: CloseCode, reason: &str) -> CloseFrame {
+    CloseFrame { code, reason: reason.into() }
+}
+
+/// WebSocket transport for browser and gateway clients
+///
+/// Data travels in binary messages. The upgrade negotiates a `frost.v<N>`
+/// subprotocol, picking the highest version both sides support, then each
+/// side sends a text hello with its node id; the remote node id becomes
+/// the `Peer` id. Both sides ping every third of
+/// `TransportConfig::timeout` and drop connections silent for a full
+/// timeout. The `WebSocket` protocol `url` is the listen address, e.g.
+/// `ws://0.0.0.0:9000/frost`; leave it empty for a client-only transport.
+/// With `use_tls`, outbound connections must use `wss://`. Compression is
+/// negotiated in the hello; each binary message then starts with a flag
+/// byte saying whether the rest is compressed.
+pub struct WebSocketTransport {
+    node_id: Uuid,
+    node_type: NodeType,
+    features: Vec<String>,
+    chain_ids: Vec<u64>,
+    max_message_size: usize,
+    codecs: CodecRegistry,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    reputation: Option<(Arc<ReputationManager>, Arc<DefaultSecurityManager>)>,
+    inner: Option<Arc<Inner>>,
+    local_addr: Option<SocketAddr>,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl WebSocketTransport {
+    /// Create transport for the local node
+    pub fn new(node_id: Uuid) -> Self {
+        Self {
+            node_id,
+            node_type: NodeType::Gateway,
+            features: Vec::new(),
+            chain_ids: Vec::new(),
+            max_message_size: 16 * 1024 * 1024,
+            codecs: CodecRegistry::default(),
+            rate_limiter: None,
+            reputation: None,
+            inner: None,
+            local_addr: None,
+            listener: None,
+        }
+    }
+
+    /// Node type advertised in the hello
+    pub fn with_node_type(mut self, node_type: NodeType) -> Self {
+        self.node_type = node_type;
+        self
+    }
+
+    /// Features advertised in the hello
+    pub fn with_features(mut self, features: Vec<String>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Chain ids advertised in the hello
+    pub fn with_chain_ids(mut self, chain_ids: Vec<u64>) -> Self {
+        self.chain_ids = chain_ids;
+        self
+    }
+
+    /// Largest message accepted or sent
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Register a compression codec alongside zstd and lz4
+    pub fn with_codec(mut self, codec: Arc<dyn Codec>) -> Self {
+        self.codecs.register(codec);
+        self
+    }
+
+    /// Drop inbound data frames the limiter rejects, e.g. the one from
+    /// `DefaultSecurityManager::rate_limiter`
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Report keep-alive latency and uptime of connected peers to
+    /// `reputation`, once `security` has authenticated them on the same
+    /// address
+    pub fn with_reputation(mut self, reputation: Arc<ReputationManager>, security: Arc<DefaultSecurityManager>) -> Self {
+        self.reputation = Some((reputation, security));
+        self
+    }
+
+    /// Local node id
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    /// Bound listen address, available after `init` with a listen url
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    fn inner(&self) -> Result<&Arc<Inner>> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| Error::Network("WebSocket transport not initialized".into()))
+    }
+
+    /// Wait for the next inbound connection
+    pub async fn accept(&self) -> Result<Peer> {
+        self.inner()?
+            .incoming
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or_else(|| Error::Network("WebSocket listener stopped".into()))
+    }
+
+    /// Compression codec negotiated with a peer, if any
+    pub fn compression(&self, peer: &Peer) -> Option<String> {
+        self.inner.as_ref()?.registry.compression(peer)
+    }
+
+    /// Currently connected peers
+    pub fn peers(&self) -> Vec<Peer> {
+        self.inner.as_ref().map(|inner| inner.registry.peers()).unwrap_or_default()
+    }
+
+    async fn listen(&mut self, inner: Arc<Inner>, url: &str) -> Result<()> {
+        let uri: Uri = url.parse().map_err(|e| Error::Network(format!("Invalid listen url {}: {}", url, e)))?;
+        if uri.scheme_str() != Some("ws") {
+            return Err(Error::Network(format!(
+                "Cannot listen on {}: TLS must be terminated in front of the node", url
+            )));
+        }
+        let host = uri.host().ok_or_else(|| Error::Network("Listen url has no host".into()))?;
+        let listener = TcpListener::bind((host, uri.port_u16().unwrap_or(80))).await?;
+        self.local_addr = Some(listener.local_addr()?);
+        let path = uri.path().to_string();
+
+        self.listener = Some(tokio::spawn(async move {
+            loop {
+                let (stream, address) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!("WebSocket accept failed: {}", e);
+                        inner.registry.counters.error();
+                        continue;
+                    }
+                };
+                let Some(permit) = inner.registry.try_begin_handshake() else {
+                    debug!("Refusing connection from {}: too many pending handshakes", address);
+                    continue;
+                };
+                let _ = stream.set_nodelay(true);
+                let inner = inner.clone();
+                let path = path.clone();
+                // Upgrades run concurrently so a slow client cannot stall the listener
+                tokio::spawn(async move {
+                    let established = match accept(&inner, stream, &path).await {
+                        Ok((stream, version)) => inner.establish(stream, address.to_string(), version, false).await,
+                        Err(e) => {
+                            inner.registry.counters.error();
+                            debug!("WebSocket upgrade from {} failed: {}", address, e);
+                            return;
+                        }
+                    };
+                    drop(permit);
+                    match established {
+                        Ok(peer) => {
+                            let _ = inner.incoming_tx.try_send(peer);
+                        }
+                        Err(e) => debug!("Inbound hello from {} failed: {}", address, e),
+                    }
+                });
+            }
+        }));
+        Ok(())
+    }
+}
+
+/// Server side of the upgrade, returning the negotiated version
+// The callback's error type is fixed by tungstenite
+#[allow(clippy::result_large_err)]
+async fn accept(inner: &Inner, stream: TcpStream, path: &str) -> Result<(WsStream, u16)> {
+    let negotiated = Arc::new(parking_lot::Mutex::new(None));
+    let selected = negotiated.clone();
+    let callback = move |request: &Request, mut response: Response| -> std::result::Result<Response, ErrorResponse> {
+        let reject = |status: StatusCode, reason: &str| {
+            let mut response = ErrorResponse::new(Some(reason.to_string()));
+            *response.status_mut() = status;
+            response
+        };
+        if request.uri().path() != path {
+            return Err(reject(StatusCode::NOT_FOUND, "unknown path"));
+        }
+        let offered = request
+            .headers()
+            .get_all(SUBPROTOCOL_HEADER)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        let Some(version) = negotiate(&offered) else {
+            return Err(reject(StatusCode::BAD_REQUEST, "no supported frost subprotocol"));
+        };
+        let header = HeaderValue::from_str(&subprotocol(version)).expect("valid header value");
+        response.headers_mut().insert(SUBPROTOCOL_HEADER, header);
+        *selected.lock() = Some(version);
+        Ok(response)
+    };
+
+    let upgrade = tokio_tungstenite::accept_hdr_async_with_config(
+        MaybeTlsStream::Plain(stream),
+        callback,
+        Some(inner.ws_config()),
+    );
+    let stream = tokio::time::timeout(inner.timeout, upgrade)
+        .await
+        .map_err(|_| Error::Network("WebSocket upgrade timed out".into()))?
+        .map_err(|e| Error::Network(format!("WebSocket upgrade failed: {}", e)))?;
+    let version = negotiated.lock().take().expect("version negotiated before upgrade");
+    Ok((stream, version))
+}
+
+impl Drop for WebSocketTransport {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+        }
+        if let Some(inner) = &self.inner {
+            inner.registry.close_all();
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    async fn init(&mut self, config: TransportConfig) -> Result<()> {
+        if self.inner.is_some() {
+            return Err(Error::Network("WebSocket transport already initialized".into()));
+        }
+        let TransportProtocol::WebSocket { url, use_tls } = config.protocol else {
+            return Err(Error::Network(format!("WebSocket transport cannot use {:?}", config.protocol)));
+        };
+        if config.encryption.enabled {
+            return Err(Error::Network("WebSocket transport does not support encryption".into()));
+        }
+        if config.timeout.is_zero() {
+            return Err(Error::Network("Transport timeout must be non-zero".into()));
+        }
+        if use_tls && !url.is_empty() {
+            return Err(Error::Network("TLS listeners must be terminated in front of the node".into()));
+        }
+
+        let compression = self.codecs.advertise(&config.compression)?;
+        let (incoming_tx, incoming_rx) = mpsc::channel(INBOX_CAPACITY);
+        let inner = Arc::new(Inner {
+            local: Hello {
+                node_id: self.node_id,
+                node_type: self.node_type.clone(),
+                features: self.features.clone(),
+                chain_ids: self.chain_ids.clone(),
+                compression,
+            },
+            timeout: config.timeout,
+            use_tls,
+            max_message_size: self.max_message_size,
+            codecs: self.codecs.clone(),
+            compression: config.compression,
+            registry: ConnectionRegistry::new(config.timeout, self.rate_limiter.clone(), self.reputation.clone()),
+            incoming_tx,
+            incoming: Mutex::new(incoming_rx),
+        });
+        if !url.is_empty() {
+            self.listen(inner.clone(), &url).await?;
+        }
+        self.inner = Some(inner);
+        Ok(())
+    }
+
+    async fn connect(&mut self, address: &str) -> Result<Peer> {
+        let inner = self.inner()?.clone();
+        if inner.use_tls && !address.starts_with("wss://") {
+            return Err(Error::Network(format!("TLS required, refusing to connect to {}", address)));
+        }
+        let mut request = address
+            .into_client_request()
+            .map_err(|e| Error::Network(format!("Invalid WebSocket address {}: {}", address, e)))?;
+        let offered = SUPPORTED_VERSIONS.iter().rev().map(|v| subprotocol(*v)).collect::<Vec<_>>().join(", ");
+        request.headers_mut().insert(
+            SUBPROTOCOL_HEADER,
+            HeaderValue::from_str(&offered).expect("valid header value"),
+        );
+
+        let connect = tokio_tungstenite::connect_async_with_config(request, Some(inner.ws_config()), true);
+        let (stream, response) = match tokio::time::timeout(inner.timeout, connect).await {
+            Ok(Ok(connected)) => connected,
+            Ok(Err(e)) => {
+                inner.registry.counters.error();
+                return Err(Error::Network(format!("Failed to connect to {}: {}", address, e)));
+            }
+            Err(_) => {
+                inner.registry.counters.error();
+                return Err(Error::Network(format!("Timed out connecting to {}", address)));
+            }
+        };
+        let version = response
+            .headers()
+            .get(SUBPROTOCOL_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(negotiate)
+            .ok_or_else(|| {
+                inner.registry.counters.error();
+                Error::Network(format!("{} did not select a supported subprotocol", address))
+            })?;
+        inner.establish(stream, address.to_string(), version, true).await
+    }
+
+    async fn disconnect(&mut self, peer: &Peer) -> Result<()> {
+        let inner = self.inner()?;
+        let Some(connection) = inner.registry.take(peer) else {
+            return Ok(());
+        };
+        if !connection.is_closed() {
+            // Best effort; the read loop is aborted so the close reply is not awaited
+            let _ = inner.write(&connection, Message::Close(Some(close_frame(CloseCode::Normal, "disconnect")))).await;
+        }
+        connection.close();
+        Ok(())
+    }
+
+    async fn send_data(&self, peer: &Peer, data: &[u8]) -> Result<usize> {
+        let inner = self.inner()?;
+        if data.len() > inner.max_message_size {
+            return Err(Error::Network(format!(
+                "Payload of {} bytes exceeds message limit {}", data.len(), inner.max_message_size
+            )));
+        }
+        let connection = inner.registry.connection(peer)?;
+        let payload = match &connection.codec {
+            Some(codec) => codec.encode(data, &inner.registry.counters)?,
+            None => data.to_vec(),
+        };
+        inner.write(&connection, Message::binary(payload)).await?;
+        Ok(data.len())
+    }
+
+    async fn receive_data(&self, peer: &Peer) -> Result<Vec<u8>> {
+        self.inner()?.registry.receive(peer).await
+    }
+
+    async fn is_connected(&self, peer: &Peer) -> bool {
+        self.inner.as_ref().is_some_and(|inner| inner.registry.connection(peer).is_ok())
+    }
+
+    fn metrics(&self) -> TransportMetrics {
+        self.inner.as_ref().map(|inner| inner.registry.metrics()).unwrap_or_default()
+    }
+}
@@ -1,9 +1,11 @@
 #![allow(unused_imports)]
 
+pub mod outbox;
 pub mod router;
 pub mod strategy;
 pub mod topology;
 
+pub use outbox::{DurableOutbox, OutboxConfig};
 pub use router::{MessageRouter as ImportedMessageRouter, RouterConfig};
 pub use strategy::{RoutingStrategy, DefaultStrategy};
 pub use topology::{NetworkTopology, TopologyNode};
@@ -14,6 +16,7 @@ use std::collections::HashMap;
 use std::error::Error;
 use async_trait::async_trait;
 use crate::message::{FrostMessage, MessageType};
+use crate::message::dead_letter::{DeadLetterOrigin, DeadLetterQueue};
 use crate::network::NetworkProtocol;
 
 /// Message router trait
@@ -67,6 +70,7 @@ pub struct BasicRouter<N: NetworkProtocol> {
     metrics: RoutingMetrics,
     routes: HashMap<String, String>,
     network: N,
+    dead_letters: Option<std::sync::Arc<DeadLetterQueue>>,
 }
 
 impl<N: NetworkProtocol> BasicRouter<N> {
@@ -77,9 +81,16 @@ impl<N: NetworkProtocol> BasicRouter<N> {
             metrics: RoutingMetrics::default(),
             routes: HashMap::new(),
             network,
+            dead_letters: None,
         }
     }
 
+    /// Capture expired messages in `dead_letters` instead of dropping them
+    pub fn with_dead_letter_queue(mut self, dead_letters: std::sync::Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
     /// Get current metrics
     pub fn get_metrics(&self) -> RoutingMetrics {
         self.metrics.clone()
@@ -89,6 +100,13 @@ impl<N: NetworkProtocol> BasicRouter<N> {
 #[async_trait]
 impl<N: NetworkProtocol> MessageRouter for BasicRouter<N> {
     async fn route(&self, message: FrostMessage) -> std::result::Result<(), Box<dyn Error>> {
+        if let Err(e) = message.check_expiry() {
+            if let Some(dead_letters) = &self.dead_letters {
+                dead_letters.push_error(message, DeadLetterOrigin::Routing, &e);
+            }
+            return Err(e.into());
+        }
+
         // Basic routing for v0
         if let Some(target) = message.target.as_ref() {
             if let Some(next_hop) = self.routes.get(target) {
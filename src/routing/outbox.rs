@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::jsonl::{JsonlLog, LogError};
+use crate::message::FrostMessage;
+use crate::message::dead_letter::{DeadLetterOrigin, DeadLetterQueue};
+use crate::network::NetworkProtocol;
+use crate::Result;
+
+/// Where an outbox message is delivered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Destination {
+    /// Broadcast to the network
+    Broadcast,
+    /// Direct send to a peer
+    Peer(String),
+}
+
+/// Outbox configuration
+#[derive(Debug, Clone)]
+pub struct OutboxConfig {
+    /// Acknowledge a message as soon as the network accepts it. When
+    /// disabled, messages stay pending until `acknowledge` is called.
+    pub ack_on_send: bool,
+    /// Compact the log after this many acknowledgements (0 disables)
+    pub compact_after_acks: usize,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            ack_on_send: true,
+            compact_after_acks: 1024,
+        }
+    }
+}
+
+/// Unacknowledged outbox message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingMessage {
+    /// Message as last written to the log
+    pub message: FrostMessage,
+    /// Delivery destination
+    pub destination: Destination,
+    /// Log sequence number, used to replay in original order
+    pub sequence: u64,
+}
+
+/// Outbox log record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum OutboxRecord {
+    Pending(Box<PendingMessage>),
+    Acked { id: Uuid },
+}
+
+struct OutboxState {
+    pending: HashMap<Uuid, PendingMessage>,
+    next_sequence: u64,
+    acks_since_compact: usize,
+    log: JsonlLog,
+}
+
+/// Write-ahead outbox between a router and the network
+///
+/// Outgoing messages are appended to a local log and synced before they
+/// are handed to the inner `NetworkProtocol`, giving at-least-once
+/// delivery across restarts. Wrap the network given to a router, e.g.
+/// `BasicRouter::new(config, DurableOutbox::open(path, network, ..))`.
+pub struct DurableOutbox<N: NetworkProtocol> {
+    config: OutboxConfig,
+    path: PathBuf,
+    network: N,
+    dead_letters: Option<Arc<DeadLetterQueue>>,
+    state: Mutex<OutboxState>,
+}
+
+impl<N: NetworkProtocol> DurableOutbox<N> {
+    /// Open or create an outbox log at `path` in front of `network`
+    ///
+    /// Unacknowledged messages from a previous run are loaded but not
+    /// resent until `replay` is called.
+    pub async fn open(path: impl AsRef<Path>, network: N, config: OutboxConfig) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (log, records) = JsonlLog::open::<OutboxRecord>(&path).await.map_err(|e| match e {
+            LogError::Corrupt { line, source } => {
+                Error::Routing(format!("Corrupt outbox log at line {}: {}", line, source))
+            }
+            LogError::Io(e) => e.into(),
+        })?;
+
+        let mut pending = HashMap::new();
+        let mut next_sequence = 0;
+        for record in records {
+            match record {
+                OutboxRecord::Pending(entry) => {
+                    next_sequence = next_sequence.max(entry.sequence + 1);
+                    pending.insert(entry.message.id, *entry);
+                }
+                OutboxRecord::Acked { id } => {
+                    pending.remove(&id);
+                }
+            }
+        }
+
+        Ok(Self {
+            config,
+            path,
+            network,
+            dead_letters: None,
+            state: Mutex::new(OutboxState {
+                pending,
+                next_sequence,
+                acks_since_compact: 0,
+                log,
+            }),
+        })
+    }
+
+    /// Capture messages that expire before delivery in `dead_letters`
+    pub fn with_dead_letter_queue(mut self, dead_letters: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Path of the backing log
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Wrapped network
+    pub fn network(&self) -> &N {
+        &self.network
+    }
+
+    /// Persist a message as pending, bumping its retry count if it already was
+    async fn persist(&self, mut message: FrostMessage, destination: Destination) -> Result<FrostMessage> {
+        let mut state = self.state.lock().await;
+        if let Some(existing) = state.pending.get(&message.id) {
+            message.metadata.retry_count = existing.message.metadata.retry_count + 1;
+        }
+
+        let entry = PendingMessage {
+            message: message.clone(),
+            destination,
+            sequence: state.next_sequence,
+        };
+        state.log.append(&OutboxRecord::Pending(Box::new(entry.clone()))).await?;
+        state.next_sequence += 1;
+        state.pending.insert(message.id, entry);
+        Ok(message)
+    }
+
+    async fn deliver(&self, message: FrostMessage, destination: Destination) -> Result<()> {
+        let id = message.id;
+        if let Err(e) = message.check_expiry() {
+            // Expired messages leave the log rather than being resent
+            self.acknowledge(id).await?;
+            if let Some(dead_letters) = &self.dead_letters {
+                dead_letters.push_error(message, DeadLetterOrigin::Outbox, &e);
+            }
+            return Err(e.into());
+        }
+        let message = self.persist(message, destination.clone()).await?;
+
+        match &destination {
+            Destination::Broadcast => self.network.broadcast(message).await?,
+            Destination::Peer(peer_id) => self.network.send_to(peer_id, message).await?,
+        }
+
+        if self.config.ack_on_send {
+            self.acknowledge(id).await?;
+        }
+        Ok(())
+    }
+
+    /// Mark a message as delivered
+    ///
+    /// Returns false if the message was not pending.
+    pub async fn acknowledge(&self, message_id: Uuid) -> Result<bool> {
+        let mut state = self.state.lock().await;
+        if !state.pending.contains_key(&message_id) {
+            return Ok(false);
+        }
+        state.log.append(&OutboxRecord::Acked { id: message_id }).await?;
+        state.pending.remove(&message_id);
+        state.acks_since_compact += 1;
+
+        if self.config.compact_after_acks > 0 && state.acks_since_compact >= self.config.compact_after_acks {
+            self.compact_locked(&mut state).await?;
+        }
+        Ok(true)
+    }
+
+    /// Unacknowledged messages in original send order
+    pub async fn pending(&self) -> Vec<PendingMessage> {
+        let state = self.state.lock().await;
+        let mut pending: Vec<_> = state.pending.values().cloned().collect();
+        pending.sort_by_key(|entry| entry.sequence);
+        pending
+    }
+
+    /// Number of unacknowledged messages
+    pub async fn pending_count(&self) -> usize {
+        self.state.lock().await.pending.len()
+    }
+
+    /// Resend all unacknowledged messages in original order
+    ///
+    /// Each resend increments the message's `retry_count`. Messages that
+    /// fail again stay pending; expired messages are dropped from the log
+    /// and dead-lettered. Returns the number of messages resent.
+    pub async fn replay(&self) -> Result<usize> {
+        let mut resent = 0;
+        for entry in self.pending().await {
+            let id = entry.message.id;
+            match self.deliver(entry.message, entry.destination).await {
+                Ok(()) => resent += 1,
+                Err(e) => warn!("Outbox replay of message {} failed: {}", id, e),
+            }
+        }
+        Ok(resent)
+    }
+
+    async fn compact_locked(&self, state: &mut OutboxState) -> Result<()> {
+        let mut pending: Vec<_> = state.pending.values().collect();
+        pending.sort_by_key(|entry| entry.sequence);
+
+        let records: Vec<_> = pending
+            .into_iter()
+            .map(|entry| OutboxRecord::Pending(Box::new(entry.clone())))
+            .collect();
+        state.log.rewrite(records).await?;
+        state.acks_since_compact = 0;
+        Ok(())
+    }
+
+    /// Rewrite the log to contain only unacknowledged messages
+    pub async fn compact(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        self.compact_locked(&mut state).await
+    }
+}
+
+#[async_trait]
+impl<N: NetworkProtocol> NetworkProtocol for DurableOutbox<N> {
+    async fn start(&mut self) -> Result<()> {
+        self.network.start().await
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.network.stop().await
+    }
+
+    async fn broadcast(&self, message: FrostMessage) -> Result<()> {
+        self.deliver(message, Destination::Broadcast).await
+    }
+
+    async fn send_to(&self, peer_id: &str, message: FrostMessage) -> Result<()> {
+        self.deliver(message, Destination::Peer(peer_id.to_string())).await
+    }
+
+    async fn get_peers(&self) -> Result<Vec<String>> {
+        self.network.get_peers().await
+    }
+}
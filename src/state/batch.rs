@@ -0,0 +1,91 @@
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Serialize, Deserialize};
+
+/// Batch verification settings for `ProofRegistry`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchConfig {
+    /// Maximum number of chunks verified concurrently
+    pub max_workers: usize,
+    /// Number of proofs handed to a verifier at once
+    pub chunk_size: usize,
+    /// Verify identical proofs in a batch only once
+    pub deduplicate: bool,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_workers: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4),
+            chunk_size: 64,
+            deduplicate: true,
+        }
+    }
+}
+
+/// Single ed25519 signature in a batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ed25519BatchItem {
+    /// Signed message
+    pub message: Vec<u8>,
+    /// Signature bytes
+    pub signature: [u8; 64],
+    /// Signer public key
+    pub public_key: [u8; 32],
+}
+
+/// Verify ed25519 signatures in batch with one result per item
+///
+/// All signatures are first checked with a single batch equation. If that
+/// fails the batch is bisected to locate the invalid signatures, so a
+/// single bad item costs O(log n) extra batch checks.
+///
+/// Every item is judged by the cofactored batch equation, alone or with
+/// others, so results do not depend on how items are grouped. Weak
+/// (small-order) public keys always fail.
+pub fn verify_ed25519_batch(items: &[Ed25519BatchItem]) -> Vec<bool> {
+    let mut results = vec![false; items.len()];
+
+    // Items with malformed or weak keys fail without joining the batch
+    let parsed: Vec<(usize, VerifyingKey, Signature)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            VerifyingKey::from_bytes(&item.public_key)
+                .ok()
+                .filter(|key| !key.is_weak())
+                .map(|key| (i, key, Signature::from_bytes(&item.signature)))
+        })
+        .collect();
+
+    bisect(items, &parsed, &mut results);
+    results
+}
+
+fn bisect(
+    items: &[Ed25519BatchItem],
+    parsed: &[(usize, VerifyingKey, Signature)],
+    results: &mut [bool],
+) {
+    if parsed.is_empty() {
+        return;
+    }
+
+    let messages: Vec<&[u8]> = parsed.iter().map(|(i, _, _)| items[*i].message.as_slice()).collect();
+    let keys: Vec<VerifyingKey> = parsed.iter().map(|(_, key, _)| *key).collect();
+    let signatures: Vec<Signature> = parsed.iter().map(|(_, _, sig)| *sig).collect();
+
+    if ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok() {
+        for (i, _, _) in parsed {
+            results[*i] = true;
+        }
+        return;
+    }
+
+    if parsed.len() > 1 {
+        let (left, right) = parsed.split_at(parsed.len() / 2);
+        bisect(items, left, results);
+        bisect(items, right, results);
+    }
+}
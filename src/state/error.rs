@@ -61,13 +61,16 @@ pub enum StateError {
     #[error("Chain specific error: {0}")]
     ChainSpecific(String),
 
+    #[error("State storage error: {0}")]
+    Storage(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
 impl StateError {
     pub fn is_retryable(&self) -> bool {
-        matches!(self, StateError::ChainSpecific(_))
+        matches!(self, StateError::ChainSpecific(_) | StateError::Storage(_))
     }
 
     pub fn severity(&self) -> ErrorSeverity {
@@ -78,6 +81,7 @@ impl StateError {
             StateError::InvalidBlockRef(_) => ErrorSeverity::Error,
             StateError::RootMismatch { .. } => ErrorSeverity::Critical,
             StateError::ChainSpecific(_) => ErrorSeverity::Warning,
+            StateError::Storage(_) => ErrorSeverity::Error,
             StateError::Internal(_) => ErrorSeverity::Critical,
         }
     }
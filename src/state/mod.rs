@@ -6,11 +6,18 @@ pub mod types;
 pub mod error;
 pub mod cache;
 pub mod revocation;
+pub mod batch;
+pub mod signature;
+pub mod smt;
+pub mod store;
+pub mod zk;
 
 pub use transition::StateTransition;
 pub use proof::StateProof;
 pub use types::{BlockId, BlockRef, StateRoot, ChainId};
 pub use error::{StateError, ErrorSeverity};
+pub use smt::{SparseMerkleTree, SparseMerkleProof};
+pub use store::{StateRootStore, InMemoryStateRootStore, FileStateRootStore};
 
 use crate::Result;
 use serde::{Serialize, Deserialize};
@@ -5,14 +5,20 @@ use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::fmt;
 use std::hash::Hash;
+use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
 use std::sync::Arc;
 use dashmap::DashMap;
+use metrics::counter;
+use sha2::{Digest, Sha256};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 use crate::state::{
     types::{BlockRef, StateRoot},
     error::StateError,
     transition::StateTransition,
+    batch::BatchConfig,
 };
 use crate::extensions::ExtensionHooks;
 
@@ -260,6 +266,7 @@ pub struct ProofRegistry {
     generators: DashMap<ProofType, Arc<dyn ProofGenerator>>,
     verifiers: DashMap<ProofType, Arc<dyn ProofVerifier>>,
     verification_cache: DashMap<String, VerificationResult>,
+    batch_config: BatchConfig,
 }
 
 impl ProofRegistry {
@@ -269,9 +276,52 @@ impl ProofRegistry {
             generators: DashMap::new(),
             verifiers: DashMap::new(),
             verification_cache: DashMap::new(),
+            batch_config: BatchConfig::default(),
         }
     }
 
+    /// Set batch verification settings
+    pub fn with_batch_config(mut self, config: BatchConfig) -> Self {
+        self.batch_config = config;
+        self
+    }
+
+    /// Get batch verification settings
+    pub fn batch_config(&self) -> &BatchConfig {
+        &self.batch_config
+    }
+
+    /// SHA-256 of the JSON encoding of `value`, or `None` if it cannot be
+    /// encoded
+    fn digest_key<T: Serialize>(value: &T) -> Option<String> {
+        let encoded = serde_json::to_vec(value).ok()?;
+        Some(hex::encode(Sha256::digest(encoded)))
+    }
+
+    /// Cache key identifying a proof and its transition
+    fn cache_key(proof: &StateProof) -> Option<String> {
+        Self::digest_key(&(&proof.transition, &proof.proof))
+    }
+
+    /// Key identifying proofs with identical content, ignoring timestamps
+    fn dedup_key(proof: &StateProof) -> Option<String> {
+        Self::digest_key(&(
+            &proof.transition,
+            &proof.proof.proof_type,
+            &proof.proof.data,
+            &proof.proof.metadata,
+            proof.proof.version,
+        ))
+    }
+
+    /// Get a cached result if it is still fresh
+    fn cached_result(&self, cache_key: &str) -> Option<bool> {
+        self.verification_cache.get(cache_key).and_then(|cached| {
+            let age = SystemTime::now().duration_since(cached.verified_at).unwrap_or_default();
+            (age < Duration::from_secs(300)).then_some(cached.success)
+        })
+    }
+
     /// Register proof generator
     pub fn register_generator(&self, generator: Arc<dyn ProofGenerator>) {
         self.generators.insert(generator.proof_type(), generator);
@@ -323,13 +373,9 @@ impl ProofRegistry {
         }
 
         // Try cache first
-        if params.use_cache {
-            let cache_key = format!("{:?}:{:?}", proof.transition, proof.proof);
-            if let Some(cached) = self.verification_cache.get(&cache_key) {
-                if SystemTime::now().duration_since(cached.verified_at).unwrap() < Duration::from_secs(300) {
-                    return Ok(cached.success);
-                }
-            }
+        let cache_key = Self::cache_key(proof).filter(|_| params.use_cache);
+        if let Some(success) = cache_key.as_deref().and_then(|key| self.cached_result(key)) {
+            return Ok(success);
         }
 
         // Verify using appropriate verifier
@@ -349,26 +395,178 @@ impl ProofRegistry {
         proof.record_verification(verification.clone());
 
         // Update cache
-        if params.use_cache {
-            let cache_key = format!("{:?}:{:?}", proof.transition, proof.proof);
-            self.verification_cache.insert(cache_key, verification);
+        if let Some(key) = cache_key {
+            self.verification_cache.insert(key, verification);
         }
 
         Ok(result)
     }
 
     /// Verify proofs in batch
+    ///
+    /// Fails with the first error in input order. Use
+    /// `verify_batch_results` to get a result for every proof.
     pub async fn verify_batch(
         &self,
         proofs: &mut [StateProof],
         params: &VerificationParams,
         context: Option<&serde_json::Value>,
     ) -> Result<Vec<bool>, StateError> {
+        self.verify_batch_results(proofs, params, context)
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Verify proofs in batch with one result per proof, in input order
+    ///
+    /// Identical proofs are verified once. Remaining proofs are grouped by
+    /// type and handed to their verifier's batch path in chunks of
+    /// `BatchConfig::chunk_size`, with up to `BatchConfig::max_workers`
+    /// chunks running concurrently.
+    pub async fn verify_batch_results(
+        &self,
+        proofs: &mut [StateProof],
+        params: &VerificationParams,
+        context: Option<&serde_json::Value>,
+    ) -> Vec<Result<bool, StateError>> {
+        let mut results: Vec<Option<Result<bool, StateError>>> = vec![None; proofs.len()];
+
+        // Unique proofs to verify, each with the positions it covers
+        let mut unique: Vec<(usize, Option<String>)> = Vec::new();
+        let mut positions: Vec<Vec<usize>> = Vec::new();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        for (i, proof) in proofs.iter().enumerate() {
+            if proof.is_expired() {
+                results[i] = Some(Err(StateError::Internal("Proof has expired".into())));
+                continue;
+            }
+
+            let cache_key = Self::cache_key(proof).filter(|_| params.use_cache);
+            if let Some(success) = cache_key.as_deref().and_then(|key| self.cached_result(key)) {
+                results[i] = Some(Ok(success));
+                continue;
+            }
+
+            if let Some(dedup_key) = Self::dedup_key(proof).filter(|_| self.batch_config.deduplicate) {
+                if let Some(&u) = seen.get(&dedup_key) {
+                    positions[u].push(i);
+                    continue;
+                }
+                seen.insert(dedup_key, unique.len());
+            }
+            unique.push((i, cache_key));
+            positions.push(vec![i]);
+        }
+
+        let duplicates = positions.iter().map(|p| p.len() - 1).sum::<usize>();
+        counter!("frost.proof.batch.verified", unique.len() as u64);
+        counter!("frost.proof.batch.deduplicated", duplicates as u64);
+
+        // Group by proof type so each verifier sees homogeneous chunks
+        let mut groups: HashMap<ProofType, Vec<usize>> = HashMap::new();
+        for (u, (i, _)) in unique.iter().enumerate() {
+            groups.entry(proofs[*i].proof_type().clone()).or_default().push(u);
+        }
+
+        let mut unique_results: Vec<Option<Result<bool, StateError>>> = vec![None; unique.len()];
+        let semaphore = Arc::new(Semaphore::new(self.batch_config.max_workers.max(1)));
+        let mut tasks = JoinSet::new();
+
+        for (proof_type, members) in groups {
+            let Some(verifier) = self.verifiers.get(&proof_type).map(|v| v.value().clone()) else {
+                for u in members {
+                    unique_results[u] = Some(Err(StateError::Internal(format!(
+                        "No verifier found for proof type: {:?}", proof_type
+                    ))));
+                }
+                continue;
+            };
+
+            for chunk in members.chunks(self.batch_config.chunk_size.max(1)) {
+                let indices = chunk.to_vec();
+                let chunk_proofs: Vec<StateProof> = chunk
+                    .iter()
+                    .map(|&u| proofs[unique[u].0].clone())
+                    .collect();
+                let verifier = verifier.clone();
+                let semaphore = semaphore.clone();
+                let params = params.clone();
+                let context = context.cloned();
+
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    let results = Self::verify_chunk(
+                        verifier.as_ref(),
+                        &chunk_proofs,
+                        &params,
+                        context.as_ref(),
+                    ).await;
+                    (indices, results)
+                });
+            }
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok((indices, chunk_results)) = joined {
+                for (u, result) in indices.into_iter().zip(chunk_results) {
+                    unique_results[u] = Some(result);
+                }
+            }
+        }
+
+        // Fan results back out to every position, recording and caching
+        for (u, result) in unique_results.into_iter().enumerate() {
+            let result = result.unwrap_or_else(|| {
+                Err(StateError::Internal("Batch verification worker failed".into()))
+            });
+
+            if let Ok(success) = result {
+                let verification = VerificationResult {
+                    success,
+                    verified_at: SystemTime::now(),
+                    params: params.clone(),
+                    error: None,
+                };
+                for &i in &positions[u] {
+                    proofs[i].record_verification(verification.clone());
+                }
+                if let Some(key) = &unique[u].1 {
+                    self.verification_cache.insert(key.clone(), verification);
+                }
+            }
+
+            for &i in &positions[u] {
+                results[i] = Some(result.clone());
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|r| r.unwrap_or_else(|| Err(StateError::Internal("Proof was not verified".into()))))
+            .collect()
+    }
+
+    /// Verify a homogeneous chunk, falling back to single verification
+    /// when the verifier's batch path fails as a whole
+    async fn verify_chunk(
+        verifier: &dyn ProofVerifier,
+        proofs: &[StateProof],
+        params: &VerificationParams,
+        context: Option<&serde_json::Value>,
+    ) -> Vec<Result<bool, StateError>> {
+        if let Ok(results) = verifier.verify_batch(proofs, params, context).await {
+            if results.len() == proofs.len() {
+                return results.into_iter().map(Ok).collect();
+            }
+        }
+
         let mut results = Vec::with_capacity(proofs.len());
         for proof in proofs {
-            results.push(self.verify_proof(proof, params, context).await?);
+            results.push(verifier.verify_proof(proof, params, context).await);
         }
-        Ok(results)
+        results
     }
 
     /// Clear verification cache
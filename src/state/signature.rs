@@ -0,0 +1,367 @@
+#![allow(unused_variables)]
+
+use std::collections::{BTreeMap, HashSet};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use k256::ecdsa::signature::Verifier as _;
+use parity_scale_codec::{Decode, Encode};
+use serde::{Serialize, Deserialize};
+
+use crate::state::{
+    ChainId,
+    batch::{Ed25519BatchItem, verify_ed25519_batch},
+    error::StateError,
+    proof::{ProofType, ProofVerifier, StateProof, VerificationParams},
+    transition::StateTransition,
+};
+
+/// Domain tag prepended to signed transitions
+const TRANSITION_DOMAIN: &[u8] = b"frost/state-transition/v1";
+
+/// Canonical bytes signed by committee members for a transition
+///
+/// Covers the chain, heights, block hashes, state roots and transition
+/// data. Timestamps and free-form metadata are excluded so independent
+/// signers produce identical messages.
+pub fn canonical_transition_bytes(transition: &StateTransition) -> Vec<u8> {
+    let pre = &transition.pre_state;
+    let post = &transition.post_state;
+    (
+        TRANSITION_DOMAIN,
+        transition.chain_id.to_string(),
+        transition.block_height,
+        (pre.block_ref.number, pre.block_ref.hash, pre.root_hash),
+        (post.block_ref.number, post.block_ref.hash, post.root_hash),
+        transition.transition_proof.clone(),
+    )
+        .encode()
+}
+
+/// Supported signature schemes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+pub enum SignatureScheme {
+    /// Ed25519 over the canonical bytes
+    Ed25519,
+    /// ECDSA secp256k1 over SHA-256 of the canonical bytes, compact low-S
+    Secp256k1,
+}
+
+/// Single signature carried in a proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SignatureEntry {
+    /// Signature scheme
+    pub scheme: SignatureScheme,
+    /// Signer public key (32-byte ed25519 or SEC1 secp256k1)
+    pub public_key: Vec<u8>,
+    /// Signature bytes (64 bytes for both schemes)
+    pub signature: Vec<u8>,
+}
+
+/// Encoded payload of a signature `ProofData`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SignatureProof {
+    /// Collected signatures
+    pub signatures: Vec<SignatureEntry>,
+}
+
+impl SignatureProof {
+    /// Decode from `ProofData::data`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, StateError> {
+        Self::decode(&mut &bytes[..])
+            .map_err(|e| StateError::InvalidProof(format!("Failed to decode signature proof: {}", e)))
+    }
+
+    /// Encode into `ProofData::data`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// Committee member
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signer {
+    /// Signature scheme
+    pub scheme: SignatureScheme,
+    /// Public key bytes
+    pub public_key: Vec<u8>,
+    /// Voting weight for weighted policies
+    pub weight: u64,
+}
+
+/// Acceptance policy for a signer set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignaturePolicy {
+    /// The set's only signer must sign
+    Single,
+    /// At least `required` distinct signers must sign
+    MultiSig { required: usize },
+    /// Signers must reach at least `min_weight` combined weight
+    Weighted { min_weight: u64 },
+}
+
+/// Signer set and policy active from a given height
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerSet {
+    /// Committee members
+    pub signers: Vec<Signer>,
+    /// Acceptance policy
+    pub policy: SignaturePolicy,
+}
+
+impl SignerSet {
+    /// Check the set is consistent with its policy
+    pub fn validate(&self) -> Result<(), StateError> {
+        let mut keys = HashSet::new();
+        for signer in &self.signers {
+            if !keys.insert((signer.scheme, signer.public_key.as_slice())) {
+                return Err(StateError::InvalidProof("Duplicate signer in signer set".into()));
+            }
+        }
+
+        match self.policy {
+            SignaturePolicy::Single if self.signers.len() != 1 => Err(StateError::InvalidProof(
+                "Single signer policy requires exactly one signer".into(),
+            )),
+            SignaturePolicy::MultiSig { required } if required == 0 || required > self.signers.len() => {
+                Err(StateError::InvalidProof(format!(
+                    "Invalid multisig threshold {} for {} signers", required, self.signers.len()
+                )))
+            }
+            SignaturePolicy::Weighted { min_weight } => {
+                let total = self
+                    .signers
+                    .iter()
+                    .try_fold(0u64, |total, s| total.checked_add(s.weight))
+                    .ok_or_else(|| StateError::InvalidProof("Signer weights overflow".into()))?;
+                if min_weight == 0 || min_weight > total {
+                    return Err(StateError::InvalidProof(format!(
+                        "Invalid weight threshold {} for total weight {}", min_weight, total
+                    )));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn find(&self, scheme: SignatureScheme, public_key: &[u8]) -> Option<usize> {
+        self.signers
+            .iter()
+            .position(|s| s.scheme == scheme && s.public_key == public_key)
+    }
+
+    /// Check whether the given signer indices satisfy the policy
+    fn is_satisfied(&self, signed: &HashSet<usize>) -> bool {
+        match self.policy {
+            SignaturePolicy::Single => signed.contains(&0),
+            SignaturePolicy::MultiSig { required } => signed.len() >= required,
+            SignaturePolicy::Weighted { min_weight } => {
+                signed
+                    .iter()
+                    .try_fold(0u64, |weight, &i| weight.checked_add(self.signers[i].weight))
+                    .is_some_and(|weight| weight >= min_weight)
+            }
+        }
+    }
+}
+
+/// Verifier for committee-signed transitions (`ProofType::Signature`)
+///
+/// Signer sets are configured per chain and keyed by activation height.
+/// A transition is checked against the set active at its post-state
+/// height. Signatures from unknown keys, duplicate signers and invalid
+/// signatures do not count towards the policy.
+pub struct SignatureVerifier {
+    chains: DashMap<ChainId, BTreeMap<u64, SignerSet>>,
+}
+
+impl Default for SignatureVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignatureVerifier {
+    /// Create verifier with no configured chains
+    pub fn new() -> Self {
+        Self {
+            chains: DashMap::new(),
+        }
+    }
+
+    /// Register a signer set taking effect at `activation_height`
+    pub fn register_signer_set(
+        &self,
+        chain_id: ChainId,
+        activation_height: u64,
+        set: SignerSet,
+    ) -> Result<(), StateError> {
+        set.validate()?;
+        self.chains.entry(chain_id).or_default().insert(activation_height, set);
+        Ok(())
+    }
+
+    /// Remove all signer sets for a chain
+    pub fn remove_chain(&self, chain_id: &ChainId) -> bool {
+        self.chains.remove(chain_id).is_some()
+    }
+
+    /// Get the signer set active at `height`
+    pub fn signer_set_at(&self, chain_id: &ChainId, height: u64) -> Option<SignerSet> {
+        self.chains
+            .get(chain_id)
+            .and_then(|sets| sets.range(..=height).next_back().map(|(_, set)| set.clone()))
+    }
+
+    fn active_set(&self, transition: &StateTransition) -> Result<SignerSet, StateError> {
+        let height = transition.post_state.block_ref.number;
+        self.signer_set_at(&transition.chain_id, height).ok_or_else(|| {
+            StateError::InvalidProof(format!(
+                "No signer set for chain {} at height {}", transition.chain_id, height
+            ))
+        })
+    }
+
+    fn decode(proof: &StateProof) -> Result<SignatureProof, StateError> {
+        if proof.proof_type() != &ProofType::Signature {
+            return Err(StateError::InvalidProof(format!(
+                "Unsupported proof type: {:?}", proof.proof_type()
+            )));
+        }
+        SignatureProof::from_bytes(&proof.proof.data)
+    }
+
+    /// Signature entries from members of `set`, keyed by entry index
+    ///
+    /// A member may appear more than once; callers count each member once
+    /// after verification so an invalid entry cannot hide a valid one.
+    fn candidates(set: &SignerSet, decoded: &SignatureProof) -> Vec<(usize, usize)> {
+        decoded
+            .signatures
+            .iter()
+            .enumerate()
+            .filter_map(|(entry, sig)| Some((entry, set.find(sig.scheme, &sig.public_key)?)))
+            .collect()
+    }
+
+    fn verify_secp256k1(message: &[u8], entry: &SignatureEntry) -> bool {
+        let Ok(key) = k256::ecdsa::VerifyingKey::from_sec1_bytes(&entry.public_key) else {
+            return false;
+        };
+        let Ok(signature) = k256::ecdsa::Signature::from_slice(&entry.signature) else {
+            return false;
+        };
+        key.verify(message, &signature).is_ok()
+    }
+
+    /// Single ed25519 check with the same semantics as `verify_batch`
+    fn verify_ed25519(message: &[u8], entry: &SignatureEntry) -> bool {
+        let (Ok(public_key), Ok(signature)) = (
+            <[u8; 32]>::try_from(entry.public_key.as_slice()),
+            <[u8; 64]>::try_from(entry.signature.as_slice()),
+        ) else {
+            return false;
+        };
+        verify_ed25519_batch(&[Ed25519BatchItem { message: message.to_vec(), signature, public_key }])[0]
+    }
+}
+
+#[async_trait]
+impl ProofVerifier for SignatureVerifier {
+    fn supported_types(&self) -> Vec<ProofType> {
+        vec![ProofType::Signature]
+    }
+
+    async fn verify_proof(
+        &self,
+        proof: &StateProof,
+        params: &VerificationParams,
+        context: Option<&serde_json::Value>,
+    ) -> Result<bool, StateError> {
+        let decoded = Self::decode(proof)?;
+        let set = self.active_set(&proof.transition)?;
+        let message = canonical_transition_bytes(&proof.transition);
+
+        let signed: HashSet<usize> = Self::candidates(&set, &decoded)
+            .into_iter()
+            .filter(|&(entry, _)| {
+                let entry = &decoded.signatures[entry];
+                match entry.scheme {
+                    SignatureScheme::Ed25519 => Self::verify_ed25519(&message, entry),
+                    SignatureScheme::Secp256k1 => Self::verify_secp256k1(&message, entry),
+                }
+            })
+            .map(|(_, member)| member)
+            .collect();
+
+        Ok(set.is_satisfied(&signed))
+    }
+
+    /// Verify many proofs with a single ed25519 batch across all of them
+    async fn verify_batch(
+        &self,
+        proofs: &[StateProof],
+        params: &VerificationParams,
+        context: Option<&serde_json::Value>,
+    ) -> Result<Vec<bool>, StateError> {
+        struct Pending {
+            set: SignerSet,
+            ed25519: Vec<(usize, usize)>,
+            signed: HashSet<usize>,
+        }
+
+        let mut pending = Vec::with_capacity(proofs.len());
+        let mut batch = Vec::new();
+
+        for proof in proofs {
+            let decoded = Self::decode(proof)?;
+            let set = self.active_set(&proof.transition)?;
+            let message = canonical_transition_bytes(&proof.transition);
+
+            let mut ed25519 = Vec::new();
+            let mut signed = HashSet::new();
+            for (entry, member) in Self::candidates(&set, &decoded) {
+                let entry = &decoded.signatures[entry];
+                match entry.scheme {
+                    SignatureScheme::Ed25519 => {
+                        match (
+                            <[u8; 32]>::try_from(entry.public_key.as_slice()),
+                            <[u8; 64]>::try_from(entry.signature.as_slice()),
+                        ) {
+                            (Ok(public_key), Ok(signature)) => {
+                                ed25519.push((batch.len(), member));
+                                batch.push(Ed25519BatchItem {
+                                    message: message.clone(),
+                                    signature,
+                                    public_key,
+                                });
+                            }
+                            _ => continue,
+                        }
+                    }
+                    SignatureScheme::Secp256k1 => {
+                        if Self::verify_secp256k1(&message, entry) {
+                            signed.insert(member);
+                        }
+                    }
+                }
+            }
+            pending.push(Pending { set, ed25519, signed });
+        }
+
+        let batch_results = verify_ed25519_batch(&batch);
+
+        Ok(pending
+            .into_iter()
+            .map(|mut p| {
+                for (item, member) in &p.ed25519 {
+                    if batch_results[*item] {
+                        p.signed.insert(*member);
+                    }
+                }
+                p.set.is_satisfied(&p.signed)
+            })
+            .collect())
+    }
+}
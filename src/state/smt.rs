@@ -0,0 +1,615 @@
+#![allow(unused_variables)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use parity_scale_codec::{Decode, Encode};
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use crate::state::{
+    error::StateError,
+    proof::{ProofData, ProofGenerator, ProofType, ProofVerifier, StateProof, VerificationParams},
+    store::StateRootStore,
+    transition::StateTransition,
+};
+
+/// 32-byte node, key and value hash
+pub type NodeHash = [u8; 32];
+
+/// Hash of an empty subtree
+pub const EMPTY_HASH: NodeHash = [0; 32];
+
+/// Name used for `ProofType::Custom` sparse Merkle proofs
+pub const SMT_PROOF_TYPE: &str = "sparse-merkle";
+
+/// Current encoding version of sparse Merkle proofs
+pub const SMT_PROOF_VERSION: u32 = 2;
+
+/// Tree depth in bits
+const TREE_DEPTH: usize = 256;
+
+/// Domain separation prefixes
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Proof type handled by the sparse Merkle generator and verifier
+pub fn smt_proof_type() -> ProofType {
+    ProofType::Custom(SMT_PROOF_TYPE.to_string())
+}
+
+/// Hash a raw key into its tree path
+pub fn hash_key(key: &[u8]) -> NodeHash {
+    Sha256::digest(key).into()
+}
+
+/// Hash a raw value into its leaf commitment
+pub fn hash_value(value: &[u8]) -> NodeHash {
+    Sha256::digest(value).into()
+}
+
+fn leaf_hash(key_hash: &NodeHash, value_hash: &NodeHash) -> NodeHash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(key_hash);
+    hasher.update(value_hash);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &NodeHash, right: &NodeHash) -> NodeHash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Bit of `key` at `depth`, counted from the most significant bit
+fn bit(key: &NodeHash, depth: usize) -> bool {
+    (key[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+/// Key range covered by the subtree at `depth` containing `key`
+fn subtree_bounds(key: &NodeHash, depth: usize) -> (NodeHash, NodeHash) {
+    let mut lo = *key;
+    let mut hi = *key;
+    for i in depth..TREE_DEPTH {
+        let mask = 1u8 << (7 - i % 8);
+        lo[i / 8] &= !mask;
+        hi[i / 8] |= mask;
+    }
+    (lo, hi)
+}
+
+/// Number of leading bits shared by two keys
+fn common_prefix_len(a: &NodeHash, b: &NodeHash) -> usize {
+    (0..TREE_DEPTH).find(|&i| bit(a, i) != bit(b, i)).unwrap_or(TREE_DEPTH)
+}
+
+/// Leaf carried by a proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SmtLeaf {
+    /// Hashed key of the leaf
+    pub key_hash: NodeHash,
+    /// Hashed value of the leaf
+    pub value_hash: NodeHash,
+}
+
+impl SmtLeaf {
+    /// Hash of this leaf node
+    pub fn hash(&self) -> NodeHash {
+        leaf_hash(&self.key_hash, &self.value_hash)
+    }
+}
+
+/// Compact inclusion or non-inclusion proof
+///
+/// Siblings are ordered from the root downwards. Empty siblings are
+/// omitted and marked by a cleared bit in `bitmap`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SparseMerkleProof {
+    /// Leaf found at the end of the path, if any
+    pub leaf: Option<SmtLeaf>,
+    /// Number of levels traversed
+    pub depth: u16,
+    /// One bit per level, set when the sibling at that level is non-empty
+    pub bitmap: Vec<u8>,
+    /// Non-empty sibling hashes
+    pub siblings: Vec<NodeHash>,
+}
+
+impl SparseMerkleProof {
+    /// Expand the compact sibling list to one hash per level
+    fn expand_siblings(&self) -> Result<Vec<NodeHash>, StateError> {
+        let depth = self.depth as usize;
+        if depth > TREE_DEPTH || self.bitmap.len() != depth.div_ceil(8) {
+            return Err(StateError::InvalidProof("Malformed sparse Merkle proof bitmap".into()));
+        }
+
+        let mut compact = self.siblings.iter();
+        let mut siblings = Vec::with_capacity(depth);
+        for level in 0..depth {
+            if (self.bitmap[level / 8] >> (7 - level % 8)) & 1 == 1 {
+                let sibling = compact.next().ok_or_else(|| {
+                    StateError::InvalidProof("Sparse Merkle proof is missing siblings".into())
+                })?;
+                siblings.push(*sibling);
+            } else {
+                siblings.push(EMPTY_HASH);
+            }
+        }
+
+        if compact.next().is_some() {
+            return Err(StateError::InvalidProof("Sparse Merkle proof has extra siblings".into()));
+        }
+        Ok(siblings)
+    }
+
+    /// Compute the root implied by this proof for `key_hash`
+    ///
+    /// `value_hash` is the expected value, or `None` to prove absence.
+    pub fn compute_root(
+        &self,
+        key_hash: &NodeHash,
+        value_hash: Option<&NodeHash>,
+    ) -> Result<NodeHash, StateError> {
+        let siblings = self.expand_siblings()?;
+        let depth = siblings.len();
+
+        let mut current = match (value_hash, &self.leaf) {
+            (Some(value_hash), Some(leaf)) => {
+                if &leaf.key_hash != key_hash || &leaf.value_hash != value_hash {
+                    return Err(StateError::InvalidProof("Proof leaf does not match key and value".into()));
+                }
+                leaf.hash()
+            }
+            (Some(_), None) => {
+                return Err(StateError::InvalidProof("Inclusion proof is missing its leaf".into()));
+            }
+            (None, Some(leaf)) => {
+                if &leaf.key_hash == key_hash {
+                    return Err(StateError::InvalidProof("Non-inclusion proof ends at the queried key".into()));
+                }
+                if common_prefix_len(&leaf.key_hash, key_hash) < depth {
+                    return Err(StateError::InvalidProof("Proof leaf is not on the queried path".into()));
+                }
+                leaf.hash()
+            }
+            (None, None) => EMPTY_HASH,
+        };
+
+        for (level, sibling) in siblings.iter().enumerate().rev() {
+            current = if bit(key_hash, level) {
+                node_hash(sibling, &current)
+            } else {
+                node_hash(&current, sibling)
+            };
+        }
+
+        Ok(current)
+    }
+
+    /// Check that `key` maps to `value` under `root`
+    pub fn verify_inclusion(&self, root: &NodeHash, key: &[u8], value: &[u8]) -> bool {
+        self.compute_root(&hash_key(key), Some(&hash_value(value)))
+            .map(|computed| &computed == root)
+            .unwrap_or(false)
+    }
+
+    /// Check that `key` is absent under `root`
+    pub fn verify_non_inclusion(&self, root: &NodeHash, key: &[u8]) -> bool {
+        self.compute_root(&hash_key(key), None)
+            .map(|computed| &computed == root)
+            .unwrap_or(false)
+    }
+}
+
+/// Sparse Merkle tree over 256-bit hashed keys
+///
+/// Subtrees holding a single leaf collapse into that leaf, so paths are only
+/// as deep as needed to separate keys. Each update only rehashes the path
+/// from the changed leaf to the root.
+#[derive(Debug, Clone)]
+pub struct SparseMerkleTree {
+    /// Leaf value hashes keyed by hashed key
+    leaves: BTreeMap<NodeHash, NodeHash>,
+    /// Internal node hashes keyed by depth and path prefix
+    nodes: HashMap<(u16, NodeHash), NodeHash>,
+    /// Current root
+    root: NodeHash,
+}
+
+impl Default for SparseMerkleTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SparseMerkleTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        Self {
+            leaves: BTreeMap::new(),
+            nodes: HashMap::new(),
+            root: EMPTY_HASH,
+        }
+    }
+
+    /// Current root hash
+    pub fn root(&self) -> NodeHash {
+        self.root
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Get the value hash stored for `key`
+    pub fn get(&self, key: &[u8]) -> Option<NodeHash> {
+        self.leaves.get(&hash_key(key)).copied()
+    }
+
+    /// Insert or update a single key and return the new root
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> NodeHash {
+        self.update_batch([(key, Some(value))])
+    }
+
+    /// Remove a single key and return the new root
+    pub fn remove(&mut self, key: &[u8]) -> NodeHash {
+        self.update_batch([(key, None::<&[u8]>)])
+    }
+
+    /// Apply a batch of updates and return the new root
+    ///
+    /// A `None` value removes the key. Later updates to the same key win.
+    pub fn update_batch<I, K, V>(&mut self, updates: I) -> NodeHash
+    where
+        I: IntoIterator<Item = (K, Option<V>)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.update_hashed_batch(updates.into_iter().map(|(key, value)| {
+            (hash_key(key.as_ref()), value.map(|v| hash_value(v.as_ref())))
+        }))
+    }
+
+    /// Apply a batch of updates given as pre-hashed keys and values
+    pub fn update_hashed_batch<I>(&mut self, updates: I) -> NodeHash
+    where
+        I: IntoIterator<Item = (NodeHash, Option<NodeHash>)>,
+    {
+        for (key_hash, value_hash) in updates {
+            match value_hash {
+                Some(value_hash) => {
+                    self.leaves.insert(key_hash, value_hash);
+                }
+                None => {
+                    self.leaves.remove(&key_hash);
+                }
+            }
+            self.root = self.update_path(&key_hash, 0);
+        }
+        self.root
+    }
+
+    /// Recompute the internal nodes on the path to `key_hash` below `depth`
+    ///
+    /// Only subtrees containing the changed key are touched, so an update
+    /// costs one hash per level of the path. Node entries below the point
+    /// where the path collapses into a single leaf are dropped.
+    fn update_path(&mut self, key_hash: &NodeHash, depth: usize) -> NodeHash {
+        let (lo, hi) = subtree_bounds(key_hash, depth);
+        let mut range = self.leaves.range(lo..=hi);
+        let collapsed = match (range.next(), range.next()) {
+            (None, _) => Some(EMPTY_HASH),
+            (Some((k, v)), None) => Some(leaf_hash(k, v)),
+            _ => None,
+        };
+
+        if let Some(hash) = collapsed {
+            for level in depth..TREE_DEPTH {
+                let (prefix, _) = subtree_bounds(key_hash, level);
+                if self.nodes.remove(&(level as u16, prefix)).is_none() {
+                    break;
+                }
+            }
+            return hash;
+        }
+
+        let mut sibling_path = *key_hash;
+        sibling_path[depth / 8] ^= 1 << (7 - depth % 8);
+        let child = self.update_path(key_hash, depth + 1);
+        let sibling = self.subtree_hash(&sibling_path, depth + 1);
+        let hash = if bit(key_hash, depth) {
+            node_hash(&sibling, &child)
+        } else {
+            node_hash(&child, &sibling)
+        };
+        self.nodes.insert((depth as u16, lo), hash);
+        hash
+    }
+
+    /// Hash of the subtree at `depth` containing `key_hash`
+    fn subtree_hash(&self, key_hash: &NodeHash, depth: usize) -> NodeHash {
+        let (lo, hi) = subtree_bounds(key_hash, depth);
+        let mut range = self.leaves.range(lo..=hi);
+        match (range.next(), range.next()) {
+            (None, _) => EMPTY_HASH,
+            (Some((k, v)), None) => leaf_hash(k, v),
+            _ => self.nodes.get(&(depth as u16, lo)).copied().unwrap_or(EMPTY_HASH),
+        }
+    }
+
+    /// Generate an inclusion or non-inclusion proof for `key`
+    pub fn prove(&self, key: &[u8]) -> SparseMerkleProof {
+        self.prove_hashed(&hash_key(key))
+    }
+
+    /// Generate a proof for a pre-hashed key
+    pub fn prove_hashed(&self, key_hash: &NodeHash) -> SparseMerkleProof {
+        let mut bitmap = Vec::new();
+        let mut siblings = Vec::new();
+        let mut depth = 0;
+
+        let leaf = loop {
+            let (lo, hi) = subtree_bounds(key_hash, depth);
+            let mut range = self.leaves.range(lo..=hi);
+            match (range.next(), range.next()) {
+                (None, _) => break None,
+                (Some((k, v)), None) => break Some(SmtLeaf { key_hash: *k, value_hash: *v }),
+                _ => {
+                    let mut sibling_path = *key_hash;
+                    sibling_path[depth / 8] ^= 1 << (7 - depth % 8);
+                    let sibling = self.subtree_hash(&sibling_path, depth + 1);
+
+                    if depth % 8 == 0 {
+                        bitmap.push(0);
+                    }
+                    if sibling != EMPTY_HASH {
+                        bitmap[depth / 8] |= 1 << (7 - depth % 8);
+                        siblings.push(sibling);
+                    }
+                    depth += 1;
+                }
+            }
+        };
+
+        SparseMerkleProof {
+            leaf,
+            depth: depth as u16,
+            bitmap,
+            siblings,
+        }
+    }
+}
+
+/// Single key witness inside a sparse Merkle state proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SmtStateWitness {
+    /// Hashed key being proven
+    pub key_hash: NodeHash,
+    /// Hashed value, or `None` for a non-inclusion witness
+    pub value_hash: Option<NodeHash>,
+    /// Proof against the post-state root
+    pub proof: SparseMerkleProof,
+}
+
+impl SmtStateWitness {
+    /// Check this witness against `root`
+    pub fn verify(&self, root: &NodeHash) -> Result<bool, StateError> {
+        let computed = self.proof.compute_root(&self.key_hash, self.value_hash.as_ref())?;
+        Ok(&computed == root)
+    }
+}
+
+/// Encoded payload of a sparse Merkle `ProofData`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct SmtStateProof {
+    /// Pre-state root of the transition the witnesses were generated for
+    ///
+    /// Prover supplied; it is not evidence that the pre-state is genuine.
+    pub pre_root: NodeHash,
+    /// Key witnesses against the transition's post-state root
+    pub witnesses: Vec<SmtStateWitness>,
+}
+
+impl SmtStateProof {
+    /// Decode from `ProofData::data`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, StateError> {
+        Self::decode(&mut &bytes[..])
+            .map_err(|e| StateError::InvalidProof(format!("Failed to decode sparse Merkle proof: {}", e)))
+    }
+
+    /// Encode into `ProofData::data`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// Proof generator backed by a shared sparse Merkle tree
+///
+/// The keys to prove are taken from the `keys` array (hex encoded) of the
+/// generation context and at least one is required. The tree root must match
+/// the transition's post-state.
+pub struct SmtProofGenerator {
+    tree: Arc<RwLock<SparseMerkleTree>>,
+}
+
+impl SmtProofGenerator {
+    /// Create generator over a shared tree
+    pub fn new(tree: Arc<RwLock<SparseMerkleTree>>) -> Self {
+        Self { tree }
+    }
+
+    /// Get the underlying tree
+    pub fn tree(&self) -> Arc<RwLock<SparseMerkleTree>> {
+        self.tree.clone()
+    }
+
+    fn context_keys(context: Option<&serde_json::Value>) -> Result<Vec<Vec<u8>>, StateError> {
+        let keys = context
+            .and_then(|c| c.get("keys"))
+            .ok_or_else(|| StateError::InvalidProof("Context must list the `keys` to prove".into()))?
+            .as_array()
+            .ok_or_else(|| StateError::InvalidProof("Context `keys` must be an array".into()))?;
+        if keys.is_empty() {
+            return Err(StateError::InvalidProof("Context `keys` must not be empty".into()));
+        }
+
+        keys.iter()
+            .map(|key| {
+                key.as_str()
+                    .ok_or_else(|| StateError::InvalidProof("Context keys must be hex strings".into()))
+                    .and_then(|s| {
+                        hex::decode(s.trim_start_matches("0x"))
+                            .map_err(|e| StateError::InvalidProof(format!("Invalid hex key: {}", e)))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ProofGenerator for SmtProofGenerator {
+    fn proof_type(&self) -> ProofType {
+        smt_proof_type()
+    }
+
+    async fn generate_proof(
+        &self,
+        transition: &StateTransition,
+        context: Option<&serde_json::Value>,
+    ) -> Result<ProofData, StateError> {
+        let keys = Self::context_keys(context)?;
+        let tree = self.tree.read().await;
+
+        let root = tree.root();
+        if root != transition.post_state.root_hash {
+            return Err(StateError::RootMismatch {
+                block_ref: transition.post_state.block_ref.clone(),
+                expected: hex::encode(transition.post_state.root_hash),
+                actual: hex::encode(root),
+            });
+        }
+
+        let witnesses = keys
+            .iter()
+            .map(|key| {
+                let key_hash = hash_key(key);
+                SmtStateWitness {
+                    key_hash,
+                    value_hash: tree.leaves.get(&key_hash).copied(),
+                    proof: tree.prove_hashed(&key_hash),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ProofData {
+            proof_type: smt_proof_type(),
+            data: SmtStateProof {
+                pre_root: transition.pre_state.root_hash,
+                witnesses,
+            }
+            .to_bytes(),
+            metadata: Some(json!({
+                "root": hex::encode(root),
+                "keys": keys.len(),
+            })),
+            generated_at: SystemTime::now(),
+            expires_at: None,
+            version: SMT_PROOF_VERSION,
+        })
+    }
+}
+
+/// Verifier for sparse Merkle state proofs
+///
+/// A proof needs at least one witness and every witness must resolve to the
+/// transition's post-state root. The witnesses say nothing about the
+/// pre-state: the `pre_root` carried in the proof is supplied by the prover
+/// and only checked for consistency with the transition. To reject
+/// transitions from an unknown or forged pre-state, attach a
+/// `StateRootStore` with `with_root_store`; the pre-state root must then be
+/// a stored root of the chain.
+#[derive(Default, Clone)]
+pub struct SmtProofVerifier {
+    roots: Option<Arc<dyn StateRootStore>>,
+}
+
+impl std::fmt::Debug for SmtProofVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtProofVerifier")
+            .field("root_store", &self.roots.is_some())
+            .finish()
+    }
+}
+
+impl SmtProofVerifier {
+    /// Create new verifier
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check transition pre-states against trusted roots
+    pub fn with_root_store(mut self, roots: Arc<dyn StateRootStore>) -> Self {
+        self.roots = Some(roots);
+        self
+    }
+}
+
+#[async_trait]
+impl ProofVerifier for SmtProofVerifier {
+    fn supported_types(&self) -> Vec<ProofType> {
+        vec![smt_proof_type()]
+    }
+
+    async fn verify_proof(
+        &self,
+        proof: &StateProof,
+        params: &VerificationParams,
+        context: Option<&serde_json::Value>,
+    ) -> Result<bool, StateError> {
+        if proof.proof_type() != &smt_proof_type() {
+            return Err(StateError::InvalidProof(format!(
+                "Unsupported proof type: {:?}", proof.proof_type()
+            )));
+        }
+        if proof.proof.version != SMT_PROOF_VERSION {
+            return Err(StateError::InvalidProof(format!(
+                "Unsupported sparse Merkle proof version: {}", proof.proof.version
+            )));
+        }
+
+        let decoded = SmtStateProof::from_bytes(&proof.proof.data)?;
+        if decoded.witnesses.is_empty() {
+            return Err(StateError::InvalidProof("Sparse Merkle proof has no witnesses".into()));
+        }
+        if decoded.pre_root != proof.transition.pre_state.root_hash {
+            return Ok(false);
+        }
+        if let Some(roots) = &self.roots {
+            match roots.check_transition(&proof.transition).await {
+                Ok(true) => {}
+                Ok(false) | Err(StateError::RootMismatch { .. }) => return Ok(false),
+                Err(e) => return Err(e),
+            }
+        }
+        let root = proof.transition.post_state.root_hash;
+        for witness in &decoded.witnesses {
+            if !witness.verify(&root)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
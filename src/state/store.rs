@@ -0,0 +1,445 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::jsonl::{JsonlLog, LogError};
+use crate::state::{
+    ChainId,
+    error::StateError,
+    transition::StateTransition,
+    types::StateRoot,
+};
+
+/// State root with storage metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredRoot {
+    /// The state root
+    pub root: StateRoot,
+    /// Whether the root's block is finalized
+    pub finalized: bool,
+    /// When the root was stored
+    pub stored_at: SystemTime,
+}
+
+impl StoredRoot {
+    /// Height of the root's block
+    pub fn height(&self) -> u64 {
+        self.root.block_ref.number
+    }
+}
+
+/// Historical store of verified state roots
+///
+/// Roots are indexed by `(ChainId, height)` and by root hash. Finality is
+/// monotonic per chain: finalizing a height finalizes everything below it,
+/// and a finalized root cannot be replaced.
+#[async_trait]
+pub trait StateRootStore: Send + Sync {
+    /// Store a root, replacing any unfinalized root at the same height
+    async fn insert(&self, root: StateRoot, finalized: bool) -> Result<(), StateError>;
+
+    /// Mark the root at `height` and all roots below it as finalized
+    async fn mark_finalized(&self, chain_id: &ChainId, height: u64) -> Result<(), StateError>;
+
+    /// Get the root at a height
+    async fn get(&self, chain_id: &ChainId, height: u64) -> Result<Option<StoredRoot>, StateError>;
+
+    /// Get all roots with the given root hash
+    async fn get_by_hash(&self, root_hash: &[u8; 32]) -> Result<Vec<StoredRoot>, StateError>;
+
+    /// Get the highest finalized root of a chain
+    async fn latest_finalized(&self, chain_id: &ChainId) -> Result<Option<StoredRoot>, StateError>;
+
+    /// Get roots with heights in `from..=to`, ascending
+    async fn range(&self, chain_id: &ChainId, from: u64, to: u64) -> Result<Vec<StoredRoot>, StateError>;
+
+    /// Get the closest stored root at least `depth` blocks below `height`
+    async fn ancestor(
+        &self,
+        chain_id: &ChainId,
+        height: u64,
+        depth: u64,
+    ) -> Result<Option<StoredRoot>, StateError>;
+
+    /// Remove roots below `watermark`, never beyond the latest finalized height
+    ///
+    /// Returns the number of pruned roots.
+    async fn prune_below(&self, chain_id: &ChainId, watermark: u64) -> Result<usize, StateError>;
+
+    /// Check a transition against known roots
+    ///
+    /// Returns `Ok(true)` if the pre-state root is known and every known
+    /// root matches, `Ok(false)` if the pre-state root is unknown, and
+    /// `StateError::RootMismatch` if a known root disagrees.
+    async fn check_transition(&self, transition: &StateTransition) -> Result<bool, StateError> {
+        let chain_id = &transition.chain_id;
+        let post = &transition.post_state;
+        if let Some(known) = self.get(chain_id, post.block_ref.number).await? {
+            check_root(&known, post)?;
+        }
+
+        let pre = &transition.pre_state;
+        match self.get(chain_id, pre.block_ref.number).await? {
+            Some(known) => check_root(&known, pre).map(|_| true),
+            None => Ok(false),
+        }
+    }
+}
+
+fn check_root(known: &StoredRoot, claimed: &StateRoot) -> Result<(), StateError> {
+    if known.root.root_hash != claimed.root_hash {
+        return Err(StateError::RootMismatch {
+            block_ref: claimed.block_ref.clone(),
+            expected: hex::encode(known.root.root_hash),
+            actual: hex::encode(claimed.root_hash),
+        });
+    }
+    Ok(())
+}
+
+/// Store mutation, also used as the file log record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum StoreOp {
+    Insert { root: StoredRoot },
+    Finalize { chain_id: ChainId, height: u64 },
+    Prune { chain_id: ChainId, watermark: u64 },
+}
+
+#[derive(Debug, Default)]
+struct ChainRoots {
+    by_height: BTreeMap<u64, StoredRoot>,
+    finalized_height: Option<u64>,
+}
+
+/// In-memory root index shared by both store implementations
+#[derive(Debug, Default)]
+struct RootIndex {
+    chains: HashMap<ChainId, ChainRoots>,
+    by_hash: HashMap<[u8; 32], BTreeSet<(ChainId, u64)>>,
+}
+
+impl RootIndex {
+    /// Check an operation can be applied without changing state
+    fn validate(&self, op: &StoreOp) -> Result<(), StateError> {
+        match op {
+            StoreOp::Insert { root } => {
+                let chain = self.chains.get(&root.root.block_ref.chain_id);
+                if let Some(existing) = chain.and_then(|c| c.by_height.get(&root.height())) {
+                    if existing.finalized && existing.root.root_hash != root.root.root_hash {
+                        return Err(StateError::RootMismatch {
+                            block_ref: root.root.block_ref.clone(),
+                            expected: hex::encode(existing.root.root_hash),
+                            actual: hex::encode(root.root.root_hash),
+                        });
+                    }
+                }
+                Ok(())
+            }
+            StoreOp::Finalize { chain_id, height } => {
+                let known = self.chains
+                    .get(chain_id)
+                    .is_some_and(|c| c.by_height.contains_key(height));
+                if !known {
+                    return Err(StateError::InvalidBlockRef(format!(
+                        "No state root for chain {} at height {}", chain_id, height
+                    )));
+                }
+                Ok(())
+            }
+            StoreOp::Prune { .. } => Ok(()),
+        }
+    }
+
+    /// Apply a validated operation, returning the number of removed roots
+    fn apply(&mut self, op: StoreOp) -> usize {
+        match op {
+            StoreOp::Insert { mut root } => {
+                let chain_id = root.root.block_ref.chain_id.clone();
+                let height = root.height();
+                let chain = self.chains.entry(chain_id.clone()).or_default();
+                if chain.finalized_height.is_some_and(|f| height <= f) {
+                    root.finalized = true;
+                }
+
+                if let Some(previous) = chain.by_height.insert(height, root.clone()) {
+                    root.finalized |= previous.finalized;
+                    Self::unindex(&mut self.by_hash, &previous, &chain_id);
+                    chain.by_height.insert(height, root.clone());
+                }
+                self.by_hash
+                    .entry(root.root.root_hash)
+                    .or_default()
+                    .insert((chain_id.clone(), height));
+
+                if root.finalized {
+                    Self::finalize(chain, height);
+                }
+                0
+            }
+            StoreOp::Finalize { chain_id, height } => {
+                if let Some(chain) = self.chains.get_mut(&chain_id) {
+                    Self::finalize(chain, height);
+                }
+                0
+            }
+            StoreOp::Prune { chain_id, watermark } => {
+                let Some(chain) = self.chains.get_mut(&chain_id) else {
+                    return 0;
+                };
+                let Some(finalized) = chain.finalized_height else {
+                    return 0;
+                };
+
+                let kept = chain.by_height.split_off(&watermark.min(finalized));
+                let pruned = std::mem::replace(&mut chain.by_height, kept);
+                for root in pruned.values() {
+                    Self::unindex(&mut self.by_hash, root, &chain_id);
+                }
+                pruned.len()
+            }
+        }
+    }
+
+    fn finalize(chain: &mut ChainRoots, height: u64) {
+        if chain.finalized_height.is_some_and(|f| f >= height) {
+            return;
+        }
+        for (_, root) in chain.by_height.range_mut(..=height) {
+            root.finalized = true;
+        }
+        chain.finalized_height = Some(height);
+    }
+
+    fn unindex(by_hash: &mut HashMap<[u8; 32], BTreeSet<(ChainId, u64)>>, root: &StoredRoot, chain_id: &ChainId) {
+        if let Some(entries) = by_hash.get_mut(&root.root.root_hash) {
+            entries.remove(&(chain_id.clone(), root.height()));
+            if entries.is_empty() {
+                by_hash.remove(&root.root.root_hash);
+            }
+        }
+    }
+
+    fn get(&self, chain_id: &ChainId, height: u64) -> Option<StoredRoot> {
+        self.chains.get(chain_id)?.by_height.get(&height).cloned()
+    }
+
+    fn get_by_hash(&self, root_hash: &[u8; 32]) -> Vec<StoredRoot> {
+        self.by_hash
+            .get(root_hash)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|(chain_id, height)| self.get(chain_id, *height))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn latest_finalized(&self, chain_id: &ChainId) -> Option<StoredRoot> {
+        let chain = self.chains.get(chain_id)?;
+        let finalized = chain.finalized_height?;
+        chain.by_height.range(..=finalized).next_back().map(|(_, root)| root.clone())
+    }
+
+    fn range(&self, chain_id: &ChainId, from: u64, to: u64) -> Vec<StoredRoot> {
+        if from > to {
+            return Vec::new();
+        }
+        self.chains
+            .get(chain_id)
+            .map(|chain| chain.by_height.range(from..=to).map(|(_, root)| root.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn ancestor(&self, chain_id: &ChainId, height: u64, depth: u64) -> Option<StoredRoot> {
+        let target = height.checked_sub(depth)?;
+        self.chains
+            .get(chain_id)?
+            .by_height
+            .range(..=target)
+            .next_back()
+            .map(|(_, root)| root.clone())
+    }
+}
+
+fn stored(root: StateRoot, finalized: bool) -> StoredRoot {
+    StoredRoot {
+        root,
+        finalized,
+        stored_at: SystemTime::now(),
+    }
+}
+
+/// In-memory state root store
+#[derive(Debug, Default)]
+pub struct InMemoryStateRootStore {
+    index: RwLock<RootIndex>,
+}
+
+impl InMemoryStateRootStore {
+    /// Create empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn execute(&self, op: StoreOp) -> Result<usize, StateError> {
+        let mut index = self.index.write().await;
+        index.validate(&op)?;
+        Ok(index.apply(op))
+    }
+}
+
+#[async_trait]
+impl StateRootStore for InMemoryStateRootStore {
+    async fn insert(&self, root: StateRoot, finalized: bool) -> Result<(), StateError> {
+        self.execute(StoreOp::Insert { root: stored(root, finalized) }).await.map(|_| ())
+    }
+
+    async fn mark_finalized(&self, chain_id: &ChainId, height: u64) -> Result<(), StateError> {
+        self.execute(StoreOp::Finalize { chain_id: chain_id.clone(), height }).await.map(|_| ())
+    }
+
+    async fn get(&self, chain_id: &ChainId, height: u64) -> Result<Option<StoredRoot>, StateError> {
+        Ok(self.index.read().await.get(chain_id, height))
+    }
+
+    async fn get_by_hash(&self, root_hash: &[u8; 32]) -> Result<Vec<StoredRoot>, StateError> {
+        Ok(self.index.read().await.get_by_hash(root_hash))
+    }
+
+    async fn latest_finalized(&self, chain_id: &ChainId) -> Result<Option<StoredRoot>, StateError> {
+        Ok(self.index.read().await.latest_finalized(chain_id))
+    }
+
+    async fn range(&self, chain_id: &ChainId, from: u64, to: u64) -> Result<Vec<StoredRoot>, StateError> {
+        Ok(self.index.read().await.range(chain_id, from, to))
+    }
+
+    async fn ancestor(
+        &self,
+        chain_id: &ChainId,
+        height: u64,
+        depth: u64,
+    ) -> Result<Option<StoredRoot>, StateError> {
+        Ok(self.index.read().await.ancestor(chain_id, height, depth))
+    }
+
+    async fn prune_below(&self, chain_id: &ChainId, watermark: u64) -> Result<usize, StateError> {
+        self.execute(StoreOp::Prune { chain_id: chain_id.clone(), watermark }).await
+    }
+}
+
+/// File-backed state root store
+///
+/// Every mutation is appended to a JSON-lines log before it is applied in
+/// memory, and the log is replayed on open. `compact` rewrites the log to
+/// the current contents.
+pub struct FileStateRootStore {
+    path: PathBuf,
+    index: RwLock<RootIndex>,
+    log: Mutex<JsonlLog>,
+}
+
+impl FileStateRootStore {
+    /// Open or create a store at `path`
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, StateError> {
+        let path = path.as_ref().to_path_buf();
+        let (log, ops) = JsonlLog::open::<StoreOp>(&path).await.map_err(|e| match e {
+            LogError::Corrupt { line, source } => {
+                StateError::Storage(format!("Corrupt state root log at line {}: {}", line, source))
+            }
+            LogError::Io(e) => StateError::Storage(e.to_string()),
+        })?;
+
+        let mut index = RootIndex::default();
+        for op in ops {
+            index.validate(&op)?;
+            index.apply(op);
+        }
+
+        Ok(Self {
+            path,
+            index: RwLock::new(index),
+            log: Mutex::new(log),
+        })
+    }
+
+    /// Path of the backing log
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn execute(&self, op: StoreOp) -> Result<usize, StateError> {
+        let mut index = self.index.write().await;
+        index.validate(&op)?;
+
+        let mut log = self.log.lock().await;
+        log.append(&op).await.map_err(|e| StateError::Storage(e.to_string()))?;
+
+        Ok(index.apply(op))
+    }
+
+    /// Rewrite the log to contain only the current roots
+    pub async fn compact(&self) -> Result<(), StateError> {
+        let index = self.index.write().await;
+        let mut log = self.log.lock().await;
+
+        let mut ops = Vec::new();
+        for (chain_id, chain) in &index.chains {
+            for root in chain.by_height.values() {
+                ops.push(StoreOp::Insert { root: root.clone() });
+            }
+            if let Some(height) = chain.finalized_height {
+                if chain.by_height.contains_key(&height) {
+                    ops.push(StoreOp::Finalize { chain_id: chain_id.clone(), height });
+                }
+            }
+        }
+
+        log.rewrite(ops).await.map_err(|e| StateError::Storage(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl StateRootStore for FileStateRootStore {
+    async fn insert(&self, root: StateRoot, finalized: bool) -> Result<(), StateError> {
+        self.execute(StoreOp::Insert { root: stored(root, finalized) }).await.map(|_| ())
+    }
+
+    async fn mark_finalized(&self, chain_id: &ChainId, height: u64) -> Result<(), StateError> {
+        self.execute(StoreOp::Finalize { chain_id: chain_id.clone(), height }).await.map(|_| ())
+    }
+
+    async fn get(&self, chain_id: &ChainId, height: u64) -> Result<Option<StoredRoot>, StateError> {
+        Ok(self.index.read().await.get(chain_id, height))
+    }
+
+    async fn get_by_hash(&self, root_hash: &[u8; 32]) -> Result<Vec<StoredRoot>, StateError> {
+        Ok(self.index.read().await.get_by_hash(root_hash))
+    }
+
+    async fn latest_finalized(&self, chain_id: &ChainId) -> Result<Option<StoredRoot>, StateError> {
+        Ok(self.index.read().await.latest_finalized(chain_id))
+    }
+
+    async fn range(&self, chain_id: &ChainId, from: u64, to: u64) -> Result<Vec<StoredRoot>, StateError> {
+        Ok(self.index.read().await.range(chain_id, from, to))
+    }
+
+    async fn ancestor(
+        &self,
+        chain_id: &ChainId,
+        height: u64,
+        depth: u64,
+    ) -> Result<Option<StoredRoot>, StateError> {
+        Ok(self.index.read().await.ancestor(chain_id, height, depth))
+    }
+
+    async fn prune_below(&self, chain_id: &ChainId, watermark: u64) -> Result<usize, StateError> {
+        self.execute(StoreOp::Prune { chain_id: chain_id.clone(), watermark }).await
+    }
+}
@@ -0,0 +1,182 @@
+#![allow(unused_variables)]
+
+use std::sync::Arc;
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use async_trait::async_trait;
+use dashmap::DashMap;
+
+use crate::state::{
+    error::StateError,
+    proof::{ProofType, ProofVerifier, StateProof, VerificationParams},
+    types::StateRoot,
+};
+
+/// Parameter key holding the circuit identifier
+pub const CIRCUIT_ID_PARAM: &str = "circuit_id";
+
+/// Parameter key holding additional public inputs (hex, big-endian)
+pub const PUBLIC_INPUTS_PARAM: &str = "public_inputs";
+
+/// Split a 32-byte root into two 128-bit big-endian field elements
+fn root_limbs(root: &[u8; 32]) -> [Fr; 2] {
+    [
+        Fr::from_be_bytes_mod_order(&root[..16]),
+        Fr::from_be_bytes_mod_order(&root[16..]),
+    ]
+}
+
+/// Public inputs binding a proof to a transition
+///
+/// Circuits must expose the pre-state and post-state roots as their first
+/// four public inputs: `pre_hi, pre_lo, post_hi, post_lo`.
+pub fn state_root_inputs(pre_state: &StateRoot, post_state: &StateRoot) -> Vec<Fr> {
+    let mut inputs = Vec::with_capacity(4);
+    inputs.extend(root_limbs(&pre_state.root_hash));
+    inputs.extend(root_limbs(&post_state.root_hash));
+    inputs
+}
+
+/// Parse a canonical big-endian hex field element
+fn parse_field_element(value: &serde_json::Value) -> Result<Fr, StateError> {
+    let hex_str = value
+        .as_str()
+        .ok_or_else(|| StateError::InvalidProof("Public inputs must be hex strings".into()))?;
+    let bytes = hex::decode(hex_str.trim_start_matches("0x"))
+        .map_err(|e| StateError::InvalidProof(format!("Invalid public input hex: {}", e)))?;
+    if bytes.len() > 32 {
+        return Err(StateError::InvalidProof("Public input exceeds 32 bytes".into()));
+    }
+
+    let element = Fr::from_be_bytes_mod_order(&bytes);
+    let canonical = element.into_bigint().to_bytes_be();
+    let mut padded = vec![0u8; canonical.len().saturating_sub(bytes.len())];
+    padded.extend_from_slice(&bytes);
+    if padded != canonical {
+        return Err(StateError::InvalidProof("Public input is not a canonical field element".into()));
+    }
+    Ok(element)
+}
+
+/// Groth16 verifier over BN254 for `ProofType::ZeroKnowledge`
+///
+/// Verifying keys are registered per circuit id. The circuit id and any
+/// extra public inputs are read from `VerificationParams::extra_params`,
+/// falling back to the proof metadata. `ProofData::data` holds the
+/// compressed arkworks encoding of the proof.
+pub struct Groth16Verifier {
+    keys: DashMap<String, Arc<PreparedVerifyingKey<Bn254>>>,
+}
+
+impl Default for Groth16Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Groth16Verifier {
+    /// Create verifier with no registered circuits
+    pub fn new() -> Self {
+        Self {
+            keys: DashMap::new(),
+        }
+    }
+
+    /// Register verifying key for a circuit
+    pub fn register_key(&self, circuit_id: impl Into<String>, vk: &VerifyingKey<Bn254>) {
+        self.keys.insert(circuit_id.into(), Arc::new(prepare_verifying_key(vk)));
+    }
+
+    /// Register compressed verifying key bytes for a circuit
+    pub fn register_key_bytes(&self, circuit_id: impl Into<String>, bytes: &[u8]) -> Result<(), StateError> {
+        let vk = VerifyingKey::<Bn254>::deserialize_compressed(bytes)
+            .map_err(|e| StateError::InvalidProof(format!("Invalid verifying key: {}", e)))?;
+        self.register_key(circuit_id, &vk);
+        Ok(())
+    }
+
+    /// Remove verifying key for a circuit
+    pub fn unregister_key(&self, circuit_id: &str) -> bool {
+        self.keys.remove(circuit_id).is_some()
+    }
+
+    /// Check if a circuit has a registered key
+    pub fn has_key(&self, circuit_id: &str) -> bool {
+        self.keys.contains_key(circuit_id)
+    }
+
+    /// List registered circuit ids
+    pub fn circuit_ids(&self) -> Vec<String> {
+        self.keys.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    /// Look up a parameter in verification params, then proof metadata
+    fn lookup_param<'a>(
+        proof: &'a StateProof,
+        params: &'a VerificationParams,
+        key: &str,
+    ) -> Option<&'a serde_json::Value> {
+        params
+            .extra_params
+            .as_ref()
+            .and_then(|p| p.get(key))
+            .or_else(|| proof.metadata().and_then(|m| m.get(key)))
+    }
+
+    fn public_inputs(proof: &StateProof, params: &VerificationParams) -> Result<Vec<Fr>, StateError> {
+        let mut inputs = state_root_inputs(&proof.transition.pre_state, &proof.transition.post_state);
+
+        if let Some(extra) = Self::lookup_param(proof, params, PUBLIC_INPUTS_PARAM) {
+            let extra = extra
+                .as_array()
+                .ok_or_else(|| StateError::InvalidProof("`public_inputs` must be an array".into()))?;
+            for value in extra {
+                inputs.push(parse_field_element(value)?);
+            }
+        }
+        Ok(inputs)
+    }
+}
+
+#[async_trait]
+impl ProofVerifier for Groth16Verifier {
+    fn supported_types(&self) -> Vec<ProofType> {
+        vec![ProofType::ZeroKnowledge]
+    }
+
+    async fn verify_proof(
+        &self,
+        proof: &StateProof,
+        params: &VerificationParams,
+        context: Option<&serde_json::Value>,
+    ) -> Result<bool, StateError> {
+        if proof.proof_type() != &ProofType::ZeroKnowledge {
+            return Err(StateError::InvalidProof(format!(
+                "Unsupported proof type: {:?}", proof.proof_type()
+            )));
+        }
+
+        let circuit_id = Self::lookup_param(proof, params, CIRCUIT_ID_PARAM)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StateError::InvalidProof("Missing circuit id".into()))?;
+
+        let pvk = self
+            .keys
+            .get(circuit_id)
+            .map(|entry| entry.value().clone())
+            .ok_or_else(|| {
+                StateError::InvalidProof(format!("No verifying key registered for circuit: {}", circuit_id))
+            })?;
+
+        let groth16_proof = Proof::<Bn254>::deserialize_compressed(proof.proof.data.as_slice())
+            .map_err(|e| StateError::InvalidProof(format!("Invalid Groth16 proof encoding: {}", e)))?;
+
+        let inputs = Self::public_inputs(proof, params)?;
+
+        Groth16::<Bn254>::verify_proof(&pvk, &groth16_proof, &inputs)
+            .map_err(|e| StateError::ProofVerificationFailed(format!("Groth16 verification error: {}", e)))
+    }
+}
@@ -0,0 +1,171 @@
+use frost_protocol::message::{
+    BatchExecutor, BatchExecutorConfig, CompensationHook, FrostMessage, MessageError,
+    MessageProcessor, MessageType,
+    batch::BatchItemOutcome,
+};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+/// Fails messages whose first payload byte is zero
+#[derive(Default)]
+struct TestProcessor {
+    order: Mutex<Vec<Uuid>>,
+    active: AtomicUsize,
+    max_active: AtomicUsize,
+    delay: Duration,
+}
+
+#[async_trait]
+impl MessageProcessor for TestProcessor {
+    async fn process(&self, message: &FrostMessage) -> Result<serde_json::Value, MessageError> {
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_active.fetch_max(active, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+
+        self.order.lock().push(message.id);
+        if message.payload[0] == 0 {
+            return Err(MessageError::HandlingFailed("rejected".into()));
+        }
+        Ok(serde_json::json!({ "value": message.payload[0] }))
+    }
+}
+
+#[derive(Default)]
+struct RecordingCompensation {
+    compensated: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl CompensationHook for RecordingCompensation {
+    async fn compensate(&self, message: &FrostMessage, result: &serde_json::Value) -> Result<(), MessageError> {
+        assert_eq!(result["value"], message.payload[0]);
+        self.compensated.lock().push(message.id);
+        Ok(())
+    }
+}
+
+fn messages(payloads: &[u8]) -> Vec<FrostMessage> {
+    payloads
+        .iter()
+        .map(|&p| FrostMessage::new(MessageType::StateProof, vec![p], "node1".into(), None))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_ordered_batch_runs_in_sequence() {
+    let processor = Arc::new(TestProcessor::default());
+    let executor = BatchExecutor::new(processor.clone());
+    let batch = FrostMessage::new_batch(messages(&[1, 2, 0, 3]), true, 0.5);
+
+    let summary = executor.execute(&batch).await;
+
+    let ids: Vec<Uuid> = batch.messages.iter().map(|m| m.id).collect();
+    assert_eq!(*processor.order.lock(), ids);
+    assert_eq!(summary.results.len(), 4);
+    assert_eq!(summary.success_count(), 3);
+    assert_eq!(summary.failure_count(), 1);
+    assert!(summary.succeeded);
+    assert!(!summary.stopped_early);
+    assert_eq!(summary.success_ratio, 0.75);
+    assert!(summary.error().is_none());
+}
+
+#[tokio::test]
+async fn test_stops_when_ratio_unreachable() {
+    let processor = Arc::new(TestProcessor::default());
+    let executor = BatchExecutor::new(processor.clone());
+    let batch = FrostMessage::new_batch(messages(&[0, 1, 0, 2, 3]), true, 0.8);
+
+    let summary = executor.execute(&batch).await;
+
+    assert_eq!(processor.order.lock().len(), 3);
+    assert!(summary.stopped_early);
+    assert!(!summary.succeeded);
+    assert!(matches!(summary.results[3].outcome, BatchItemOutcome::Skipped));
+    assert!(matches!(summary.results[4].outcome, BatchItemOutcome::Skipped));
+    match summary.error() {
+        Some(MessageError::BatchValidationFailed { batch_id, required_ratio, .. }) => {
+            assert_eq!(batch_id, batch.batch_id);
+            assert_eq!(required_ratio, 0.8);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unordered_batch_bounded_concurrency() {
+    let processor = Arc::new(TestProcessor {
+        delay: Duration::from_millis(20),
+        ..Default::default()
+    });
+    let executor = BatchExecutor::new(processor.clone()).with_config(BatchExecutorConfig {
+        max_concurrency: 3,
+        ..Default::default()
+    });
+    let batch = FrostMessage::new_batch(messages(&[1; 10]), false, 1.0);
+
+    let summary = executor.execute(&batch).await;
+
+    assert!(summary.succeeded);
+    assert_eq!(summary.success_count(), 10);
+    let max_active = processor.max_active.load(Ordering::SeqCst);
+    assert!(max_active > 1 && max_active <= 3, "max active {}", max_active);
+    // Results are reported in batch order regardless of completion order
+    for (message, result) in batch.messages.iter().zip(&summary.results) {
+        assert_eq!(message.id, result.message_id);
+    }
+}
+
+#[tokio::test]
+async fn test_unordered_batch_stops_launching() {
+    let processor = Arc::new(TestProcessor::default());
+    let executor = BatchExecutor::new(processor.clone()).with_config(BatchExecutorConfig {
+        max_concurrency: 1,
+        ..Default::default()
+    });
+    let batch = FrostMessage::new_batch(messages(&[0, 0, 1, 1]), false, 0.75);
+
+    let summary = executor.execute(&batch).await;
+
+    assert_eq!(processor.order.lock().len(), 2);
+    assert!(summary.stopped_early);
+    assert_eq!(summary.failure_count(), 2);
+}
+
+#[tokio::test]
+async fn test_compensation_on_failure() {
+    let processor = Arc::new(TestProcessor::default());
+    let hook = Arc::new(RecordingCompensation::default());
+    let executor = BatchExecutor::new(processor.clone()).with_compensation(hook.clone());
+    let batch = FrostMessage::new_batch(messages(&[1, 2, 0, 3]), true, 1.0);
+
+    let summary = executor.execute(&batch).await;
+
+    assert!(!summary.succeeded);
+    assert_eq!(*hook.compensated.lock(), vec![batch.messages[1].id, batch.messages[0].id]);
+    assert!(summary.results[0].compensated && summary.results[1].compensated);
+    assert!(!summary.results[2].compensated);
+    assert!(matches!(summary.results[3].outcome, BatchItemOutcome::Skipped));
+
+    // Compensation can be disabled
+    let hook = Arc::new(RecordingCompensation::default());
+    let executor = BatchExecutor::new(processor)
+        .with_config(BatchExecutorConfig { compensate_on_failure: false, ..Default::default() })
+        .with_compensation(hook.clone());
+    executor.execute(&batch).await;
+    assert!(hook.compensated.lock().is_empty());
+}
+
+#[tokio::test]
+async fn test_empty_batch() {
+    let executor = BatchExecutor::new(Arc::new(TestProcessor::default()));
+    let summary = executor.execute(&FrostMessage::new_batch(vec![], false, 1.0)).await;
+    assert!(summary.succeeded);
+    assert_eq!(summary.success_ratio, 1.0);
+}
@@ -0,0 +1,241 @@
+use frost_protocol::{
+    message::{
+        dead_letter::{DeadLetterFilter, DeadLetterOrigin},
+        handler::MessageStatus,
+        validation::{BasicValidationPipeline, ValidationPipeline},
+        DeadLetterConfig, DeadLetterQueue, DeadLetterReason, FrostMessage, MessageError,
+        MessageHandler, MessageProcessor, MessageType, QueueConfig, QueuedMessageHandler,
+    },
+    network::{NetworkError, NetworkProtocol, RetryPolicy, retry::RetryMetrics},
+    routing::{BasicRouter, DurableOutbox, MessageRouter, OutboxConfig, RoutingConfig},
+    Result,
+};
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+/// Fails every message with the configured error, or succeeds if none
+#[derive(Default)]
+struct FailingProcessor {
+    error: Mutex<Option<MessageError>>,
+    processed: Mutex<usize>,
+}
+
+#[async_trait]
+impl MessageProcessor for FailingProcessor {
+    async fn process(&self, _message: &FrostMessage) -> std::result::Result<serde_json::Value, MessageError> {
+        *self.processed.lock() += 1;
+        match self.error.lock().clone() {
+            Some(error) => Err(error),
+            None => Ok(serde_json::Value::Null),
+        }
+    }
+}
+
+struct RetryTwice;
+
+#[async_trait]
+impl RetryPolicy for RetryTwice {
+    async fn should_retry(&self, error: &NetworkError, attempt: u32) -> bool {
+        error.is_retryable() && attempt < 2
+    }
+
+    async fn get_delay(&self, _attempt: u32) -> Duration {
+        Duration::ZERO
+    }
+
+    fn metrics(&self) -> RetryMetrics {
+        RetryMetrics::default()
+    }
+}
+
+#[derive(Clone, Default)]
+struct RecordingNetwork {
+    sent: Arc<Mutex<Vec<FrostMessage>>>,
+}
+
+#[async_trait]
+impl NetworkProtocol for RecordingNetwork {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn broadcast(&self, message: FrostMessage) -> Result<()> {
+        self.sent.lock().push(message);
+        Ok(())
+    }
+
+    async fn send_to(&self, _peer_id: &str, message: FrostMessage) -> Result<()> {
+        self.sent.lock().push(message);
+        Ok(())
+    }
+
+    async fn get_peers(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn message() -> FrostMessage {
+    FrostMessage::new(MessageType::Discovery, vec![1, 2, 3], "node1".into(), None)
+}
+
+fn expired() -> FrostMessage {
+    message().with_deadline(now() - 1)
+}
+
+fn handler(processor: Arc<FailingProcessor>, dead_letters: Arc<DeadLetterQueue>) -> QueuedMessageHandler {
+    QueuedMessageHandler::new(QueueConfig::default(), processor, Arc::new(RetryTwice))
+        .with_dead_letter_queue(dead_letters)
+}
+
+#[test]
+fn test_message_expiry() {
+    let message = message();
+    assert!(!message.is_expired());
+    assert!(message.check_expiry().is_ok());
+
+    let ttl = message.clone().with_ttl(Duration::from_secs(60));
+    assert_eq!(ttl.metadata.expires_at, Some(message.timestamp + 60));
+    assert!(!ttl.is_expired());
+    assert!(ttl.is_expired_at(message.timestamp + 60));
+
+    assert!(matches!(expired().check_expiry(), Err(MessageError::Expired { .. })));
+    assert!(!MessageError::Expired { message_id: message.id, expires_at: 0 }.is_retryable());
+
+    // Older encodings without a deadline still decode
+    let mut value = serde_json::to_value(&message).unwrap();
+    value["metadata"].as_object_mut().unwrap().remove("expires_at");
+    let decoded: FrostMessage = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded.metadata.expires_at, None);
+}
+
+#[tokio::test]
+async fn test_validation_rejects_expired() {
+    let dead_letters = Arc::new(DeadLetterQueue::default());
+    let pipeline = BasicValidationPipeline::new().with_dead_letter_queue(dead_letters.clone());
+
+    let result = pipeline.pre_validate(&message()).await;
+    assert!(result.rules_passed.contains(&"message_expiry".to_string()));
+
+    let message = expired();
+    let result = pipeline.pre_validate(&message).await;
+    assert!(!result.is_valid);
+    assert_eq!(result.rules_failed[0].rule_id, "message_expiry");
+
+    let letter = dead_letters.get(message.id).unwrap();
+    assert_eq!(letter.reason, DeadLetterReason::Expired);
+    assert_eq!(letter.origin, DeadLetterOrigin::Validation);
+}
+
+#[tokio::test]
+async fn test_handler_dead_letters() {
+    let dead_letters = Arc::new(DeadLetterQueue::default());
+    let processor = Arc::new(FailingProcessor::default());
+    let handler = handler(processor.clone(), dead_letters.clone());
+
+    // Expired messages are dead-lettered without reaching the processor
+    let stale = expired();
+    let status = handler.handle_message(stale.clone()).await.unwrap();
+    assert!(matches!(status, MessageStatus::Failed { error: MessageError::Expired { .. }, .. }));
+    assert_eq!(*processor.processed.lock(), 0);
+    assert_eq!(dead_letters.get(stale.id).unwrap().reason, DeadLetterReason::Expired);
+
+    // Retryable failures are dead-lettered once retries are exhausted
+    *processor.error.lock() = Some(MessageError::HandlingFailed("unavailable".into()));
+    let failing = message();
+    handler.queue_message(failing.clone()).await.unwrap();
+    while handler.process_next().await.is_some() {}
+    assert_eq!(*processor.processed.lock(), 2);
+    let letter = dead_letters.get(failing.id).unwrap();
+    assert_eq!(letter.reason, DeadLetterReason::Failed);
+    assert_eq!(letter.origin, DeadLetterOrigin::Handler);
+    assert!(letter.error.contains("unavailable"));
+}
+
+#[tokio::test]
+async fn test_routing_and_outbox_drop_expired() {
+    let dead_letters = Arc::new(DeadLetterQueue::default());
+    let network = RecordingNetwork::default();
+    let router = BasicRouter::new(RoutingConfig::default(), network.clone())
+        .with_dead_letter_queue(dead_letters.clone());
+
+    assert!(router.route(expired()).await.is_err());
+    router.route(message()).await.unwrap();
+    assert_eq!(network.sent.lock().len(), 1);
+    assert_eq!(dead_letters.query(&DeadLetterFilter {
+        origin: Some(DeadLetterOrigin::Routing),
+        ..Default::default()
+    }).len(), 1);
+
+    // A pending message that expires is dropped on replay
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("outbox.log");
+    let config = OutboxConfig { ack_on_send: false, ..Default::default() };
+    let outbox = DurableOutbox::open(&path, network.clone(), config.clone())
+        .await
+        .unwrap()
+        .with_dead_letter_queue(dead_letters.clone());
+    let pending = message().with_deadline(now() + 1);
+    outbox.broadcast(pending.clone()).await.unwrap();
+    assert_eq!(outbox.pending_count().await, 1);
+
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+    assert_eq!(outbox.replay().await.unwrap(), 0);
+    assert_eq!(outbox.pending_count().await, 0);
+    assert_eq!(dead_letters.get(pending.id).unwrap().origin, DeadLetterOrigin::Outbox);
+
+    let reopened = DurableOutbox::open(&path, network, config).await.unwrap();
+    assert_eq!(reopened.pending_count().await, 0);
+}
+
+#[tokio::test]
+async fn test_query_and_replay() {
+    let dead_letters = Arc::new(DeadLetterQueue::new(DeadLetterConfig {
+        max_entries: 3,
+        replay_ttl: Duration::from_secs(120),
+    }));
+    let messages: Vec<_> = (0..4).map(|_| message()).collect();
+    for (i, message) in messages.iter().enumerate() {
+        let reason = if i % 2 == 0 { DeadLetterReason::Expired } else { DeadLetterReason::Failed };
+        dead_letters.push(message.clone(), reason, DeadLetterOrigin::Handler, "error");
+    }
+
+    // The oldest letter is evicted
+    assert_eq!(dead_letters.len(), 3);
+    assert!(dead_letters.get(messages[0].id).is_none());
+    let failed = dead_letters.query(&DeadLetterFilter {
+        reason: Some(DeadLetterReason::Failed),
+        ..Default::default()
+    });
+    assert_eq!(failed.iter().map(|l| l.message.id).collect::<Vec<_>>(), vec![messages[1].id, messages[3].id]);
+
+    // Replaying an expired message gives it a fresh deadline and resets retries
+    let mut stale = expired();
+    stale.metadata.retry_count = 4;
+    dead_letters.push(stale.clone(), DeadLetterReason::Expired, DeadLetterOrigin::Routing, "expired");
+    let replayed = dead_letters.take_for_replay(stale.id, None).unwrap();
+    assert_eq!(replayed.metadata.retry_count, 0);
+    assert!(replayed.metadata.expires_at.unwrap() >= now() + 119);
+    assert!(dead_letters.get(stale.id).is_none());
+    assert!(dead_letters.take_for_replay(stale.id, None).is_err());
+
+    // Replay through a handler
+    let processor = Arc::new(FailingProcessor::default());
+    let handler = handler(processor.clone(), dead_letters.clone());
+    dead_letters.replay(messages[3].id, Some(Duration::from_secs(60)), &handler).await.unwrap();
+    assert_eq!(handler.queue_len(), 1);
+    assert!(dead_letters.get(messages[3].id).is_none());
+
+    assert_eq!(dead_letters.purge(&DeadLetterFilter::default()), 1);
+    assert!(dead_letters.is_empty());
+}
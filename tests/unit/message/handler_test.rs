@@ -0,0 +1,267 @@
+use frost_protocol::{
+    message::{
+        FrostMessage, MessageError, MessageHandler, MessagePriority, MessageProcessor, MessageType,
+        QueueConfig, QueuedMessageHandler, handler::MessageStatus,
+    },
+    network::{NetworkError, RetryPolicy, retry::RetryMetrics},
+};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+/// Fails each message a configured number of times, recording processing order
+#[derive(Default)]
+struct TestProcessor {
+    failures: Mutex<HashMap<Uuid, (u32, MessageError)>>,
+    order: Mutex<Vec<Uuid>>,
+}
+
+impl TestProcessor {
+    fn fail(&self, id: Uuid, times: u32, error: MessageError) {
+        self.failures.lock().insert(id, (times, error));
+    }
+}
+
+#[async_trait]
+impl MessageProcessor for TestProcessor {
+    async fn process(&self, message: &FrostMessage) -> Result<serde_json::Value, MessageError> {
+        self.order.lock().push(message.id);
+        if let Some((remaining, error)) = self.failures.lock().get_mut(&message.id) {
+            if *remaining > 0 {
+                *remaining -= 1;
+                return Err(error.clone());
+            }
+        }
+        Ok(serde_json::json!({ "retries": message.metadata.retry_count }))
+    }
+}
+
+struct FixedRetryPolicy {
+    max_attempts: u32,
+}
+
+#[async_trait]
+impl RetryPolicy for FixedRetryPolicy {
+    async fn should_retry(&self, error: &NetworkError, attempt: u32) -> bool {
+        error.is_retryable() && attempt < self.max_attempts
+    }
+
+    async fn get_delay(&self, _attempt: u32) -> Duration {
+        Duration::ZERO
+    }
+
+    fn metrics(&self) -> RetryMetrics {
+        RetryMetrics::default()
+    }
+}
+
+fn handler_with(config: QueueConfig, max_attempts: u32) -> (Arc<TestProcessor>, QueuedMessageHandler) {
+    let processor = Arc::new(TestProcessor::default());
+    let handler = QueuedMessageHandler::new(
+        config,
+        processor.clone(),
+        Arc::new(FixedRetryPolicy { max_attempts }),
+    );
+    (processor, handler)
+}
+
+fn message(priority: MessagePriority) -> FrostMessage {
+    let mut message = FrostMessage::new(MessageType::Discovery, vec![0; 16], "node1".into(), None);
+    message.metadata.priority = priority;
+    message
+}
+
+fn position(status: MessageStatus) -> u64 {
+    match status {
+        MessageStatus::Queued { position, .. } => position,
+        other => panic!("expected queued status, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_priority_order_and_positions() {
+    let (processor, handler) = handler_with(QueueConfig { workers: 1, ..Default::default() }, 1);
+    let messages = [
+        message(MessagePriority::Low),
+        message(MessagePriority::Normal),
+        message(MessagePriority::Critical),
+        message(MessagePriority::High),
+        message(MessagePriority::Critical),
+    ];
+    for m in &messages {
+        handler.queue_message(m.clone()).await.unwrap();
+    }
+
+    let expected = [2, 4, 3, 1, 0];
+    for (rank, &index) in expected.iter().enumerate() {
+        assert_eq!(position(handler.message_status(messages[index].id).await.unwrap()), rank as u64);
+    }
+
+    while handler.process_next().await.is_some() {}
+    let order: Vec<Uuid> = expected.iter().map(|&i| messages[i].id).collect();
+    assert_eq!(*processor.order.lock(), order);
+    assert_eq!(handler.queue_len(), 0);
+    assert_eq!(handler.queued_bytes(), 0);
+}
+
+#[tokio::test]
+async fn test_starvation_protection() {
+    let (processor, handler) = handler_with(QueueConfig {
+        starvation_threshold: Duration::from_millis(20),
+        ..Default::default()
+    }, 1);
+
+    let low = message(MessagePriority::Low);
+    handler.queue_message(low.clone()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let high = message(MessagePriority::High);
+    handler.queue_message(high.clone()).await.unwrap();
+
+    // The aged low-priority message has been promoted to High and is older
+    assert_eq!(position(handler.message_status(low.id).await.unwrap()), 0);
+    handler.process_next().await.unwrap();
+    assert_eq!(processor.order.lock()[0], low.id);
+}
+
+#[tokio::test]
+async fn test_status_lifecycle() {
+    let (_, handler) = handler_with(QueueConfig {
+        workers: 2,
+        initial_processing_estimate: Duration::from_millis(100),
+        ..Default::default()
+    }, 1);
+
+    let messages: Vec<_> = (0..5).map(|_| message(MessagePriority::Normal)).collect();
+    for m in &messages {
+        handler.queue_message(m.clone()).await.unwrap();
+    }
+    match handler.message_status(messages[4].id).await.unwrap() {
+        MessageStatus::Queued { position, estimated_time } => {
+            assert_eq!(position, 4);
+            assert_eq!(estimated_time, Duration::from_millis(200));
+        }
+        other => panic!("unexpected status {:?}", other),
+    }
+
+    // Queuing an in-flight id again is rejected
+    assert!(handler.queue_message(messages[0].clone()).await.is_err());
+
+    let (id, status) = handler.process_next().await.unwrap();
+    assert_eq!(id, messages[0].id);
+    assert!(matches!(status, MessageStatus::Completed { ref result, .. } if result.success));
+    assert!(matches!(
+        handler.message_status(id).await.unwrap(),
+        MessageStatus::Completed { .. }
+    ));
+    assert_eq!(position(handler.message_status(messages[4].id).await.unwrap()), 3);
+
+    assert!(handler.message_status(Uuid::new_v4()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_automatic_retry() {
+    let (processor, handler) = handler_with(QueueConfig::default(), 3);
+    let m = message(MessagePriority::Normal);
+    processor.fail(m.id, 2, MessageError::HandlingFailed("busy".into()));
+
+    handler.queue_message(m.clone()).await.unwrap();
+    assert!(matches!(handler.process_next().await.unwrap().1, MessageStatus::Queued { .. }));
+    assert!(matches!(handler.process_next().await.unwrap().1, MessageStatus::Queued { .. }));
+    match handler.process_next().await.unwrap().1 {
+        MessageStatus::Completed { result, .. } => assert_eq!(result.metadata["retries"], 2),
+        other => panic!("unexpected status {:?}", other),
+    }
+
+    // Exhausting the policy leaves the message failed
+    let m = message(MessagePriority::Normal);
+    processor.fail(m.id, 5, MessageError::HandlingFailed("down".into()));
+    handler.queue_message(m.clone()).await.unwrap();
+    while handler.process_next().await.is_some() {}
+    assert!(matches!(
+        handler.message_status(m.id).await.unwrap(),
+        MessageStatus::Failed { can_retry: true, .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_manual_retry() {
+    let (processor, handler) = handler_with(QueueConfig { auto_retry: false, ..Default::default() }, 3);
+
+    let m = message(MessagePriority::Normal);
+    processor.fail(m.id, 1, MessageError::HandlingFailed("busy".into()));
+    let status = handler.handle_message(m.clone()).await.unwrap();
+    assert!(matches!(status, MessageStatus::Failed { can_retry: true, .. }));
+
+    assert!(matches!(handler.retry_message(m.id).await.unwrap(), MessageStatus::Queued { .. }));
+    assert!(handler.retry_message(m.id).await.is_err());
+    assert!(matches!(handler.process_next().await.unwrap().1, MessageStatus::Completed { .. }));
+
+    let bad = message(MessagePriority::Normal);
+    processor.fail(bad.id, 1, MessageError::InvalidFormat("garbage".into()));
+    let status = handler.handle_message(bad.clone()).await.unwrap();
+    assert!(matches!(status, MessageStatus::Failed { can_retry: false, .. }));
+    assert!(handler.retry_message(bad.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_bounds() {
+    let (_, handler) = handler_with(QueueConfig {
+        max_queue_size: 2,
+        max_queued_bytes: 40,
+        max_tracked_messages: 2,
+        ..Default::default()
+    }, 1);
+
+    handler.queue_message(message(MessagePriority::Normal)).await.unwrap();
+    handler.queue_message(message(MessagePriority::Normal)).await.unwrap();
+    assert!(handler.queue_message(message(MessagePriority::Normal)).await.is_err());
+
+    handler.process_next().await.unwrap();
+    let mut large = message(MessagePriority::Normal);
+    large.payload = vec![0; 32];
+    assert!(handler.queue_message(large).await.is_err());
+
+    let (_, handler) = handler_with(QueueConfig {
+        max_tracked_messages: 2,
+        ..Default::default()
+    }, 1);
+    let messages: Vec<_> = (0..3).map(|_| message(MessagePriority::Normal)).collect();
+    for m in &messages {
+        handler.handle_message(m.clone()).await.unwrap();
+    }
+    assert_eq!(handler.tracked_len(), 2);
+    assert!(handler.message_status(messages[0].id).await.is_err());
+    assert!(handler.message_status(messages[2].id).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_workers_drain_queue() {
+    let (processor, handler) = handler_with(QueueConfig { workers: 3, ..Default::default() }, 1);
+    let handler = Arc::new(handler);
+    let workers = handler.start();
+
+    let messages: Vec<_> = (0..20).map(|_| message(MessagePriority::Normal)).collect();
+    for m in &messages {
+        handler.queue_message(m.clone()).await.unwrap();
+    }
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while processor.order.lock().len() < messages.len() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    for m in &messages {
+        assert!(matches!(handler.message_status(m.id).await.unwrap(), MessageStatus::Completed { .. }));
+    }
+    handler.shutdown();
+    for worker in workers {
+        worker.await.unwrap();
+    }
+}
@@ -1 +1,8 @@
-pub mod validation_test; 
\ No newline at end of file
+mod batch_test;
+mod dead_letter_test;
+mod handler_test;
+mod replay_test;
+mod rules_test;
+mod schema_test;
+mod validator_test;
+pub mod validation_test;
\ No newline at end of file
@@ -0,0 +1,142 @@
+use frost_protocol::{
+    message::{FrostMessage, MessageError, MessageType, ReplayConfig, ReplayGuard, replay::message_digest},
+    state::{BlockRef, ChainId},
+};
+
+use std::time::Duration;
+
+fn message(source: &str) -> FrostMessage {
+    FrostMessage::new(MessageType::Discovery, vec![1, 2, 3], source.into(), None)
+}
+
+fn at_height(height: u64) -> FrostMessage {
+    let mut message = message("node1");
+    message.block_ref = Some(BlockRef::new(ChainId::new("ethereum"), height, [height as u8; 32]));
+    message
+}
+
+#[test]
+fn test_duplicate_and_replay_are_distinct() {
+    let guard = ReplayGuard::new(ReplayConfig::default());
+    let original = message("node1");
+    guard.check(&original).unwrap();
+
+    // Same message via a different path, with local metadata changed
+    let mut relayed = original.clone();
+    relayed.metadata.retry_count = 3;
+    assert_eq!(message_digest(&relayed), message_digest(&original));
+    assert!(matches!(
+        guard.check(&relayed),
+        Err(MessageError::DuplicateMessage { message_id }) if message_id == original.id
+    ));
+
+    // Same content under a fresh id
+    let mut reissued = original.clone();
+    reissued.id = uuid::Uuid::new_v4();
+    assert!(matches!(guard.check(&reissued), Err(MessageError::ReplayDetected { .. })));
+
+    // Same id with different content
+    let mut tampered = original.clone();
+    tampered.payload = vec![9];
+    assert!(matches!(guard.check(&tampered), Err(MessageError::ReplayDetected { .. })));
+
+    assert_eq!(guard.len(), 1);
+}
+
+#[test]
+fn test_timestamp_skew() {
+    let guard = ReplayGuard::new(ReplayConfig {
+        max_clock_skew: Duration::from_secs(30),
+        ..Default::default()
+    });
+
+    let mut old = message("node1");
+    old.timestamp -= 120;
+    assert!(matches!(guard.check(&old), Err(MessageError::StaleMessage { .. })));
+
+    let mut future = message("node1");
+    future.timestamp += 120;
+    assert!(matches!(guard.check(&future), Err(MessageError::StaleMessage { .. })));
+
+    let mut slightly_off = message("node1");
+    slightly_off.timestamp -= 10;
+    assert!(guard.check(&slightly_off).is_ok());
+}
+
+#[test]
+fn test_nonces() {
+    let mut guard = ReplayGuard::new(ReplayConfig::default());
+    guard.add_signed_source("validator");
+
+    let mut first = message("validator");
+    first.metadata.nonce = Some(5);
+    guard.check(&first).unwrap();
+    assert_eq!(guard.last_nonce("validator"), Some(5));
+
+    let mut stale = message("validator");
+    stale.metadata.nonce = Some(5);
+    assert!(matches!(guard.check(&stale), Err(MessageError::ReplayDetected { .. })));
+
+    let mut next = message("validator");
+    next.metadata.nonce = Some(9);
+    guard.check(&next).unwrap();
+
+    // Signed sources must carry a nonce; others may omit it
+    assert!(matches!(guard.check(&message("validator")), Err(MessageError::ReplayDetected { .. })));
+    assert!(guard.check(&message("gossip-peer")).is_ok());
+
+    // Nonces from unsigned sources are neither checked nor remembered
+    let mut spoofed = message("gossip-peer");
+    spoofed.metadata.nonce = Some(u64::MAX);
+    guard.check(&spoofed).unwrap();
+    let mut lower = message("gossip-peer");
+    lower.metadata.nonce = Some(1);
+    guard.check(&lower).unwrap();
+    assert_eq!(guard.last_nonce("gossip-peer"), None);
+}
+
+#[test]
+fn test_height_window() {
+    let guard = ReplayGuard::new(ReplayConfig {
+        height_window: Some(10),
+        ..Default::default()
+    });
+
+    let early = at_height(100);
+    guard.check(&early).unwrap();
+    guard.check(&at_height(105)).unwrap();
+    guard.check(&at_height(120)).unwrap();
+
+    // Heights 100 and 105 fell out of the window: forgotten, and rejected if resent
+    assert_eq!(guard.len(), 1);
+    assert!(matches!(guard.check(&early), Err(MessageError::StaleMessage { .. })));
+    assert!(guard.check(&at_height(112)).is_ok());
+}
+
+#[test]
+fn test_window_bounds() {
+    let guard = ReplayGuard::new(ReplayConfig {
+        max_entries: 3,
+        ..Default::default()
+    });
+    let messages: Vec<_> = (0..5u8)
+        .map(|i| FrostMessage::new(MessageType::Discovery, vec![i], "node1".into(), None))
+        .collect();
+    for m in &messages {
+        guard.check(m).unwrap();
+    }
+    assert_eq!(guard.len(), 3);
+
+    // Oldest entries were evicted; recent ones are still detected
+    assert!(guard.check(&messages[4]).is_err());
+    assert!(guard.check(&messages[0]).is_ok());
+}
+
+#[test]
+fn test_error_guidance() {
+    let duplicate = MessageError::DuplicateMessage { message_id: uuid::Uuid::new_v4() };
+    let replay = MessageError::ReplayDetected { source_id: "node1".into(), details: "nonce".into() };
+    assert!(!duplicate.is_retryable());
+    assert!(!replay.is_retryable());
+    assert_ne!(duplicate.severity(), replay.severity());
+}
@@ -0,0 +1,163 @@
+use frost_protocol::{
+    message::{
+        FrostMessage, MessagePriority, MessageType, MessageValidator, RuleSet, RuleSetConfig, RuleSpec,
+        rules::{ChainField, ConfiguredRule, RuleConfig},
+        validation::{BasicValidationPipeline, ValidationPipeline, ValidationSeverity, ValidationStage},
+    },
+    state::ChainId,
+};
+
+const TOML_RULES: &str = r#"
+short_circuit = "Critical"
+
+[[pre_validation]]
+rule = "max_payload_size"
+max_bytes = 8
+severity = "Critical"
+
+[[pre_validation]]
+rule = "source_allowlist"
+sources = ["relayer-1", "relayer-2"]
+
+[[pre_validation]]
+rule = "priority_cap"
+max_priority = "High"
+severity = "Warning"
+
+[[pre_validation]]
+rule = "allowed_message_types"
+types = ["StateTransition", "Discovery", { Custom = "heartbeat" }]
+
+[[state_validation]]
+id = "transition_chains"
+rule = "required_chain_fields"
+message_types = ["StateTransition"]
+fields = ["SourceChain", "TargetChain"]
+"#;
+
+fn message(source: &str, payload: Vec<u8>) -> FrostMessage {
+    FrostMessage::new(MessageType::Discovery, payload, source.into(), None)
+}
+
+#[tokio::test]
+async fn test_toml_and_json_configs_match() {
+    let from_toml = RuleSetConfig::from_toml(TOML_RULES).unwrap();
+    let json = serde_json::to_string(&from_toml).unwrap();
+    assert_eq!(RuleSetConfig::from_json(&json).unwrap(), from_toml);
+
+    assert_eq!(from_toml.pre_validation.len(), 4);
+    assert_eq!(from_toml.pre_validation[0].spec, RuleSpec::MaxPayloadSize { max_bytes: 8 });
+    assert_eq!(from_toml.pre_validation[1].severity, ValidationSeverity::Error);
+
+    let rules = RuleSet::from_config(from_toml);
+    assert_eq!(rules.len(), 5);
+    assert_eq!(rules.rule_ids(ValidationStage::StateValidation), vec!["transition_chains"]);
+}
+
+#[tokio::test]
+async fn test_invalid_config_rejected() {
+    assert!(RuleSet::from_toml("[[pre_validation]]\nrule = \"no_such_rule\"").is_err());
+    assert!(RuleSet::from_toml("[[pre_validation]]\nrule = \"max_payload_size\"").is_err());
+    assert!(RuleSet::from_json("{ not json").is_err());
+}
+
+#[tokio::test]
+async fn test_severity_and_reporting() {
+    let rules = RuleSet::from_toml(TOML_RULES).unwrap();
+
+    let mut urgent = message("relayer-1", vec![1]);
+    urgent.metadata.priority = MessagePriority::Critical;
+    let result = rules.evaluate(ValidationStage::PreValidation, &urgent).await;
+    // Only a warning failed
+    assert!(result.is_valid);
+    assert_eq!(result.rules_failed.len(), 1);
+    assert_eq!(result.rules_failed[0].rule_id, "priority_cap");
+    assert_eq!(result.rules_passed.len(), 3);
+
+    // Non-critical failures are all reported
+    let mut unknown = message("stranger", vec![1]);
+    unknown.msg_type = MessageType::Custom("other".into());
+    let result = rules.evaluate(ValidationStage::PreValidation, &unknown).await;
+    assert!(!result.is_valid);
+    let failed: Vec<_> = result.rules_failed.iter().map(|f| f.rule_id.as_str()).collect();
+    assert_eq!(failed, vec!["source_allowlist", "allowed_message_types"]);
+    assert!(result.rules_failed[0].reason.contains("stranger"));
+
+    // A critical failure short-circuits the stage
+    let oversized = message("stranger", vec![0; 64]);
+    let result = rules.evaluate(ValidationStage::PreValidation, &oversized).await;
+    assert!(!result.is_valid);
+    assert_eq!(result.rules_failed.len(), 1);
+    assert!(result.rules_passed.is_empty());
+    assert_eq!(result.metadata.unwrap()["short_circuited"], true);
+}
+
+#[tokio::test]
+async fn test_stages_evaluated_in_order() {
+    let rules = RuleSet::from_toml(TOML_RULES).unwrap();
+
+    let mut transition = FrostMessage::new_chain_message(
+        MessageType::StateTransition,
+        vec![1],
+        "relayer-2".into(),
+        None,
+        ChainId::new("ethereum"),
+        ChainId::new("polygon"),
+        None,
+        None,
+        None,
+        None,
+    );
+    let results = rules.evaluate_all(&transition).await;
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|r| r.is_valid));
+
+    transition.target_chain = None;
+    let result = rules.validate(&transition).await.unwrap();
+    assert!(!result.is_valid);
+    assert_eq!(result.stage, ValidationStage::StateValidation);
+    assert!(result.rules_failed[0].reason.contains("TargetChain"));
+
+    // Stops at the first invalid stage
+    let results = rules.evaluate_all(&message("stranger", vec![1])).await;
+    assert_eq!(results.len(), 1);
+}
+
+#[tokio::test]
+async fn test_timestamp_rule_and_removal() {
+    let mut rules = RuleSet::new();
+    rules.add_rule(Box::new(ConfiguredRule::new(RuleConfig {
+        id: None,
+        severity: ValidationSeverity::Error,
+        spec: RuleSpec::TimestampSkew { max_skew_secs: 30 },
+    })));
+    rules.add_stage_rule(ValidationStage::PostValidation, Box::new(ConfiguredRule::new(RuleConfig {
+        id: Some("needs_block".into()),
+        severity: ValidationSeverity::Error,
+        spec: RuleSpec::RequiredChainFields { message_types: vec![], fields: vec![ChainField::BlockRef] },
+    })));
+
+    let mut old = message("node1", vec![1]);
+    old.timestamp -= 120;
+    assert!(!rules.validate(&old).await.unwrap().is_valid);
+
+    rules.remove_rule("timestamp_skew");
+    rules.remove_rule("needs_block");
+    assert!(rules.is_empty());
+    assert!(rules.validate(&old).await.unwrap().is_valid);
+}
+
+#[tokio::test]
+async fn test_pipeline_uses_rules() {
+    let pipeline = BasicValidationPipeline::new().with_rules(RuleSet::from_toml(TOML_RULES).unwrap());
+
+    let mut valid = message("relayer-1", vec![1, 2]);
+    assert!(pipeline.validate(&mut valid).await.is_ok());
+
+    let mut rejected = message("stranger", vec![1, 2]);
+    let err = pipeline.validate(&mut rejected).await.unwrap_err();
+    assert!(err.to_string().contains("not allowlisted"));
+
+    let pre = pipeline.pre_validate(&valid).await;
+    assert_eq!(pre.rules_passed[0], "basic_validation");
+}
@@ -0,0 +1,217 @@
+use frost_protocol::message::{
+    FrostMessage, MessageError, MessageType, SchemaRegistry,
+    schema::{PayloadSchema, ScaleSchema},
+    validation::{BasicValidationPipeline, ValidationPipeline},
+};
+
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Transfer {
+    asset: String,
+    amount: u64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+enum Vote {
+    Aye { weight: u32 },
+    Nay,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+struct Ballot {
+    proposal: [u8; 4],
+    votes: Vec<Vote>,
+    #[codec(compact)]
+    round: u64,
+    note: String,
+}
+
+fn transfer_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "required": ["asset", "amount"],
+        "additionalProperties": false,
+        "properties": {
+            "asset": {"type": "string", "pattern": "^[A-Z]{3,5}$"},
+            "amount": {"type": "integer", "minimum": 1}
+        }
+    })
+}
+
+fn registry() -> SchemaRegistry {
+    let registry = SchemaRegistry::new();
+    registry.register_json("transfer", 1, transfer_schema()).unwrap();
+    registry
+        .register_json("transfer", 2, json!({
+            "type": "object",
+            "required": ["asset", "amount", "memo"],
+            "properties": {"memo": {"type": "string", "maxLength": 8}}
+        }))
+        .unwrap();
+    registry.register_scale::<Ballot>("ballot", 1).unwrap();
+    registry
+}
+
+fn custom(type_name: &str, version: u16, payload: Vec<u8>) -> FrostMessage {
+    let mut message = FrostMessage::new(MessageType::Custom(type_name.into()), payload, "node1".into(), None);
+    message.metadata.version = version;
+    message
+}
+
+#[test]
+fn test_json_payload_validation() {
+    let registry = registry();
+    let transfer = Transfer { asset: "ATOM".into(), amount: 5 };
+
+    let message = registry.encode_json("transfer", 1, &transfer, "node1".into(), None).unwrap();
+    assert_eq!(message.msg_type, MessageType::Custom("transfer".into()));
+    assert_eq!(message.metadata.version, 1);
+    assert_eq!(registry.decode_json::<Transfer>("transfer", &message).unwrap(), transfer);
+
+    for payload in [
+        json!({"asset": "atom", "amount": 5}),
+        json!({"asset": "ATOM", "amount": 0}),
+        json!({"asset": "ATOM"}),
+        json!({"asset": "ATOM", "amount": 5, "extra": true}),
+    ] {
+        let message = custom("transfer", 1, serde_json::to_vec(&payload).unwrap());
+        assert!(matches!(registry.validate(&message), Err(MessageError::SchemaViolation { .. })), "{}", payload);
+    }
+    assert!(registry.validate(&custom("transfer", 1, b"not json".to_vec())).is_err());
+
+    // Encoding refuses values that do not match
+    let invalid = Transfer { asset: "x".into(), amount: 5 };
+    assert!(registry.encode_json("transfer", 1, &invalid, "node1".into(), None).is_err());
+
+    // Version 2 requires a memo
+    let v1_payload = serde_json::to_vec(&transfer).unwrap();
+    assert!(registry.validate(&custom("transfer", 2, v1_payload)).is_err());
+}
+
+#[test]
+fn test_scale_payload_validation() {
+    let registry = registry();
+    let ballot = Ballot {
+        proposal: *b"prop",
+        votes: vec![Vote::Aye { weight: 3 }, Vote::Nay],
+        round: 300,
+        note: "ok".into(),
+    };
+
+    let message = registry.encode_scale("ballot", 1, &ballot, "node1".into(), None).unwrap();
+    assert_eq!(registry.decode_scale::<Ballot>("ballot", &message).unwrap(), ballot);
+
+    let encoded = ballot.encode();
+    // Truncated, trailing bytes and an unknown variant index
+    assert!(registry.validate(&custom("ballot", 1, encoded[..encoded.len() - 1].to_vec())).is_err());
+    let mut trailing = encoded.clone();
+    trailing.push(0);
+    assert!(registry.validate(&custom("ballot", 1, trailing)).is_err());
+    let mut bad_variant = encoded;
+    bad_variant[5] = 7;
+    assert!(matches!(
+        registry.validate(&custom("ballot", 1, bad_variant)),
+        Err(MessageError::SchemaViolation { details, .. }) if details.contains("variant")
+    ));
+
+    let schema = PayloadSchema::from(ScaleSchema::of::<Vote>());
+    assert!(schema.validate(&Vote::Nay.encode()).is_ok());
+    assert!(schema.validate(&[2]).is_err());
+}
+
+#[test]
+fn test_version_negotiation() {
+    let registry = registry();
+    assert_eq!(registry.versions("transfer"), vec![1, 2]);
+    assert_eq!(registry.negotiate("transfer", &[1, 2, 3]), Some(2));
+    assert_eq!(registry.negotiate("transfer", &[1]), Some(1));
+    assert_eq!(registry.negotiate("transfer", &[3]), None);
+    assert_eq!(registry.type_names(), vec!["ballot".to_string(), "transfer".to_string()]);
+
+    assert!(matches!(
+        registry.validate(&custom("transfer", 3, b"{}".to_vec())),
+        Err(MessageError::UnsupportedSchemaVersion { supported, .. }) if supported == vec![1, 2]
+    ));
+
+    // Re-registering a version is an error
+    assert!(registry.register_json("transfer", 1, transfer_schema()).is_err());
+    assert!(registry.register_json("bad", 1, json!({"$ref": "#/defs/x"})).is_err());
+    assert!(registry.register_json("bad", 1, json!({"pattern": "("})).is_err());
+}
+
+#[test]
+fn test_unsupported_json_keywords_rejected() {
+    let registry = SchemaRegistry::new();
+    for schema in [
+        json!({"type": "object", "patternProperties": {"^x": {"type": "string"}}}),
+        json!({"if": {"type": "string"}, "then": {"maxLength": 3}}),
+        json!({"properties": {"nested": {"type": "string", "format": "email"}}}),
+        json!({"type": "strnig"}),
+        json!({"minimum": "3"}),
+        json!({"required": "asset"}),
+    ] {
+        assert!(registry.register_json("bad", 1, schema.clone()).is_err(), "accepted {}", schema);
+    }
+
+    registry
+        .register_json("annotated", 1, json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Annotated",
+            "oneOf": [{"type": "string"}, {"type": "integer"}]
+        }))
+        .unwrap();
+    assert!(registry.validate(&custom("annotated", 1, b"\"x\"".to_vec())).is_ok());
+    assert!(registry.validate(&custom("annotated", 1, b"true".to_vec())).is_err());
+}
+
+#[test]
+fn test_zero_sized_sequences_are_capped() {
+    let schema = PayloadSchema::from(ScaleSchema::of::<Vec<()>>());
+    assert!(schema.validate(&vec![(); 16].encode()).is_ok());
+    assert!(schema.validate(&parity_scale_codec::Compact(u32::MAX).encode()).is_err());
+}
+
+#[test]
+fn test_compact_integers_checked_at_inner_width() {
+    use parity_scale_codec::Compact;
+
+    let schema = PayloadSchema::from(ScaleSchema::of::<Compact<u8>>());
+    assert!(schema.validate(&Compact(255u8).encode()).is_ok());
+    assert!(schema.validate(&Compact(256u32).encode()).is_err());
+
+    let schema = PayloadSchema::from(ScaleSchema::of::<Compact<u32>>());
+    assert!(schema.validate(&Compact(u32::MAX).encode()).is_ok());
+    assert!(schema.validate(&Compact(u64::from(u32::MAX) + 1).encode()).is_err());
+}
+
+#[test]
+fn test_unregistered_types() {
+    let message = custom("unknown", 1, vec![1, 2, 3]);
+    assert!(registry().validate(&message).is_ok());
+
+    let strict = SchemaRegistry::new().with_unregistered_rejected(true);
+    assert!(matches!(strict.validate(&message), Err(MessageError::SchemaViolation { .. })));
+
+    // Non-custom messages are not checked
+    let discovery = FrostMessage::new(MessageType::Discovery, vec![1], "node1".into(), None);
+    assert!(strict.validate(&discovery).is_ok());
+}
+
+#[tokio::test]
+async fn test_pipeline_validates_custom_payloads() {
+    let pipeline = BasicValidationPipeline::new().with_schema_registry(Arc::new(registry()));
+
+    let valid = custom("transfer", 1, serde_json::to_vec(&json!({"asset": "DOT", "amount": 1})).unwrap());
+    let result = pipeline.pre_validate(&valid).await;
+    assert!(result.is_valid);
+    assert!(result.rules_passed.contains(&"payload_schema".to_string()));
+
+    let invalid = custom("transfer", 1, serde_json::to_vec(&json!({"asset": "DOT"})).unwrap());
+    let result = pipeline.pre_validate(&invalid).await;
+    assert!(!result.is_valid);
+    assert_eq!(result.rules_failed[0].rule_id, "payload_schema");
+}
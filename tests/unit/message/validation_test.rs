@@ -3,13 +3,21 @@ use frost_protocol::{
         FrostMessage,
         MessageType,
         MessageValidator,
-        validation::{ValidationRule, ValidationSeverity, ValidationResult, ValidationStage, ValidationFailure},
+        validation::{
+            ValidationPipeline, ValidationRule, ValidationSeverity, ValidationResult, ValidationStage,
+            ValidationFailure,
+        },
     },
+    extensions::{DefaultExtensionManager, ExtensionHooks},
+    network::{BasicNetwork, NetworkConfig},
     state::{ChainId, StateTransition, BlockId},
     Result,
 };
 
 use async_trait::async_trait;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::RwLock;
 
 // Custom validation rule implementation
 struct PayloadSizeRule {
@@ -228,4 +236,76 @@ async fn test_message_size_limits() {
     let result = validator.validate(&msg).await.unwrap();
     assert!(!result.is_valid, "Large message should fail validation");
     assert!(!result.rules_failed.is_empty(), "Should have failed size rule");
-} 
\ No newline at end of file
+} 
+
+// Pipeline overriding only the original result hook
+struct LegacyPipeline {
+    hooks: ExtensionHooks,
+    processed: AtomicUsize,
+}
+
+impl LegacyPipeline {
+    fn new() -> Self {
+        Self {
+            hooks: ExtensionHooks::new(
+                Arc::new(RwLock::new(DefaultExtensionManager::new())),
+                Arc::new(BasicNetwork::new(NetworkConfig::default())),
+            ),
+            processed: AtomicUsize::new(0),
+        }
+    }
+
+    fn pass(stage: ValidationStage) -> ValidationResult {
+        ValidationResult {
+            is_valid: true,
+            rules_passed: vec![],
+            rules_failed: vec![],
+            stage,
+            duration_ms: 0,
+            metadata: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidationPipeline for LegacyPipeline {
+    fn installed_extension_hooks(&self) -> Option<&ExtensionHooks> {
+        Some(&self.hooks)
+    }
+
+    async fn pre_validate(&self, _msg: &FrostMessage) -> ValidationResult {
+        Self::pass(ValidationStage::PreValidation)
+    }
+
+    async fn validate_proof(&self, _msg: &FrostMessage) -> ValidationResult {
+        Self::pass(ValidationStage::ProofValidation)
+    }
+
+    async fn validate_state(&self, _msg: &FrostMessage) -> ValidationResult {
+        Self::pass(ValidationStage::StateValidation)
+    }
+
+    async fn post_validate(&self, _msg: &FrostMessage) -> ValidationResult {
+        Self::pass(ValidationStage::PostValidation)
+    }
+
+    async fn process_validation_result(
+        &self,
+        _msg: &mut FrostMessage,
+        _result: &ValidationResult,
+        _stage: ValidationStage,
+    ) -> Result<()> {
+        self.processed.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_legacy_pipeline_overrides_still_apply() {
+    let pipeline = LegacyPipeline::new();
+    let mut msg = FrostMessage::new(MessageType::StateTransition, vec![1], "node".into(), None);
+
+    pipeline.validate(&mut msg).await.unwrap();
+    pipeline.validate_from(uuid::Uuid::new_v4(), &mut msg).await.unwrap();
+    assert_eq!(pipeline.processed.load(Ordering::SeqCst), 8);
+}
@@ -0,0 +1,708 @@
+use frost_protocol::message::{
+    FrostMessage, MessageError, MessageType,
+    cosmos::{self, AddressKind},
+    ethereum::{self, Abi, EthereumTransaction, TransactionType},
+    solana::{self, MessageVersion, SolanaTransaction},
+    validator::{CosmosValidator, EthereumValidator, MessageValidator, SolanaValidator, ValidatorConfig},
+};
+
+use serde_json::json;
+
+const TOKEN: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+const RECIPIENT: &str = "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359";
+
+fn rlp_prefix(offset: u8, len: usize) -> Vec<u8> {
+    if len <= 55 {
+        return vec![offset + len as u8];
+    }
+    let len = (len as u64).to_be_bytes();
+    let start = len.iter().position(|b| *b != 0).unwrap();
+    let mut prefix = vec![offset + 55 + (8 - start) as u8];
+    prefix.extend_from_slice(&len[start..]);
+    prefix
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_prefix(0x80, bytes.len());
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn rlp_uint(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(8);
+    rlp_bytes(&bytes[start..])
+}
+
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload: Vec<u8> = items.concat();
+    let mut out = rlp_prefix(0xc0, payload.len());
+    out.extend(payload);
+    out
+}
+
+fn transfer_calldata(to: &str, amount: u64) -> Vec<u8> {
+    let mut data = hex::decode("a9059cbb").unwrap();
+    data.extend([0u8; 12]);
+    data.extend(ethereum::parse_address(to).unwrap());
+    data.extend([0u8; 24]);
+    data.extend(amount.to_be_bytes());
+    data
+}
+
+fn eip1559_tx(chain_id: u64, to: &str, data: &[u8]) -> Vec<u8> {
+    let mut tx = vec![0x02];
+    tx.extend(rlp_list(&[
+        rlp_uint(chain_id),
+        rlp_uint(7),
+        rlp_uint(1_000_000_000),
+        rlp_uint(30_000_000_000),
+        rlp_uint(60_000),
+        rlp_bytes(&ethereum::parse_address(to).unwrap()),
+        rlp_uint(0),
+        rlp_bytes(data),
+        rlp_list(&[]),
+        rlp_uint(1),
+        rlp_bytes(&[0x11; 32]),
+        rlp_bytes(&[0x22; 32]),
+    ]));
+    tx
+}
+
+fn erc20_abi() -> serde_json::Value {
+    json!([
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [
+                {"name": "to", "type": "address"},
+                {"name": "amount", "type": "uint256"}
+            ]
+        },
+        {"type": "event", "name": "Transfer", "inputs": []}
+    ])
+}
+
+fn validator(chain_params: serde_json::Value) -> EthereumValidator {
+    EthereumValidator::new(ValidatorConfig {
+        max_message_size: 4096,
+        chain_params,
+    })
+    .unwrap()
+}
+
+fn message(payload: Vec<u8>, payload_type: &str) -> FrostMessage {
+    let mut message = FrostMessage::new(MessageType::StateTransition, payload, "node1".into(), None);
+    message.metadata.chain_metadata = Some(json!({"chain": "ethereum", "payload_type": payload_type}));
+    message
+}
+
+#[test]
+fn test_checksum_addresses() {
+    let address = ethereum::parse_address(TOKEN).unwrap();
+    assert_eq!(ethereum::to_checksum_address(&address), TOKEN);
+    assert!(ethereum::parse_address(&TOKEN.to_lowercase()).is_ok());
+    assert!(ethereum::parse_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+    assert!(ethereum::parse_address("0x1234").is_err());
+}
+
+#[test]
+fn test_abi_decoding_and_type_checks() {
+    let abi = Abi::from_json(&json!([{
+        "name": "submit",
+        "inputs": [
+            {"name": "flag", "type": "bool"},
+            {"name": "delta", "type": "int8"},
+            {"name": "note", "type": "string"},
+            {"name": "ids", "type": "uint16[]"}
+        ]
+    }]))
+    .unwrap();
+    let function = abi.function(&ethereum::keccak256(b"submit(bool,int8,string,uint16[])")[..4].try_into().unwrap());
+    assert_eq!(function.unwrap().signature(), "submit(bool,int8,string,uint16[])");
+
+    let selector = &ethereum::keccak256(b"submit(bool,int8,string,uint16[])")[..4];
+    let word = |last: &[u8]| {
+        let mut w = [0u8; 32];
+        w[32 - last.len()..].copy_from_slice(last);
+        w.to_vec()
+    };
+    let mut data = selector.to_vec();
+    data.extend(word(&[1]));
+    data.extend([0xff; 32]);
+    data.extend(word(&[0x80]));
+    data.extend(word(&[0xc0]));
+    data.extend(word(&[2]));
+    data.extend(b"hi".iter().copied().chain([0u8; 30]));
+    data.extend(word(&[2]));
+    data.extend(word(&[0x01, 0x00]));
+    data.extend(word(&[0x02, 0x00]));
+
+    let call = abi.decode_call(&data).unwrap();
+    assert_eq!(call.function, "submit");
+    assert_eq!(call.args[0], ("flag".to_string(), json!(true)));
+    assert_eq!(call.args[1], ("delta".to_string(), json!("-1")));
+    assert_eq!(call.args[2], ("note".to_string(), json!("hi")));
+    assert_eq!(call.args[3], ("ids".to_string(), json!(["256", "512"])));
+
+    // Bool outside 0/1
+    let mut bad_bool = data.clone();
+    bad_bool[4 + 31] = 2;
+    assert!(abi.decode_call(&bad_bool).is_err());
+
+    // Dynamic offset past the end
+    let mut bad_offset = data.clone();
+    bad_offset[4 + 2 * 32 + 31] = 0xf0;
+    assert!(abi.decode_call(&bad_offset).is_err());
+
+    // Array element too wide for uint16
+    let mut bad_uint = data;
+    let last = bad_uint.len() - 3;
+    bad_uint[last] = 1;
+    assert!(abi.decode_call(&bad_uint).is_err());
+}
+
+#[test]
+fn test_abi_rejects_aliased_offsets() {
+    let abi = Abi::from_json(&json!([
+        {"name": "pair", "inputs": [{"type": "string"}, {"type": "string"}]},
+        {"name": "nested", "inputs": [{"type": "uint256[][]"}]}
+    ]))
+    .unwrap();
+    let word = |value: usize| {
+        let mut w = [0u8; 32];
+        w[24..].copy_from_slice(&(value as u64).to_be_bytes());
+        w.to_vec()
+    };
+
+    let pair = &ethereum::keccak256(b"pair(string,string)")[..4];
+    let encode_pair = |first: usize, second: usize| {
+        let mut data = pair.to_vec();
+        data.extend(word(first));
+        data.extend(word(second));
+        data.extend(word(2));
+        data.extend(b"hi".iter().copied().chain([0u8; 30]));
+        data.extend(word(2));
+        data.extend(b"yo".iter().copied().chain([0u8; 30]));
+        data
+    };
+    let call = abi.decode_call(&encode_pair(0x40, 0x80)).unwrap();
+    assert_eq!(call.args[1].1, json!("yo"));
+
+    // Both heads pointing at the same tail
+    assert!(abi.decode_call(&encode_pair(0x40, 0x40)).is_err());
+    // Tails out of order, or pointing back into the head
+    assert!(abi.decode_call(&encode_pair(0x80, 0x40)).is_err());
+    assert!(abi.decode_call(&encode_pair(0x00, 0x80)).is_err());
+
+    // Every element of an outer array pointing at one shared inner array
+    let count = 64;
+    let mut data = ethereum::keccak256(b"nested(uint256[][])")[..4].to_vec();
+    data.extend(word(0x20));
+    data.extend(word(count));
+    for _ in 0..count {
+        data.extend(word(count * 32));
+    }
+    data.extend(word(count));
+    data.extend(std::iter::repeat_n(0u8, count * 32));
+    assert!(abi.decode_call(&data).is_err());
+}
+
+#[test]
+fn test_abi_rejects_oversized_fixed_arrays() {
+    // 2^59 words of 32 bytes overflow a 64-bit head size
+    assert!(ethereum::AbiType::parse("uint256[576460752303423488]").is_err());
+    assert!(ethereum::AbiType::parse("uint256[4][144115188075855872]").is_err());
+    assert!(ethereum::AbiType::parse("uint256[3][2]").is_ok());
+    assert!(Abi::from_json(&json!([
+        {"name": "huge", "inputs": [{"type": "tuple[2]", "components": [
+            {"type": "uint256[288230376151711744]"},
+            {"type": "uint256[288230376151711744]"}
+        ]}]}
+    ]))
+    .is_err());
+}
+
+#[test]
+fn test_abi_caps_decoded_elements() {
+    let abi = Abi::from_json(&json!([{"name": "many", "inputs": [{"type": "uint8[]"}]}])).unwrap();
+    let mut data = ethereum::keccak256(b"many(uint8[])")[..4].to_vec();
+    let count = 70_000u64;
+    data.extend([0u8; 31].iter().copied().chain([0x20]));
+    data.extend([0u8; 24].iter().copied().chain(count.to_be_bytes()));
+    data.extend(std::iter::repeat_n(0u8, count as usize * 32));
+    assert!(abi.decode_call(&data).is_err());
+}
+
+#[test]
+fn test_decode_typed_and_legacy_transactions() {
+    let tx = EthereumTransaction::decode(&eip1559_tx(1, TOKEN, &[])).unwrap();
+    assert_eq!(tx.tx_type, TransactionType::DynamicFee);
+    assert_eq!(tx.chain_id, Some(1));
+    assert_eq!(tx.nonce, 7);
+    assert_eq!(tx.max_fee_per_gas, Some(30_000_000_000));
+    assert_eq!(tx.to, Some(ethereum::parse_address(TOKEN).unwrap()));
+
+    let mut access_list_tx = vec![0x01];
+    access_list_tx.extend(rlp_list(&[
+        rlp_uint(5),
+        rlp_uint(0),
+        rlp_uint(20_000_000_000),
+        rlp_uint(21_000),
+        rlp_bytes(&[]),
+        rlp_uint(0),
+        rlp_bytes(&[0x60, 0x00]),
+        rlp_list(&[rlp_list(&[rlp_bytes(&[0xaa; 20]), rlp_list(&[rlp_bytes(&[0x01; 32])])])]),
+        rlp_uint(0),
+        rlp_bytes(&[0x11; 32]),
+        rlp_bytes(&[0x22; 32]),
+    ]));
+    let tx = EthereumTransaction::decode(&access_list_tx).unwrap();
+    assert_eq!(tx.tx_type, TransactionType::AccessList);
+    assert_eq!(tx.to, None);
+    assert_eq!(tx.access_list.len(), 1);
+    assert_eq!(tx.access_list[0].1, vec![[0x01; 32]]);
+
+    // EIP-155 legacy transaction on chain 1 (v = 37)
+    let legacy_fields = |nonce: Vec<u8>| rlp_list(&[
+        nonce,
+        rlp_uint(20_000_000_000),
+        rlp_uint(21_000),
+        rlp_bytes(&[0x35; 20]),
+        rlp_uint(1_000_000_000_000_000_000),
+        rlp_bytes(&[]),
+        rlp_uint(37),
+        rlp_bytes(&[0x11; 32]),
+        rlp_bytes(&[0x22; 32]),
+    ]);
+    let tx = EthereumTransaction::decode(&legacy_fields(rlp_uint(9))).unwrap();
+    assert_eq!(tx.tx_type, TransactionType::Legacy);
+    assert_eq!(tx.chain_id, Some(1));
+    assert_eq!(tx.to_json()["value"], "1000000000000000000");
+
+    // Truncated and non-canonical encodings
+    let raw = eip1559_tx(1, TOKEN, &[]);
+    assert!(EthereumTransaction::decode(&raw[..raw.len() - 1]).is_err());
+    // Nonce 9 encoded as a one-byte string instead of a single byte
+    assert!(EthereumTransaction::decode(&legacy_fields(vec![0x81, 0x09])).is_err());
+    assert!(EthereumTransaction::decode(&[0x03, 0xc0]).is_err());
+}
+
+#[tokio::test]
+async fn test_validator_checks_calldata_against_abi() {
+    let validator = validator(json!({"abi": erc20_abi()}));
+
+    let calldata = transfer_calldata(RECIPIENT, 1000);
+    validator.validate_message(&message(calldata.clone(), "calldata")).await.unwrap();
+
+    // Unknown selector
+    let mut unknown = calldata.clone();
+    unknown[0] = 0;
+    assert!(matches!(
+        validator.validate_message(&message(unknown, "calldata")).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("Unknown function selector")
+    ));
+
+    // Address argument with dirty padding
+    let mut dirty = calldata;
+    dirty[4] = 1;
+    assert!(validator.validate_message(&message(dirty, "calldata")).await.is_err());
+
+    let metrics = validator.get_metrics().await;
+    assert_eq!(metrics.total_validated, 3);
+    assert_eq!(metrics.failed_validations, 2);
+    let ethereum = &metrics.chain_metrics["ethereum"];
+    assert_eq!(ethereum["decoded_calls"], 1);
+    assert_eq!(ethereum["last_call"]["function"], "transfer");
+    assert_eq!(ethereum["last_call"]["args"]["to"], RECIPIENT);
+    assert_eq!(ethereum["last_call"]["args"]["amount"], "1000");
+}
+
+#[tokio::test]
+async fn test_validator_checks_transactions() {
+    let validator = validator(json!({
+        "abi": erc20_abi(),
+        "chain_id": 1,
+        "allowed_addresses": [TOKEN],
+        "payload_type": "transaction"
+    }));
+    let call = transfer_calldata(RECIPIENT, 5);
+
+    let mut tx = message(eip1559_tx(1, TOKEN, &call), "transaction");
+    tx.metadata.chain_metadata = Some(json!({"chain": "ethereum"}));
+    validator.validate_message(&tx).await.unwrap();
+
+    let wrong_chain = message(eip1559_tx(137, TOKEN, &call), "transaction");
+    assert!(validator.validate_message(&wrong_chain).await.is_err());
+
+    let wrong_recipient = message(eip1559_tx(1, RECIPIENT, &call), "transaction");
+    assert!(validator.validate_message(&wrong_recipient).await.is_err());
+
+    let mut other_chain = message(eip1559_tx(1, TOKEN, &call), "transaction");
+    other_chain.metadata.chain_metadata = Some(json!({"chain": "solana"}));
+    assert!(validator.validate_message(&other_chain).await.is_err());
+
+    // Senders cannot downgrade a transaction to calldata to skip the checks
+    let disguised = message(eip1559_tx(137, RECIPIENT, &call), "calldata");
+    assert!(matches!(
+        validator.validate_message(&disguised).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("configured payload type")
+    ));
+
+    let metrics = validator.get_metrics().await;
+    let ethereum = &metrics.chain_metrics["ethereum"];
+    assert_eq!(ethereum["decoded_transactions"], 1);
+    assert_eq!(ethereum["last_transaction"]["type"], "DynamicFee");
+    assert_eq!(ethereum["last_transaction"]["to"], TOKEN);
+    assert_eq!(ethereum["last_call"]["args"]["amount"], "5");
+}
+
+#[tokio::test]
+async fn test_invalid_chain_params_are_rejected() {
+    let config = |chain_params| ValidatorConfig { max_message_size: 1024, chain_params };
+
+    assert!(EthereumValidator::new(config(json!({"allowed_addresses": ["0xnot-an-address"]}))).is_err());
+    assert!(EthereumValidator::new(config(json!({"abi": [{"name": "f", "inputs": [{"type": "uint7"}]}]}))).is_err());
+    assert!(EthereumValidator::new(config(json!({"chain_id": "mainnet"}))).is_err());
+
+    let mut validator = validator(json!({}));
+    assert!(validator.update_config(config(json!({"payload_type": "blob"}))).await.is_err());
+    validator.update_config(config(json!({"chain_id": 10}))).await.unwrap();
+}
+
+fn shortvec(len: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut value = len;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Serialized Solana message: `signers` sign, `program` is invoked with every account
+fn solana_message(signers: &[[u8; 32]], program: [u8; 32], v0: bool) -> Vec<u8> {
+    let mut keys: Vec<[u8; 32]> = signers.to_vec();
+    keys.push(program);
+
+    let mut message = Vec::new();
+    if v0 {
+        message.push(0x80);
+    }
+    message.extend([signers.len() as u8, 0, 1]);
+    message.extend(shortvec(keys.len()));
+    keys.iter().for_each(|k| message.extend(k));
+    message.extend([7u8; 32]);
+
+    let program_index = signers.len() as u8;
+    let mut accounts: Vec<u8> = (0..program_index).collect();
+    if v0 {
+        accounts.push(program_index + 1);
+    }
+    message.extend(shortvec(1));
+    message.push(program_index);
+    message.extend(shortvec(accounts.len()));
+    message.extend(&accounts);
+    message.extend(shortvec(2));
+    message.extend([1, 2]);
+
+    if v0 {
+        message.extend(shortvec(1));
+        message.extend([9u8; 32]);
+        message.extend(shortvec(1));
+        message.push(0);
+        message.extend(shortvec(0));
+    }
+    message
+}
+
+fn solana_tx(signers: &[ed25519_dalek::SigningKey], program: [u8; 32], v0: bool) -> Vec<u8> {
+    let keys: Vec<[u8; 32]> = signers.iter().map(|k| k.verifying_key().to_bytes()).collect();
+    let message = solana_message(&keys, program, v0);
+    let mut tx = shortvec(signers.len());
+    for key in signers {
+        tx.extend(ed25519_dalek::Signer::sign(key, &message).to_bytes());
+    }
+    tx.extend(message);
+    tx
+}
+
+fn solana_validator(chain_params: serde_json::Value) -> SolanaValidator {
+    SolanaValidator::new(ValidatorConfig {
+        max_message_size: 1232,
+        chain_params,
+    })
+    .unwrap()
+}
+
+fn solana_message_for(payload: Vec<u8>) -> FrostMessage {
+    let mut message = FrostMessage::new(MessageType::StateTransition, payload, "node1".into(), None);
+    message.metadata.chain_metadata = Some(json!({"chain": "solana"}));
+    message
+}
+
+#[test]
+fn test_decode_solana_transactions() {
+    let payer = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+    let program = [3u8; 32];
+
+    let legacy = SolanaTransaction::decode(&solana_tx(std::slice::from_ref(&payer), program, false)).unwrap();
+    assert_eq!(legacy.version, MessageVersion::Legacy);
+    assert_eq!(legacy.signers(), &[payer.verifying_key().to_bytes()]);
+    assert_eq!(legacy.program_ids().collect::<Vec<_>>(), vec![&program]);
+    legacy.verify_signatures().unwrap();
+
+    let v0 = SolanaTransaction::decode(&solana_tx(&[payer], program, true)).unwrap();
+    assert_eq!(v0.version, MessageVersion::V0);
+    assert_eq!(v0.address_table_lookups.len(), 1);
+    assert_eq!(v0.total_account_count(), 3);
+    v0.verify_signatures().unwrap();
+
+    assert!(SolanaTransaction::decode(&[]).is_err());
+    assert!(SolanaTransaction::decode(&[0x80, 0x80, 0x00]).is_err());
+}
+
+#[tokio::test]
+async fn test_solana_validator_checks_signatures_and_programs() {
+    let payer = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+    let cosigner = ed25519_dalek::SigningKey::from_bytes(&[2; 32]);
+    let program = [3u8; 32];
+    let validator = solana_validator(json!({
+        "allowed_programs": [solana::encode_pubkey(&program)],
+        "max_accounts": 3
+    }));
+
+    let tx = solana_tx(std::slice::from_ref(&payer), program, true);
+    validator.validate_message(&solana_message_for(tx.clone())).await.unwrap();
+
+    // Tampered instruction data
+    let mut tampered = tx;
+    let last = tampered.len() - 37;
+    tampered[last] ^= 1;
+    assert!(matches!(
+        validator.validate_message(&solana_message_for(tampered)).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("Invalid signature")
+    ));
+
+    // Program outside the allowlist
+    let other_program = solana_tx(std::slice::from_ref(&payer), [4u8; 32], false);
+    assert!(validator.validate_message(&solana_message_for(other_program)).await.is_err());
+
+    // Too many accounts
+    let crowded = solana_tx(&[payer.clone(), cosigner], program, true);
+    assert!(validator.validate_message(&solana_message_for(crowded)).await.is_err());
+
+    let metrics = validator.get_metrics().await;
+    assert_eq!(metrics.total_validated, 4);
+    assert_eq!(metrics.failed_validations, 3);
+    let solana = &metrics.chain_metrics["solana"];
+    assert_eq!(solana["decoded_transactions"], 1);
+    assert_eq!(solana["last_transaction"]["version"], "V0");
+    assert_eq!(solana["last_transaction"]["fee_payer"], solana::encode_pubkey(&payer.verifying_key().to_bytes()));
+}
+
+#[tokio::test]
+async fn test_solana_validator_rejects_duplicate_signers() {
+    let payer = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+    let validator = solana_validator(json!({"verify_signatures": false}));
+
+    let duplicated = solana_tx(&[payer.clone(), payer], [3u8; 32], false);
+    assert!(matches!(
+        validator.validate_message(&solana_message_for(duplicated)).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("Duplicate signer")
+    ));
+
+    assert!(SolanaValidator::new(ValidatorConfig {
+        max_message_size: 1232,
+        chain_params: json!({"allowed_programs": ["not base58!"]}),
+    })
+    .is_err());
+}
+
+fn any<M: prost::Message>(type_url: &str, msg: &M) -> cosmos::Any {
+    cosmos::Any { type_url: type_url.into(), value: msg.encode_to_vec() }
+}
+
+fn cosmos_address(prefix: &str, seed: u8) -> String {
+    cosmos::bech32_encode(prefix, &[seed; 20])
+}
+
+fn msg_send(from: &str, to: &str) -> cosmos::Any {
+    any(cosmos::MSG_SEND, &cosmos::MsgSend {
+        from_address: from.into(),
+        to_address: to.into(),
+        amount: vec![cosmos::Coin { denom: "uatom".into(), amount: "100".into() }],
+    })
+}
+
+fn cosmos_tx(messages: Vec<cosmos::Any>, signer_infos: usize, signatures: usize) -> Vec<u8> {
+    use prost::Message;
+
+    let body = cosmos::TxBody { messages, memo: "relay".into(), ..Default::default() };
+    let auth_info = cosmos::AuthInfo {
+        signer_infos: vec![cosmos::SignerInfo { public_key: None, sequence: 1 }; signer_infos],
+        fee: Some(cosmos::Fee { gas_limit: 200_000, ..Default::default() }),
+    };
+    cosmos::TxRaw {
+        body_bytes: body.encode_to_vec(),
+        auth_info_bytes: auth_info.encode_to_vec(),
+        signatures: vec![vec![0xab; 64]; signatures],
+    }
+    .encode_to_vec()
+}
+
+fn cosmos_validator(chain_params: serde_json::Value) -> CosmosValidator {
+    CosmosValidator::new(ValidatorConfig {
+        max_message_size: 65536,
+        chain_params,
+    })
+    .unwrap()
+}
+
+fn cosmos_message(payload: Vec<u8>) -> FrostMessage {
+    let mut message = FrostMessage::new(MessageType::StateTransition, payload, "node1".into(), None);
+    message.metadata.chain_metadata = Some(json!({"chain": "cosmos"}));
+    message
+}
+
+#[test]
+fn test_bech32_addresses() {
+    let address = cosmos_address("cosmos", 7);
+    assert_eq!(cosmos::bech32_decode(&address).unwrap(), ("cosmos".to_string(), vec![7; 20]));
+    assert!(cosmos::check_address(&address, "cosmos", AddressKind::Account).is_ok());
+    assert!(cosmos::check_address(&address, "osmo", AddressKind::Account).is_err());
+    assert!(cosmos::check_address(&address, "cosmos", AddressKind::Validator).is_err());
+    assert!(cosmos::check_address(&cosmos_address("cosmosvaloper", 7), "cosmos", AddressKind::Validator).is_ok());
+
+    // Corrupted checksum and mixed case
+    let mut corrupted = address.clone().into_bytes();
+    let last = corrupted.len() - 1;
+    corrupted[last] = if corrupted[last] == b'q' { b'p' } else { b'q' };
+    assert!(cosmos::bech32_decode(std::str::from_utf8(&corrupted).unwrap()).is_err());
+    assert!(cosmos::bech32_decode(&format!("COSMOS{}", &address[6..])).is_err());
+    assert!(cosmos::bech32_decode(&address.to_uppercase()).is_ok());
+
+    // BIP-173 test vector
+    let (hrp, data) = cosmos::bech32_decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+    assert_eq!(hrp, "abcdef");
+    assert_eq!(cosmos::bech32_encode(&hrp, &data), "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw");
+}
+
+#[tokio::test]
+async fn test_cosmos_validator_checks_type_urls_and_prefixes() {
+    let validator = cosmos_validator(json!({
+        "allowed_type_urls": [cosmos::MSG_SEND, cosmos::MSG_TRANSFER, cosmos::MSG_EXEC],
+        "bech32_prefix": "cosmos"
+    }));
+    let alice = cosmos_address("cosmos", 1);
+    let bob = cosmos_address("cosmos", 2);
+
+    let transfer = any(cosmos::MSG_TRANSFER, &cosmos::MsgTransfer {
+        source_port: "transfer".into(),
+        source_channel: "channel-0".into(),
+        token: None,
+        sender: alice.clone(),
+        receiver: cosmos_address("osmo", 3),
+    });
+    validator
+        .validate_message(&cosmos_message(cosmos_tx(vec![msg_send(&alice, &bob), transfer], 1, 1)))
+        .await
+        .unwrap();
+
+    // Critical extension options need an allowed type URL too
+    let strict = cosmos_validator(json!({"allowed_type_urls": [cosmos::MSG_SEND]}));
+    let with_extension = |type_url: &str| {
+        let body = cosmos::TxBody {
+            messages: vec![msg_send(&alice, &bob)],
+            extension_options: vec![cosmos::Any { type_url: type_url.into(), value: vec![] }],
+            ..Default::default()
+        };
+        cosmos::TxRaw {
+            body_bytes: prost::Message::encode_to_vec(&body),
+            auth_info_bytes: prost::Message::encode_to_vec(&cosmos::AuthInfo {
+                signer_infos: vec![cosmos::SignerInfo { public_key: None, sequence: 1 }],
+                fee: None,
+            }),
+            signatures: vec![vec![0xab; 64]],
+        }
+    };
+    let unknown = prost::Message::encode_to_vec(&with_extension("/ethermint.evm.v1.ExtensionOptionsEthereumTx"));
+    assert!(matches!(
+        strict.validate_message(&cosmos_message(unknown)).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("Extension option")
+    ));
+    let known = prost::Message::encode_to_vec(&with_extension(cosmos::MSG_SEND));
+    strict.validate_message(&cosmos_message(known)).await.unwrap();
+
+    // Wrong prefix
+    let foreign = msg_send(&alice, &cosmos_address("osmo", 2));
+    assert!(validator.validate_message(&cosmos_message(cosmos_tx(vec![foreign], 1, 1))).await.is_err());
+
+    // Disallowed type, including when wrapped in MsgExec
+    let delegate = any(cosmos::MSG_DELEGATE, &cosmos::MsgDelegate {
+        delegator_address: alice.clone(),
+        validator_address: cosmos_address("cosmosvaloper", 9),
+    });
+    assert!(validator
+        .validate_message(&cosmos_message(cosmos_tx(vec![delegate.clone()], 1, 1)))
+        .await
+        .is_err());
+    let exec = any(cosmos::MSG_EXEC, &cosmos::MsgExec { grantee: bob.clone(), msgs: vec![delegate] });
+    assert!(matches!(
+        validator.validate_message(&cosmos_message(cosmos_tx(vec![exec], 1, 1))).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains(cosmos::MSG_DELEGATE)
+    ));
+
+    let metrics = validator.get_metrics().await;
+    assert_eq!(metrics.total_validated, 4);
+    assert_eq!(metrics.failed_validations, 3);
+    let decoded = &metrics.chain_metrics["cosmos"];
+    assert_eq!(decoded["decoded_transactions"], 1);
+    assert_eq!(decoded["last_transaction"]["messages"], json!([cosmos::MSG_SEND, cosmos::MSG_TRANSFER]));
+    assert_eq!(decoded["last_transaction"]["gas_limit"], 200_000);
+}
+
+#[tokio::test]
+async fn test_cosmos_validator_checks_signatures_and_bodies() {
+    let validator = cosmos_validator(json!({"max_messages": 2}));
+    let alice = cosmos_address("cosmos", 1);
+    let send = || msg_send(&alice, &alice);
+
+    // Signer infos must match signatures
+    assert!(matches!(
+        validator.validate_message(&cosmos_message(cosmos_tx(vec![send()], 2, 1))).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("signer infos")
+    ));
+    assert!(validator.validate_message(&cosmos_message(cosmos_tx(vec![send()], 0, 0))).await.is_err());
+    assert!(validator.validate_message(&cosmos_message(cosmos_tx(vec![send(); 3], 1, 1))).await.is_err());
+    assert!(validator.validate_message(&cosmos_message(cosmos_tx(vec![], 1, 1))).await.is_err());
+    assert!(validator.validate_message(&cosmos_message(vec![0xff, 0xff, 0xff])).await.is_err());
+
+    // Messages cannot switch to bare TxBody to skip the signature checks
+    let body = prost::Message::encode_to_vec(&cosmos::TxBody { messages: vec![send()], ..Default::default() });
+    let mut message = cosmos_message(body);
+    message.metadata.chain_metadata = Some(json!({"chain": "cosmos", "payload_type": "tx_body"}));
+    assert!(matches!(
+        validator.validate_message(&message).await,
+        Err(MessageError::InvalidFormat(reason)) if reason.contains("configured payload type")
+    ));
+
+    // Bare TxBody selected by the operator
+    let bodies = cosmos_validator(json!({"payload_type": "tx_body"}));
+    bodies.validate_message(&message).await.unwrap();
+    message.metadata.chain_metadata = Some(json!({"chain": "cosmos"}));
+    bodies.validate_message(&message).await.unwrap();
+
+    assert!(CosmosValidator::new(ValidatorConfig {
+        max_message_size: 1024,
+        chain_params: json!({"bech32_prefix": "Cosmos"}),
+    })
+    .is_err());
+}
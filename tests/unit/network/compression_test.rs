@@ -0,0 +1,185 @@
+use frost_protocol::network::{
+    compression::{negotiate, Lz4Codec, ZstdCodec},
+    transport::{CompressionConfig, EncryptionConfig, TransportProtocol},
+    Codec, CodecRegistry, TcpTransport, Transport, TransportConfig, WebSocketTransport,
+};
+use frost_protocol::Result;
+
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Trivial codec that reverses the payload, to exercise registration
+struct ReverseCodec;
+
+impl Codec for ReverseCodec {
+    fn name(&self) -> &str {
+        "reverse"
+    }
+
+    fn compress(&self, data: &[u8], _level: u8) -> Result<Vec<u8>> {
+        Ok(data.iter().rev().copied().take(data.len() - 1).collect())
+    }
+
+    fn decompress(&self, data: &[u8], _max_size: usize) -> Result<Vec<u8>> {
+        let mut data: Vec<u8> = data.iter().rev().copied().collect();
+        data.push(data[0]);
+        Ok(data)
+    }
+}
+
+fn compression(enabled: bool, algorithm: &str) -> CompressionConfig {
+    CompressionConfig { enabled, algorithm: algorithm.into(), level: 3, threshold: 512 }
+}
+
+fn config(protocol: TransportProtocol, compression: CompressionConfig) -> TransportConfig {
+    TransportConfig {
+        protocol,
+        encryption: EncryptionConfig { enabled: false, algorithm: String::new(), key_size: 0 },
+        compression,
+        timeout: Duration::from_secs(2),
+        buffer_size: 64 * 1024,
+    }
+}
+
+fn tcp_config(compression: CompressionConfig) -> TransportConfig {
+    config(TransportProtocol::TCP { port: 0, keep_alive: false }, compression)
+}
+
+async fn tcp(compression: CompressionConfig) -> TcpTransport {
+    let mut transport = TcpTransport::new(Uuid::new_v4()).with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    transport.init(tcp_config(compression)).await.unwrap();
+    transport
+}
+
+/// Highly compressible payload, like a serialized state proof
+fn proof() -> Vec<u8> {
+    (0..256 * 1024).map(|i| (i % 64) as u8).collect()
+}
+
+#[test]
+fn test_codecs_round_trip() {
+    let data = proof();
+    for codec in [&ZstdCodec as &dyn Codec, &Lz4Codec] {
+        let compressed = codec.compress(&data, 3).unwrap();
+        assert!(compressed.len() < data.len() / 10, "{} ratio", codec.name());
+        assert_eq!(codec.decompress(&compressed, data.len()).unwrap(), data);
+        // Output beyond the limit is refused rather than allocated
+        assert!(codec.decompress(&compressed, data.len() - 1).is_err());
+        assert!(codec.decompress(b"garbage", data.len()).is_err());
+        // A generous limit is a bound, not a reservation
+        let small = codec.decompress(&codec.compress(b"small", 3).unwrap(), usize::MAX).unwrap();
+        assert_eq!(small, b"small");
+        assert!(small.capacity() < 1024 * 1024);
+    }
+}
+
+#[test]
+fn test_registry_and_negotiation() {
+    let mut registry = CodecRegistry::default();
+    assert_eq!(registry.names(), vec!["zstd", "lz4"]);
+    registry.register(Arc::new(ReverseCodec));
+    assert!(registry.get("reverse").is_some());
+    assert!(CodecRegistry::empty().get("zstd").is_none());
+
+    let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(negotiate(&names(&["lz4", "zstd"]), &names(&["zstd", "lz4"])), Some("lz4".into()));
+    assert_eq!(negotiate(&names(&["reverse", "zstd"]), &names(&["zstd"])), Some("zstd".into()));
+    assert_eq!(negotiate(&names(&["zstd"]), &[]), None);
+}
+
+#[tokio::test]
+async fn test_tcp_compression() {
+    let mut client = tcp(compression(true, "zstd")).await;
+    let server = tcp(compression(true, "zstd")).await;
+    let peer = client.connect(&server.local_addr().unwrap().to_string()).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert_eq!(client.compression(&peer).as_deref(), Some("zstd"));
+    assert_eq!(server.compression(&client_peer).as_deref(), Some("zstd"));
+
+    let data = proof();
+    client.send_data(&peer, &data).await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), data);
+
+    // Payloads under the threshold skip the codec
+    let before = client.metrics().uncompressed_bytes;
+    client.send_data(&peer, b"small").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"small");
+    assert_eq!(client.metrics().uncompressed_bytes, before);
+
+    let metrics = client.metrics();
+    assert_eq!(metrics.uncompressed_bytes, data.len() as u64);
+    assert!(metrics.compression_ratio() < 0.1);
+    assert!(metrics.compression_time > Duration::ZERO);
+    assert!(metrics.bytes_sent < data.len() as u64 / 10);
+    assert!(server.metrics().decompression_time > Duration::ZERO);
+}
+
+#[tokio::test]
+async fn test_tcp_negotiation() {
+    // The dialer's preferred codec wins when the listener supports it
+    let mut client = tcp(compression(true, "lz4")).await;
+    let server = tcp(compression(true, "zstd")).await;
+    let peer = client.connect(&server.local_addr().unwrap().to_string()).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert_eq!(client.compression(&peer).as_deref(), Some("lz4"));
+    assert_eq!(server.compression(&client_peer).as_deref(), Some("lz4"));
+
+    // Either side disabling compression keeps the connection uncompressed
+    let mut plain = tcp(compression(false, "")).await;
+    let peer = plain.connect(&server.local_addr().unwrap().to_string()).await.unwrap();
+    let plain_peer = server.accept().await.unwrap();
+    assert_eq!(plain.compression(&peer), None);
+    assert_eq!(server.compression(&plain_peer), None);
+    let data = proof();
+    server.send_data(&plain_peer, &data).await.unwrap();
+    assert_eq!(plain.receive_data(&peer).await.unwrap(), data);
+    assert_eq!(server.metrics().uncompressed_bytes, 0);
+}
+
+#[tokio::test]
+async fn test_custom_codec() {
+    let mut transport = TcpTransport::new(Uuid::new_v4());
+    assert!(transport.init(tcp_config(compression(true, "reverse"))).await.is_err());
+
+    let custom = || TcpTransport::new(Uuid::new_v4())
+        .with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .with_codec(Arc::new(ReverseCodec));
+    let (mut client, mut server) = (custom(), custom());
+    client.init(tcp_config(compression(true, "reverse"))).await.unwrap();
+    server.init(tcp_config(compression(true, "reverse"))).await.unwrap();
+    let peer = client.connect(&server.local_addr().unwrap().to_string()).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert_eq!(client.compression(&peer).as_deref(), Some("reverse"));
+
+    let data: Vec<u8> = (0..1024).map(|_| 5).collect();
+    client.send_data(&peer, &data).await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), data);
+    assert_eq!(client.metrics().compressed_bytes, data.len() as u64 - 1);
+}
+
+#[tokio::test]
+async fn test_websocket_compression() {
+    let ws = |url: &str, algorithm: &str| config(
+        TransportProtocol::WebSocket { url: url.into(), use_tls: false },
+        compression(true, algorithm),
+    );
+    let mut server = WebSocketTransport::new(Uuid::new_v4());
+    server.init(ws("ws://127.0.0.1:0/frost", "zstd")).await.unwrap();
+    let mut client = WebSocketTransport::new(Uuid::new_v4());
+    client.init(ws("", "lz4")).await.unwrap();
+
+    let url = format!("ws://{}/frost", server.local_addr().unwrap());
+    let peer = client.connect(&url).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert_eq!(server.compression(&client_peer).as_deref(), Some("lz4"));
+
+    let data = proof();
+    server.send_data(&client_peer, &data).await.unwrap();
+    assert_eq!(client.receive_data(&peer).await.unwrap(), data);
+    client.send_data(&peer, b"ack").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"ack");
+    assert!(server.metrics().compression_ratio() < 0.1);
+    assert!(client.metrics().bytes_received < data.len() as u64 / 10);
+}
@@ -0,0 +1,305 @@
+use frost_protocol::{
+    message::{FrostMessage, MessageType},
+    network::{
+        fragment::{self, Fragment, RetransmitRequest, FRAGMENT_TYPE, MANIFEST_TYPE},
+        FragmentConfig, FragmentingNetwork, NetworkProtocol, Reassembler,
+    },
+    Result,
+};
+
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use parity_scale_codec::{Decode, Encode};
+use parking_lot::Mutex;
+
+/// Network recording every message sent
+#[derive(Clone, Default)]
+struct RecordingNetwork {
+    sent: Arc<Mutex<Vec<(String, FrostMessage)>>>,
+    peers: Vec<String>,
+}
+
+impl RecordingNetwork {
+    fn take(&self) -> Vec<(String, FrostMessage)> {
+        std::mem::take(&mut *self.sent.lock())
+    }
+}
+
+#[async_trait]
+impl NetworkProtocol for RecordingNetwork {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn broadcast(&self, message: FrostMessage) -> Result<()> {
+        self.sent.lock().push(("broadcast".into(), message));
+        Ok(())
+    }
+
+    async fn send_to(&self, peer_id: &str, message: FrostMessage) -> Result<()> {
+        self.sent.lock().push((peer_id.to_string(), message));
+        Ok(())
+    }
+
+    async fn get_peers(&self) -> Result<Vec<String>> {
+        Ok(self.peers.clone())
+    }
+}
+
+fn config() -> FragmentConfig {
+    FragmentConfig {
+        max_message_size: 1024,
+        chunk_size: 256,
+        ..Default::default()
+    }
+}
+
+fn large_message(len: usize) -> FrostMessage {
+    let payload = (0..len).map(|i| (i % 251) as u8).collect();
+    FrostMessage::new(MessageType::StateTransition, payload, "node1".into(), Some("node2".into()))
+}
+
+fn is_type(message: &FrostMessage, kind: &str) -> bool {
+    message.msg_type == MessageType::Custom(kind.into())
+}
+
+#[tokio::test]
+async fn test_small_messages_pass_through() {
+    let network = RecordingNetwork::default();
+    let sender = FragmentingNetwork::new(network.clone(), "node1", config());
+    let message = large_message(10);
+
+    sender.send_to("node2", message.clone()).await.unwrap();
+    let sent = network.take();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].1, message);
+    assert_eq!(sender.cached_transfers(), 0);
+
+    let receiver = FragmentingNetwork::new(RecordingNetwork::default(), "node2", config());
+    assert_eq!(receiver.receive("node1", message.clone()).await.unwrap(), Some(message));
+}
+
+#[tokio::test]
+async fn test_fragment_and_reassemble_out_of_order() {
+    let network = RecordingNetwork::default();
+    let sender = FragmentingNetwork::new(network.clone(), "node1", config());
+    let message = large_message(4000);
+
+    sender.send_to("node2", message.clone()).await.unwrap();
+    let mut sent: Vec<FrostMessage> = network.take().into_iter().map(|(_, m)| m).collect();
+    assert!(is_type(&sent[0], MANIFEST_TYPE));
+    assert!(sent[1..].iter().all(|m| is_type(m, FRAGMENT_TYPE)));
+    assert!(sent[1..].iter().all(|m| m.payload.len() <= 1024));
+    assert_eq!(sender.cached_transfers(), 1);
+
+    // Fragments before the manifest, in reverse order
+    sent.reverse();
+    let receiver = FragmentingNetwork::new(RecordingNetwork::default(), "node2", config());
+    let last = sent.pop().unwrap();
+    for fragment in sent {
+        assert_eq!(receiver.receive("node1", fragment).await.unwrap(), None);
+    }
+    assert_eq!(receiver.reassembler().pending_transfers(), 1);
+
+    let reassembled = receiver.receive("node1", last).await.unwrap().unwrap();
+    assert_eq!(reassembled, message);
+    assert_eq!(receiver.reassembler().pending_transfers(), 0);
+    assert_eq!(receiver.reassembler().buffered_bytes(), 0);
+}
+
+#[tokio::test]
+async fn test_corrupt_chunks_are_rejected() {
+    let message = large_message(4000);
+    let (manifest, mut fragments) = fragment::fragment(&message, &config()).unwrap().unwrap();
+    let reassembler = Reassembler::new(config());
+
+    // A corrupt chunk after the manifest is dropped and the good copy accepted
+    reassembler.accept("node1", manifest.clone()).unwrap();
+    let mut corrupt = Fragment::decode(&mut &fragments[0].payload[..]).unwrap();
+    corrupt.data[0] ^= 0xff;
+    let mut tampered = fragments[0].clone();
+    tampered.payload = corrupt.encode();
+    reassembler.accept("node1", tampered).unwrap();
+    assert_eq!(reassembler.buffered_bytes(), 0);
+
+    let last = fragments.pop().unwrap();
+    for fragment in fragments {
+        reassembler.accept("node1", fragment).unwrap();
+    }
+    assert!(matches!(
+        reassembler.accept("node1", last).unwrap(),
+        fragment::ReassemblyEvent::Complete(m) if m == message
+    ));
+
+    // Chunks from another peer never join this peer's transfer, whatever
+    // source they claim
+    let (manifest, mut fragments) = fragment::fragment(&message, &config()).unwrap().unwrap();
+    reassembler.accept("node1", manifest).unwrap();
+    let mut forged = Fragment::decode(&mut &fragments[0].payload[..]).unwrap();
+    forged.data[0] ^= 0xff;
+    let mut spoofed = fragments[0].clone();
+    spoofed.payload = forged.encode();
+    reassembler.accept("mallory", spoofed).unwrap();
+    assert_eq!(reassembler.pending_transfers(), 2);
+
+    let last = fragments.pop().unwrap();
+    for fragment in fragments {
+        reassembler.accept("node1", fragment).unwrap();
+    }
+    assert!(matches!(
+        reassembler.accept("node1", last).unwrap(),
+        fragment::ReassemblyEvent::Complete(m) if m == message
+    ));
+}
+
+#[tokio::test]
+async fn test_memory_limits() {
+    let message = large_message(4000);
+    let (manifest, fragments) = fragment::fragment(&message, &config()).unwrap().unwrap();
+
+    let small = Reassembler::new(FragmentConfig { max_transfer_bytes: 1024, ..config() });
+    assert!(small.accept("node1", manifest.clone()).is_err());
+    // Rejected chunks do not leave empty transfers behind
+    let tiny = Reassembler::new(FragmentConfig { max_transfer_bytes: 100, ..config() });
+    assert!(tiny.accept("node1", fragments[0].clone()).is_err());
+    assert_eq!(tiny.pending_transfers(), 0);
+
+    // Each chunk is charged its data plus a fixed overhead
+    let max_buffered_bytes = 2 * (256 + fragment::CHUNK_OVERHEAD);
+    let buffer = Reassembler::new(FragmentConfig { max_buffered_bytes, ..config() });
+    buffer.accept("node1", fragments[0].clone()).unwrap();
+    buffer.accept("node1", fragments[1].clone()).unwrap();
+    assert_eq!(buffer.buffered_bytes(), max_buffered_bytes);
+    assert!(buffer.accept("node1", fragments[2].clone()).is_err());
+
+    // Empty chunks cannot grow a transfer before its manifest bounds the indices
+    let empty = Reassembler::new(config());
+    for index in 0..16 {
+        let mut chunk = Fragment::decode(&mut &fragments[0].payload[..]).unwrap();
+        chunk.index = 1000 + index;
+        chunk.data.clear();
+        let mut message = fragments[0].clone();
+        message.payload = chunk.encode();
+        assert!(empty.accept("node1", message).is_err());
+    }
+    assert_eq!(empty.pending_transfers(), 0);
+
+    let transfers = Reassembler::new(FragmentConfig { max_transfers: 1, ..config() });
+    transfers.accept("node1", fragments[0].clone()).unwrap();
+    let other = fragment::fragment(&large_message(4000), &config()).unwrap().unwrap();
+    assert!(transfers.accept("node1", other.0).is_err());
+}
+
+#[tokio::test]
+async fn test_retransmit_missing_chunks() {
+    let config = FragmentConfig {
+        retransmit_after: Duration::from_millis(100),
+        max_retransmit_requests: 1,
+        max_retransmits_per_transfer: 3,
+        retransmit_interval: Duration::ZERO,
+        ..config()
+    };
+    let sender_net = RecordingNetwork::default();
+    let receiver_net = RecordingNetwork::default();
+    let sender = FragmentingNetwork::new(sender_net.clone(), "node1", config.clone());
+    let receiver = FragmentingNetwork::new(receiver_net.clone(), "node2", config);
+    let message = large_message(4000);
+
+    sender.send_to("node2", message.clone()).await.unwrap();
+    let mut sent: Vec<FrostMessage> = sender_net.take().into_iter().map(|(_, m)| m).collect();
+    // Lose the manifest and two chunks
+    sent.remove(4);
+    sent.remove(2);
+    sent.remove(0);
+    for m in sent {
+        assert_eq!(receiver.receive("node1", m).await.unwrap(), None);
+    }
+
+    assert_eq!(receiver.poll_retransmits().await.unwrap(), 0);
+    tokio::time::sleep(Duration::from_millis(120)).await;
+    assert_eq!(receiver.poll_retransmits().await.unwrap(), 1);
+    let (peer, request) = receiver_net.take().pop().unwrap();
+    assert_eq!(peer, "node1");
+    let decoded = RetransmitRequest::decode(&mut &request.payload[..]).unwrap();
+    assert!(decoded.manifest);
+
+    // Sender resends the manifest; the receiver then asks for chunks by index
+    assert_eq!(sender.receive("node2", request).await.unwrap(), None);
+    let resent = sender_net.take();
+    assert_eq!(resent.len(), 1);
+    assert_eq!(resent[0].0, "node2");
+    assert_eq!(receiver.receive("node1", resent[0].1.clone()).await.unwrap(), None);
+
+    let missing = RetransmitRequest { transfer_id: decoded.transfer_id, manifest: false, missing: vec![1, 3] };
+    assert_eq!(sender.retransmit("node2", &missing).await.unwrap(), 2);
+    let mut delivered = None;
+    for (_, m) in sender_net.take() {
+        delivered = receiver.receive("node1", m).await.unwrap();
+    }
+    assert_eq!(delivered, Some(message));
+
+    // Other peers cannot pull a transfer addressed elsewhere
+    assert!(sender.retransmit("mallory", &missing).await.is_err());
+
+    // Repeated and out-of-range indices resend each chunk once
+    let flood = RetransmitRequest {
+        transfer_id: decoded.transfer_id,
+        manifest: false,
+        missing: [vec![0; 1000], vec![u32::MAX, 100_000]].concat(),
+    };
+    assert_eq!(sender.retransmit("node2", &flood).await.unwrap(), 1);
+    // Each transfer answers a bounded number of requests
+    assert!(sender.retransmit("node2", &missing).await.is_err());
+}
+
+#[tokio::test]
+async fn test_broadcast_retransmits_only_reach_recipients() {
+    let network = RecordingNetwork { peers: vec!["node2".into(), "node3".into()], ..Default::default() };
+    let sender = FragmentingNetwork::new(network.clone(), "node1", config());
+    let message = large_message(4000);
+
+    sender.broadcast(message.clone()).await.unwrap();
+    network.take();
+    let transfer_id = *message.id.as_bytes();
+    let request = RetransmitRequest { transfer_id, manifest: true, missing: vec![0, 1, 2, 3] };
+
+    // A request claiming to come from a recipient is answered to the
+    // authenticated sender only, and refused if that sender is not one
+    let spoofed = FrostMessage::new(
+        MessageType::Custom(fragment::RETRANSMIT_TYPE.into()),
+        request.encode(),
+        "node3".into(),
+        Some("node1".into()),
+    );
+    assert!(sender.receive("mallory", spoofed).await.is_err());
+    assert!(network.take().is_empty());
+
+    assert_eq!(sender.retransmit("node3", &request).await.unwrap(), 5);
+    assert!(network.take().iter().all(|(peer, _)| peer == "node3"));
+
+    // Repeated requests from one peer are rate limited per transfer
+    assert!(sender.retransmit("node3", &request).await.is_err());
+    assert!(network.take().is_empty());
+    assert_eq!(sender.retransmit("node2", &request).await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn test_stalled_transfers_expire() {
+    let reassembler = Reassembler::new(FragmentConfig {
+        reassembly_timeout: Duration::from_millis(10),
+        ..config()
+    });
+    let (_, fragments) = fragment::fragment(&large_message(4000), &config()).unwrap().unwrap();
+    reassembler.accept("node1", fragments[0].clone()).unwrap();
+    assert!(reassembler.buffered_bytes() > 0);
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(reassembler.poll().is_empty());
+    assert_eq!(reassembler.pending_transfers(), 0);
+    assert_eq!(reassembler.buffered_bytes(), 0);
+}
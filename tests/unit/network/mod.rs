@@ -1,4 +1,14 @@
 pub mod p2p_test;
+pub mod fragment_test;
+pub mod tcp_test;
+pub mod ws_test;
+pub mod compression_test;
+pub mod noise_test;
+pub mod security_test;
+pub mod policy_test;
+pub mod rate_limit_test;
+pub mod peer_manager_test;
+pub mod reputation_test;
 pub mod discovery_test;
 pub mod circuit_breaker_test;
 pub mod backpressure_test; 
\ No newline at end of file
@@ -0,0 +1,271 @@
+use frost_protocol::network::{
+    noise::noise_params,
+    p2p::NodeIdentity,
+    peer::{NodeType, PeerState},
+    transport::{CompressionConfig, EncryptionConfig, TransportMetrics, TransportProtocol},
+    EncryptedTransport, Peer, PeerInfo, TcpTransport, Transport, TransportConfig,
+};
+use frost_protocol::{Error, Result};
+
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// One end of an in-process link that records everything it sends
+struct MemoryTransport {
+    remote: Peer,
+    tx: mpsc::Sender<Vec<u8>>,
+    rx: tokio::sync::Mutex<mpsc::Receiver<Vec<u8>>>,
+    wire: Arc<Mutex<Vec<Vec<u8>>>>,
+    connected: AtomicBool,
+}
+
+fn peer() -> Peer {
+    Peer {
+        id: Uuid::new_v4(),
+        info: PeerInfo {
+            address: "memory".into(),
+            protocol_version: "1".into(),
+            supported_features: vec![],
+            chain_ids: vec![],
+            node_type: NodeType::Validator,
+        },
+        state: PeerState::Connected,
+    }
+}
+
+/// Linked endpoints; each one's `remote` is the other
+fn memory_pair() -> (MemoryTransport, MemoryTransport) {
+    let (a_tx, b_rx) = mpsc::channel(64);
+    let (b_tx, a_rx) = mpsc::channel(64);
+    let endpoint = |remote, tx, rx| MemoryTransport {
+        remote,
+        tx,
+        rx: tokio::sync::Mutex::new(rx),
+        wire: Arc::default(),
+        connected: AtomicBool::new(true),
+    };
+    (endpoint(peer(), a_tx, a_rx), endpoint(peer(), b_tx, b_rx))
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn init(&mut self, config: TransportConfig) -> Result<()> {
+        assert!(!config.encryption.enabled, "encryption is handled by the wrapper");
+        Ok(())
+    }
+
+    async fn connect(&mut self, _address: &str) -> Result<Peer> {
+        Ok(self.remote.clone())
+    }
+
+    async fn disconnect(&mut self, _peer: &Peer) -> Result<()> {
+        self.connected.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn send_data(&self, _peer: &Peer, data: &[u8]) -> Result<usize> {
+        self.wire.lock().push(data.to_vec());
+        self.tx.send(data.to_vec()).await.map_err(|_| Error::Network("closed".into()))?;
+        Ok(data.len())
+    }
+
+    async fn receive_data(&self, _peer: &Peer) -> Result<Vec<u8>> {
+        let mut rx = self.rx.lock().await;
+        tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .map_err(|_| Error::Network("timed out".into()))?
+            .ok_or_else(|| Error::Network("closed".into()))
+    }
+
+    async fn is_connected(&self, _peer: &Peer) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    fn metrics(&self) -> TransportMetrics {
+        TransportMetrics::default()
+    }
+}
+
+fn encryption(algorithm: &str) -> EncryptionConfig {
+    EncryptionConfig { enabled: true, algorithm: algorithm.into(), key_size: 256 }
+}
+
+fn config(encryption: EncryptionConfig) -> TransportConfig {
+    TransportConfig {
+        protocol: TransportProtocol::TCP { port: 0, keep_alive: false },
+        encryption,
+        compression: CompressionConfig { enabled: false, algorithm: String::new(), level: 0, threshold: 512 },
+        timeout: Duration::from_secs(2),
+        buffer_size: 64 * 1024,
+    }
+}
+
+type Endpoint = EncryptedTransport<MemoryTransport>;
+
+/// Encrypted endpoints with a completed handshake, and each one's view of the other
+async fn connected(interval: Duration) -> (Endpoint, Peer, Endpoint, Peer) {
+    let (a, b) = memory_pair();
+    let (a_peer, b_peer) = (a.remote.clone(), b.remote.clone());
+    let mut client = EncryptedTransport::new(a, NodeIdentity::new()).with_key_rotation_interval(interval);
+    let mut server = EncryptedTransport::new(b, NodeIdentity::new()).with_key_rotation_interval(interval);
+    client.init(config(encryption("chacha20poly1305"))).await.unwrap();
+    server.init(config(encryption("chacha20poly1305"))).await.unwrap();
+
+    let (connected, accepted) = tokio::join!(client.connect("memory"), server.accept(&b_peer));
+    assert_eq!(connected.unwrap().id, a_peer.id);
+    accepted.unwrap();
+    (client, a_peer, server, b_peer)
+}
+
+#[tokio::test]
+async fn test_handshake_binds_identities() {
+    let (client, server_peer, server, client_peer) = connected(Duration::from_secs(60)).await;
+
+    assert_eq!(client.remote_identity(&server_peer), Some(server.identity().peer_id));
+    assert_eq!(server.remote_identity(&client_peer), Some(client.identity().peer_id));
+    assert!(client.is_connected(&server_peer).await);
+
+    let (ours, theirs) = (client.session(&server_peer).unwrap(), server.session(&client_peer).unwrap());
+    assert_eq!(ours.session_id, theirs.session_id);
+    assert_eq!(ours.encryption_key, theirs.encryption_key);
+    assert_ne!(ours.encryption_key, ours.signing_key);
+    assert_eq!(ours.expires_at.duration_since(ours.created_at).unwrap(), Duration::from_secs(60));
+    // Both ends see the same channel binding for challenge signatures
+    assert_eq!(client.channel_binding(&server_peer), server.channel_binding(&client_peer));
+    assert!(client.channel_binding(&server_peer).is_some());
+}
+
+#[tokio::test]
+async fn test_encrypted_round_trip() {
+    let (client, server_peer, server, client_peer) = connected(Duration::from_secs(60)).await;
+
+    // Payloads larger than one Noise message are split and reassembled
+    let large: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+    for payload in [b"top secret".to_vec(), Vec::new(), large] {
+        assert_eq!(client.send_data(&server_peer, &payload).await.unwrap(), payload.len());
+        assert_eq!(server.receive_data(&client_peer).await.unwrap(), payload);
+    }
+    server.send_data(&client_peer, b"reply").await.unwrap();
+    assert_eq!(client.receive_data(&server_peer).await.unwrap(), b"reply");
+
+    let wire = client.inner().wire.lock();
+    assert!(!wire.iter().any(|m| m.windows(10).any(|w| w == b"top secret")));
+}
+
+#[tokio::test]
+async fn test_key_rotation() {
+    let (client, server_peer, server, client_peer) = connected(Duration::from_millis(200)).await;
+    let initial = client.session(&server_peer).unwrap();
+
+    client.send_data(&server_peer, b"before").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"before");
+
+    // Keys older than the interval are rotated on the next send
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    client.send_data(&server_peer, b"after").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"after");
+    let rotated = client.session(&server_peer).unwrap();
+    assert_eq!(rotated.session_id, initial.session_id);
+    assert_ne!(rotated.encryption_key, initial.encryption_key);
+    assert!(rotated.expires_at > initial.expires_at);
+
+    // Explicit rotation, in both directions
+    client.rotate_keys(&server_peer).await.unwrap();
+    server.rotate_keys(&client_peer).await.unwrap();
+    client.send_data(&server_peer, b"one").await.unwrap();
+    server.send_data(&client_peer, b"two").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"one");
+    assert_eq!(client.receive_data(&server_peer).await.unwrap(), b"two");
+}
+
+#[tokio::test]
+async fn test_tampering_and_handshake_failures() {
+    let (client, server_peer, server, client_peer) = connected(Duration::from_secs(60)).await;
+    client.inner().tx.send(vec![0, 4, 1, 2, 3, 4]).await.unwrap();
+    assert!(server.receive_data(&client_peer).await.is_err());
+    assert!(client.send_data(&peer(), b"unknown").await.is_err());
+
+    // A tampered message is rejected without disturbing the session, so a
+    // forged rotation cannot desynchronize the keys
+    client.send_data(&server_peer, b"genuine").await.unwrap();
+    let original = server.inner().rx.lock().await.recv().await.unwrap();
+    let mut flipped = original.clone();
+    flipped[2] ^= 1;
+    for tampered in [[&[1u8][..], &original].concat(), flipped] {
+        client.inner().tx.send(tampered).await.unwrap();
+        assert!(server.receive_data(&client_peer).await.is_err());
+    }
+    client.inner().tx.send(original).await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"genuine");
+
+    // Peers with different ciphers cannot complete the handshake
+    let (a, b) = memory_pair();
+    let b_peer = b.remote.clone();
+    let mut client = EncryptedTransport::new(a, NodeIdentity::new());
+    let mut server = EncryptedTransport::new(b, NodeIdentity::new());
+    client.init(config(encryption("chacha20poly1305"))).await.unwrap();
+    server.init(config(encryption("aes256gcm"))).await.unwrap();
+    let (connected, accepted) = tokio::join!(client.connect("memory"), server.accept(&b_peer));
+    assert!(connected.is_err() && accepted.is_err());
+    assert!(!client.inner().connected.load(Ordering::SeqCst));
+
+    // A node cannot open a session with its own identity
+    let (a, b) = memory_pair();
+    let b_peer = b.remote.clone();
+    let identity = NodeIdentity::new();
+    let mut client = EncryptedTransport::new(a, identity.clone());
+    let mut server = EncryptedTransport::new(b, identity);
+    client.init(config(encryption("chacha20poly1305"))).await.unwrap();
+    server.init(config(encryption("chacha20poly1305"))).await.unwrap();
+    let (connected, _) = tokio::join!(client.connect("memory"), server.accept(&b_peer));
+    assert!(connected.is_err());
+}
+
+#[tokio::test]
+async fn test_config_validation() {
+    assert!(noise_params(&encryption("chacha20poly1305")).is_ok());
+    assert!(noise_params(&encryption("aes256gcm")).is_ok());
+    assert!(noise_params(&encryption("rot13")).is_err());
+    assert!(noise_params(&EncryptionConfig { key_size: 128, ..encryption("aes256gcm") }).is_err());
+
+    // Disabled encryption passes data through untouched
+    let (a, b) = memory_pair();
+    let (a_peer, b_peer) = (a.remote.clone(), b.remote.clone());
+    let mut client = EncryptedTransport::new(a, NodeIdentity::new());
+    let mut server = EncryptedTransport::new(b, NodeIdentity::new());
+    let disabled = EncryptionConfig { enabled: false, ..encryption("") };
+    client.init(config(disabled.clone())).await.unwrap();
+    server.init(config(disabled)).await.unwrap();
+    client.connect("memory").await.unwrap();
+    client.send_data(&a_peer, b"plain").await.unwrap();
+    assert_eq!(server.receive_data(&b_peer).await.unwrap(), b"plain");
+    assert!(client.session(&a_peer).is_none());
+}
+
+#[tokio::test]
+async fn test_over_tcp() {
+    let tcp = || TcpTransport::new(Uuid::new_v4()).with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    let mut client = EncryptedTransport::new(tcp(), NodeIdentity::new());
+    let mut server = EncryptedTransport::new(tcp(), NodeIdentity::new());
+    client.init(config(encryption("chacha20poly1305"))).await.unwrap();
+    server.init(config(encryption("chacha20poly1305"))).await.unwrap();
+
+    let address = server.inner().local_addr().unwrap().to_string();
+    let accept = async {
+        let peer = server.inner().accept().await.unwrap();
+        server.accept(&peer).await.unwrap();
+        peer
+    };
+    let (connected, client_peer) = tokio::join!(client.connect(&address), accept);
+    let server_peer = connected.unwrap();
+
+    client.send_data(&server_peer, b"over tcp").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"over tcp");
+    assert_eq!(client.remote_identity(&server_peer), Some(server.identity().peer_id));
+}
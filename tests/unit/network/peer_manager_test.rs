@@ -0,0 +1,232 @@
+use frost_protocol::extensions::{
+    errors::ExtensionResult, DefaultExtensionManager, ExtensionConfig, ExtensionHooks, ExtensionManager,
+    ExtensionMetadata, ExtensionState, PeerEventType, ProtocolExtension,
+};
+use frost_protocol::message::FrostMessage;
+use frost_protocol::network::{
+    peer::{NodeType, PeerState},
+    BasicNetwork, InMemoryPeerManager, NetworkConfig, Peer, PeerInfo, PeerManager,
+};
+use frost_protocol::state::StateTransition;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Extension recording the peer events it sees
+struct Recorder {
+    metadata: ExtensionMetadata,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl ProtocolExtension for Recorder {
+    fn metadata(&self) -> &ExtensionMetadata {
+        &self.metadata
+    }
+
+    async fn initialize(&mut self, _config: ExtensionConfig) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn start(&mut self) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn handle_message(&self, _message: &FrostMessage) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn pre_process_message(&self, _message: &mut FrostMessage) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn post_process_message(&self, _message: &FrostMessage) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn handle_state_transition(&self, _transition: &StateTransition) -> ExtensionResult<()> {
+        Ok(())
+    }
+
+    async fn handle_peer_event(&self, peer: &Peer, event_type: PeerEventType) -> ExtensionResult<()> {
+        self.events.lock().push(format!("{} {:?}", peer.info.address, event_type));
+        Ok(())
+    }
+
+    async fn get_state(&self) -> ExtensionResult<ExtensionState> {
+        Ok(ExtensionState::Active)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+async fn hooks() -> (Arc<ExtensionHooks>, Arc<Mutex<Vec<String>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut manager = DefaultExtensionManager::new();
+    let recorder = Recorder {
+        metadata: ExtensionMetadata {
+            name: "recorder".into(),
+            version: "1.0.0".into(),
+            description: "Records peer events".into(),
+            dependencies: vec![],
+            capabilities: vec![],
+        },
+        events: events.clone(),
+    };
+    let config = ExtensionConfig { enabled: true, priority: 0, parameters: HashMap::new() };
+    manager.register_extension(Box::new(recorder), config).await.unwrap();
+    let network = Arc::new(BasicNetwork::new(NetworkConfig::default()));
+    (Arc::new(ExtensionHooks::new(Arc::new(RwLock::new(manager)), network)), events)
+}
+
+fn info(address: &str, node_type: NodeType) -> PeerInfo {
+    PeerInfo {
+        address: address.into(),
+        protocol_version: "1".into(),
+        supported_features: vec![],
+        chain_ids: vec![1],
+        node_type,
+    }
+}
+
+#[tokio::test]
+async fn test_state_transitions_and_events() {
+    let (hooks, events) = hooks().await;
+    let mut manager = InMemoryPeerManager::new().with_hooks(hooks);
+
+    let node_id = Uuid::new_v4();
+    let peer = manager.add_peer(node_id, info("a:1", NodeType::Validator)).await.unwrap();
+    assert_eq!((peer.id, peer.state), (node_id, PeerState::Handshaking));
+    // Peers are tracked under their node id, once
+    assert!(manager.add_peer(node_id, info("a:2", NodeType::Validator)).await.is_err());
+    manager.set_state(peer.id, PeerState::Connected).await.unwrap();
+    // Connected peers must disconnect before handshaking again
+    assert!(manager.set_state(peer.id, PeerState::Handshaking).await.is_err());
+    assert!(manager.set_state(peer.id, PeerState::Banned).await.is_err());
+    manager.set_state(peer.id, PeerState::Disconnected).await.unwrap();
+    manager.set_state(peer.id, PeerState::Handshaking).await.unwrap();
+    manager.set_state(peer.id, PeerState::Connected).await.unwrap();
+
+    let stats = manager.peer_stats();
+    assert_eq!((stats.total_peers, stats.connected_peers, stats.handshaking_peers), (1, 1, 0));
+    assert_eq!(manager.get_peer(peer.id).await.unwrap().unwrap().state, PeerState::Connected);
+
+    manager.remove_peer(&peer).await.unwrap();
+    assert!(manager.get_peer(peer.id).await.unwrap().is_none());
+    assert!(manager.remove_peer(&peer).await.is_err());
+    assert!(manager.set_state(peer.id, PeerState::Connected).await.is_err());
+
+    assert_eq!(*events.lock(), vec![
+        "a:1 StateChanged(\"Handshaking\")",
+        "a:1 Connected",
+        "a:1 Disconnected",
+        "a:1 StateChanged(\"Handshaking\")",
+        "a:1 Connected",
+        "a:1 Disconnected",
+    ]);
+}
+
+#[tokio::test]
+async fn test_max_peers_per_node_type() {
+    let mut manager = InMemoryPeerManager::new().with_max_peers(NodeType::Observer, 2);
+    let first = manager.add_peer(Uuid::new_v4(), info("o:1", NodeType::Observer)).await.unwrap();
+    manager.add_peer(Uuid::new_v4(), info("o:2", NodeType::Observer)).await.unwrap();
+    assert!(manager.add_peer(Uuid::new_v4(), info("o:3", NodeType::Observer)).await.is_err());
+    // Other node types are not capped
+    for port in 0..5 {
+        manager.add_peer(Uuid::new_v4(), info(&format!("v:{}", port), NodeType::Validator)).await.unwrap();
+    }
+
+    // Disconnected peers free their slot until they handshake again
+    manager.set_state(first.id, PeerState::Disconnected).await.unwrap();
+    let third = manager.add_peer(Uuid::new_v4(), info("o:3", NodeType::Observer)).await.unwrap();
+    assert!(manager.set_state(first.id, PeerState::Handshaking).await.is_err());
+    manager.remove_peer(&third).await.unwrap();
+    manager.set_state(first.id, PeerState::Handshaking).await.unwrap();
+    assert_eq!(manager.list_peers().await.unwrap().len(), 7);
+}
+
+#[tokio::test]
+async fn test_bans() {
+    let (hooks, events) = hooks().await;
+    let mut manager = InMemoryPeerManager::new().with_hooks(hooks);
+    let peer = manager.add_peer(Uuid::new_v4(), info("10.0.1.1:9000", NodeType::Relay)).await.unwrap();
+    manager.set_state(peer.id, PeerState::Connected).await.unwrap();
+
+    manager.ban_peer(&peer, "invalid proofs".into()).await.unwrap();
+    assert_eq!(manager.get_peer(peer.id).await.unwrap().unwrap().state, PeerState::Banned);
+    assert!(manager.is_banned("10.0.1.1:9000"));
+    assert_eq!(manager.peer_stats().banned_peers, 1);
+    assert!(manager.set_state(peer.id, PeerState::Connected).await.is_err());
+    // The address cannot rejoin under a new id or source port
+    assert!(manager.is_banned("10.0.1.1:40312"));
+    let error = manager.add_peer(Uuid::new_v4(), info("10.0.1.1:40312", NodeType::Relay)).await.unwrap_err();
+    assert!(error.to_string().contains("invalid proofs"));
+
+    manager.unban_peer(&peer).await.unwrap();
+    assert!(!manager.is_banned("10.0.1.1:9000"));
+    assert_eq!(manager.get_peer(peer.id).await.unwrap().unwrap().state, PeerState::Disconnected);
+    manager.add_peer(Uuid::new_v4(), info("10.0.1.1:9000", NodeType::Relay)).await.unwrap();
+
+    let events = events.lock();
+    assert!(events.contains(&"10.0.1.1:9000 Banned(\"invalid proofs\")".to_string()));
+    assert!(events.contains(&"10.0.1.1:9000 Unbanned".to_string()));
+}
+
+#[tokio::test]
+async fn test_timed_bans() {
+    let mut manager = InMemoryPeerManager::new().with_ban_duration(Duration::from_millis(100));
+    let peer = manager.add_peer(Uuid::new_v4(), info("10.0.2.1:9000", NodeType::Gateway)).await.unwrap();
+    manager.ban_peer(&peer, "spam".into()).await.unwrap();
+    manager.ban_peer_for(&peer, "flood".into(), None).await.unwrap();
+    let other = manager.add_peer(Uuid::new_v4(), info("10.0.2.2:9000", NodeType::Gateway)).await.unwrap();
+    manager.ban_peer(&other, "spam".into()).await.unwrap();
+    assert_eq!(manager.bans().len(), 2);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    // Expired bans stop applying straight away and are lifted by pruning
+    assert!(!manager.is_banned("10.0.2.2:9000"));
+    assert!(manager.is_banned("10.0.2.1:9000"));
+    assert_eq!(manager.prune_bans().await.unwrap(), 1);
+    assert_eq!(manager.get_peer(other.id).await.unwrap().unwrap().state, PeerState::Disconnected);
+    assert_eq!(manager.bans()[0].reason, "flood");
+}
+
+#[tokio::test]
+async fn test_ban_list_persistence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bans.json");
+    {
+        let mut manager = InMemoryPeerManager::open(&path).await.unwrap();
+        let peer = manager.add_peer(Uuid::new_v4(), info("10.0.3.1:9000", NodeType::Relay)).await.unwrap();
+        manager.ban_peer(&peer, "equivocation".into()).await.unwrap();
+        let short = manager.add_peer(Uuid::new_v4(), info("10.0.3.2:9000", NodeType::Relay)).await.unwrap();
+        manager.ban_peer_for(&short, "spam".into(), Some(Duration::from_millis(50))).await.unwrap();
+        let lifted = manager.add_peer(Uuid::new_v4(), info("10.0.3.3:9000", NodeType::Relay)).await.unwrap();
+        manager.ban_peer(&lifted, "mistake".into()).await.unwrap();
+        manager.unban_peer(&lifted).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Only bans still in force survive a restart
+    let mut manager = InMemoryPeerManager::open(&path).await.unwrap();
+    let bans = manager.bans();
+    assert_eq!(bans.len(), 1);
+    assert_eq!((bans[0].address.as_str(), bans[0].reason.as_str()), ("10.0.3.1", "equivocation"));
+    assert!(manager.add_peer(Uuid::new_v4(), info("10.0.3.1:9000", NodeType::Relay)).await.is_err());
+    manager.add_peer(Uuid::new_v4(), info("10.0.3.2:9000", NodeType::Relay)).await.unwrap();
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(InMemoryPeerManager::open(&path).await.is_err());
+}
@@ -0,0 +1,207 @@
+use frost_protocol::message::MessageType;
+use frost_protocol::network::{
+    peer::{NodeType, PeerState},
+    policy::{AuthorizationRequest, Effect, PolicyRule, Role, RoleBinding},
+    security::{Action, ActionType, AuthenticationMethod, PeerCertificate, RateLimitConfig},
+    AuthorizationPolicy, DefaultSecurityManager, Peer, PeerInfo, PolicyEngine, SecurityConfig, SecurityManager,
+};
+
+use std::sync::Arc;
+use std::time::Duration;
+use ed25519_dalek::SigningKey;
+use uuid::Uuid;
+
+const SERVER_ID: Uuid = Uuid::from_u128(1);
+const BINDING: &[u8] = b"noise handshake hash";
+
+fn broadcast(msg_type: MessageType) -> Action {
+    Action::new(ActionType::BroadcastMessage, "chain/1").with_message_type(&msg_type)
+}
+
+fn allowed(engine: &PolicyEngine, node_type: NodeType, permissions: &[String], action: &Action) -> bool {
+    engine
+        .evaluate(&AuthorizationRequest {
+            peer_id: Uuid::new_v4(),
+            node_type: Some(&node_type),
+            permissions,
+            action,
+        })
+        .allowed
+}
+
+const POLICY_TOML: &str = r#"
+[[roles]]
+name = "relay"
+
+[[roles.rules]]
+actions = ["SendMessage", "BroadcastMessage"]
+resources = ["chain/*"]
+
+[[roles.rules]]
+effect = "deny"
+resources = ["chain/*/admin"]
+
+[[bindings]]
+role = "relay"
+node_types = ["Relay"]
+permissions = ["relay"]
+"#;
+
+#[test]
+fn test_standard_policy() {
+    let engine = PolicyEngine::new(AuthorizationPolicy::standard()).unwrap();
+
+    // Observers may follow the chain but never broadcast state transitions
+    assert!(allowed(&engine, NodeType::Observer, &[], &Action::new(ActionType::Connect, "")));
+    assert!(allowed(&engine, NodeType::Observer, &[], &Action::new(ActionType::ReceiveMessage, "")));
+    assert!(!allowed(&engine, NodeType::Observer, &[], &broadcast(MessageType::StateTransition)));
+    assert!(!allowed(&engine, NodeType::Observer, &[], &broadcast(MessageType::Discovery)));
+
+    assert!(allowed(&engine, NodeType::Validator, &[], &broadcast(MessageType::StateTransition)));
+    assert!(allowed(&engine, NodeType::Relay, &[], &broadcast(MessageType::StateTransition)));
+    assert!(!allowed(&engine, NodeType::Gateway, &[], &Action::new(ActionType::ModifyPeer, "peers")));
+
+    let log = engine.audit_log();
+    assert_eq!(log.len(), 7);
+    let denial = &log[2];
+    assert_eq!(denial.node_type, Some(NodeType::Observer));
+    assert_eq!(denial.message_type.as_deref(), Some("StateTransition"));
+    assert!(!denial.decision.allowed);
+    assert_eq!(denial.decision.matched, Some(("observer".into(), 1)));
+    // Nothing matched, so the default deny applies
+    assert_eq!(log[3].decision.matched, None);
+}
+
+#[test]
+fn test_rules_and_bindings() {
+    let engine = PolicyEngine::new(AuthorizationPolicy::from_toml(POLICY_TOML).unwrap()).unwrap();
+    let send = |resource: &str| Action::new(ActionType::SendMessage, resource);
+
+    assert!(allowed(&engine, NodeType::Relay, &[], &send("chain/7")));
+    assert!(!allowed(&engine, NodeType::Relay, &[], &send("other/7")));
+    // Deny wins over an allow matching the same action
+    assert!(!allowed(&engine, NodeType::Relay, &[], &send("chain/7/admin")));
+    assert!(!allowed(&engine, NodeType::Relay, &[], &Action::new(ActionType::ModifyPeer, "chain/7")));
+
+    // Roles can be bound through session permissions or peer ids
+    assert!(!allowed(&engine, NodeType::Gateway, &[], &send("chain/7")));
+    assert!(allowed(&engine, NodeType::Gateway, &["relay".into()], &send("chain/7")));
+    let peer_id = Uuid::new_v4();
+    let mut policy = (*engine.policy()).clone();
+    policy.bindings.push(RoleBinding { role: "relay".into(), peers: vec![peer_id], ..Default::default() });
+    engine.reload(policy).unwrap();
+    let action = send("chain/7");
+    let request = AuthorizationRequest { peer_id, node_type: Some(&NodeType::Gateway), permissions: &[], action: &action };
+    assert!(engine.evaluate(&request).allowed);
+    // Without an attested node type only peer and permission bindings apply
+    let request = AuthorizationRequest { peer_id: Uuid::new_v4(), node_type: None, permissions: &[], action: &action };
+    assert!(!engine.evaluate(&request).allowed);
+}
+
+#[test]
+fn test_loading_and_validation() {
+    let toml = AuthorizationPolicy::from_toml(POLICY_TOML).unwrap();
+    let json = AuthorizationPolicy::from_json(&serde_json::to_string(&toml).unwrap()).unwrap();
+    assert_eq!(toml, json);
+    assert_eq!(toml.roles[0].rules[1].effect, Effect::Deny);
+
+    assert!(AuthorizationPolicy::from_toml("roles = 3").is_err());
+    assert!(AuthorizationPolicy::from_json(r#"{"bindings": [{"role": "ghost"}]}"#).is_err());
+
+    // A broken policy is rejected and the current one stays in force
+    let engine = PolicyEngine::new(AuthorizationPolicy::standard()).unwrap();
+    let duplicate = AuthorizationPolicy {
+        roles: vec![Role { name: "a".into(), rules: vec![PolicyRule::default()] }; 2],
+        bindings: vec![],
+    };
+    assert!(engine.reload(duplicate).is_err());
+    assert_eq!(*engine.policy(), AuthorizationPolicy::standard());
+
+    let engine = PolicyEngine::new(AuthorizationPolicy::standard()).unwrap().with_audit_capacity(2);
+    for _ in 0..5 {
+        allowed(&engine, NodeType::Validator, &[], &broadcast(MessageType::Batch));
+    }
+    assert_eq!(engine.audit_log().len(), 2);
+}
+
+#[tokio::test]
+async fn test_hot_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("policy.toml");
+    std::fs::write(&path, POLICY_TOML).unwrap();
+    let engine = Arc::new(PolicyEngine::from_file(&path).unwrap());
+    assert!(!engine.reload_from_file().unwrap());
+    let _watcher = engine.watch(Duration::from_millis(20));
+
+    let action = Action::new(ActionType::SendMessage, "chain/1");
+    assert!(allowed(&engine, NodeType::Relay, &[], &action));
+
+    std::fs::write(&path, POLICY_TOML.replace("chain/*\"]\n\n", "other/*\"]\n\n")).unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!allowed(&engine, NodeType::Relay, &[], &action));
+
+    // An invalid edit is ignored until it is fixed
+    std::fs::write(&path, "[[bindings]]\nrole = \"ghost\"\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(engine.policy().roles.len(), 1);
+}
+
+#[tokio::test]
+async fn test_security_manager_enforces_policy() {
+    let (ca, client_key) = (SigningKey::from_bytes(&rand::random()), SigningKey::from_bytes(&rand::random()));
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("policy.json");
+    std::fs::write(&path, serde_json::to_string(&AuthorizationPolicy::standard()).unwrap()).unwrap();
+
+    let observer = Peer {
+        id: Uuid::new_v4(),
+        info: PeerInfo {
+            address: "127.0.0.1:9000".into(),
+            protocol_version: "1".into(),
+            supported_features: vec![],
+            chain_ids: vec![],
+            // Advertised in the unauthenticated hello, so ignored by the policy
+            node_type: NodeType::Validator,
+        },
+        state: PeerState::Connected,
+    };
+    let mut server = DefaultSecurityManager::new()
+        .with_node_id(SERVER_ID)
+        .with_certificate_authority(ca.verifying_key());
+    server
+        .init(SecurityConfig {
+            authentication_method: AuthenticationMethod::Certificate {
+                ca_cert: String::new(),
+                client_cert: String::new(),
+                client_key: String::new(),
+            },
+            key_rotation_interval: Duration::from_secs(600),
+            signature_algorithm: "ed25519".into(),
+            tls_config: None,
+            rate_limiting: RateLimitConfig {
+                max_requests: 100,
+                window_size: Duration::from_secs(1),
+                per_ip_limit: false,
+                burst_size: 10,
+            },
+            authorization_policy: Some(path),
+        })
+        .await
+        .unwrap();
+    let connect = Action::new(ActionType::Connect, "");
+    // Unauthenticated peers are refused before the policy is consulted
+    assert!(!server.authorize_action(&connect, &observer).await.unwrap());
+    assert!(server.policy().unwrap().audit_log().is_empty());
+
+    let certificate = PeerCertificate::issue(
+        observer.id, &client_key.verifying_key(), Some(NodeType::Observer), vec![], Duration::from_secs(600), &ca,
+    );
+    let client = DefaultSecurityManager::new().with_signing_key(client_key).with_certificate(certificate);
+    let challenge = server.issue_challenge(&observer, BINDING);
+    server.present_credentials(&observer, client.respond_to_challenge(observer.id, SERVER_ID, BINDING, &challenge));
+    assert!(server.authenticate_peer(&observer).await.unwrap().success);
+
+    assert!(server.authorize_action(&connect, &observer).await.unwrap());
+    assert!(!server.authorize_action(&broadcast(MessageType::StateTransition), &observer).await.unwrap());
+    assert_eq!(server.policy().unwrap().audit_log().len(), 2);
+}
@@ -0,0 +1,223 @@
+use frost_protocol::message::{FrostMessage, MessageType};
+use frost_protocol::network::{
+    peer::{NodeType, PeerState},
+    rate_limit::{RateLimitPenalties, TokenBucket},
+    security::{AuthenticationMethod, RateLimitConfig},
+    transport::{CompressionConfig, EncryptionConfig, TransportProtocol},
+    DefaultSecurityManager, NodeIdentity, Peer, PeerInfo, RateLimiter, ReputationConfig, ReputationManager,
+    SecurityConfig, SecurityManager, TcpTransport, Transport, TransportConfig,
+};
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn limits(max_requests: u32, burst_size: u32, per_ip_limit: bool) -> RateLimitConfig {
+    RateLimitConfig {
+        max_requests,
+        window_size: Duration::from_secs(1),
+        per_ip_limit,
+        burst_size,
+    }
+}
+
+fn tcp_config() -> TransportConfig {
+    TransportConfig {
+        protocol: TransportProtocol::TCP { port: 0, keep_alive: false },
+        encryption: EncryptionConfig { enabled: false, algorithm: String::new(), key_size: 0 },
+        compression: CompressionConfig { enabled: false, algorithm: String::new(), level: 0, threshold: 512 },
+        timeout: Duration::from_millis(500),
+        buffer_size: 64 * 1024,
+    }
+}
+
+const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+#[test]
+fn test_token_bucket() {
+    let mut bucket = TokenBucket::new(2, 20, Duration::from_secs(1));
+    assert!(bucket.try_take());
+    assert!(bucket.try_take());
+    assert!(!bucket.try_take());
+
+    std::thread::sleep(Duration::from_millis(60));
+    assert!(bucket.try_take());
+    assert!(!bucket.try_take());
+
+    // Refills never exceed the capacity
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(bucket.available(), 2.0);
+}
+
+#[tokio::test]
+async fn test_peer_and_ip_limits() {
+    let limiter = RateLimiter::new(limits(10, 3, false));
+    let (peer, other) = (Uuid::new_v4(), Uuid::new_v4());
+    for _ in 0..3 {
+        limiter.check(peer, Some(IP), None).await.unwrap();
+    }
+    assert!(limiter.check(peer, Some(IP), None).await.is_err());
+    // Peers have separate buckets, and per-IP limits are off
+    limiter.check(other, Some(IP), None).await.unwrap();
+    assert_eq!(limiter.hits(), 1);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    limiter.check(peer, None, None).await.unwrap();
+
+    // Peers behind one address share its bucket
+    let limiter = RateLimiter::new(limits(10, 2, true));
+    limiter.check(peer, Some(IP), None).await.unwrap();
+    limiter.check(other, Some(IP), None).await.unwrap();
+    let error = limiter.check(Uuid::new_v4(), Some(IP), None).await.unwrap_err();
+    assert!(error.to_string().contains("ip rate limit"));
+    limiter.check(peer, Some(IpAddr::V4(Ipv4Addr::LOCALHOST)), None).await.unwrap();
+
+    // No requests allowed means no limit
+    let unlimited = RateLimiter::new(limits(0, 0, true));
+    for _ in 0..100 {
+        unlimited.check(peer, Some(IP), None).await.unwrap();
+    }
+    assert_eq!(unlimited.tracked(), 0);
+}
+
+#[tokio::test]
+async fn test_tracked_buckets_are_capped() {
+    let limiter = RateLimiter::new(limits(1, 1, false)).with_max_tracked(64);
+    let busy = Uuid::new_v4();
+    limiter.check(busy, None, None).await.unwrap();
+
+    // A flood of fresh ids evicts the least recently used buckets only
+    for _ in 0..1000 {
+        limiter.check(Uuid::new_v4(), None, None).await.unwrap();
+        assert!(limiter.check(busy, None, None).await.is_err());
+        assert!(limiter.tracked() <= 64);
+    }
+}
+
+#[tokio::test]
+async fn test_message_type_limits() {
+    let named = HashMap::from([("Discovery".to_string(), 1)]);
+    let limiter = RateLimiter::new(limits(100, 100, false))
+        .with_message_type_limit(&MessageType::StateProof, 2)
+        .with_message_type_limits(&named);
+    let peer = Uuid::new_v4();
+
+    limiter.check(peer, None, Some(&MessageType::StateProof)).await.unwrap();
+    limiter.check(peer, None, Some(&MessageType::StateProof)).await.unwrap();
+    let error = limiter.check(peer, None, Some(&MessageType::StateProof)).await.unwrap_err();
+    assert!(error.to_string().contains("message_type rate limit"));
+
+    limiter.check(peer, None, Some(&MessageType::Discovery)).await.unwrap();
+    assert!(limiter.check(peer, None, Some(&MessageType::Discovery)).await.is_err());
+    // Types without a limit only count against the peer
+    for _ in 0..10 {
+        limiter.check(peer, None, Some(&MessageType::StateTransition)).await.unwrap();
+    }
+    limiter.check(Uuid::new_v4(), None, Some(&MessageType::StateProof)).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_penalties_feed_reputation() {
+    let reputation = Arc::new(ReputationManager::new(NodeIdentity::new(), ReputationConfig::default()));
+    let limiter = RateLimiter::new(limits(10, 1, false))
+        .with_message_type_limit(&MessageType::Batch, 1)
+        .with_reputation(reputation.clone())
+        .with_penalties(RateLimitPenalties { peer: 3.0, ip: 1.0, message_type: 0.5 });
+    let peer = Uuid::new_v4();
+
+    limiter.check(peer, None, None).await.unwrap();
+    assert!(limiter.check(peer, None, None).await.is_err());
+    assert!(limiter.check(peer, None, None).await.is_err());
+    let score = reputation.get_reputation(&peer).await.unwrap();
+    assert_eq!(score.reliability_score, 44.0);
+    assert_eq!(reputation.get_metrics(&peer).await.unwrap().protocol_violations, 2);
+
+    let flooder = Uuid::new_v4();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    limiter.check(flooder, None, Some(&MessageType::Batch)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(limiter.check(flooder, None, Some(&MessageType::Batch)).await.is_err());
+    assert_eq!(reputation.get_reputation(&flooder).await.unwrap().reliability_score, 49.5);
+}
+
+fn security_config(rate_limiting: RateLimitConfig) -> SecurityConfig {
+    SecurityConfig {
+        authentication_method: AuthenticationMethod::MultiFactor {
+            methods: vec!["certificate".into()],
+            required_factors: 1,
+        },
+        key_rotation_interval: Duration::from_secs(600),
+        signature_algorithm: "ed25519".into(),
+        tls_config: None,
+        rate_limiting,
+        authorization_policy: None,
+    }
+}
+
+#[tokio::test]
+async fn test_security_manager_inbound() {
+    let limiter = Arc::new(
+        RateLimiter::new(limits(5, 2, true)).with_message_type_limit(&MessageType::StateTransition, 2),
+    );
+    let mut manager = DefaultSecurityManager::new().with_rate_limiter(limiter.clone());
+    manager.init(security_config(limits(5, 2, true))).await.unwrap();
+    let peer = Peer {
+        id: Uuid::new_v4(),
+        info: PeerInfo {
+            address: "10.0.0.1:9000".into(),
+            protocol_version: "1".into(),
+            supported_features: vec![],
+            chain_ids: vec![],
+            node_type: NodeType::Relay,
+        },
+        state: PeerState::Connected,
+    };
+    let message = FrostMessage::new(MessageType::StateTransition, vec![1], "relay".into(), None);
+
+    manager.check_inbound(&peer, &message).await.unwrap();
+    manager.check_inbound(&peer, &message).await.unwrap();
+    assert!(manager.check_inbound(&peer, &message).await.is_err());
+    // Type limits are per peer, and other types are not limited here
+    let neighbour = Peer { id: Uuid::new_v4(), ..peer.clone() };
+    manager.check_inbound(&neighbour, &message).await.unwrap();
+    let batch = FrostMessage::new(MessageType::Batch, vec![1], "relay".into(), None);
+    for _ in 0..5 {
+        manager.check_inbound(&peer, &batch).await.unwrap();
+    }
+    assert_eq!(manager.metrics().rate_limit_hits, 1);
+
+    // Peer and IP buckets are left to the transports
+    limiter.check(peer.id, Some(IP), None).await.unwrap();
+    limiter.check(peer.id, Some(IP), None).await.unwrap();
+    assert!(limiter.check(peer.id, Some(IP), None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_shared_limiter_charges_each_message_once() {
+    let mut manager = DefaultSecurityManager::new();
+    manager.init(security_config(limits(1, 3, true))).await.unwrap();
+    let limiter = manager.rate_limiter().unwrap();
+
+    let mut server = TcpTransport::new(Uuid::new_v4())
+        .with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .with_rate_limiter(limiter.clone());
+    server.init(tcp_config()).await.unwrap();
+    let mut client = TcpTransport::new(Uuid::new_v4()).with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    client.init(tcp_config()).await.unwrap();
+    let peer = client.connect(&server.local_addr().unwrap().to_string()).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    // The whole burst gets through the frame check and the message check
+    for i in 0..4u8 {
+        client.send_data(&peer, &[i]).await.unwrap();
+    }
+    for i in 0..3u8 {
+        assert_eq!(server.receive_data(&client_peer).await.unwrap(), [i]);
+        let message = FrostMessage::new(MessageType::StateTransition, vec![i], "client".into(), None);
+        manager.check_inbound(&client_peer, &message).await.unwrap();
+    }
+    assert!(server.receive_data(&client_peer).await.is_err());
+    assert_eq!(limiter.hits(), 1);
+}
@@ -0,0 +1,281 @@
+use frost_protocol::finality::FinalitySignal;
+use frost_protocol::message::{
+    validation::{BasicValidationPipeline, ValidationPipeline},
+    FrostMessage, MessageType, RuleSet,
+};
+use frost_protocol::network::{
+    peer::{NodeType, PeerState, PeerStats},
+    reputation::ReputationWeights,
+    InMemoryPeerManager, NodeIdentity, Peer, PeerInfo, PeerManager, ReputationConfig, ReputationEvent,
+    ReputationManager,
+};
+use frost_protocol::{Error, Result};
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+fn manager(config: ReputationConfig) -> ReputationManager {
+    ReputationManager::new(NodeIdentity::new(), config)
+}
+
+fn info(address: &str) -> PeerInfo {
+    PeerInfo {
+        address: address.into(),
+        protocol_version: "1".into(),
+        supported_features: vec![],
+        chain_ids: vec![1],
+        node_type: NodeType::Relay,
+    }
+}
+
+fn signal(block_hash: u8) -> FinalitySignal {
+    FinalitySignal {
+        chain_id: "eth".into(),
+        block_number: 100,
+        block_hash: [block_hash; 32],
+        proof_data: vec![],
+        metadata: serde_json::json!({}),
+    }
+}
+
+#[tokio::test]
+async fn test_events_apply_weights() {
+    let weights = ReputationWeights { invalid_proof: 20.0, ..Default::default() };
+    let reputation = manager(ReputationConfig { weights, ..Default::default() });
+    let peer = Uuid::new_v4();
+
+    reputation.update_reputation(peer, ReputationEvent::InvalidProof).await.unwrap();
+    let score = reputation.get_reputation(&peer).await.unwrap();
+    assert_eq!((score.base_score, score.performance_score), (30.0, 30.0));
+
+    reputation.update_reputation(peer, ReputationEvent::Latency(Duration::from_millis(100))).await.unwrap();
+    reputation.update_reputation(peer, ReputationEvent::Latency(Duration::from_secs(2))).await.unwrap();
+    assert_eq!(reputation.get_reputation(&peer).await.unwrap().performance_score, 29.5);
+
+    reputation.update_reputation(peer, ReputationEvent::Uptime(100.0)).await.unwrap();
+    let metrics = reputation.get_metrics(&peer).await.unwrap();
+    assert_eq!(reputation.get_reputation(&peer).await.unwrap().participation_score, 60.0);
+    assert_eq!(metrics.uptime_percentage, 100.0);
+    assert_eq!(metrics.failed_validations, 1);
+    // Only latency samples count towards the average
+    assert_eq!(metrics.response_samples, 2);
+    assert_eq!(metrics.avg_response_time, Duration::from_millis(1050));
+
+    // Only contradicting claims for the same block count as disagreements
+    assert!(!reputation.report_finality(peer, &signal(1), &signal(1)).await.unwrap());
+    assert!(!reputation.report_finality(peer, &FinalitySignal { block_number: 99, ..signal(2) }, &signal(1)).await.unwrap());
+    assert!(reputation.report_finality(peer, &signal(2), &signal(1)).await.unwrap());
+    let score = reputation.get_reputation(&peer).await.unwrap();
+    assert_eq!((score.base_score, score.reliability_score), (15.0, 35.0));
+}
+
+#[tokio::test]
+async fn test_decay_towards_neutral() {
+    let config = ReputationConfig {
+        score_decay_rate: 0.5,
+        update_interval: Duration::from_secs(60),
+        ..Default::default()
+    };
+    let reputation = manager(config);
+    let (bad, good) = (Uuid::new_v4(), Uuid::new_v4());
+    reputation.update_reputation(bad, ReputationEvent::InvalidProof).await.unwrap();
+    for _ in 0..10 {
+        reputation.update_reputation(good, ReputationEvent::SuccessfulValidation {
+            response_time: Duration::from_millis(10),
+        }).await.unwrap();
+    }
+
+    reputation.apply_decay(Duration::from_secs(60)).await;
+    assert_eq!(reputation.get_reputation(&bad).await.unwrap().performance_score, 45.0);
+    assert_eq!(reputation.get_reputation(&good).await.unwrap().performance_score, 55.0);
+    reputation.apply_decay(Duration::from_secs(120)).await;
+    assert_eq!(reputation.get_reputation(&bad).await.unwrap().base_score, 48.75);
+}
+
+#[tokio::test]
+async fn test_demotion_and_ranking() {
+    let reputation = manager(ReputationConfig::default());
+    let (bad, good, unknown) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    for _ in 0..5 {
+        reputation.update_reputation(bad, ReputationEvent::InvalidProof).await.unwrap();
+        reputation.update_reputation(good, ReputationEvent::SuccessfulValidation {
+            response_time: Duration::from_millis(10),
+        }).await.unwrap();
+    }
+    reputation.update_reputation(bad, ReputationEvent::ProtocolViolation).await.unwrap();
+
+    assert!(reputation.is_demoted(&bad).await);
+    assert!(!reputation.is_demoted(&good).await);
+    assert!(!reputation.is_demoted(&unknown).await);
+    assert_eq!(reputation.rank(&[bad, unknown, good]).await, vec![good, unknown]);
+}
+
+#[tokio::test]
+async fn test_bad_relayer_is_banned() {
+    let peers = Arc::new(RwLock::new(InMemoryPeerManager::new()));
+    // Tracked under the node id that reputation events are reported for
+    let node_id = Uuid::new_v4();
+    peers.write().await.add_peer(node_id, info("10.0.0.9:9000")).await.unwrap();
+    let reputation = manager(ReputationConfig::default()).with_peer_manager(peers.clone());
+
+    // Invalid proofs alone demote but do not ban
+    for _ in 0..5 {
+        reputation.update_reputation(node_id, ReputationEvent::InvalidProof).await.unwrap();
+    }
+    assert_eq!(peers.read().await.get_peer(node_id).await.unwrap().unwrap().state, PeerState::Handshaking);
+
+    for _ in 0..3 {
+        reputation.report_finality(node_id, &signal(2), &signal(1)).await.unwrap();
+    }
+    let peers = peers.read().await;
+    assert_eq!(peers.get_peer(node_id).await.unwrap().unwrap().state, PeerState::Banned);
+    let bans = peers.bans();
+    assert_eq!(bans.len(), 1);
+    assert!(bans[0].reason.starts_with("Reputation"));
+}
+
+#[tokio::test]
+async fn test_connection_reports_only_ban_the_recorded_address() {
+    let peers = Arc::new(RwLock::new(InMemoryPeerManager::new()));
+    let node_id = Uuid::new_v4();
+    let recorded = peers.write().await.add_peer(node_id, info("10.0.0.9:9000")).await.unwrap();
+    let reputation = manager(ReputationConfig::default()).with_peer_manager(peers.clone());
+
+    // A connection claiming the node id from elsewhere lowers the score
+    // but cannot get the recorded peer banned
+    let impostor = Peer { id: node_id, info: info("10.0.0.66:9000"), state: PeerState::Connected };
+    for _ in 0..10 {
+        for event in [ReputationEvent::InvalidProof, ReputationEvent::FinalityDisagreement, ReputationEvent::ProtocolViolation] {
+            reputation.report_connection(&impostor, event).await.unwrap();
+        }
+    }
+    assert!(reputation.is_demoted(&node_id).await);
+    assert_eq!(peers.read().await.get_peer(node_id).await.unwrap().unwrap().state, PeerState::Handshaking);
+    assert!(peers.read().await.bans().is_empty());
+
+    reputation.report_connection(&recorded, ReputationEvent::ProtocolViolation).await.unwrap();
+    assert_eq!(peers.read().await.get_peer(node_id).await.unwrap().unwrap().state, PeerState::Banned);
+}
+
+/// Peer manager whose bans of one peer always fail
+struct StubbornPeers {
+    peers: InMemoryPeerManager,
+    stubborn: Uuid,
+}
+
+#[async_trait]
+impl PeerManager for StubbornPeers {
+    async fn add_peer(&mut self, id: Uuid, info: PeerInfo) -> Result<Peer> {
+        self.peers.add_peer(id, info).await
+    }
+
+    async fn remove_peer(&mut self, peer: &Peer) -> Result<()> {
+        self.peers.remove_peer(peer).await
+    }
+
+    async fn ban_peer(&mut self, peer: &Peer, reason: String) -> Result<()> {
+        if peer.id == self.stubborn {
+            return Err(Error::Network("ban list unavailable".into()));
+        }
+        self.peers.ban_peer(peer, reason).await
+    }
+
+    async fn unban_peer(&mut self, peer: &Peer) -> Result<()> {
+        self.peers.unban_peer(peer).await
+    }
+
+    async fn get_peer(&self, id: Uuid) -> Result<Option<Peer>> {
+        self.peers.get_peer(id).await
+    }
+
+    async fn list_peers(&self) -> Result<Vec<Peer>> {
+        self.peers.list_peers().await
+    }
+
+    fn peer_stats(&self) -> PeerStats {
+        self.peers.peer_stats()
+    }
+}
+
+#[tokio::test]
+async fn test_failed_ban_does_not_stop_enforcement() {
+    let relayers: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+    let stubborn = relayers[0];
+    let peers = Arc::new(RwLock::new(StubbornPeers { peers: InMemoryPeerManager::new(), stubborn }));
+    let config = ReputationConfig {
+        score_decay_rate: 0.0,
+        update_interval: Duration::from_millis(50),
+        ..Default::default()
+    };
+    let reputation = Arc::new(manager(config).with_peer_manager(peers.clone()));
+
+    // Scores drop while the peers are untracked, so only the periodic update bans them
+    for relayer in &relayers {
+        for _ in 0..5 {
+            reputation.update_reputation(*relayer, ReputationEvent::InvalidProof).await.unwrap();
+        }
+        for _ in 0..3 {
+            reputation.report_finality(*relayer, &signal(2), &signal(1)).await.unwrap();
+        }
+    }
+    for (i, relayer) in relayers.iter().enumerate() {
+        peers.write().await.add_peer(*relayer, info(&format!("10.0.0.{}:9000", i + 1))).await.unwrap();
+    }
+    reputation.start().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let peers = peers.read().await;
+    assert_eq!(peers.get_peer(stubborn).await.unwrap().unwrap().state, PeerState::Handshaking);
+    for relayer in &relayers[1..] {
+        assert_eq!(peers.get_peer(*relayer).await.unwrap().unwrap().state, PeerState::Banned);
+    }
+}
+
+#[tokio::test]
+async fn test_scores_persist() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reputation.json");
+    let peer = Uuid::new_v4();
+    {
+        let reputation = ReputationManager::open(&path, NodeIdentity::new(), ReputationConfig::default()).await.unwrap();
+        reputation.update_reputation(peer, ReputationEvent::FailedValidation).await.unwrap();
+        reputation.save().await.unwrap();
+    }
+
+    let reputation = ReputationManager::open(&path, NodeIdentity::new(), ReputationConfig::default()).await.unwrap();
+    assert_eq!(reputation.get_reputation(&peer).await.unwrap().performance_score, 48.0);
+    assert_eq!(reputation.get_metrics(&peer).await.unwrap().failed_validations, 1);
+
+    std::fs::write(&path, "{").unwrap();
+    assert!(ReputationManager::open(&path, NodeIdentity::new(), ReputationConfig::default()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_validation_failures_feed_reputation() {
+    let reputation = Arc::new(manager(ReputationConfig::default()));
+    let rules = RuleSet::from_toml("[[proof_validation]]\nrule = \"max_payload_size\"\nmax_bytes = 4").unwrap();
+    let pipeline = BasicValidationPipeline::new().with_rules(rules).with_reputation(reputation.clone());
+    let peer = Uuid::new_v4();
+
+    let mut oversized = FrostMessage::new(MessageType::Discovery, vec![0; 8], "relayer".into(), None);
+    assert!(pipeline.validate_from(peer, &mut oversized).await.is_err());
+    let mut empty = FrostMessage::new(MessageType::Discovery, vec![], "relayer".into(), None);
+    assert!(pipeline.validate_from(peer, &mut empty).await.is_err());
+    let mut fine = FrostMessage::new(MessageType::Discovery, vec![1], "relayer".into(), None);
+    pipeline.validate_from(peer, &mut fine).await.unwrap();
+
+    let score = reputation.get_reputation(&peer).await.unwrap();
+    assert_eq!((score.base_score, score.performance_score), (40.0, 38.0));
+    assert_eq!(reputation.get_metrics(&peer).await.unwrap().failed_validations, 2);
+
+    // The claimed source is never blamed, even when it names a peer
+    let framed = Uuid::new_v4();
+    let mut forged = FrostMessage::new(MessageType::Discovery, vec![], framed.to_string(), None);
+    assert!(pipeline.validate_from(peer, &mut forged).await.is_err());
+    assert!(pipeline.validate(&mut forged).await.is_err());
+    assert!(reputation.get_reputation(&framed).await.is_none());
+    assert_eq!(reputation.get_metrics(&peer).await.unwrap().failed_validations, 3);
+}
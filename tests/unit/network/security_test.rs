@@ -0,0 +1,403 @@
+use frost_protocol::network::{
+    peer::{NodeType, PeerState},
+    security::{
+        Action, ActionType, AuthenticationMethod, PeerCertificate, RateLimitConfig, TokenClaims,
+        CERTIFICATE_FACTOR, TOKEN_FACTOR,
+    },
+    DefaultSecurityManager, Peer, PeerInfo, SecurityConfig, SecurityManager,
+};
+
+use std::time::{Duration, SystemTime};
+use ed25519_dalek::{Signer, SigningKey};
+use uuid::Uuid;
+
+/// Node id of the verifying side
+const SERVER_ID: Uuid = Uuid::from_u128(1);
+/// Stands in for the Noise handshake hash of the connection
+const BINDING: &[u8] = b"noise handshake hash";
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&rand::random())
+}
+
+fn peer() -> Peer {
+    Peer {
+        id: Uuid::new_v4(),
+        info: PeerInfo {
+            address: "127.0.0.1:9000".into(),
+            protocol_version: "1".into(),
+            supported_features: vec![],
+            chain_ids: vec![],
+            node_type: NodeType::Validator,
+        },
+        state: PeerState::Connected,
+    }
+}
+
+fn config(authentication_method: AuthenticationMethod) -> SecurityConfig {
+    SecurityConfig {
+        authentication_method,
+        key_rotation_interval: Duration::from_secs(600),
+        signature_algorithm: "ed25519".into(),
+        tls_config: None,
+        rate_limiting: RateLimitConfig {
+            max_requests: 100,
+            window_size: Duration::from_secs(1),
+            per_ip_limit: false,
+            burst_size: 10,
+        },
+        authorization_policy: None,
+    }
+}
+
+fn certificate_method(ca: Option<&SigningKey>) -> AuthenticationMethod {
+    AuthenticationMethod::Certificate {
+        ca_cert: ca.map(|k| hex::encode(k.verifying_key().to_bytes())).unwrap_or_default(),
+        client_cert: String::new(),
+        client_key: String::new(),
+    }
+}
+
+fn token_method(issuer: &SigningKey) -> AuthenticationMethod {
+    AuthenticationMethod::Token {
+        token_type: "ed25519".into(),
+        token_value: hex::encode(issuer.verifying_key().to_bytes()),
+    }
+}
+
+fn multi_factor(required_factors: usize) -> AuthenticationMethod {
+    AuthenticationMethod::MultiFactor {
+        methods: vec![CERTIFICATE_FACTOR.into(), TOKEN_FACTOR.into()],
+        required_factors,
+    }
+}
+
+fn token(subject: Uuid, holder: &SigningKey, ttl: u64, issuer: &SigningKey) -> String {
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    TokenClaims {
+        subject,
+        public_key: holder.verifying_key().to_bytes().to_vec(),
+        node_type: None,
+        permissions: vec!["relay".into()],
+        expires_at: now + ttl,
+    }
+    .sign(issuer)
+}
+
+/// Run the challenge exchange and authenticate `client` as `peer`
+async fn authenticate(server: &DefaultSecurityManager, client: &DefaultSecurityManager, peer: &Peer) -> bool {
+    let challenge = server.issue_challenge(peer, BINDING);
+    server.present_credentials(peer, client.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge));
+    server.authenticate_peer(peer).await.unwrap().success
+}
+
+fn action() -> Action {
+    Action {
+        action_type: ActionType::SendMessage,
+        resource: "state".into(),
+        timestamp: SystemTime::now(),
+        metadata: serde_json::Value::Null,
+    }
+}
+
+#[tokio::test]
+async fn test_pinned_key() {
+    let (peer, client_key) = (peer(), key());
+    let mut server = DefaultSecurityManager::new()
+        .with_node_id(SERVER_ID)
+        .with_pinned_key(peer.id, client_key.verifying_key());
+    server.init(config(certificate_method(None))).await.unwrap();
+    let client = DefaultSecurityManager::new().with_signing_key(client_key.clone());
+
+    assert!(authenticate(&server, &client, &peer).await);
+    assert!(server.authorize_action(&action(), &peer).await.unwrap());
+    assert_eq!(server.metrics().active_sessions, 1);
+
+    // Challenges are single use, so captured credentials cannot be replayed
+    let challenge = server.issue_challenge(&peer, BINDING);
+    let credentials = client.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge);
+    server.present_credentials(&peer, credentials.clone());
+    assert!(server.authenticate_peer(&peer).await.unwrap().success);
+    server.present_credentials(&peer, credentials);
+    assert!(!server.authenticate_peer(&peer).await.unwrap().success);
+
+    // A key that is not pinned, or a signature for another node, fails
+    let impostor = DefaultSecurityManager::new().with_signing_key(key());
+    assert!(!authenticate(&server, &impostor, &peer).await);
+    let challenge = server.issue_challenge(&peer, BINDING);
+    server.present_credentials(&peer, client.respond_to_challenge(Uuid::new_v4(), SERVER_ID, BINDING, &challenge));
+    assert!(!server.authenticate_peer(&peer).await.unwrap().success);
+
+    let metrics = server.metrics();
+    assert_eq!(metrics.authentication_attempts, 5);
+    assert_eq!(metrics.failed_authentications, 3);
+
+    // Answers made for another verifier or connection cannot be relayed
+    let challenge = server.issue_challenge(&peer, BINDING);
+    server.present_credentials(&peer, client.respond_to_challenge(peer.id, Uuid::new_v4(), BINDING, &challenge));
+    assert!(!server.authenticate_peer(&peer).await.unwrap().success);
+    let challenge = server.issue_challenge(&peer, BINDING);
+    server.present_credentials(&peer, client.respond_to_challenge(peer.id, SERVER_ID, b"other connection", &challenge));
+    assert!(!server.authenticate_peer(&peer).await.unwrap().success);
+
+    // Without a local node id no answer verifies
+    let mut anonymous = DefaultSecurityManager::new().with_pinned_key(peer.id, client_key.verifying_key());
+    anonymous.init(config(certificate_method(None))).await.unwrap();
+    assert!(!authenticate(&anonymous, &client, &peer).await);
+}
+
+#[tokio::test]
+async fn test_certificate_authority() {
+    let (ca, peer, client_key) = (key(), peer(), key());
+    let mut server = DefaultSecurityManager::new().with_node_id(SERVER_ID);
+    server.init(config(certificate_method(Some(&ca)))).await.unwrap();
+
+    // The client is configured from the same `AuthenticationMethod`
+    let certificate = PeerCertificate::issue(
+        peer.id, &client_key.verifying_key(), None, vec!["submit".into()], Duration::from_secs(3600), &ca,
+    );
+    let mut client = DefaultSecurityManager::new();
+    client.init(config(AuthenticationMethod::Certificate {
+        ca_cert: hex::encode(ca.verifying_key().to_bytes()),
+        client_cert: certificate.encode(),
+        client_key: hex::encode(client_key.to_bytes()),
+    })).await.unwrap();
+
+    let challenge = server.issue_challenge(&peer, BINDING);
+    server.present_credentials(&peer, client.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge));
+    let result = server.authenticate_peer(&peer).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.permissions, vec!["submit".to_string()]);
+    assert!(result.expiry.unwrap() <= SystemTime::UNIX_EPOCH + Duration::from_secs(certificate.expires_at));
+
+    // Certificates for another node, expired, or from an unknown CA are rejected
+    let other = PeerCertificate::issue(Uuid::new_v4(), &client_key.verifying_key(), None, vec![], Duration::from_secs(60), &ca);
+    let expired = PeerCertificate::issue(peer.id, &client_key.verifying_key(), None, vec![], Duration::ZERO, &ca);
+    let untrusted = PeerCertificate::issue(peer.id, &client_key.verifying_key(), None, vec![], Duration::from_secs(60), &key());
+    for certificate in [other, expired, untrusted] {
+        let client = DefaultSecurityManager::new().with_signing_key(client_key.clone()).with_certificate(certificate);
+        assert!(!authenticate(&server, &client, &peer).await);
+    }
+    assert!(PeerCertificate::decode("zz").is_err());
+}
+
+#[tokio::test]
+async fn test_bearer_tokens() {
+    let (issuer, peer, client_key) = (key(), peer(), key());
+    let mut server = DefaultSecurityManager::new().with_node_id(SERVER_ID);
+    server.init(config(token_method(&issuer))).await.unwrap();
+
+    let client = DefaultSecurityManager::new()
+        .with_signing_key(client_key.clone())
+        .with_token(token(peer.id, &client_key, 30, &issuer));
+    let challenge = server.issue_challenge(&peer, BINDING);
+    server.present_credentials(&peer, client.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge));
+    let result = server.authenticate_peer(&peer).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.permissions, vec!["relay".to_string()]);
+    assert!(result.expiry.unwrap() <= SystemTime::now() + Duration::from_secs(30));
+
+    // Wrong subject or key, expired, untrusted issuer and tampered tokens fail
+    let mut tampered = token(peer.id, &client_key, 30, &issuer);
+    tampered.replace_range(0..2, "00");
+    for token in [
+        token(Uuid::new_v4(), &client_key, 30, &issuer),
+        token(peer.id, &key(), 30, &issuer),
+        token(peer.id, &client_key, 0, &issuer),
+        token(peer.id, &client_key, 30, &key()),
+        tampered,
+    ] {
+        let client = DefaultSecurityManager::new().with_signing_key(client_key.clone()).with_token(token);
+        assert!(!authenticate(&server, &client, &peer).await);
+    }
+
+    // A valid token without a challenge signature from its key fails
+    let unsigned = DefaultSecurityManager::new().with_token(token(peer.id, &client_key, 30, &issuer));
+    assert!(!authenticate(&server, &unsigned, &peer).await);
+
+    // Nothing presented at all
+    assert!(!server.authenticate_peer(&peer).await.unwrap().success);
+}
+
+#[tokio::test]
+async fn test_token_replay_to_another_verifier() {
+    let (issuer, peer, client_key) = (key(), peer(), key());
+    let verifier_b = Uuid::from_u128(2);
+    let mut first = DefaultSecurityManager::new().with_node_id(SERVER_ID);
+    first.init(config(token_method(&issuer))).await.unwrap();
+    let mut second = DefaultSecurityManager::new().with_node_id(verifier_b);
+    second.init(config(token_method(&issuer))).await.unwrap();
+
+    let client = DefaultSecurityManager::new()
+        .with_signing_key(client_key.clone())
+        .with_token(token(peer.id, &client_key, 60, &issuer));
+    let challenge = first.issue_challenge(&peer, BINDING);
+    let credentials = client.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge);
+    first.present_credentials(&peer, credentials.clone());
+    assert!(first.authenticate_peer(&peer).await.unwrap().success);
+
+    // The first verifier replays the token, with its own key or with the
+    // answer it was given, to a second verifier
+    let relay = DefaultSecurityManager::new().with_signing_key(key()).with_token(credentials.token.clone().unwrap());
+    let challenge = second.issue_challenge(&peer, BINDING);
+    second.present_credentials(&peer, relay.respond_to_challenge(peer.id, verifier_b, BINDING, &challenge));
+    assert!(!second.authenticate_peer(&peer).await.unwrap().success);
+    second.issue_challenge(&peer, BINDING);
+    second.present_credentials(&peer, credentials);
+    assert!(!second.authenticate_peer(&peer).await.unwrap().success);
+
+    // The holder itself still authenticates with the second verifier
+    let challenge = second.issue_challenge(&peer, BINDING);
+    second.present_credentials(&peer, client.respond_to_challenge(peer.id, verifier_b, BINDING, &challenge));
+    assert!(second.authenticate_peer(&peer).await.unwrap().success);
+    assert_eq!(second.metrics().failed_authentications, 2);
+}
+
+#[tokio::test]
+async fn test_multi_factor() {
+    let (ca, issuer, peer, client_key) = (key(), key(), peer(), key());
+    let certificate = PeerCertificate::issue(
+        peer.id, &client_key.verifying_key(), None, vec!["submit".into()], Duration::from_secs(3600), &ca,
+    );
+    let both = DefaultSecurityManager::new()
+        .with_signing_key(client_key.clone())
+        .with_certificate(certificate.clone())
+        .with_token(token(peer.id, &client_key, 60, &issuer));
+    let certificate_only = DefaultSecurityManager::new()
+        .with_signing_key(client_key.clone())
+        .with_certificate(certificate);
+    let token_only = DefaultSecurityManager::new()
+        .with_signing_key(client_key.clone())
+        .with_token(token(peer.id, &client_key, 60, &issuer));
+    // Credentials attesting different node types
+    let expires_at = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs() + 60;
+    let conflicting = DefaultSecurityManager::new()
+        .with_signing_key(client_key.clone())
+        .with_certificate(PeerCertificate::issue(
+            peer.id, &client_key.verifying_key(), Some(NodeType::Relay), vec![], Duration::from_secs(60), &ca,
+        ))
+        .with_token(TokenClaims {
+            subject: peer.id,
+            public_key: client_key.verifying_key().to_bytes().to_vec(),
+            node_type: Some(NodeType::Validator),
+            permissions: vec![],
+            expires_at,
+        }.sign(&issuer));
+
+    let (ca, issuer) = (ca.verifying_key(), issuer.verifying_key());
+    let server = |required| async move {
+        let mut server = DefaultSecurityManager::new()
+            .with_node_id(SERVER_ID)
+            .with_certificate_authority(ca)
+            .with_token_issuer(issuer);
+        server.init(config(multi_factor(required))).await.unwrap();
+        server
+    };
+
+    let strict = server(2).await;
+    let challenge = strict.issue_challenge(&peer, BINDING);
+    strict.present_credentials(&peer, both.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge));
+    let result = strict.authenticate_peer(&peer).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.permissions, vec!["relay".to_string(), "submit".to_string()]);
+    assert!(!authenticate(&strict, &certificate_only, &peer).await);
+    assert!(!authenticate(&strict, &token_only, &peer).await);
+    assert!(!authenticate(&strict, &conflicting, &peer).await);
+
+    let lenient = server(1).await;
+    assert!(authenticate(&lenient, &certificate_only, &peer).await);
+    assert!(authenticate(&lenient, &token_only, &peer).await);
+}
+
+#[tokio::test]
+async fn test_sessions_and_revocation() {
+    let (peer, client_key) = (peer(), key());
+    let mut server = DefaultSecurityManager::new()
+        .with_node_id(SERVER_ID)
+        .with_pinned_key(peer.id, client_key.verifying_key());
+    server.init(config(certificate_method(None))).await.unwrap();
+    let client = DefaultSecurityManager::new().with_signing_key(client_key.clone());
+
+    assert!(server.generate_session(&peer).await.is_err());
+    assert!(!server.authorize_action(&action(), &peer).await.unwrap());
+
+    let challenge = server.issue_challenge(&peer, BINDING);
+    server.present_credentials(&peer, client.respond_to_challenge(peer.id, SERVER_ID, BINDING, &challenge));
+    let session_id = server.authenticate_peer(&peer).await.unwrap().session_id.unwrap();
+    assert!(server.is_session_active(session_id));
+
+    let keys = server.generate_session(&peer).await.unwrap();
+    assert_eq!(keys.session_id, session_id);
+    assert_eq!(keys.encryption_key.len(), 32);
+    assert!(keys.expires_at <= keys.created_at + Duration::from_secs(600));
+
+    // Signatures verify against the key the peer authenticated with
+    let message = b"state root";
+    let signature = client_key.sign(message).to_bytes();
+    assert!(server.validate_signature(message, &signature, &peer).await.unwrap());
+    assert!(!server.validate_signature(b"other", &signature, &peer).await.unwrap());
+    assert!(!server.validate_signature(message, &[0; 3], &peer).await.unwrap());
+
+    assert!(server.revoke_session(session_id));
+    assert!(!server.revoke_session(session_id));
+    assert!(!server.is_session_active(session_id));
+    assert!(!server.authorize_action(&action(), &peer).await.unwrap());
+    assert!(!server.validate_signature(message, &signature, &peer).await.unwrap());
+
+    assert!(authenticate(&server, &client, &peer).await);
+    assert!(authenticate(&server, &client, &peer).await);
+    assert_eq!(server.revoke_peer(peer.id), 2);
+
+    let metrics = server.metrics();
+    assert_eq!(metrics.active_sessions, 0);
+    assert_eq!(metrics.revoked_sessions, 3);
+    assert_eq!(metrics.signature_validations, 4);
+    assert_eq!(metrics.failed_validations, 3);
+}
+
+#[tokio::test]
+async fn test_session_ttl() {
+    let (peer, client_key) = (peer(), key());
+    let mut server = DefaultSecurityManager::new()
+        .with_node_id(SERVER_ID)
+        .with_session_ttl(Duration::from_millis(100))
+        .with_pinned_key(peer.id, client_key.verifying_key());
+    server.init(config(certificate_method(None))).await.unwrap();
+    let client = DefaultSecurityManager::new().with_signing_key(client_key);
+
+    assert!(authenticate(&server, &client, &peer).await);
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(!server.authorize_action(&action(), &peer).await.unwrap());
+    assert_eq!(server.metrics().active_sessions, 0);
+}
+
+#[tokio::test]
+async fn test_init_validation() {
+    let peer = peer();
+    let uninitialized = DefaultSecurityManager::new();
+    assert!(uninitialized.authenticate_peer(&peer).await.is_err());
+
+    let mut manager = DefaultSecurityManager::new();
+    let mut rsa = config(certificate_method(None));
+    rsa.signature_algorithm = "rsa".into();
+    assert!(manager.init(rsa).await.is_err());
+    assert!(manager.init(config(AuthenticationMethod::Token {
+        token_type: "jwt".into(),
+        token_value: String::new(),
+    })).await.is_err());
+    assert!(manager.init(config(AuthenticationMethod::Certificate {
+        ca_cert: "not hex".into(),
+        client_cert: String::new(),
+        client_key: String::new(),
+    })).await.is_err());
+    assert!(manager.init(config(multi_factor(3))).await.is_err());
+    assert!(manager.init(config(AuthenticationMethod::MultiFactor {
+        methods: vec!["sms".into()],
+        required_factors: 1,
+    })).await.is_err());
+    // One token cannot count as two factors
+    assert!(manager.init(config(AuthenticationMethod::MultiFactor {
+        methods: vec![TOKEN_FACTOR.into(), TOKEN_FACTOR.into()],
+        required_factors: 2,
+    })).await.is_err());
+}
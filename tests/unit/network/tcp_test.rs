@@ -0,0 +1,351 @@
+use frost_protocol::network::{
+    pool::{ConnectionPool, DefaultConnectionPool, DynamicAdjustment, DynamicPoolConfig, PoolConfig},
+    tcp::{PROTOCOL_NAME, PROTOCOL_VERSION},
+    security::{AuthenticationMethod, RateLimitConfig},
+    transport::{CompressionConfig, EncryptionConfig, TransportProtocol},
+    DefaultSecurityManager, NodeIdentity, RateLimiter, ReputationConfig, ReputationManager, SecurityConfig,
+    SecurityManager, TcpTransport, Transport, TransportConfig,
+};
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use ed25519_dalek::SigningKey;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use uuid::Uuid;
+
+/// Node id of the accepting side
+const SERVER_ID: Uuid = Uuid::from_u128(1);
+
+fn config(timeout: Duration, keep_alive: bool) -> TransportConfig {
+    TransportConfig {
+        protocol: TransportProtocol::TCP { port: 0, keep_alive },
+        encryption: EncryptionConfig { enabled: false, algorithm: String::new(), key_size: 0 },
+        compression: CompressionConfig { enabled: false, algorithm: String::new(), level: 0, threshold: 512 },
+        timeout,
+        buffer_size: 64 * 1024,
+    }
+}
+
+async fn transport(timeout: Duration, keep_alive: bool) -> TcpTransport {
+    let mut transport = TcpTransport::new(Uuid::new_v4())
+        .with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .with_chain_ids(vec![1]);
+    transport.init(config(timeout, keep_alive)).await.unwrap();
+    transport
+}
+
+fn address(transport: &TcpTransport) -> String {
+    transport.local_addr().unwrap().to_string()
+}
+
+fn security_config() -> SecurityConfig {
+    SecurityConfig {
+        authentication_method: AuthenticationMethod::Certificate {
+            ca_cert: String::new(),
+            client_cert: String::new(),
+            client_key: String::new(),
+        },
+        key_rotation_interval: Duration::from_secs(600),
+        signature_algorithm: "ed25519".into(),
+        tls_config: None,
+        rate_limiting: RateLimitConfig {
+            max_requests: 100,
+            window_size: Duration::from_secs(1),
+            per_ip_limit: false,
+            burst_size: 10,
+        },
+        authorization_policy: None,
+    }
+}
+
+fn frame(kind: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = ((payload.len() + 1) as u32).to_be_bytes().to_vec();
+    frame.push(kind);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn handshake(version: u16) -> Vec<u8> {
+    handshake_as(Uuid::new_v4(), version)
+}
+
+fn handshake_as(node_id: Uuid, version: u16) -> Vec<u8> {
+    frame(0, &serde_json::to_vec(&serde_json::json!({
+        "protocol": PROTOCOL_NAME,
+        "version": version,
+        "node_id": node_id,
+        "node_type": "Observer",
+        "features": [],
+        "chain_ids": [],
+    })).unwrap())
+}
+
+async fn wait_until(mut condition: impl FnMut() -> bool) {
+    for _ in 0..100 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("condition not reached");
+}
+
+#[tokio::test]
+async fn test_handshake_and_framing() {
+    let mut client = transport(Duration::from_secs(2), true).await;
+    let server = transport(Duration::from_secs(2), true).await;
+
+    let server_peer = client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert_eq!(server_peer.id, server.node_id());
+    assert_eq!(client_peer.id, client.node_id());
+    assert_eq!(server_peer.info.protocol_version, format!("{}/{}", PROTOCOL_NAME, PROTOCOL_VERSION));
+    assert_eq!(client_peer.info.chain_ids, vec![1]);
+    assert!(client.is_connected(&server_peer).await);
+
+    // Frames keep their boundaries, including empty and large payloads
+    let large = vec![7u8; 1024 * 1024];
+    for payload in [b"hello".to_vec(), Vec::new(), large.clone()] {
+        assert_eq!(client.send_data(&server_peer, &payload).await.unwrap(), payload.len());
+    }
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"hello");
+    assert!(server.receive_data(&client_peer).await.unwrap().is_empty());
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), large);
+
+    server.send_data(&client_peer, b"reply").await.unwrap();
+    assert_eq!(client.receive_data(&server_peer).await.unwrap(), b"reply");
+
+    let (sent, received) = (client.metrics(), server.metrics());
+    assert_eq!(sent.active_connections, 1);
+    assert_eq!(received.active_connections, 1);
+    assert!(sent.bytes_sent > large.len() as u64);
+    assert!(sent.average_latency > Duration::ZERO);
+    wait_until(|| server.metrics().bytes_received == client.metrics().bytes_sent).await;
+    assert_eq!(sent.connection_errors, 0);
+}
+
+#[tokio::test]
+async fn test_frame_size_limit() {
+    let mut client = transport(Duration::from_secs(2), false).await;
+    let mut server = TcpTransport::new(Uuid::new_v4()).with_max_frame_size(1024);
+    server.init(config(Duration::from_secs(2), false)).await.unwrap();
+    let address = format!("127.0.0.1:{}", server.local_addr().unwrap().port());
+
+    let peer = client.connect(&address).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert!(server.send_data(&client_peer, &[0; 2048]).await.is_err());
+
+    // The receiver drops a peer sending oversized frames
+    client.send_data(&peer, &[0; 2048]).await.unwrap();
+    assert!(server.receive_data(&client_peer).await.is_err());
+    wait_until(|| server.metrics().connection_errors == 1).await;
+}
+
+#[tokio::test]
+async fn test_handshake_rejections() {
+    let server = transport(Duration::from_millis(500), false).await;
+    let addr: SocketAddr = server.local_addr().unwrap();
+
+    // Unsupported protocol version
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(&handshake(PROTOCOL_VERSION + 1)).await.unwrap();
+    let mut buf = [0u8; 1];
+    assert_eq!(stream.read(&mut buf).await.unwrap_or(0), 0);
+
+    // Data before the handshake
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(&frame(1, b"data")).await.unwrap();
+    assert_eq!(stream.read(&mut buf).await.unwrap_or(0), 0);
+
+    // A silent client times out
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    assert_eq!(stream.read(&mut buf).await.unwrap_or(0), 0);
+
+    // Handshakes are limited well below the data frame size
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(&frame(0, &[b' '; 64 * 1024])).await.unwrap_or(());
+    assert_eq!(stream.read(&mut buf).await.unwrap_or(0), 0);
+
+    wait_until(|| server.metrics().connection_errors == 4).await;
+    assert_eq!(server.metrics().active_connections, 0);
+
+    // Connecting to self is refused
+    let mut same = TcpTransport::new(server.node_id());
+    same.init(config(Duration::from_millis(500), false)).await.unwrap();
+    assert!(same.connect(&addr.to_string()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_duplicate_node_id_does_not_evict() {
+    let mut client = transport(Duration::from_secs(2), false).await;
+    let server = transport(Duration::from_secs(2), false).await;
+    let peer = client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    // A second connection claiming the same node id is refused
+    let mut stream = TcpStream::connect(server.local_addr().unwrap()).await.unwrap();
+    stream.write_all(&handshake_as(client.node_id(), PROTOCOL_VERSION)).await.unwrap();
+    let mut buf = [0u8; 64];
+    while stream.read(&mut buf).await.unwrap_or(0) > 0 {}
+    wait_until(|| server.metrics().connection_errors == 1).await;
+
+    // The original connection is untouched
+    assert!(server.is_connected(&client_peer).await);
+    client.send_data(&peer, b"still here").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"still here");
+}
+
+#[tokio::test]
+async fn test_rate_limited_frames_are_dropped() {
+    let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+        max_requests: 1,
+        window_size: Duration::from_secs(60),
+        per_ip_limit: true,
+        burst_size: 2,
+    }));
+    let mut client = transport(Duration::from_millis(500), false).await;
+    let mut server = TcpTransport::new(Uuid::new_v4())
+        .with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .with_rate_limiter(limiter.clone());
+    server.init(config(Duration::from_millis(500), false)).await.unwrap();
+    let peer = client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    for i in 0..5u8 {
+        client.send_data(&peer, &[i]).await.unwrap();
+    }
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), [0]);
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), [1]);
+    assert!(server.receive_data(&client_peer).await.is_err());
+    assert_eq!(limiter.hits(), 3);
+    assert!(server.is_connected(&client_peer).await);
+}
+
+#[tokio::test]
+async fn test_keep_alive_and_timeouts() {
+    let mut client = transport(Duration::from_millis(500), true).await;
+    let server = transport(Duration::from_millis(500), true).await;
+    let peer = client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    // Receiving with nothing sent times out without closing the connection
+    assert!(client.receive_data(&peer).await.is_err());
+
+    // Pings keep an idle connection open
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert!(client.is_connected(&peer).await);
+    assert!(server.is_connected(&client_peer).await);
+
+    // A peer that completes the handshake but never pings is dropped
+    let mut stream = TcpStream::connect(server.local_addr().unwrap()).await.unwrap();
+    stream.write_all(&handshake(PROTOCOL_VERSION)).await.unwrap();
+    let silent = server.accept().await.unwrap();
+    assert!(server.is_connected(&silent).await);
+    wait_until(|| server.peers().len() == 1).await;
+    assert!(server.metrics().connection_errors >= 1);
+}
+
+#[tokio::test]
+async fn test_keep_alive_feeds_reputation() {
+    let reputation = Arc::new(ReputationManager::new(NodeIdentity::new(), ReputationConfig::default()));
+    let mut security = DefaultSecurityManager::new().with_node_id(SERVER_ID);
+    security.init(security_config()).await.unwrap();
+    let security = Arc::new(security);
+    let mut client = transport(Duration::from_millis(300), true).await;
+    let mut server = TcpTransport::new(SERVER_ID)
+        .with_listen_ip(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .with_reputation(reputation.clone(), security.clone());
+    server.init(config(Duration::from_millis(300), true)).await.unwrap();
+    client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    // Node ids in the hello are unauthenticated, so nothing is reported yet
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(reputation.get_metrics(&client_peer.id).await.is_none());
+
+    let client_key = SigningKey::from_bytes(&rand::random());
+    security.pin_key(client_peer.id, client_key.verifying_key());
+    let client_security = DefaultSecurityManager::new().with_signing_key(client_key);
+    let challenge = security.issue_challenge(&client_peer, b"binding");
+    security.present_credentials(
+        &client_peer,
+        client_security.respond_to_challenge(client_peer.id, SERVER_ID, b"binding", &challenge),
+    );
+    assert!(security.authenticate_peer(&client_peer).await.unwrap().success);
+
+    // Answered pings report latency, and uptime once the next ping is due
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let metrics = reputation.get_metrics(&client_peer.id).await.unwrap();
+    assert!(metrics.response_samples >= 1);
+    assert!(metrics.avg_response_time < Duration::from_millis(100));
+    assert_eq!(metrics.uptime_percentage, 100.0);
+}
+
+#[tokio::test]
+async fn test_disconnect() {
+    let mut client = transport(Duration::from_secs(2), false).await;
+    let server = transport(Duration::from_secs(2), false).await;
+    let peer = client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    client.send_data(&peer, b"last").await.unwrap();
+    client.disconnect(&peer).await.unwrap();
+    assert!(!client.is_connected(&peer).await);
+    assert!(client.send_data(&peer, b"more").await.is_err());
+
+    // Data sent before the close is still delivered
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"last");
+    wait_until(|| server.peers().is_empty()).await;
+    assert_eq!(server.metrics().active_connections, 0);
+    assert!(client.connect("127.0.0.1:1").await.is_err());
+    assert_eq!(client.metrics().connection_errors, 1);
+}
+
+#[tokio::test]
+async fn test_rejects_unsupported_config() {
+    let mut transport = TcpTransport::new(Uuid::new_v4());
+    let mut websocket = config(Duration::from_secs(1), false);
+    websocket.protocol = TransportProtocol::WebSocket { url: "ws://localhost".into(), use_tls: false };
+    assert!(transport.init(websocket).await.is_err());
+    assert!(transport.connect("127.0.0.1:1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_connection_pool_over_loopback() {
+    let mut client = transport(Duration::from_secs(2), false).await;
+    let server = transport(Duration::from_secs(2), false).await;
+    let peer = client.connect(&address(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    let pool = DefaultConnectionPool::new(DynamicPoolConfig {
+        base: PoolConfig {
+            min_idle_per_peer: 2,
+            max_per_peer: 4,
+            max_lifetime: Duration::from_secs(60),
+            idle_timeout: Duration::from_secs(60),
+            connection_timeout: Duration::from_secs(2),
+            validation_interval: Duration::from_secs(10),
+        },
+        dynamic: DynamicAdjustment {
+            adaptation_rate: 0.1,
+            max_growth_rate: 0.5,
+            min_total_connections: 1,
+            max_total_connections: 16,
+            scale_up_threshold: 0.8,
+            scale_down_threshold: 0.2,
+        },
+    }, client);
+
+    let connection = pool.acquire(&peer).await.unwrap();
+    pool.transport().send_data(&connection.peer, b"pooled").await.unwrap();
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"pooled");
+    pool.release(connection).await.unwrap();
+
+    // Idle connections are revalidated against the transport
+    drop(server);
+    wait_until(|| pool.transport().peers().is_empty()).await;
+    let reacquired = pool.acquire(&peer).await.unwrap();
+    assert!(pool.transport().send_data(&reacquired.peer, b"gone").await.is_err());
+}
@@ -0,0 +1,217 @@
+use frost_protocol::network::{
+    peer::NodeType,
+    transport::{CompressionConfig, EncryptionConfig, TransportProtocol},
+    Transport, TransportConfig, WebSocketTransport,
+};
+
+use std::time::Duration;
+use futures::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+use uuid::Uuid;
+
+fn config(url: &str, timeout: Duration) -> TransportConfig {
+    TransportConfig {
+        protocol: TransportProtocol::WebSocket { url: url.into(), use_tls: false },
+        encryption: EncryptionConfig { enabled: false, algorithm: String::new(), key_size: 0 },
+        compression: CompressionConfig { enabled: false, algorithm: String::new(), level: 0, threshold: 512 },
+        timeout,
+        buffer_size: 64 * 1024,
+    }
+}
+
+async fn server(timeout: Duration) -> WebSocketTransport {
+    let mut server = WebSocketTransport::new(Uuid::new_v4())
+        .with_node_type(NodeType::Relay)
+        .with_chain_ids(vec![1]);
+    server.init(config("ws://127.0.0.1:0/frost", timeout)).await.unwrap();
+    server
+}
+
+async fn client(timeout: Duration) -> WebSocketTransport {
+    let mut client = WebSocketTransport::new(Uuid::new_v4());
+    client.init(config("", timeout)).await.unwrap();
+    client
+}
+
+fn url(server: &WebSocketTransport) -> String {
+    format!("ws://{}/frost", server.local_addr().unwrap())
+}
+
+async fn wait_until(mut condition: impl FnMut() -> bool) {
+    for _ in 0..100 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("condition not reached");
+}
+
+#[tokio::test]
+async fn test_binary_round_trip() {
+    let server = server(Duration::from_secs(2)).await;
+    let mut client = client(Duration::from_secs(2)).await;
+
+    let server_peer = client.connect(&url(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+    assert_eq!(server_peer.id, server.node_id());
+    assert_eq!(server_peer.info.node_type, NodeType::Relay);
+    assert_eq!(server_peer.info.protocol_version, "frost.v1");
+    assert_eq!(client_peer.id, client.node_id());
+    assert_eq!(client_peer.info.node_type, NodeType::Gateway);
+
+    let large = vec![9u8; 512 * 1024];
+    for payload in [b"hello".to_vec(), Vec::new(), large.clone()] {
+        assert_eq!(client.send_data(&server_peer, &payload).await.unwrap(), payload.len());
+    }
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"hello");
+    assert!(server.receive_data(&client_peer).await.unwrap().is_empty());
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), large);
+
+    server.send_data(&client_peer, b"reply").await.unwrap();
+    assert_eq!(client.receive_data(&server_peer).await.unwrap(), b"reply");
+
+    assert_eq!(client.metrics().active_connections, 1);
+    assert!(client.metrics().bytes_sent > large.len() as u64);
+    wait_until(|| server.metrics().bytes_received == client.metrics().bytes_sent).await;
+    assert_eq!(server.metrics().connection_errors, 0);
+}
+
+#[tokio::test]
+async fn test_subprotocol_negotiation() {
+    let server = server(Duration::from_secs(2)).await;
+
+    // The highest common version is selected
+    let mut request = url(&server).into_client_request().unwrap();
+    request.headers_mut().insert("Sec-WebSocket-Protocol", "frost.v7, frost.v1".parse().unwrap());
+    let (mut stream, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(response.headers()["Sec-WebSocket-Protocol"], "frost.v1");
+
+    // A browser client speaking the in-band hello is accepted
+    let hello = serde_json::json!({
+        "node_id": Uuid::new_v4(),
+        "node_type": "Gateway",
+        "features": ["browser"],
+        "chain_ids": [],
+    });
+    stream.send(Message::text(hello.to_string())).await.unwrap();
+    let peer = server.accept().await.unwrap();
+    assert_eq!(peer.info.supported_features, vec!["browser".to_string()]);
+    assert!(matches!(stream.next().await, Some(Ok(Message::Text(_)))));
+
+    stream.send(Message::binary(b"from browser".to_vec())).await.unwrap();
+    assert_eq!(server.receive_data(&peer).await.unwrap(), b"from browser");
+
+    // Text after the hello is a protocol violation
+    stream.send(Message::text("oops")).await.unwrap();
+    wait_until(|| server.peers().is_empty()).await;
+
+    // No common version
+    let mut request = url(&server).into_client_request().unwrap();
+    request.headers_mut().insert("Sec-WebSocket-Protocol", "frost.v7".parse().unwrap());
+    assert!(tokio_tungstenite::connect_async(request).await.is_err());
+
+    // No subprotocol at all, and an unknown path
+    assert!(tokio_tungstenite::connect_async(url(&server)).await.is_err());
+    let mut client = client(Duration::from_secs(2)).await;
+    let wrong_path = format!("ws://{}/other", server.local_addr().unwrap());
+    assert!(client.connect(&wrong_path).await.is_err());
+    assert_eq!(client.metrics().connection_errors, 1);
+}
+
+#[tokio::test]
+async fn test_keep_alive() {
+    let server = server(Duration::from_millis(500)).await;
+    let mut client = client(Duration::from_millis(500)).await;
+    let peer = client.connect(&url(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    // Pings keep an idle connection open and measure latency
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert!(client.is_connected(&peer).await);
+    assert!(server.is_connected(&client_peer).await);
+    assert!(client.metrics().average_latency > Duration::ZERO);
+
+    // A peer that never answers pings after the hello idles out
+    let mut request = url(&server).into_client_request().unwrap();
+    request.headers_mut().insert("Sec-WebSocket-Protocol", "frost.v1".parse().unwrap());
+    let (mut stream, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+    let hello = serde_json::json!({
+        "node_id": Uuid::new_v4(),
+        "node_type": "Observer",
+        "features": [],
+        "chain_ids": [],
+    });
+    stream.send(Message::text(hello.to_string())).await.unwrap();
+    let silent = server.accept().await.unwrap();
+    assert!(server.is_connected(&silent).await);
+    wait_until(|| server.peers().len() == 1).await;
+    assert!(server.metrics().connection_errors >= 1);
+    drop(stream);
+}
+
+#[tokio::test]
+async fn test_graceful_close() {
+    let server = server(Duration::from_secs(2)).await;
+    let mut client = client(Duration::from_secs(2)).await;
+    let peer = client.connect(&url(&server)).await.unwrap();
+    let client_peer = server.accept().await.unwrap();
+
+    client.send_data(&peer, b"last").await.unwrap();
+    client.disconnect(&peer).await.unwrap();
+    assert!(!client.is_connected(&peer).await);
+    assert!(client.send_data(&peer, b"more").await.is_err());
+
+    // Data sent before the close is delivered and the close is not an error
+    assert_eq!(server.receive_data(&client_peer).await.unwrap(), b"last");
+    wait_until(|| server.peers().is_empty()).await;
+    assert!(server.receive_data(&client_peer).await.is_err());
+    assert_eq!(server.metrics().active_connections, 0);
+    assert_eq!(server.metrics().connection_errors, 0);
+    assert_eq!(client.metrics().connection_errors, 0);
+}
+
+#[tokio::test]
+async fn test_rejects_unsupported_config() {
+    let mut transport = WebSocketTransport::new(Uuid::new_v4());
+    let mut tcp = config("", Duration::from_secs(1));
+    tcp.protocol = TransportProtocol::TCP { port: 0, keep_alive: true };
+    assert!(transport.init(tcp).await.is_err());
+    assert!(transport.connect("ws://127.0.0.1:1").await.is_err());
+
+    // Listening with TLS needs a terminating proxy
+    assert!(transport.init(config("wss://127.0.0.1:0", Duration::from_secs(1))).await.is_err());
+
+    // TLS clients refuse plain connections
+    let mut secure = WebSocketTransport::new(Uuid::new_v4());
+    let mut tls = config("", Duration::from_secs(1));
+    tls.protocol = TransportProtocol::WebSocket { url: String::new(), use_tls: true };
+    secure.init(tls).await.unwrap();
+    assert!(secure.connect("ws://127.0.0.1:1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_refuses_connections_past_pending_upgrade_limit() {
+    use tokio::io::AsyncReadExt;
+
+    let server = server(Duration::from_secs(5)).await;
+    let address = server.local_addr().unwrap();
+
+    // Idle sockets that never send an upgrade request hold every slot
+    let mut idle = Vec::new();
+    for _ in 0..64 {
+        idle.push(tokio::net::TcpStream::connect(address).await.unwrap());
+    }
+
+    let mut refused = tokio::net::TcpStream::connect(address).await.unwrap();
+    let mut buf = [0u8; 1];
+    let read = tokio::time::timeout(Duration::from_secs(2), refused.read(&mut buf)).await;
+    assert!(matches!(read, Ok(Ok(0)) | Ok(Err(_))));
+    assert_eq!(server.metrics().connection_errors, 1);
+
+    // Slots free up once the stalled upgrades fail
+    drop(idle);
+    wait_until(|| server.metrics().connection_errors == 65).await;
+    let mut client = client(Duration::from_secs(2)).await;
+    client.connect(&url(&server)).await.unwrap();
+}
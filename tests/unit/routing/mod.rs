@@ -1 +1,2 @@
-pub mod router_test; 
\ No newline at end of file
+mod outbox_test;
+pub mod router_test;
\ No newline at end of file
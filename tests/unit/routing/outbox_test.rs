@@ -0,0 +1,183 @@
+use frost_protocol::{
+    routing::{BasicRouter, DurableOutbox, MessageRouter, OutboxConfig, RoutingConfig, outbox::Destination},
+    message::{FrostMessage, MessageType},
+    network::NetworkProtocol,
+    Result,
+};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+/// Network recording deliveries that can be switched offline
+#[derive(Clone, Default)]
+struct FlakyNetwork {
+    delivered: Arc<Mutex<Vec<(String, FrostMessage)>>>,
+    offline: Arc<AtomicBool>,
+}
+
+impl FlakyNetwork {
+    fn record(&self, destination: &str, message: FrostMessage) -> Result<()> {
+        if self.offline.load(Ordering::SeqCst) {
+            return Err(frost_protocol::Error::Network("network offline".into()));
+        }
+        self.delivered.lock().push((destination.to_string(), message));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NetworkProtocol for FlakyNetwork {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn broadcast(&self, message: FrostMessage) -> Result<()> {
+        self.record("broadcast", message)
+    }
+
+    async fn send_to(&self, peer_id: &str, message: FrostMessage) -> Result<()> {
+        self.record(peer_id, message)
+    }
+
+    async fn get_peers(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+}
+
+fn message(target: Option<&str>) -> FrostMessage {
+    FrostMessage::new(
+        MessageType::Discovery,
+        vec![1, 2, 3],
+        "node1".to_string(),
+        target.map(str::to_string),
+    )
+}
+
+#[tokio::test]
+async fn test_router_delivers_through_outbox() {
+    let dir = tempfile::tempdir().unwrap();
+    let network = FlakyNetwork::default();
+    let outbox = DurableOutbox::open(dir.path().join("outbox.log"), network.clone(), OutboxConfig::default())
+        .await
+        .unwrap();
+    let mut router = BasicRouter::new(RoutingConfig::default(), outbox);
+    router
+        .update_routes(HashMap::from([("node2".to_string(), "peer1".to_string())]))
+        .await
+        .unwrap();
+
+    router.route(message(Some("node2"))).await.unwrap();
+    router.route(message(None)).await.unwrap();
+
+    let delivered = network.delivered.lock();
+    assert_eq!(delivered.len(), 2);
+    assert_eq!(delivered[0].0, "peer1");
+    assert_eq!(delivered[1].0, "broadcast");
+}
+
+#[tokio::test]
+async fn test_replay_after_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("outbox.log");
+    let network = FlakyNetwork::default();
+
+    let sent = message(Some("node2"));
+    let lost = [message(None), message(Some("node3"))];
+    {
+        let outbox = DurableOutbox::open(&path, network.clone(), OutboxConfig::default()).await.unwrap();
+        outbox.send_to("peer1", sent.clone()).await.unwrap();
+
+        network.offline.store(true, Ordering::SeqCst);
+        assert!(outbox.broadcast(lost[0].clone()).await.is_err());
+        assert!(outbox.send_to("peer2", lost[1].clone()).await.is_err());
+        assert_eq!(outbox.pending_count().await, 2);
+    }
+
+    // Simulated restart with the network back online
+    network.offline.store(false, Ordering::SeqCst);
+    let outbox = DurableOutbox::open(&path, network.clone(), OutboxConfig::default()).await.unwrap();
+    let pending = outbox.pending().await;
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].message.id, lost[0].id);
+    assert_eq!(pending[1].destination, Destination::Peer("peer2".into()));
+
+    assert_eq!(outbox.replay().await.unwrap(), 2);
+    assert_eq!(outbox.pending_count().await, 0);
+
+    let delivered = network.delivered.lock();
+    assert_eq!(delivered.len(), 3);
+    assert_eq!(delivered[1].1.id, lost[0].id);
+    assert_eq!(delivered[1].1.metadata.retry_count, 1);
+    assert_eq!(delivered[2].0, "peer2");
+    assert_eq!(delivered[2].1.metadata.retry_count, 1);
+}
+
+#[tokio::test]
+async fn test_explicit_acknowledgement() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("outbox.log");
+    let network = FlakyNetwork::default();
+    let config = OutboxConfig { ack_on_send: false, ..Default::default() };
+
+    let first = message(None);
+    let second = message(None);
+    {
+        let outbox = DurableOutbox::open(&path, network.clone(), config.clone()).await.unwrap();
+        outbox.broadcast(first.clone()).await.unwrap();
+        outbox.broadcast(second.clone()).await.unwrap();
+        assert!(outbox.acknowledge(first.id).await.unwrap());
+        assert!(!outbox.acknowledge(first.id).await.unwrap());
+    }
+
+    let outbox = DurableOutbox::open(&path, network.clone(), config).await.unwrap();
+    let pending = outbox.pending().await;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].message.id, second.id);
+}
+
+#[tokio::test]
+async fn test_compaction_and_torn_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("outbox.log");
+    let network = FlakyNetwork::default();
+    let config = OutboxConfig { ack_on_send: false, compact_after_acks: 0 };
+
+    let keep = message(None);
+    {
+        let outbox = DurableOutbox::open(&path, network.clone(), config.clone()).await.unwrap();
+        for _ in 0..10 {
+            let m = message(None);
+            outbox.broadcast(m.clone()).await.unwrap();
+            outbox.acknowledge(m.id).await.unwrap();
+        }
+        outbox.broadcast(keep.clone()).await.unwrap();
+
+        let before = std::fs::metadata(&path).unwrap().len();
+        outbox.compact().await.unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() < before);
+    }
+
+    // A crash mid-write leaves a partial trailing record
+    let mut contents = std::fs::read(&path).unwrap();
+    contents.extend_from_slice(b"{\"Pending\":{\"mess");
+    std::fs::write(&path, contents).unwrap();
+
+    let outbox = DurableOutbox::open(&path, network.clone(), config.clone()).await.unwrap();
+    let pending = outbox.pending().await;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].message.id, keep.id);
+
+    // The partial record is cut so records appended after it still load
+    let later = message(None);
+    outbox.broadcast(later.clone()).await.unwrap();
+    drop(outbox);
+    let outbox = DurableOutbox::open(&path, network, config).await.unwrap();
+    assert_eq!(outbox.pending_count().await, 2);
+}
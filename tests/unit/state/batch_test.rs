@@ -0,0 +1,209 @@
+use frost_protocol::state::{
+    ChainId,
+    batch::{BatchConfig, Ed25519BatchItem, verify_ed25519_batch},
+    proof::{ProofData, ProofRegistry, ProofType, ProofVerifier, StateProof, VerificationParams},
+    transition::StateTransition,
+    types::BlockId,
+    error::StateError,
+};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey};
+
+fn transition() -> StateTransition {
+    let mut transition = StateTransition::new(
+        ChainId::new("test-chain"),
+        BlockId::Number(1),
+        BlockId::Number(2),
+        vec![1],
+    );
+    transition.metadata.timestamp = 1_700_000_000;
+    transition
+}
+
+fn proof_with(proof_type: ProofType, data: Vec<u8>) -> StateProof {
+    StateProof::new(transition(), ProofData {
+        proof_type,
+        data,
+        metadata: None,
+        generated_at: SystemTime::now(),
+        expires_at: None,
+        version: 1,
+    })
+}
+
+fn no_cache() -> VerificationParams {
+    VerificationParams {
+        use_cache: false,
+        ..Default::default()
+    }
+}
+
+/// Accepts proofs whose first byte is even, tracking calls and concurrency
+#[derive(Default)]
+struct CountingVerifier {
+    verified: AtomicUsize,
+    batch_calls: AtomicUsize,
+    active: AtomicUsize,
+    max_active: AtomicUsize,
+    fail_batches: bool,
+}
+
+#[async_trait]
+impl ProofVerifier for CountingVerifier {
+    fn supported_types(&self) -> Vec<ProofType> {
+        vec![ProofType::Basic]
+    }
+
+    async fn verify_proof(
+        &self,
+        proof: &StateProof,
+        _params: &VerificationParams,
+        _context: Option<&serde_json::Value>,
+    ) -> Result<bool, StateError> {
+        self.verified.fetch_add(1, Ordering::SeqCst);
+        Ok(proof.proof.data[0].is_multiple_of(2))
+    }
+
+    async fn verify_batch(
+        &self,
+        proofs: &[StateProof],
+        _params: &VerificationParams,
+        _context: Option<&serde_json::Value>,
+    ) -> Result<Vec<bool>, StateError> {
+        self.batch_calls.fetch_add(1, Ordering::SeqCst);
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_active.fetch_max(active, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+
+        if self.fail_batches {
+            return Err(StateError::Internal("batch path unavailable".into()));
+        }
+        self.verified.fetch_add(proofs.len(), Ordering::SeqCst);
+        Ok(proofs.iter().map(|p| p.proof.data[0].is_multiple_of(2)).collect())
+    }
+}
+
+fn signed_item(seed: u8, message: &[u8]) -> Ed25519BatchItem {
+    let key = SigningKey::from_bytes(&[seed; 32]);
+    Ed25519BatchItem {
+        message: message.to_vec(),
+        signature: key.sign(message).to_bytes(),
+        public_key: key.verifying_key().to_bytes(),
+    }
+}
+
+#[test]
+fn test_ed25519_batch_all_valid() {
+    let items: Vec<_> = (0..16u8).map(|i| signed_item(i, &[i; 8])).collect();
+    assert!(verify_ed25519_batch(&items).into_iter().all(|ok| ok));
+    assert!(verify_ed25519_batch(&[]).is_empty());
+}
+
+#[test]
+fn test_ed25519_batch_locates_invalid() {
+    let mut items: Vec<_> = (0..16u8).map(|i| signed_item(i, &[i; 8])).collect();
+    items[3].message = b"tampered".to_vec();
+    items[11].signature[0] ^= 0x01;
+    items[7].public_key = [0xff; 32];
+
+    let results = verify_ed25519_batch(&items);
+    for (i, ok) in results.iter().enumerate() {
+        assert_eq!(*ok, ![3, 7, 11].contains(&i), "unexpected result at {}", i);
+    }
+}
+
+#[tokio::test]
+async fn test_batch_preserves_order_and_deduplicates() {
+    let verifier = Arc::new(CountingVerifier::default());
+    let registry = ProofRegistry::new();
+    registry.register_verifier(verifier.clone());
+
+    let mut proofs = vec![
+        proof_with(ProofType::Basic, vec![2]),
+        proof_with(ProofType::Basic, vec![3]),
+        proof_with(ProofType::Basic, vec![2]),
+        proof_with(ProofType::Basic, vec![4]),
+        proof_with(ProofType::Basic, vec![3]),
+    ];
+    let results = registry.verify_batch(&mut proofs, &no_cache(), None).await.unwrap();
+
+    assert_eq!(results, vec![true, false, true, true, false]);
+    assert_eq!(verifier.verified.load(Ordering::SeqCst), 3);
+    assert!(proofs.iter().all(|p| p.last_verification().is_some()));
+}
+
+#[tokio::test]
+async fn test_batch_per_item_errors() {
+    let registry = ProofRegistry::new();
+    registry.register_verifier(Arc::new(CountingVerifier::default()));
+
+    let mut proofs = vec![
+        proof_with(ProofType::Basic, vec![2]),
+        proof_with(ProofType::ZeroKnowledge, vec![2]),
+        proof_with(ProofType::Basic, vec![1]),
+    ];
+    let results = registry.verify_batch_results(&mut proofs, &no_cache(), None).await;
+
+    assert!(matches!(results[0], Ok(true)));
+    assert!(matches!(results[1], Err(StateError::Internal(_))));
+    assert!(matches!(results[2], Ok(false)));
+
+    // The fallible variant surfaces the first error
+    assert!(registry.verify_batch(&mut proofs, &no_cache(), None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_batch_chunks_respect_worker_limit() {
+    let verifier = Arc::new(CountingVerifier::default());
+    let registry = ProofRegistry::new().with_batch_config(BatchConfig {
+        max_workers: 2,
+        chunk_size: 2,
+        deduplicate: true,
+    });
+    registry.register_verifier(verifier.clone());
+
+    let mut proofs: Vec<_> = (0..12u8).map(|i| proof_with(ProofType::Basic, vec![i])).collect();
+    let results = registry.verify_batch(&mut proofs, &no_cache(), None).await.unwrap();
+
+    assert_eq!(results, (0..12u8).map(|i| i.is_multiple_of(2)).collect::<Vec<_>>());
+    assert_eq!(verifier.batch_calls.load(Ordering::SeqCst), 6);
+    assert!(verifier.max_active.load(Ordering::SeqCst) <= 2);
+}
+
+#[tokio::test]
+async fn test_batch_falls_back_to_single_verification() {
+    let verifier = Arc::new(CountingVerifier {
+        fail_batches: true,
+        ..Default::default()
+    });
+    let registry = ProofRegistry::new();
+    registry.register_verifier(verifier.clone());
+
+    let mut proofs = vec![
+        proof_with(ProofType::Basic, vec![2]),
+        proof_with(ProofType::Basic, vec![5]),
+    ];
+    let results = registry.verify_batch(&mut proofs, &no_cache(), None).await.unwrap();
+
+    assert_eq!(results, vec![true, false]);
+    assert_eq!(verifier.verified.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_batch_uses_cache() {
+    let verifier = Arc::new(CountingVerifier::default());
+    let registry = ProofRegistry::new();
+    registry.register_verifier(verifier.clone());
+    let params = VerificationParams::default();
+
+    let mut proofs = vec![proof_with(ProofType::Basic, vec![2])];
+    registry.verify_batch(&mut proofs, &params, None).await.unwrap();
+    registry.verify_batch(&mut proofs, &params, None).await.unwrap();
+
+    assert_eq!(verifier.verified.load(Ordering::SeqCst), 1);
+}
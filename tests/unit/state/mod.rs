@@ -1,3 +1,8 @@
+mod batch_test;
 mod cache_test;
 mod proof_test;
-mod transition_test;
\ No newline at end of file
+mod signature_test;
+mod smt_test;
+mod store_test;
+mod transition_test;
+mod zk_test;
\ No newline at end of file
@@ -0,0 +1,272 @@
+use frost_protocol::state::{
+    ChainId,
+    proof::{ProofData, ProofRegistry, ProofType, ProofVerifier, StateProof, VerificationParams},
+    signature::{
+        canonical_transition_bytes, Signer as CommitteeMember, SignatureEntry, SignaturePolicy,
+        SignatureProof, SignatureScheme, SignatureVerifier, SignerSet,
+    },
+    transition::StateTransition,
+    types::BlockId,
+    error::StateError,
+};
+
+use std::sync::Arc;
+use std::time::SystemTime;
+use ed25519_dalek::Signer as _;
+
+enum TestKey {
+    Ed25519(ed25519_dalek::SigningKey),
+    Secp256k1(k256::ecdsa::SigningKey),
+}
+
+impl TestKey {
+    fn ed25519(seed: u8) -> Self {
+        Self::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[seed; 32]))
+    }
+
+    fn secp256k1(seed: u8) -> Self {
+        Self::Secp256k1(k256::ecdsa::SigningKey::from_bytes(&[seed; 32].into()).unwrap())
+    }
+
+    fn member(&self, weight: u64) -> CommitteeMember {
+        match self {
+            Self::Ed25519(key) => CommitteeMember {
+                scheme: SignatureScheme::Ed25519,
+                public_key: key.verifying_key().to_bytes().to_vec(),
+                weight,
+            },
+            Self::Secp256k1(key) => CommitteeMember {
+                scheme: SignatureScheme::Secp256k1,
+                public_key: key.verifying_key().to_sec1_bytes().to_vec(),
+                weight,
+            },
+        }
+    }
+
+    fn sign(&self, transition: &StateTransition) -> SignatureEntry {
+        let message = canonical_transition_bytes(transition);
+        match self {
+            Self::Ed25519(key) => SignatureEntry {
+                scheme: SignatureScheme::Ed25519,
+                public_key: key.verifying_key().to_bytes().to_vec(),
+                signature: key.sign(&message).to_bytes().to_vec(),
+            },
+            Self::Secp256k1(key) => {
+                let signature: k256::ecdsa::Signature = key.sign(&message);
+                SignatureEntry {
+                    scheme: SignatureScheme::Secp256k1,
+                    public_key: key.verifying_key().to_sec1_bytes().to_vec(),
+                    signature: signature.to_bytes().to_vec(),
+                }
+            }
+        }
+    }
+}
+
+fn chain() -> ChainId {
+    ChainId::new("committee-chain")
+}
+
+fn transition_at(height: u64) -> StateTransition {
+    StateTransition::new(
+        chain(),
+        BlockId::Composite { number: height - 1, hash: [1; 32] },
+        BlockId::Composite { number: height, hash: [2; 32] },
+        vec![9, 9, 9],
+    )
+}
+
+fn signed_proof(transition: StateTransition, signers: &[&TestKey]) -> StateProof {
+    let proof = SignatureProof {
+        signatures: signers.iter().map(|key| key.sign(&transition)).collect(),
+    };
+    StateProof::new(transition, ProofData {
+        proof_type: ProofType::Signature,
+        data: proof.to_bytes(),
+        metadata: None,
+        generated_at: SystemTime::now(),
+        expires_at: None,
+        version: 1,
+    })
+}
+
+async fn verify(verifier: &SignatureVerifier, proof: &StateProof) -> Result<bool, StateError> {
+    verifier.verify_proof(proof, &VerificationParams::default(), None).await
+}
+
+#[tokio::test]
+async fn test_single_signer() {
+    let key = TestKey::ed25519(1);
+    let verifier = SignatureVerifier::new();
+    verifier.register_signer_set(chain(), 0, SignerSet {
+        signers: vec![key.member(1)],
+        policy: SignaturePolicy::Single,
+    }).unwrap();
+
+    let proof = signed_proof(transition_at(10), &[&key]);
+    assert!(verify(&verifier, &proof).await.unwrap());
+
+    // Signature does not carry over to a different transition
+    let mut tampered = proof.clone();
+    tampered.transition.post_state.root_hash = [3; 32];
+    assert!(!verify(&verifier, &tampered).await.unwrap());
+
+    let outsider = TestKey::ed25519(2);
+    let proof = signed_proof(transition_at(10), &[&outsider]);
+    assert!(!verify(&verifier, &proof).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_multisig_mixed_schemes() {
+    let keys = [TestKey::ed25519(1), TestKey::secp256k1(2), TestKey::secp256k1(3)];
+    let verifier = SignatureVerifier::new();
+    verifier.register_signer_set(chain(), 0, SignerSet {
+        signers: keys.iter().map(|k| k.member(1)).collect(),
+        policy: SignaturePolicy::MultiSig { required: 2 },
+    }).unwrap();
+
+    let proof = signed_proof(transition_at(10), &[&keys[0], &keys[2]]);
+    assert!(verify(&verifier, &proof).await.unwrap());
+
+    let proof = signed_proof(transition_at(10), &[&keys[1]]);
+    assert!(!verify(&verifier, &proof).await.unwrap());
+
+    // The same signer twice only counts once
+    let proof = signed_proof(transition_at(10), &[&keys[1], &keys[1]]);
+    assert!(!verify(&verifier, &proof).await.unwrap());
+
+    // An invalid entry does not hide a later valid one from the same signer
+    let mut proof = signed_proof(transition_at(10), &[&keys[0], &keys[0], &keys[2]]);
+    let mut shadowed = SignatureProof::from_bytes(&proof.proof.data).unwrap();
+    shadowed.signatures[0].signature[0] ^= 0x01;
+    proof.proof.data = shadowed.to_bytes();
+    assert!(verify(&verifier, &proof).await.unwrap());
+    let batch = verifier.verify_batch(&[proof], &VerificationParams::default(), None).await.unwrap();
+    assert_eq!(batch, vec![true]);
+}
+
+#[tokio::test]
+async fn test_weighted_threshold() {
+    let keys = [TestKey::ed25519(1), TestKey::ed25519(2), TestKey::secp256k1(3)];
+    let verifier = SignatureVerifier::new();
+    verifier.register_signer_set(chain(), 0, SignerSet {
+        signers: vec![keys[0].member(50), keys[1].member(30), keys[2].member(20)],
+        policy: SignaturePolicy::Weighted { min_weight: 67 },
+    }).unwrap();
+
+    let proof = signed_proof(transition_at(10), &[&keys[0], &keys[2]]);
+    assert!(verify(&verifier, &proof).await.unwrap());
+
+    let proof = signed_proof(transition_at(10), &[&keys[1], &keys[2]]);
+    assert!(!verify(&verifier, &proof).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_signer_rotation() {
+    let old = TestKey::ed25519(1);
+    let new = TestKey::ed25519(2);
+    let verifier = SignatureVerifier::new();
+    verifier.register_signer_set(chain(), 0, SignerSet {
+        signers: vec![old.member(1)],
+        policy: SignaturePolicy::Single,
+    }).unwrap();
+    verifier.register_signer_set(chain(), 100, SignerSet {
+        signers: vec![new.member(1)],
+        policy: SignaturePolicy::Single,
+    }).unwrap();
+
+    assert!(verify(&verifier, &signed_proof(transition_at(99), &[&old])).await.unwrap());
+    assert!(!verify(&verifier, &signed_proof(transition_at(99), &[&new])).await.unwrap());
+    assert!(verify(&verifier, &signed_proof(transition_at(100), &[&new])).await.unwrap());
+    assert!(!verify(&verifier, &signed_proof(transition_at(150), &[&old])).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_invalid_configuration() {
+    let keys = [TestKey::ed25519(1), TestKey::ed25519(2)];
+    let verifier = SignatureVerifier::new();
+
+    let single = SignerSet {
+        signers: keys.iter().map(|k| k.member(1)).collect(),
+        policy: SignaturePolicy::Single,
+    };
+    assert!(verifier.register_signer_set(chain(), 0, single).is_err());
+
+    let multisig = SignerSet {
+        signers: keys.iter().map(|k| k.member(1)).collect(),
+        policy: SignaturePolicy::MultiSig { required: 3 },
+    };
+    assert!(verifier.register_signer_set(chain(), 0, multisig).is_err());
+
+    let overflowing = SignerSet {
+        signers: vec![keys[0].member(u64::MAX), keys[1].member(1)],
+        policy: SignaturePolicy::Weighted { min_weight: 1 },
+    };
+    assert!(verifier.register_signer_set(chain(), 0, overflowing).is_err());
+
+    // No signer set configured for the chain
+    let proof = signed_proof(transition_at(10), &[&keys[0]]);
+    assert!(matches!(verify(&verifier, &proof).await, Err(StateError::InvalidProof(_))));
+}
+
+#[tokio::test]
+async fn test_weak_keys_rejected() {
+    // The identity point verifies an identity `R` with zero `s` for any
+    // message unless weak keys are refused
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+    let verifier = SignatureVerifier::new();
+    verifier.register_signer_set(chain(), 0, SignerSet {
+        signers: vec![CommitteeMember { scheme: SignatureScheme::Ed25519, public_key: identity.to_vec(), weight: 1 }],
+        policy: SignaturePolicy::Single,
+    }).unwrap();
+
+    let mut signature = vec![0u8; 64];
+    signature[0] = 1;
+    let forged = SignatureProof {
+        signatures: vec![SignatureEntry { scheme: SignatureScheme::Ed25519, public_key: identity.to_vec(), signature }],
+    };
+    let mut proof = signed_proof(transition_at(10), &[]);
+    proof.proof.data = forged.to_bytes();
+    assert!(!verify(&verifier, &proof).await.unwrap());
+    let batch = verifier.verify_batch(&[proof], &VerificationParams::default(), None).await.unwrap();
+    assert_eq!(batch, vec![false]);
+}
+
+#[tokio::test]
+async fn test_batch_matches_single_path() {
+    let keys = [TestKey::ed25519(1), TestKey::ed25519(2), TestKey::secp256k1(3)];
+    let verifier = Arc::new(SignatureVerifier::new());
+    verifier.register_signer_set(chain(), 0, SignerSet {
+        signers: keys.iter().map(|k| k.member(1)).collect(),
+        policy: SignaturePolicy::MultiSig { required: 2 },
+    }).unwrap();
+
+    let mut proofs = Vec::new();
+    for height in 10..20u64 {
+        let signers: Vec<&TestKey> = if height % 3 == 0 {
+            vec![&keys[0]]
+        } else {
+            vec![&keys[(height % 3) as usize], &keys[0]]
+        };
+        proofs.push(signed_proof(transition_at(height), &signers));
+    }
+    // Corrupt one ed25519 signature inside an otherwise valid batch
+    let mut corrupted = SignatureProof::from_bytes(&proofs[1].proof.data).unwrap();
+    corrupted.signatures[1].signature[0] ^= 0x01;
+    proofs[1].proof.data = corrupted.to_bytes();
+
+    let params = VerificationParams::default();
+    let batch = verifier.verify_batch(&proofs, &params, None).await.unwrap();
+    for (proof, result) in proofs.iter().zip(&batch) {
+        assert_eq!(*result, verify(&verifier, proof).await.unwrap());
+    }
+    assert!(!batch[1]);
+    assert!(batch[0]);
+    assert!(!batch[2]);
+
+    let registry = ProofRegistry::new();
+    registry.register_verifier(verifier.clone());
+    let results = registry.verify_batch(&mut proofs, &params, None).await.unwrap();
+    assert_eq!(results, batch);
+}
@@ -0,0 +1,263 @@
+use frost_protocol::state::{
+    ChainId,
+    proof::{ProofGenerator, ProofVerifier, StateProof, VerificationParams},
+    smt::{
+        SparseMerkleTree, SparseMerkleProof, SmtProofGenerator, SmtProofVerifier,
+        SmtStateProof, EMPTY_HASH, hash_key,
+    },
+    store::{InMemoryStateRootStore, StateRootStore},
+    transition::StateTransition,
+    types::BlockId,
+    error::StateError,
+};
+
+use std::sync::Arc;
+use parity_scale_codec::{Decode, Encode};
+use serde_json::json;
+use tokio::sync::RwLock;
+
+fn populated_tree(count: u32) -> SparseMerkleTree {
+    let mut tree = SparseMerkleTree::new();
+    tree.update_batch((0..count).map(|i| {
+        (format!("account-{}", i).into_bytes(), Some(i.to_be_bytes().to_vec()))
+    }));
+    tree
+}
+
+fn transition_to(root: [u8; 32]) -> StateTransition {
+    StateTransition::new(
+        ChainId::new("app-chain"),
+        BlockId::Composite { number: 10, hash: [7; 32] },
+        BlockId::Composite { number: 11, hash: root },
+        vec![1, 2, 3],
+    )
+}
+
+#[test]
+fn test_empty_tree() {
+    let tree = SparseMerkleTree::new();
+    assert_eq!(tree.root(), EMPTY_HASH);
+    assert!(tree.is_empty());
+
+    let proof = tree.prove(b"missing");
+    assert!(proof.verify_non_inclusion(&EMPTY_HASH, b"missing"));
+}
+
+#[test]
+fn test_root_is_order_independent() {
+    let batched = populated_tree(64);
+
+    let mut sequential = SparseMerkleTree::new();
+    for i in (0..64u32).rev() {
+        sequential.insert(format!("account-{}", i).as_bytes(), &i.to_be_bytes());
+    }
+
+    assert_eq!(batched.len(), 64);
+    assert_ne!(batched.root(), EMPTY_HASH);
+    assert_eq!(batched.root(), sequential.root());
+}
+
+#[test]
+fn test_update_and_remove() {
+    let mut tree = populated_tree(16);
+    let original = tree.root();
+
+    tree.insert(b"account-3", b"new-balance");
+    assert_ne!(tree.root(), original);
+
+    tree.insert(b"account-3", &3u32.to_be_bytes());
+    assert_eq!(tree.root(), original);
+
+    tree.insert(b"extra", b"value");
+    tree.remove(b"extra");
+    assert_eq!(tree.root(), original);
+    assert!(tree.get(b"extra").is_none());
+}
+
+#[test]
+fn test_incremental_updates_match_fresh_tree() {
+    let mut tree = populated_tree(200);
+    for i in (0..200u32).step_by(3) {
+        tree.remove(format!("account-{}", i).as_bytes());
+    }
+    for i in (1..200u32).step_by(7) {
+        tree.insert(format!("account-{}", i).as_bytes(), b"updated");
+    }
+
+    let mut fresh = SparseMerkleTree::new();
+    for i in (0..200u32).filter(|i| i % 3 != 0 || i % 7 == 1) {
+        let value = if i % 7 == 1 { b"updated".to_vec() } else { i.to_be_bytes().to_vec() };
+        fresh.insert(format!("account-{}", i).as_bytes(), &value);
+    }
+    assert_eq!(tree.root(), fresh.root());
+
+    let root = tree.root();
+    for i in 0..200u32 {
+        let key = format!("account-{}", i);
+        let proof = tree.prove(key.as_bytes());
+        assert_eq!(proof, fresh.prove(key.as_bytes()));
+        if i % 3 == 0 && i % 7 != 1 {
+            assert!(proof.verify_non_inclusion(&root, key.as_bytes()));
+        }
+    }
+
+    // Removing everything leaves no stale internal nodes behind
+    for i in 0..200u32 {
+        tree.remove(format!("account-{}", i).as_bytes());
+    }
+    assert_eq!(tree.root(), EMPTY_HASH);
+    assert!(tree.prove(b"account-1").verify_non_inclusion(&EMPTY_HASH, b"account-1"));
+}
+
+#[test]
+fn test_inclusion_proofs() {
+    let tree = populated_tree(100);
+    let root = tree.root();
+
+    for i in 0..100u32 {
+        let key = format!("account-{}", i);
+        let proof = tree.prove(key.as_bytes());
+        assert!(proof.verify_inclusion(&root, key.as_bytes(), &i.to_be_bytes()));
+        assert!(!proof.verify_inclusion(&root, key.as_bytes(), b"wrong"));
+        assert!(!proof.verify_non_inclusion(&root, key.as_bytes()));
+    }
+}
+
+#[test]
+fn test_non_inclusion_proofs() {
+    let tree = populated_tree(100);
+    let root = tree.root();
+
+    let mut ended_at_leaf = false;
+    for i in 100..200u32 {
+        let key = format!("account-{}", i);
+        let proof = tree.prove(key.as_bytes());
+        ended_at_leaf |= proof.leaf.is_some();
+        assert!(proof.verify_non_inclusion(&root, key.as_bytes()));
+        assert!(!proof.verify_inclusion(&root, key.as_bytes(), &i.to_be_bytes()));
+    }
+    // Both empty-slot and neighbouring-leaf cases should have been exercised
+    assert!(ended_at_leaf);
+}
+
+#[test]
+fn test_proof_is_compact() {
+    let tree = populated_tree(1_000);
+    let proof = tree.prove(b"account-500");
+
+    // Paths only go as deep as needed to separate keys
+    assert!(proof.depth < 32);
+    assert!(proof.siblings.len() <= proof.depth as usize);
+
+    let decoded = SparseMerkleProof::decode(&mut &proof.encode()[..]).unwrap();
+    assert_eq!(decoded, proof);
+}
+
+#[test]
+fn test_tampered_proof_rejected() {
+    let tree = populated_tree(32);
+    let root = tree.root();
+
+    let mut proof = tree.prove(b"account-1");
+    proof.siblings[0][0] ^= 0xff;
+    assert!(!proof.verify_inclusion(&root, b"account-1", &1u32.to_be_bytes()));
+
+    let mut proof = tree.prove(b"account-1");
+    proof.siblings.pop();
+    assert!(proof.compute_root(&hash_key(b"account-1"), None).is_err());
+}
+
+#[tokio::test]
+async fn test_generator_verifier_roundtrip() {
+    let tree = populated_tree(20);
+    let transition = transition_to(tree.root());
+    let generator = SmtProofGenerator::new(Arc::new(RwLock::new(tree)));
+    let verifier = SmtProofVerifier::new();
+
+    let context = json!({
+        "keys": [hex::encode(b"account-4"), hex::encode(b"absent")]
+    });
+    let proof_data = generator.generate_proof(&transition, Some(&context)).await.unwrap();
+    let decoded = SmtStateProof::from_bytes(&proof_data.data).unwrap();
+    assert_eq!(decoded.witnesses.len(), 2);
+    assert!(decoded.witnesses[0].value_hash.is_some());
+    assert!(decoded.witnesses[1].value_hash.is_none());
+
+    let proof = StateProof::new(transition.clone(), proof_data.clone());
+    let params = VerificationParams::default();
+    assert!(verifier.verify_proof(&proof, &params, None).await.unwrap());
+
+    // A proof against a different post-state root must not verify
+    let other = transition_to(populated_tree(21).root());
+    let proof = StateProof::new(other, proof_data.clone());
+    assert!(!verifier.verify_proof(&proof, &params, None).await.unwrap());
+
+    // Nor against a transition from another pre-state
+    let mut replayed = transition.clone();
+    replayed.pre_state.root_hash = [3; 32];
+    let proof = StateProof::new(replayed, proof_data.clone());
+    assert!(!verifier.verify_proof(&proof, &params, None).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_verifier_checks_pre_state_against_store() {
+    let tree = populated_tree(10);
+    let transition = transition_to(tree.root());
+    let generator = SmtProofGenerator::new(Arc::new(RwLock::new(tree)));
+    let context = json!({ "keys": [hex::encode(b"account-2")] });
+    let proof_data = generator.generate_proof(&transition, Some(&context)).await.unwrap();
+
+    let store = Arc::new(InMemoryStateRootStore::new());
+    let verifier = SmtProofVerifier::new().with_root_store(store.clone());
+    let params = VerificationParams::default();
+
+    // Unknown pre-state is rejected even though the proof is self-consistent
+    let proof = StateProof::new(transition.clone(), proof_data.clone());
+    assert!(!verifier.verify_proof(&proof, &params, None).await.unwrap());
+
+    store.insert(transition.pre_state.clone(), true).await.unwrap();
+    assert!(verifier.verify_proof(&proof, &params, None).await.unwrap());
+
+    // A forged pre-state with a matching prover-supplied pre_root still fails
+    let mut forged = transition.clone();
+    forged.pre_state.root_hash = [3; 32];
+    let mut decoded = SmtStateProof::from_bytes(&proof_data.data).unwrap();
+    decoded.pre_root = [3; 32];
+    let mut forged_data = proof_data.clone();
+    forged_data.data = decoded.to_bytes();
+    let proof = StateProof::new(forged.clone(), forged_data.clone());
+    assert!(SmtProofVerifier::new().verify_proof(&proof, &params, None).await.unwrap());
+    assert!(!verifier.verify_proof(&proof, &params, None).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_empty_proofs_rejected() {
+    let tree = populated_tree(3);
+    let transition = transition_to(tree.root());
+    let generator = SmtProofGenerator::new(Arc::new(RwLock::new(tree)));
+    assert!(generator.generate_proof(&transition, None).await.is_err());
+    assert!(generator.generate_proof(&transition, Some(&json!({ "keys": [] }))).await.is_err());
+
+    let mut proof_data = generator
+        .generate_proof(&transition, Some(&json!({ "keys": [hex::encode(b"account-1")] })))
+        .await
+        .unwrap();
+    proof_data.data = SmtStateProof {
+        pre_root: transition.pre_state.root_hash,
+        witnesses: vec![],
+    }
+    .to_bytes();
+    let proof = StateProof::new(transition, proof_data);
+    let result = SmtProofVerifier::new().verify_proof(&proof, &VerificationParams::default(), None).await;
+    assert!(matches!(result, Err(StateError::InvalidProof(_))));
+}
+
+#[tokio::test]
+async fn test_generator_rejects_root_mismatch() {
+    let generator = SmtProofGenerator::new(Arc::new(RwLock::new(populated_tree(5))));
+    let transition = transition_to([9; 32]);
+
+    let context = json!({ "keys": [hex::encode(b"account-1")] });
+    let result = generator.generate_proof(&transition, Some(&context)).await;
+    assert!(matches!(result, Err(StateError::RootMismatch { .. })));
+}
@@ -0,0 +1,221 @@
+use frost_protocol::state::{
+    ChainId,
+    BlockRef,
+    StateRoot,
+    store::{FileStateRootStore, InMemoryStateRootStore, StateRootStore},
+    transition::StateTransition,
+    types::BlockId,
+    error::StateError,
+};
+
+fn chain() -> ChainId {
+    ChainId::new("ethereum")
+}
+
+fn root(height: u64, hash: u8) -> StateRoot {
+    StateRoot {
+        block_ref: BlockRef::new(chain(), height, [hash; 32]),
+        root_hash: [hash; 32],
+        metadata: None,
+    }
+}
+
+async fn populate(store: &dyn StateRootStore) {
+    for height in 1..=10u64 {
+        store.insert(root(height, height as u8), false).await.unwrap();
+    }
+}
+
+async fn check_queries(store: &dyn StateRootStore) {
+    populate(store).await;
+    assert!(store.latest_finalized(&chain()).await.unwrap().is_none());
+
+    store.mark_finalized(&chain(), 6).await.unwrap();
+    let latest = store.latest_finalized(&chain()).await.unwrap().unwrap();
+    assert_eq!(latest.height(), 6);
+    assert!(store.get(&chain(), 3).await.unwrap().unwrap().finalized);
+    assert!(!store.get(&chain(), 7).await.unwrap().unwrap().finalized);
+
+    let range = store.range(&chain(), 4, 7).await.unwrap();
+    assert_eq!(range.iter().map(|r| r.height()).collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+    assert!(store.range(&chain(), 7, 4).await.unwrap().is_empty());
+
+    let ancestor = store.ancestor(&chain(), 10, 3).await.unwrap().unwrap();
+    assert_eq!(ancestor.height(), 7);
+    assert!(store.ancestor(&chain(), 2, 5).await.unwrap().is_none());
+
+    let by_hash = store.get_by_hash(&[5; 32]).await.unwrap();
+    assert_eq!(by_hash.len(), 1);
+    assert_eq!(by_hash[0].height(), 5);
+    assert!(store.get(&ChainId::new("other"), 5).await.unwrap().is_none());
+}
+
+async fn check_finality_rules(store: &dyn StateRootStore) {
+    populate(store).await;
+    store.mark_finalized(&chain(), 5).await.unwrap();
+
+    // Unfinalized roots may be replaced by a reorg
+    store.insert(root(8, 0x88), false).await.unwrap();
+    assert_eq!(store.get(&chain(), 8).await.unwrap().unwrap().root.root_hash, [0x88; 32]);
+    assert!(store.get_by_hash(&[8; 32]).await.unwrap().is_empty());
+
+    // Finalized roots may not
+    let result = store.insert(root(4, 0x44), false).await;
+    assert!(matches!(result, Err(StateError::RootMismatch { .. })));
+
+    // Finalizing an unknown height fails
+    assert!(store.mark_finalized(&chain(), 42).await.is_err());
+}
+
+async fn check_pruning(store: &dyn StateRootStore) {
+    populate(store).await;
+
+    // Nothing is pruned before anything is finalized
+    assert_eq!(store.prune_below(&chain(), 5).await.unwrap(), 0);
+
+    store.mark_finalized(&chain(), 4).await.unwrap();
+    // The watermark is capped at the latest finalized height
+    assert_eq!(store.prune_below(&chain(), 8).await.unwrap(), 3);
+    assert!(store.get(&chain(), 3).await.unwrap().is_none());
+    assert!(store.get_by_hash(&[2; 32]).await.unwrap().is_empty());
+    assert_eq!(store.latest_finalized(&chain()).await.unwrap().unwrap().height(), 4);
+}
+
+async fn check_transitions(store: &dyn StateRootStore) {
+    populate(store).await;
+
+    let known = StateTransition::new(
+        chain(),
+        BlockId::Composite { number: 3, hash: [3; 32] },
+        BlockId::Composite { number: 4, hash: [4; 32] },
+        vec![1],
+    );
+    assert!(store.check_transition(&known).await.unwrap());
+
+    let unknown = StateTransition::new(
+        chain(),
+        BlockId::Composite { number: 20, hash: [20; 32] },
+        BlockId::Composite { number: 21, hash: [21; 32] },
+        vec![1],
+    );
+    assert!(!store.check_transition(&unknown).await.unwrap());
+
+    let conflicting = StateTransition::new(
+        chain(),
+        BlockId::Composite { number: 3, hash: [0x33; 32] },
+        BlockId::Composite { number: 4, hash: [4; 32] },
+        vec![1],
+    );
+    let result = store.check_transition(&conflicting).await;
+    assert!(matches!(result, Err(StateError::RootMismatch { .. })));
+}
+
+#[tokio::test]
+async fn test_memory_store() {
+    check_queries(&InMemoryStateRootStore::new()).await;
+    check_finality_rules(&InMemoryStateRootStore::new()).await;
+    check_pruning(&InMemoryStateRootStore::new()).await;
+    check_transitions(&InMemoryStateRootStore::new()).await;
+}
+
+#[tokio::test]
+async fn test_file_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let open = |name: &str| FileStateRootStore::open(dir.path().join(name));
+
+    check_queries(&open("queries").await.unwrap()).await;
+    check_finality_rules(&open("finality").await.unwrap()).await;
+    check_pruning(&open("pruning").await.unwrap()).await;
+    check_transitions(&open("transitions").await.unwrap()).await;
+}
+
+#[tokio::test]
+async fn test_file_store_persists_across_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("roots.log");
+
+    {
+        let store = FileStateRootStore::open(&path).await.unwrap();
+        populate(&store).await;
+        store.mark_finalized(&chain(), 6).await.unwrap();
+        store.insert(root(9, 0x99), false).await.unwrap();
+        store.prune_below(&chain(), 3).await.unwrap();
+    }
+
+    let store = FileStateRootStore::open(&path).await.unwrap();
+    assert_eq!(store.latest_finalized(&chain()).await.unwrap().unwrap().height(), 6);
+    assert_eq!(store.get(&chain(), 9).await.unwrap().unwrap().root.root_hash, [0x99; 32]);
+    assert!(store.get(&chain(), 2).await.unwrap().is_none());
+    assert_eq!(store.range(&chain(), 0, 100).await.unwrap().len(), 8);
+
+    // Compaction keeps the same contents in a smaller log
+    let before = std::fs::metadata(&path).unwrap().len();
+    store.compact().await.unwrap();
+    assert!(std::fs::metadata(&path).unwrap().len() < before);
+    store.insert(root(11, 11), false).await.unwrap();
+
+    let store = FileStateRootStore::open(&path).await.unwrap();
+    assert_eq!(store.latest_finalized(&chain()).await.unwrap().unwrap().height(), 6);
+    assert_eq!(store.range(&chain(), 0, 100).await.unwrap().len(), 9);
+}
+
+#[tokio::test]
+async fn test_file_store_rejects_corrupt_log() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("roots.log");
+    {
+        let store = FileStateRootStore::open(&path).await.unwrap();
+        populate(&store).await;
+    }
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, format!("not json\n{}", log)).unwrap();
+
+    let result = FileStateRootStore::open(&path).await;
+    assert!(matches!(result, Err(StateError::Storage(_))));
+}
+
+#[tokio::test]
+async fn test_file_store_drops_unterminated_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("roots.log");
+    {
+        let store = FileStateRootStore::open(&path).await.unwrap();
+        populate(&store).await;
+    }
+    // A crash between the record and its newline leaves a parseable line
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, log.strip_suffix('\n').unwrap()).unwrap();
+
+    let store = FileStateRootStore::open(&path).await.unwrap();
+    assert!(store.get(&chain(), 10).await.unwrap().is_none());
+    store.insert(root(10, 0xaa), false).await.unwrap();
+    drop(store);
+
+    // The next append starts on its own line, so the log still opens
+    let store = FileStateRootStore::open(&path).await.unwrap();
+    assert_eq!(store.get(&chain(), 10).await.unwrap().unwrap().root.root_hash, [0xaa; 32]);
+    assert_eq!(store.range(&chain(), 0, 100).await.unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn test_file_store_drops_torn_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("roots.log");
+    {
+        let store = FileStateRootStore::open(&path).await.unwrap();
+        populate(&store).await;
+    }
+    let log = std::fs::read_to_string(&path).unwrap();
+    let last = log.trim_end().rfind('\n').unwrap() + 1;
+    std::fs::write(&path, &log[..last + 10]).unwrap();
+
+    // The torn insert of height 10 is dropped and later writes still replay
+    let store = FileStateRootStore::open(&path).await.unwrap();
+    assert!(store.get(&chain(), 10).await.unwrap().is_none());
+    store.insert(root(10, 0xaa), false).await.unwrap();
+    drop(store);
+
+    let store = FileStateRootStore::open(&path).await.unwrap();
+    assert_eq!(store.get(&chain(), 10).await.unwrap().unwrap().root.root_hash, [0xaa; 32]);
+    assert_eq!(store.range(&chain(), 0, 100).await.unwrap().len(), 10);
+}
@@ -0,0 +1,177 @@
+use frost_protocol::{
+    message::types::ProofMetadata,
+    state::{
+        ChainId,
+        proof::{ProofData, ProofRegistry, ProofType, ProofVerifier, StateProof, VerificationParams},
+        transition::StateTransition,
+        types::BlockId,
+        zk::Groth16Verifier,
+        error::StateError,
+    },
+};
+
+use std::sync::Arc;
+use std::time::SystemTime;
+use serde_json::{json, Value};
+
+const FIXTURE: &str = include_str!("../../fixtures/groth16_bn254.json");
+
+struct Fixture {
+    circuit_id: String,
+    pre_root: [u8; 32],
+    post_root: [u8; 32],
+    verifying_key: Vec<u8>,
+    proof: Vec<u8>,
+}
+
+fn fixture() -> Fixture {
+    let value: Value = serde_json::from_str(FIXTURE).unwrap();
+    let bytes = |key: &str| hex::decode(value[key].as_str().unwrap()).unwrap();
+    Fixture {
+        circuit_id: value["circuit_id"].as_str().unwrap().to_string(),
+        pre_root: bytes("pre_state_root").try_into().unwrap(),
+        post_root: bytes("post_state_root").try_into().unwrap(),
+        verifying_key: bytes("verifying_key"),
+        proof: bytes("proof"),
+    }
+}
+
+fn zk_proof(pre_root: [u8; 32], post_root: [u8; 32], data: Vec<u8>, metadata: Option<Value>) -> StateProof {
+    let transition = StateTransition::new(
+        ChainId::new("zk-rollup"),
+        BlockId::Composite { number: 100, hash: pre_root },
+        BlockId::Composite { number: 101, hash: post_root },
+        vec![1],
+    );
+    StateProof::new(transition, ProofData {
+        proof_type: ProofType::ZeroKnowledge,
+        data,
+        metadata,
+        generated_at: SystemTime::now(),
+        expires_at: None,
+        version: 1,
+    })
+}
+
+fn params_for(circuit_id: &str) -> VerificationParams {
+    VerificationParams {
+        extra_params: Some(json!({ "circuit_id": circuit_id })),
+        ..Default::default()
+    }
+}
+
+fn verifier(fixture: &Fixture) -> Groth16Verifier {
+    let verifier = Groth16Verifier::new();
+    verifier.register_key_bytes(fixture.circuit_id.clone(), &fixture.verifying_key).unwrap();
+    verifier
+}
+
+#[tokio::test]
+async fn test_valid_proof_verifies() {
+    let fixture = fixture();
+    let verifier = verifier(&fixture);
+    assert!(verifier.has_key(&fixture.circuit_id));
+
+    let proof = zk_proof(fixture.pre_root, fixture.post_root, fixture.proof.clone(), None);
+    let result = verifier.verify_proof(&proof, &params_for(&fixture.circuit_id), None).await;
+    assert!(result.unwrap());
+}
+
+#[tokio::test]
+async fn test_circuit_id_from_proof_metadata() {
+    let fixture = fixture();
+    let verifier = verifier(&fixture);
+
+    let metadata = json!({ "circuit_id": fixture.circuit_id });
+    let proof = zk_proof(fixture.pre_root, fixture.post_root, fixture.proof.clone(), Some(metadata));
+    let result = verifier.verify_proof(&proof, &VerificationParams::default(), None).await;
+    assert!(result.unwrap());
+}
+
+#[tokio::test]
+async fn test_params_from_message_proof_metadata() {
+    let fixture = fixture();
+    let verifier = verifier(&fixture);
+
+    let metadata = ProofMetadata {
+        proof_type: "groth16".into(),
+        proof_version: 1,
+        verification_params: Some(json!({ "circuit_id": fixture.circuit_id })),
+        security_level: 90,
+        expires_at: None,
+    };
+    let params = metadata.to_verification_params();
+    assert_eq!(params.security_level, 90);
+
+    let proof = zk_proof(fixture.pre_root, fixture.post_root, fixture.proof.clone(), None);
+    assert!(verifier.verify_proof(&proof, &params, None).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_proof_bound_to_state_roots() {
+    let fixture = fixture();
+    let verifier = verifier(&fixture);
+    let params = params_for(&fixture.circuit_id);
+
+    let proof = zk_proof([0x22; 32], fixture.post_root, fixture.proof.clone(), None);
+    assert!(!verifier.verify_proof(&proof, &params, None).await.unwrap());
+
+    let proof = zk_proof(fixture.pre_root, [0x33; 32], fixture.proof.clone(), None);
+    assert!(!verifier.verify_proof(&proof, &params, None).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_rejects_unknown_circuit_and_bad_encoding() {
+    let fixture = fixture();
+    let verifier = verifier(&fixture);
+
+    let proof = zk_proof(fixture.pre_root, fixture.post_root, fixture.proof.clone(), None);
+    let result = verifier.verify_proof(&proof, &params_for("unknown"), None).await;
+    assert!(matches!(result, Err(StateError::InvalidProof(_))));
+
+    let result = verifier.verify_proof(&proof, &VerificationParams::default(), None).await;
+    assert!(matches!(result, Err(StateError::InvalidProof(_))));
+
+    let proof = zk_proof(fixture.pre_root, fixture.post_root, vec![0xff; 16], None);
+    let result = verifier.verify_proof(&proof, &params_for(&fixture.circuit_id), None).await;
+    assert!(matches!(result, Err(StateError::InvalidProof(_))));
+}
+
+#[tokio::test]
+async fn test_extra_public_inputs_checked() {
+    let fixture = fixture();
+    let verifier = verifier(&fixture);
+    let proof = zk_proof(fixture.pre_root, fixture.post_root, fixture.proof.clone(), None);
+
+    // The fixture circuit takes exactly the four root limbs
+    let params = VerificationParams {
+        extra_params: Some(json!({
+            "circuit_id": fixture.circuit_id,
+            "public_inputs": ["01"],
+        })),
+        ..Default::default()
+    };
+    assert!(verifier.verify_proof(&proof, &params, None).await.is_err());
+
+    let params = VerificationParams {
+        extra_params: Some(json!({
+            "circuit_id": fixture.circuit_id,
+            "public_inputs": ["ff".repeat(32)],
+        })),
+        ..Default::default()
+    };
+    let result = verifier.verify_proof(&proof, &params, None).await;
+    assert!(matches!(result, Err(StateError::InvalidProof(_))));
+}
+
+#[tokio::test]
+async fn test_registry_dispatch() {
+    let fixture = fixture();
+    let registry = ProofRegistry::new();
+    registry.register_verifier(Arc::new(verifier(&fixture)));
+
+    let mut proof = zk_proof(fixture.pre_root, fixture.post_root, fixture.proof.clone(), None);
+    let result = registry.verify_proof(&mut proof, &params_for(&fixture.circuit_id), None).await;
+    assert!(result.unwrap());
+    assert!(proof.last_verification().unwrap().success);
+}